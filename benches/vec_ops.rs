@@ -0,0 +1,157 @@
+//! Throughput of the hot per-vector operations, for `f32`, `f64`, and
+//! fixed point. Run with `cargo bench`; add `--features cordic` to include
+//! fixed-point norms.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fixed::types::I16F16;
+use std::hint::black_box;
+use xyzvec::{VecInner, VecNum, Vector, XYVec, XYZVec};
+
+/// Number of vectors in the batch benchmarks.
+const BATCH: usize = 1024;
+
+fn points2<T>(f: impl Fn(f64) -> T) -> Vec<XYVec<T>>
+where
+    T: VecInner,
+{
+    (0..BATCH)
+        .map(|i| {
+            let t = i as f64 * 0.01;
+            XYVec::new([f(t.cos()), f(t.sin() * 0.5)])
+        })
+        .collect()
+}
+
+fn points3<T>(f: impl Fn(f64) -> T) -> Vec<XYZVec<T>>
+where
+    T: VecInner,
+{
+    (0..BATCH)
+        .map(|i| {
+            let t = i as f64 * 0.01;
+            XYZVec::new([f(t.cos()), f(t.sin() * 0.5), f(0.25 - t.cos() * 0.1)])
+        })
+        .collect()
+}
+
+/// Sum of the dot products of neighboring vectors.
+fn sum_of_dots<V: Vector>(vs: &[V]) -> V::Scalar
+where
+    V::Scalar: VecNum,
+{
+    vs.windows(2)
+        .fold(V::Scalar::zero(), |acc, w| acc + w[0].dot_prod(w[1]))
+}
+
+macro_rules! bench_scalar {
+    ($c:expr, $name:literal, $f:expr) => {{
+        let xy = points2($f);
+        let xyz = points3($f);
+
+        let mut group = $c.benchmark_group(concat!("single/", $name));
+        let (a, b) = (xy[1], xy[2]);
+        let (p, q) = (xyz[1], xyz[2]);
+        group.bench_function("xy_dot", |bench| {
+            bench.iter(|| black_box(a).dot_prod(black_box(b)))
+        });
+        group.bench_function("xy_cross", |bench| {
+            bench.iter(|| black_box(a).cross_prod(black_box(b)))
+        });
+        group.bench_function("xy_cross_sqd", |bench| {
+            bench.iter(|| black_box(a).cross_prod_sqd(black_box(b)))
+        });
+        group.bench_function("xy_norm_sqd", |bench| {
+            bench.iter(|| black_box(a).l2_norm_sqd())
+        });
+        group.bench_function("xyz_dot", |bench| {
+            bench.iter(|| black_box(p).dot_prod(black_box(q)))
+        });
+        group.bench_function("xyz_cross", |bench| {
+            bench.iter(|| black_box(p).cross_prod(black_box(q)))
+        });
+        group.bench_function("xyz_norm_sqd", |bench| {
+            bench.iter(|| black_box(p).l2_norm_sqd())
+        });
+        group.finish();
+
+        let mut group = $c.benchmark_group(concat!("batch/", $name));
+        group.throughput(Throughput::Elements(BATCH as u64));
+        group.bench_with_input(BenchmarkId::new("xy_sum", BATCH), &xy, |bench, xy| {
+            bench.iter(|| black_box(xy).iter().sum::<XYVec<_>>())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("xyz_dot_pairs", BATCH),
+            &xyz,
+            |bench, xyz| bench.iter(|| sum_of_dots(black_box(xyz))),
+        );
+        group.finish();
+    }};
+}
+
+macro_rules! bench_float {
+    ($c:expr, $name:literal, $t:ty) => {{
+        let xy = points2(|v| v as $t);
+        let xyz = points3(|v| v as $t);
+
+        let mut group = $c.benchmark_group(concat!("single/", $name));
+        let (a, p) = (xy[1], xyz[1]);
+        group.bench_function("xy_norm", |bench| bench.iter(|| black_box(a).l2_norm()));
+        group.bench_function("xyz_norm", |bench| bench.iter(|| black_box(p).l2_norm()));
+        group.bench_function("xy_rotate", |bench| {
+            bench.iter(|| black_box(a).rotated_by(black_box(0.3 as $t)))
+        });
+        group.finish();
+
+        let mut group = $c.benchmark_group(concat!("batch/", $name));
+        group.throughput(Throughput::Elements(BATCH as u64));
+        group.bench_with_input(BenchmarkId::new("xy_rotate", BATCH), &xy, |bench, xy| {
+            bench.iter(|| {
+                black_box(xy)
+                    .iter()
+                    .map(|v| v.rotated_by(0.3 as $t))
+                    .collect::<Vec<_>>()
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("xyz_normalize", BATCH),
+            &xyz,
+            |bench, xyz| {
+                bench.iter(|| {
+                    black_box(xyz)
+                        .iter()
+                        .filter_map(|v| v.try_normalize())
+                        .count()
+                })
+            },
+        );
+        group.finish();
+    }};
+}
+
+fn scalar_ops(c: &mut Criterion) {
+    bench_scalar!(c, "f32", |v| v as f32);
+    bench_scalar!(c, "f64", |v| v);
+    bench_scalar!(c, "I16F16", I16F16::from_num::<f64>);
+}
+
+fn float_ops(c: &mut Criterion) {
+    bench_float!(c, "f32", f32);
+    bench_float!(c, "f64", f64);
+}
+
+#[cfg(feature = "cordic")]
+fn fixed_norms(c: &mut Criterion) {
+    let xy = points2(I16F16::from_num::<f64>);
+    let xyz = points3(I16F16::from_num::<f64>);
+    let mut group = c.benchmark_group("single/I16F16");
+    let (a, p) = (xy[1], xyz[1]);
+    group.bench_function("xy_norm", |bench| bench.iter(|| black_box(a).l2_norm()));
+    group.bench_function("xyz_norm", |bench| bench.iter(|| black_box(p).l2_norm()));
+    group.finish();
+}
+
+#[cfg(not(feature = "cordic"))]
+fn fixed_norms(_: &mut Criterion) {}
+
+criterion_group!(benches, scalar_ops, float_ops, fixed_norms);
+criterion_main!(benches);
@@ -0,0 +1,187 @@
+use crate::{VecNum, Vector, XYVec, XYZVec};
+
+/// Axis-aligned bounding box spanning `min` to `max` (inclusive).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Aabb<V> {
+    pub min: V,
+    pub max: V,
+}
+
+pub type Aabb2<T> = Aabb<XYVec<T>>;
+pub type Aabb3<T> = Aabb<XYZVec<T>>;
+
+impl<V: Vector> Aabb<V>
+where
+    V::Scalar: VecNum,
+{
+    /// Box spanned by two opposite corners, given in any order.
+    /// ```
+    ///     use xyzvec::{Aabb2, XYVec};
+    ///
+    ///     let b = Aabb2::new(XYVec::new([2.0f64, -1.0]), XYVec::new([0.0, 1.0]));
+    ///     assert_eq!(b.min, XYVec::new([0.0, -1.0]));
+    ///     assert_eq!(b.max, XYVec::new([2.0, 1.0]));
+    /// ```
+    pub fn new(a: V, b: V) -> Self {
+        let min = a.zip_map(b, |a, b| if b < a { b } else { a });
+        let max = a.zip_map(b, |a, b| if b > a { b } else { a });
+        Self { min, max }
+    }
+
+    pub fn center(&self) -> V {
+        (self.min + self.max).div_by(V::Scalar::one() + V::Scalar::one())
+    }
+
+    /// Side lengths along each axis.
+    pub fn size(&self) -> V {
+        self.max - self.min
+    }
+
+    /// Whether `p` lies inside the box or on its boundary.
+    pub fn contains_point(&self, p: V) -> bool {
+        (0..V::DIM).all(|i| {
+            let c = p.component(i);
+            self.min.component(i) <= c && c <= self.max.component(i)
+        })
+    }
+
+    /// Smallest box containing every point, or `None` if there are none.
+    /// ```
+    ///     use xyzvec::{Aabb2, XYVec};
+    ///
+    ///     let points = [XYVec::new([1, 5]), XYVec::new([-2, 3]), XYVec::new([4, -1])];
+    ///     let b = Aabb2::from_iter(points).unwrap();
+    ///     assert_eq!((b.min, b.max), (XYVec::new([-2, -1]), XYVec::new([4, 5])));
+    ///     assert!(Aabb2::<f32>::from_iter([]).is_none());
+    /// ```
+    // Returns `Option`, so it can't be `FromIterator`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I: IntoIterator<Item = V>>(points: I) -> Option<Self> {
+        let (min, max) = min_max_components(points)?;
+        Some(Self { min, max })
+    }
+
+    /// `p` with each component clamped to the box.
+    pub fn clamp(&self, p: V) -> V {
+        p.zip_map(self.min, |c, lo| if c < lo { lo } else { c })
+            .zip_map(self.max, |c, hi| if c > hi { hi } else { c })
+    }
+}
+
+/// Component-wise minimum and maximum over `points`, or `None` if there
+/// are none.
+pub fn min_max_components<V, I>(points: I) -> Option<(V, V)>
+where
+    V: Vector,
+    V::Scalar: VecNum,
+    I: IntoIterator<Item = V>,
+{
+    let mut points = points.into_iter();
+    let first = points.next()?;
+    Some(points.fold((first, first), |(min, max), p| {
+        (
+            min.zip_map(p, |a, b| if b < a { b } else { a }),
+            max.zip_map(p, |a, b| if b > a { b } else { a }),
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::min_max_components;
+    use crate::{Aabb2, Aabb3, Vector, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    #[test]
+    fn contains_f32() {
+        let b = Aabb2::new(XYVec::new([0.0f32, 0.0]), XYVec::new([2.0, 1.0]));
+        assert!(b.contains_point(XYVec::new([1.0, 0.5])));
+        assert!(b.contains_point(XYVec::new([2.0, 1.0])));
+        assert!(!b.contains_point(XYVec::new([2.1, 0.5])));
+        assert_relative_eq!(b.center().x(), 1.0);
+        assert_relative_eq!(b.size().y(), 1.0);
+    }
+
+    #[test]
+    fn clamp_f64() {
+        let b = Aabb3::new(
+            XYZVec::new([-1.0f64, -1.0, -1.0]),
+            XYZVec::new([1.0, 1.0, 1.0]),
+        );
+        let c = b.clamp(XYZVec::new([3.0, 0.5, -2.0]));
+        assert_relative_eq!(c.x(), 1.0);
+        assert_relative_eq!(c.y(), 0.5);
+        assert_relative_eq!(c.z(), -1.0);
+    }
+
+    #[test]
+    fn fixed() {
+        let b = Aabb2::new(
+            XYVec::new([I28F4::from_num(3), I28F4::from_num(-1)]),
+            XYVec::new([I28F4::from_num(-1), I28F4::from_num(1)]),
+        );
+        assert_eq!(b.center().x(), 1);
+        assert_eq!(b.center().y(), 0);
+        assert!(b.contains_point(XYVec::new([I28F4::from_num(0), I28F4::from_num(0.5)])));
+    }
+
+    #[test]
+    fn from_iter_f64() {
+        let b = Aabb3::from_iter((0..10).map(|i| {
+            let t = i as f64;
+            XYZVec::new([t.sin(), t.cos(), t - 4.0])
+        }))
+        .unwrap();
+        assert_relative_eq!(b.max.y(), 1.0);
+        assert_relative_eq!(b.min.z(), -4.0);
+        assert_relative_eq!(b.max.z(), 5.0);
+        assert!(b.min.x() >= -1.0 && b.max.x() <= 1.0);
+        let single = Aabb2::from_iter([XYVec::new([2.0f64, 3.0])]).unwrap();
+        assert_eq!(single.min, single.max);
+        let (lo, hi) = min_max_components(vec![XYVec::new([1.0f64, -1.0])]).unwrap();
+        assert_eq!((lo, hi), (XYVec::new([1.0, -1.0]), XYVec::new([1.0, -1.0])));
+        assert!(min_max_components(Vec::<XYZVec<f64>>::new()).is_none());
+    }
+
+    #[test]
+    fn periodic_f64() {
+        let world = Aabb3::new(
+            XYZVec::new([-1.0f64, 0.0, 0.0]),
+            XYZVec::new([1.0, 4.0, 3.0]),
+        );
+        for p in [
+            XYZVec::new([0.5f64, 2.0, 1.0]),
+            XYZVec::new([-7.25, 13.0, -0.5]),
+            XYZVec::new([1.0, -1e-17, 300.0]),
+        ] {
+            let w = p.wrap_into(&world);
+            assert!(world.contains_point(w) && w.x() < 1.0 && w.y() < 4.0 && w.z() < 3.0);
+            // Wrapping only moves by whole periods.
+            let k = (p - w).zip_map(world.size(), |c, l| c / l);
+            assert!(k.iter().all(|c| (c - c.round()).abs() < 1e-9));
+        }
+        let extents = world.size();
+        let (a, b) = (XYZVec::new([0.9, 0.5, 0.1]), XYZVec::new([-0.9, 3.5, 2.9]));
+        let d = a.torus_delta(b, extents);
+        assert_relative_eq!(d.x(), 0.2, epsilon = 1e-12);
+        assert_relative_eq!(d.y(), -1.0, epsilon = 1e-12);
+        assert_relative_eq!(d.z(), -0.2, epsilon = 1e-12);
+        assert_relative_eq!(a.torus_distance(b, extents), d.l2_norm());
+        assert_relative_eq!(b.torus_distance(a, extents), d.l2_norm());
+        // Adding the delta lands on an image of `b`.
+        let image = (a + d).wrap_into(&world) - b.wrap_into(&world);
+        assert_relative_eq!(image.l2_norm(), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn periodic_f32() {
+        let world = Aabb2::new(XYVec::new([0.0f32, 0.0]), XYVec::new([8.0, 8.0]));
+        assert_eq!(
+            XYVec::new([-0.5f32, 17.0]).wrap_into(&world),
+            XYVec::new([7.5, 1.0])
+        );
+        let d = XYVec::new([1.0f32, 7.0]).torus_delta(XYVec::new([7.0, 1.0]), world.size());
+        assert_eq!(d, XYVec::new([-2.0, 2.0]));
+    }
+}
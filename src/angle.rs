@@ -0,0 +1,415 @@
+//! Angle helpers and the [`Radians`] and [`Degrees`] newtypes. Angles are
+//! counter-clockwise positive, and bare scalar angles are in radians. There
+//! are two exceptions: [`Heading`], a clockwise compass bearing, and
+//! [`XYVec::rotated_by`], which for historical reasons turns clockwise.
+
+use crate::{VecFloat, VecInner, XYVec};
+use std::{
+    f64::consts::{PI, TAU},
+    fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+};
+
+/// An angle in radians. Rotation APIs take `impl Into<Radians<T>>`, so they
+/// accept a bare scalar (taken as radians), a `Radians`, or a [`Degrees`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Radians<T>(pub T);
+
+/// An angle in degrees. Converts into [`Radians`] wherever an angle is
+/// expected.
+/// ```
+///     use xyzvec::{angle::Degrees, XYVec};
+///     use approx::assert_relative_eq;
+///
+///     let v = XYVec::from_angle(Degrees(60.0f64));
+///     assert_relative_eq!(v.x(), 0.5, epsilon = 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Degrees<T>(pub T);
+
+impl<T: VecFloat> Radians<T> {
+    pub fn to_degrees(self) -> Degrees<T> {
+        Degrees(T::from_f64(self.0.to_f64().to_degrees()))
+    }
+
+    pub fn sin(self) -> T {
+        self.0.sin()
+    }
+
+    pub fn cos(self) -> T {
+        self.0.cos()
+    }
+
+    /// The same angle wrapped into `(-π, π]`. See [`wrap_angle`].
+    pub fn wrapped(self) -> Self {
+        Radians(wrap_angle(self.0))
+    }
+}
+
+impl<T: VecFloat> Degrees<T> {
+    pub fn to_radians(self) -> Radians<T> {
+        Radians(T::from_f64(self.0.to_f64().to_radians()))
+    }
+
+    /// The same angle wrapped into `(-180, 180]`.
+    pub fn wrapped(self) -> Self {
+        self.to_radians().wrapped().to_degrees()
+    }
+}
+
+/// Bare scalars convert to `Radians`. This is implemented per scalar type
+/// rather than for every `T`, so that `Degrees<T>` converts unambiguously.
+macro_rules! impl_radians_from_scalar {
+    ($($t:ty),* ; $($fixed:ident),*) => {
+        $(
+            impl From<$t> for Radians<$t> {
+                fn from(theta: $t) -> Self {
+                    Radians(theta)
+                }
+            }
+        )*
+        $(
+            impl<Frac> From<fixed::$fixed<Frac>> for Radians<fixed::$fixed<Frac>> {
+                fn from(theta: fixed::$fixed<Frac>) -> Self {
+                    Radians(theta)
+                }
+            }
+        )*
+    };
+}
+
+impl_radians_from_scalar!(f32, f64; FixedI8, FixedI16, FixedI32, FixedI64);
+
+impl<T: VecFloat> From<Degrees<T>> for Radians<T> {
+    fn from(theta: Degrees<T>) -> Self {
+        theta.to_radians()
+    }
+}
+
+impl<T: VecFloat> From<Radians<T>> for Degrees<T> {
+    fn from(theta: Radians<T>) -> Self {
+        theta.to_degrees()
+    }
+}
+
+macro_rules! impl_angle_ops {
+    ($name:ident, $unit:literal) => {
+        impl<T: VecInner> Add for $name<T> {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                $name(self.0 + other.0)
+            }
+        }
+
+        impl<T: VecInner> AddAssign for $name<T> {
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl<T: VecInner> Sub for $name<T> {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                $name(self.0 - other.0)
+            }
+        }
+
+        impl<T: VecInner> SubAssign for $name<T> {
+            fn sub_assign(&mut self, other: Self) {
+                self.0 -= other.0;
+            }
+        }
+
+        impl<T: VecInner> Neg for $name<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $name(-self.0)
+            }
+        }
+
+        impl<T: VecInner> Mul<T> for $name<T> {
+            type Output = Self;
+
+            fn mul(self, d: T) -> Self {
+                $name(self.0 * d)
+            }
+        }
+
+        impl<T: VecInner> Div<T> for $name<T> {
+            type Output = Self;
+
+            fn div(self, d: T) -> Self {
+                $name(self.0 / d)
+            }
+        }
+
+        impl<T: VecInner> Display for $name<T> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                Display::fmt(&self.0, f)?;
+                f.write_str($unit)
+            }
+        }
+    };
+}
+
+impl_angle_ops!(Radians, " rad");
+impl_angle_ops!(Degrees, "°");
+
+/// `theta` wrapped into `(-π, π]`.
+/// ```
+///     use xyzvec::angle::wrap_angle;
+///     use approx::assert_relative_eq;
+///     use std::f64::consts::PI;
+///
+///     assert_relative_eq!(wrap_angle(3.0 * PI / 2.0), -PI / 2.0);
+///     assert_relative_eq!(wrap_angle(-PI), PI);
+///     assert_relative_eq!(wrap_angle(5.0 * PI), PI);
+/// ```
+pub fn wrap_angle<T: VecFloat>(theta: T) -> T {
+    let theta = theta.to_f64();
+    let wrapped = theta - TAU * ((theta + PI) / TAU).floor();
+    // `wrapped` is in [-π, π); move the lower bound over to π.
+    if wrapped <= -PI {
+        T::from_f64(wrapped + TAU)
+    } else {
+        T::from_f64(wrapped)
+    }
+}
+
+/// Signed shortest rotation taking angle `from` to angle `to`, in `(-π, π]`.
+/// ```
+///     use xyzvec::angle::angle_difference;
+///     use approx::assert_relative_eq;
+///     use std::f64::consts::PI;
+///
+///     assert_relative_eq!(angle_difference(0.1, 0.4), 0.3);
+///     // Across the seam, the short way round is negative.
+///     assert_relative_eq!(angle_difference(-PI + 0.1, PI - 0.1), -0.2, epsilon = 1e-12);
+/// ```
+pub fn angle_difference<T: VecFloat>(from: T, to: T) -> T {
+    wrap_angle(to - from)
+}
+
+/// A compass bearing: degrees clockwise from north, kept in `[0, 360)`.
+///
+/// Math angles are counter-clockwise from `+x`, so the two conventions
+/// only agree at 45° and 225°. `Heading` keeps them apart: it converts to
+/// and from math angles and direction vectors explicitly, with `+x` east
+/// and `+y` north as in [`LatLon::to_local_xy`](crate::LatLon::to_local_xy).
+/// Adding [`Degrees`] turns clockwise, and subtracting two headings gives
+/// the shortest turn between them.
+/// ```
+///     use xyzvec::angle::{Degrees, Heading, Radians};
+///     use approx::assert_relative_eq;
+///
+///     let east = Heading::from_math_angle(Radians(0.0f64));
+///     assert_relative_eq!(east.degrees(), 90.0);
+///     let south_west = east + Degrees(135.0);
+///     assert_relative_eq!(south_west.degrees(), 225.0);
+///     let dir = south_west.to_direction();
+///     assert!(dir.x() < 0.0 && dir.y() < 0.0);
+///     let turn = Heading::from_degrees(10.0) - Heading::from_degrees(350.0);
+///     assert_relative_eq!(turn.0, 20.0, epsilon = 1e-9);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Heading<T>(T);
+
+impl<T: VecFloat> Heading<T> {
+    /// Heading `degrees` clockwise from north, wrapped into `[0, 360)`.
+    pub fn from_degrees(degrees: T) -> Self {
+        let wrapped = degrees.to_f64().rem_euclid(360.0);
+        // `rem_euclid` rounds tiny negative angles up to exactly 360.
+        Heading(T::from_f64(if wrapped >= 360.0 { 0.0 } else { wrapped }))
+    }
+
+    /// Heading of the math angle `theta`, counter-clockwise from east.
+    pub fn from_math_angle(theta: impl Into<Radians<T>>) -> Self {
+        let ninety = T::from_f64(90.0);
+        Self::from_degrees(ninety - theta.into().to_degrees().0)
+    }
+
+    /// Heading pointing along `v`, or north for the zero vector.
+    pub fn from_direction(v: XYVec<T>) -> Self {
+        Self::from_degrees(Radians(v.x().atan2(v.y())).to_degrees().0)
+    }
+
+    /// Degrees clockwise from north, in `[0, 360)`.
+    pub fn degrees(self) -> T {
+        self.0
+    }
+
+    /// The same direction as a math angle, counter-clockwise from east, in
+    /// `(-π, π]`.
+    pub fn to_math_angle(self) -> Radians<T> {
+        (Degrees(T::from_f64(90.0)) - Degrees(self.0))
+            .to_radians()
+            .wrapped()
+    }
+
+    /// Unit vector pointing along the heading.
+    pub fn to_direction(self) -> XYVec<T> {
+        let theta = Degrees(self.0).to_radians();
+        XYVec::new([theta.sin(), theta.cos()])
+    }
+}
+
+/// Turns clockwise by `turn`.
+impl<T: VecFloat> Add<Degrees<T>> for Heading<T> {
+    type Output = Self;
+
+    fn add(self, turn: Degrees<T>) -> Self {
+        Heading::from_degrees(self.0 + turn.0)
+    }
+}
+
+/// Turns counter-clockwise by `turn`.
+impl<T: VecFloat> Sub<Degrees<T>> for Heading<T> {
+    type Output = Self;
+
+    fn sub(self, turn: Degrees<T>) -> Self {
+        Heading::from_degrees(self.0 - turn.0)
+    }
+}
+
+/// The shortest turn from `other` to `self`, in `(-180, 180]`, positive
+/// when clockwise.
+impl<T: VecFloat> Sub for Heading<T> {
+    type Output = Degrees<T>;
+
+    fn sub(self, other: Self) -> Degrees<T> {
+        Degrees(self.0 - other.0).wrapped()
+    }
+}
+
+impl<T: VecInner> Display for Heading<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)?;
+        f.write_str("°")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn newtypes_f64() {
+        let right = Degrees(90.0f64);
+        assert_relative_eq!(right.to_radians().0, FRAC_PI_2);
+        assert_relative_eq!(Radians(PI).to_degrees().0, 180.0);
+        let sum: Radians<f64> = Radians::from(right) + Radians(FRAC_PI_2);
+        assert_relative_eq!(sum.0, PI);
+        assert_relative_eq!((-sum * 3.0).wrapped().0, PI, epsilon = 1e-12);
+        assert_relative_eq!(Degrees(270.0f64).wrapped().0, -90.0, epsilon = 1e-12);
+        let mut a = Degrees(10.0f64);
+        a += Degrees(5.0);
+        a -= Degrees(1.0);
+        assert_eq!(a / 2.0, Degrees(7.0));
+        assert_eq!(format!("{}", Degrees(45.0f64)), "45°");
+        assert_eq!(format!("{:.2}", Radians(1.0f64)), "1.00 rad");
+    }
+
+    #[test]
+    fn rotation_apis_accept_angles_f32() {
+        let v = XYVec::new([1.0f32, 0.0]);
+        let by_degrees = v.rotated_by(Degrees(90.0));
+        let by_radians = v.rotated_by(Radians(std::f32::consts::FRAC_PI_2));
+        let by_scalar = v.rotated_by(std::f32::consts::FRAC_PI_2);
+        assert_relative_eq!(by_degrees.x(), by_radians.x(), epsilon = 1e-6);
+        assert_relative_eq!(by_degrees.y(), by_radians.y(), epsilon = 1e-6);
+        assert_eq!(by_radians, by_scalar);
+        let u = XYVec::from_angle(Degrees(150.0f32));
+        assert_relative_eq!(u.y(), 0.5, epsilon = 1e-6);
+        assert_relative_eq!(u.angle().to_degrees().0, 150.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn wrap_angle_f32() {
+        assert_relative_eq!(
+            wrap_angle(7.0f32),
+            7.0 - std::f32::consts::TAU,
+            epsilon = 1e-6
+        );
+        assert_relative_eq!(wrap_angle(0.5f32), 0.5);
+        assert_relative_eq!(wrap_angle(-0.5f32), -0.5);
+    }
+
+    #[test]
+    fn headings() {
+        let north = Heading::from_degrees(720.0f64);
+        assert_eq!(north.degrees(), 0.0);
+        assert_eq!(Heading::from_degrees(-1e-20f64).degrees(), 0.0);
+        assert_relative_eq!(north.to_math_angle().0, FRAC_PI_2);
+        let dir = north.to_direction();
+        assert_relative_eq!(dir.x(), 0.0);
+        assert_relative_eq!(dir.y(), 1.0);
+
+        // Math angle and compass bearing round trips.
+        for deg in [0.0, 30.0, 135.0, 200.0, 359.0] {
+            let h = Heading::from_degrees(deg);
+            let back = Heading::from_math_angle(h.to_math_angle());
+            assert_relative_eq!(back.degrees(), deg, epsilon = 1e-9);
+            let back = Heading::from_direction(h.to_direction());
+            assert_relative_eq!(back.degrees(), deg, epsilon = 1e-9);
+        }
+        assert_relative_eq!(
+            Heading::from_direction(XYVec::new([1.0f32, -1.0])).degrees(),
+            135.0,
+            epsilon = 1e-4
+        );
+
+        // Turns wrap, and differences take the short way round.
+        let h = Heading::from_degrees(350.0f64) + Degrees(20.0);
+        assert_relative_eq!(h.degrees(), 10.0, epsilon = 1e-9);
+        assert_relative_eq!((h - Degrees(30.0)).degrees(), 340.0, epsilon = 1e-9);
+        let turn = Heading::from_degrees(90.0f64) - Heading::from_degrees(300.0);
+        assert_relative_eq!(turn.0, 150.0, epsilon = 1e-9);
+        assert_eq!(format!("{:.1}", Heading::from_degrees(45.0f64)), "45.0°");
+    }
+
+    #[test]
+    fn rotate_towards_2d_f64() {
+        let v = XYVec::new([2.0f64, 0.0]);
+        let up = XYVec::new([0.0, 5.0]);
+        let r = v.rotate_towards(up, FRAC_PI_4);
+        assert_relative_eq!(r.x(), 2.0f64.sqrt(), epsilon = 1e-12);
+        assert_relative_eq!(r.y(), 2.0f64.sqrt(), epsilon = 1e-12);
+        // Reaching the target keeps the original length.
+        let r = v.rotate_towards(up, PI);
+        assert_relative_eq!(r.x(), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(r.y(), 2.0, epsilon = 1e-12);
+        // Clockwise targets turn clockwise.
+        let r = v.rotate_towards(XYVec::new([0.0, -1.0]), FRAC_PI_2 / 3.0);
+        assert!(r.y() < 0.0);
+        assert_eq!(v.rotate_towards(XYVec::new([0.0, 0.0]), 1.0), v);
+    }
+
+    #[test]
+    fn rotate_towards_3d_f64() {
+        let v = XYZVec::new([0.0f64, 0.0, 3.0]);
+        let target = XYZVec::new([1.0, 0.0, 0.0]);
+        let r = v.rotate_towards(target, FRAC_PI_4);
+        assert_relative_eq!(r.x(), 4.5f64.sqrt(), epsilon = 1e-12);
+        assert_relative_eq!(r.y(), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(r.z(), 4.5f64.sqrt(), epsilon = 1e-12);
+        let r = v.rotate_towards(target, 2.0);
+        assert_relative_eq!(r.x(), 3.0, epsilon = 1e-12);
+        assert_relative_eq!(r.z(), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn rotate_towards_opposite_f32() {
+        let v = XYZVec::new([1.0f32, 0.0, 0.0]);
+        let r = v.rotate_towards(-v, 0.1);
+        assert_relative_eq!(r.l2_norm(), 1.0, epsilon = 1e-6);
+        assert_relative_eq!(r.x(), 0.1f32.cos(), epsilon = 1e-6);
+        let r = v.rotate_towards(-v, 4.0);
+        assert_relative_eq!(r.x(), -1.0, epsilon = 1e-6);
+    }
+}
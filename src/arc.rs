@@ -0,0 +1,188 @@
+//! Circular arcs in the plane.
+
+use crate::{angle::Radians, VecFloat, VecInner, XYVec};
+use std::{
+    f64::consts::TAU,
+    fmt::{self, Formatter},
+};
+
+/// Arc of the circle around `center` with the given `radius`, running from
+/// `start_angle` to `end_angle` (radians, counterclockwise from `+x`). It
+/// turns counterclockwise when `end_angle > start_angle` and clockwise
+/// otherwise, and may wrap more than once.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Arc2<T> {
+    pub center: XYVec<T>,
+    pub radius: T,
+    pub start_angle: T,
+    pub end_angle: T,
+}
+
+impl<T: VecFloat> Arc2<T> {
+    pub fn new(
+        center: XYVec<T>,
+        radius: T,
+        start_angle: impl Into<Radians<T>>,
+        end_angle: impl Into<Radians<T>>,
+    ) -> Self {
+        Self {
+            center,
+            radius,
+            start_angle: start_angle.into().0,
+            end_angle: end_angle.into().0,
+        }
+    }
+
+    /// Signed angle turned from start to end; negative for clockwise arcs.
+    pub fn sweep(&self) -> T {
+        self.end_angle - self.start_angle
+    }
+
+    /// Point on the arc's circle at `angle`, whether or not the arc covers
+    /// it.
+    pub fn point_at_angle(&self, angle: impl Into<Radians<T>>) -> XYVec<T> {
+        self.center + XYVec::from_angle(angle).scale_by(self.radius)
+    }
+
+    pub fn start_point(&self) -> XYVec<T> {
+        self.point_at_angle(Radians(self.start_angle))
+    }
+
+    pub fn end_point(&self) -> XYVec<T> {
+        self.point_at_angle(Radians(self.end_angle))
+    }
+
+    /// Arc length.
+    pub fn length(&self) -> T {
+        self.sweep().abs() * self.radius
+    }
+
+    /// Whether the direction `angle` from the center falls within the arc,
+    /// endpoints included.
+    /// ```
+    ///     use xyzvec::{angle::Degrees, arc::Arc2, XYVec};
+    ///
+    ///     // Clockwise from 10° round to -100°, through 0°.
+    ///     let arc = Arc2::new(XYVec::new([0.0f64, 0.0]), 1.0, Degrees(10.0), Degrees(-100.0));
+    ///     assert!(arc.contains_angle(Degrees(-45.0)));
+    ///     assert!(arc.contains_angle(Degrees(370.0)));
+    ///     assert!(!arc.contains_angle(Degrees(90.0)));
+    /// ```
+    pub fn contains_angle(&self, angle: impl Into<Radians<T>>) -> bool {
+        let sweep = self.sweep().to_f64();
+        if sweep.abs() >= TAU {
+            return true;
+        }
+        let offset = angle.into().0.to_f64() - self.start_angle.to_f64();
+        // How far along the arc's direction of travel `angle` lies.
+        let along = if sweep >= 0.0 { offset } else { -offset };
+        along.rem_euclid(TAU) <= sweep.abs()
+    }
+
+    /// Unit tangent at `angle`, pointing in the arc's direction of travel.
+    fn tangent_at(&self, angle: T) -> XYVec<T> {
+        let dir = XYVec::from_angle(Radians(angle));
+        let left = XYVec::new([-dir.y(), dir.x()]);
+        if self.sweep() < T::zero() {
+            -left
+        } else {
+            left
+        }
+    }
+
+    /// Unit direction of travel leaving the start point.
+    pub fn start_tangent(&self) -> XYVec<T> {
+        self.tangent_at(self.start_angle)
+    }
+
+    /// Unit direction of travel arriving at the end point.
+    pub fn end_tangent(&self) -> XYVec<T> {
+        self.tangent_at(self.end_angle)
+    }
+
+    /// Polyline from the start point to the end point whose chords stay
+    /// within `tolerance` of the arc. Points are evenly spaced by angle.
+    /// ```
+    ///     use xyzvec::{arc::Arc2, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///     use std::f64::consts::PI;
+    ///
+    ///     let arc = Arc2::new(XYVec::new([1.0f64, 1.0]), 2.0, 0.0, PI);
+    ///     let points = arc.flatten(0.01);
+    ///     assert_eq!(points[0], arc.start_point());
+    ///     assert_relative_eq!(points[points.len() - 1].x(), -1.0);
+    ///     assert!(arc.flatten(0.001).len() > points.len());
+    /// ```
+    pub fn flatten(&self, tolerance: T) -> Vec<XYVec<T>> {
+        let (r, tol) = (self.radius.to_f64(), tolerance.to_f64());
+        let sweep = self.sweep().to_f64();
+        // A chord spanning angle `step` strays `r * (1 - cos(step / 2))`
+        // from the arc.
+        let step = if tol >= r {
+            TAU / 2.0
+        } else {
+            2.0 * (1.0 - tol / r).acos()
+        };
+        let n = if step > 0.0 {
+            (sweep.abs() / step).ceil().max(1.0) as usize
+        } else {
+            1
+        };
+        (0..=n)
+            .map(|i| {
+                let angle = self.start_angle.to_f64() + sweep * i as f64 / n as f64;
+                self.point_at_angle(Radians(T::from_f64(angle)))
+            })
+            .collect()
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Arc2<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Arc2 {{ center: {:?}, radius: {:?}, start_angle: {:?}, end_angle: {:?} }}",
+            self.center, self.radius, self.start_angle, self.end_angle
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Arc2;
+    use crate::{angle::Degrees, XYVec};
+    use approx::assert_relative_eq;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn quarter_f32() {
+        let arc = Arc2::new(XYVec::new([2.0f32, -1.0]), 3.0, 0.0, FRAC_PI_2);
+        assert_relative_eq!(arc.length(), 1.5 * PI);
+        assert_relative_eq!(arc.start_point().x(), 5.0);
+        assert_relative_eq!(arc.end_point().y(), 2.0);
+        assert_relative_eq!(arc.start_tangent().y(), 1.0);
+        assert_relative_eq!(arc.end_tangent().x(), -1.0);
+        assert!(arc.contains_angle(Degrees(45.0)));
+        assert!(arc.contains_angle(Degrees(-315.0)));
+        assert!(!arc.contains_angle(Degrees(135.0)));
+        let points = arc.flatten(0.01);
+        for w in points.windows(2) {
+            let mid = (w[0] + w[1]).scale_by(0.5);
+            let sag = 3.0 - (mid - arc.center).l2_norm();
+            assert!((0.0..=0.01).contains(&sag));
+        }
+
+        // Reversed, the tangents flip.
+        let back = Arc2::new(arc.center, 3.0, FRAC_PI_2, 0.0);
+        assert_relative_eq!(back.start_tangent().x(), 1.0);
+        assert_relative_eq!(back.end_tangent().y(), -1.0);
+        assert!(back.contains_angle(Degrees(45.0)));
+        assert!(!back.contains_angle(Degrees(-45.0)));
+        let full = Arc2::new(arc.center, 3.0, 0.0, 2.0 * PI);
+        assert!(full.contains_angle(Degrees(200.0)));
+        // A tolerance as coarse as the radius still ends on the end point.
+        let coarse = arc.flatten(10.0);
+        assert_eq!(coarse.len(), 2);
+        assert_relative_eq!(coarse[1].y(), 2.0);
+    }
+}
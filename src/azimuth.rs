@@ -0,0 +1,189 @@
+//! Azimuth/elevation/range coordinates for [`XYZVec`], as used for
+//! pointing antennas, turrets, and cameras.
+//!
+//! Azimuth is measured in the horizontal plane, counter-clockwise about
+//! the [`UpAxis`] (looking down it) from the axis after it in `x, y, z`
+//! order: from `+x` when `z` is up, from `+z` when `y` is up. Elevation is
+//! the angle above that plane, in `[-π/2, π/2]`. For a compass-style
+//! azimuth, clockwise from north, go through
+//! [`Heading::from_math_angle`](crate::angle::Heading::from_math_angle).
+//! ```
+//!     use xyzvec::{azimuth::UpAxis, XYZVec};
+//!     use approx::assert_relative_eq;
+//!     use std::f64::consts::FRAC_PI_4;
+//!
+//!     let v = XYZVec::new([1.0f64, 1.0, 2.0f64.sqrt()]);
+//!     let (az, el, range) = v.to_azimuth_elevation(UpAxis::Z);
+//!     assert_relative_eq!(az.0, FRAC_PI_4);
+//!     assert_relative_eq!(el.0, FRAC_PI_4);
+//!     assert_relative_eq!(range, 2.0);
+//!     let back = XYZVec::<f64>::from_azimuth_elevation(az, el, range, UpAxis::Z);
+//!     assert_relative_eq!(back.y(), 1.0, epsilon = 1e-12);
+//! ```
+
+use crate::{angle::Radians, VecInner, XYZVec};
+
+/// Which axis points up for azimuth and elevation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UpAxis {
+    X,
+    /// Common in graphics and game engines.
+    Y,
+    /// Common in robotics, surveying, and aerospace (east-north-up).
+    #[default]
+    Z,
+}
+
+impl UpAxis {
+    /// Component indices of zero azimuth, azimuth `π/2`, and up.
+    fn axes(self) -> [usize; 3] {
+        match self {
+            UpAxis::X => [1, 2, 0],
+            UpAxis::Y => [2, 0, 1],
+            UpAxis::Z => [0, 1, 2],
+        }
+    }
+}
+
+fn to_azimuth_elevation<T: VecInner>(
+    v: &XYZVec<T>,
+    up: UpAxis,
+    atan2: impl Fn(T, T) -> T,
+    sqrt: impl Fn(T) -> T,
+) -> (Radians<T>, Radians<T>, T) {
+    let [a, b, u] = up.axes().map(|i| v.as_ref()[i]);
+    let horizontal = sqrt(a * a + b * b);
+    (
+        Radians(atan2(b, a)),
+        Radians(atan2(u, horizontal)),
+        sqrt(v.l2_norm_sqd()),
+    )
+}
+
+fn from_azimuth_elevation<T: VecInner>(
+    azimuth: Radians<T>,
+    elevation: Radians<T>,
+    range: T,
+    up: UpAxis,
+    sin: impl Fn(T) -> T,
+    cos: impl Fn(T) -> T,
+) -> XYZVec<T> {
+    let horizontal = range * cos(elevation.0);
+    let parts = [
+        horizontal * cos(azimuth.0),
+        horizontal * sin(azimuth.0),
+        range * sin(elevation.0),
+    ];
+    let axes = up.axes();
+    XYZVec::new(std::array::from_fn(|i| {
+        parts[axes.iter().position(|&a| a == i).unwrap()]
+    }))
+}
+
+macro_rules! impl_azimuth_float {
+    ($($t:ty),*) => {$(
+        impl XYZVec<$t> {
+            /// `(azimuth, elevation, range)` of `self`. Both angles are
+            /// zero for the zero vector.
+            pub fn to_azimuth_elevation(&self, up: UpAxis) -> (Radians<$t>, Radians<$t>, $t) {
+                to_azimuth_elevation(self, up, <$t>::atan2, <$t>::sqrt)
+            }
+
+            /// The point at `range` in the direction given by `azimuth`
+            /// and `elevation`.
+            pub fn from_azimuth_elevation(
+                azimuth: impl Into<Radians<$t>>,
+                elevation: impl Into<Radians<$t>>,
+                range: $t,
+                up: UpAxis,
+            ) -> Self {
+                let (az, el) = (azimuth.into(), elevation.into());
+                from_azimuth_elevation(az, el, range, up, <$t>::sin, <$t>::cos)
+            }
+        }
+    )*};
+}
+
+impl_azimuth_float!(f32, f64);
+
+#[cfg(feature = "cordic")]
+use crate::CordicPhantomTrait;
+#[cfg(feature = "cordic")]
+use cordic::{atan2, cos, sin, sqrt, CordicNumber};
+#[cfg(feature = "cordic")]
+impl<T: CordicNumber + CordicPhantomTrait + std::fmt::Display + std::fmt::Debug> XYZVec<T> {
+    /// `(azimuth, elevation, range)` of `self`, by CORDIC. The range must
+    /// fit in `T`.
+    pub fn to_azimuth_elevation(&self, up: UpAxis) -> (Radians<T>, Radians<T>, T) {
+        to_azimuth_elevation(self, up, atan2, sqrt)
+    }
+
+    /// The point at `range` in the direction given by `azimuth` and
+    /// `elevation`, by CORDIC.
+    pub fn from_azimuth_elevation(
+        azimuth: impl Into<Radians<T>>,
+        elevation: impl Into<Radians<T>>,
+        range: T,
+        up: UpAxis,
+    ) -> Self {
+        let (az, el) = (azimuth.into(), elevation.into());
+        from_azimuth_elevation(az, el, range, up, sin, cos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UpAxis;
+    use crate::XYZVec;
+    use approx::assert_relative_eq;
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    #[test]
+    fn up_axes_f64() {
+        // Straight up and straight along zero azimuth, for each convention.
+        for (up, up_vec, zero_az) in [
+            (UpAxis::X, [1.0f64, 0.0, 0.0], [0.0f64, 1.0, 0.0]),
+            (UpAxis::Y, [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]),
+            (UpAxis::Z, [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]),
+        ] {
+            let (_, el, range) = XYZVec::new(up_vec).scale_by(3.0).to_azimuth_elevation(up);
+            assert_relative_eq!(el.0, FRAC_PI_2);
+            assert_relative_eq!(range, 3.0);
+            let (az, el, _) = XYZVec::new(zero_az).to_azimuth_elevation(up);
+            assert_eq!((az.0, el.0), (0.0, 0.0));
+        }
+        // Y up: azimuth turns from +z toward +x.
+        let (az, el, _) = XYZVec::new([1.0f64, -1.0, 0.0]).to_azimuth_elevation(UpAxis::Y);
+        assert_relative_eq!(az.0, FRAC_PI_2);
+        assert_relative_eq!(el.0, -FRAC_PI_4);
+    }
+
+    #[test]
+    fn round_trips_f32() {
+        for up in [UpAxis::X, UpAxis::Y, UpAxis::Z] {
+            let v = XYZVec::new([-2.0f32, 0.5, 3.0]);
+            let (az, el, range) = v.to_azimuth_elevation(up);
+            let back = XYZVec::<f32>::from_azimuth_elevation(az, el, range, up);
+            assert_relative_eq!(back.x(), v.x(), epsilon = 1e-5);
+            assert_relative_eq!(back.y(), v.y(), epsilon = 1e-5);
+            assert_relative_eq!(back.z(), v.z(), epsilon = 1e-5);
+        }
+        let behind = XYZVec::<f32>::from_azimuth_elevation(PI as f32, 0.0, 2.0, UpAxis::Z);
+        assert_relative_eq!(behind.x(), -2.0);
+    }
+
+    #[cfg(feature = "cordic")]
+    #[test]
+    fn round_trip_cordic() {
+        use fixed::types::I16F16;
+
+        let f = I16F16::from_num;
+        let v = XYZVec::new([f(3.0), f(-4.0), f(12.0)]);
+        let (az, el, range) = v.to_azimuth_elevation(UpAxis::Z);
+        assert!((range - f(13.0)).abs() < f(0.01));
+        assert!((az.0 - f((-4.0f64).atan2(3.0))).abs() < f(0.01));
+        assert!((el.0 - f(12.0f64.atan2(5.0))).abs() < f(0.01));
+        let back = XYZVec::from_azimuth_elevation(az, el, range, UpAxis::Z);
+        assert!((back - v).iter().all(|c| c.abs() < f(0.05)));
+    }
+}
@@ -0,0 +1,321 @@
+//! Circles and spheres, and the smallest ones enclosing a set of points.
+
+use crate::{VecFloat, VecNum, Vector, XYVec, XYZVec};
+
+/// Closed disk or ball of points within `radius` of `center`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Ball<V: Vector> {
+    pub center: V,
+    pub radius: V::Scalar,
+}
+
+pub type Circle<T> = Ball<XYVec<T>>;
+pub type Sphere<T> = Ball<XYZVec<T>>;
+
+impl<V: Vector> Ball<V>
+where
+    V::Scalar: VecNum,
+{
+    pub fn new(center: V, radius: V::Scalar) -> Self {
+        Self { center, radius }
+    }
+
+    /// Whether `p` lies inside the ball or on its boundary.
+    pub fn contains_point(&self, p: V) -> bool {
+        (p - self.center).l2_norm_sqd() <= self.radius * self.radius
+    }
+}
+
+impl<V: Vector> Ball<V>
+where
+    V::Scalar: VecFloat,
+{
+    /// Smallest ball with `a` and `b` on its boundary.
+    fn through_two(a: V, b: V) -> Self {
+        let two = V::Scalar::one() + V::Scalar::one();
+        Self::new(a.lerp(b, V::Scalar::one() / two), (b - a).l2_norm() / two)
+    }
+
+    /// `contains_point` with a little slack for rounding, so that points
+    /// on the boundary of a ball built through them are still inside.
+    fn covers(&self, p: V) -> bool {
+        let slack = V::Scalar::epsilon() * V::Scalar::from_f64(64.0);
+        (p - self.center).l2_norm() <= self.radius + self.radius * slack
+    }
+
+    /// Smallest of `candidates` that covers every point in `points`.
+    fn smallest_covering(candidates: impl Iterator<Item = Self>, points: &[V]) -> Self {
+        candidates
+            .filter(|ball| points.iter().all(|&p| ball.covers(p)))
+            .reduce(|best, ball| {
+                if ball.radius < best.radius {
+                    ball
+                } else {
+                    best
+                }
+            })
+            .expect("the ball through the farthest pair covers the rest")
+    }
+}
+
+impl<T: VecFloat> Circle<T> {
+    /// Circumcircle of a triangle, falling back to the smallest circle
+    /// through two of the points when they are collinear.
+    fn through_three(a: XYVec<T>, b: XYVec<T>, c: XYVec<T>) -> Self {
+        let (ab, ac) = (b - a, c - a);
+        let d = (ab.cross_prod(ac)) * (T::one() + T::one());
+        if d == T::zero() {
+            let pairs = [(a, b), (a, c), (b, c)].map(|(p, q)| Self::through_two(p, q));
+            return Self::smallest_covering(pairs.into_iter(), &[a, b, c]);
+        }
+        let (bb, cc) = (ab.l2_norm_sqd(), ac.l2_norm_sqd());
+        let offset = XYVec::new([
+            (ac.y() * bb - ab.y() * cc) / d,
+            (ab.x() * cc - ac.x() * bb) / d,
+        ]);
+        Self::new(a + offset, offset.l2_norm())
+    }
+}
+
+impl<T: VecFloat> Sphere<T> {
+    /// Smallest sphere with three points on its boundary: the one through
+    /// their circumcircle. Collinear points fall back to two of them.
+    fn through_three(a: XYZVec<T>, b: XYZVec<T>, c: XYZVec<T>) -> Self {
+        let (ab, ac) = (b - a, c - a);
+        let n = ab.cross(ac);
+        let nn = n.l2_norm_sqd();
+        if nn == T::zero() {
+            let pairs = [(a, b), (a, c), (b, c)].map(|(p, q)| Self::through_two(p, q));
+            return Self::smallest_covering(pairs.into_iter(), &[a, b, c]);
+        }
+        let two = T::one() + T::one();
+        let offset = (n.cross(ab).scale_by(ac.l2_norm_sqd())
+            + ac.cross(n).scale_by(ab.l2_norm_sqd()))
+        .div_by(two * nn);
+        Self::new(a + offset, offset.l2_norm())
+    }
+
+    /// Circumsphere of a tetrahedron, falling back to the smallest sphere
+    /// through three or two of the points when they are coplanar.
+    fn through_four(a: XYZVec<T>, b: XYZVec<T>, c: XYZVec<T>, d: XYZVec<T>) -> Self {
+        let (ab, ac, ad) = (b - a, c - a, d - a);
+        let det = ab.dot_prod(ac.cross(ad)) * (T::one() + T::one());
+        if det == T::zero() {
+            let points = [a, b, c, d];
+            let triples = [(a, b, c), (a, b, d), (a, c, d), (b, c, d)]
+                .map(|(p, q, r)| Self::through_three(p, q, r));
+            let pairs = [(a, b), (a, c), (a, d), (b, c), (b, d), (c, d)]
+                .map(|(p, q)| Self::through_two(p, q));
+            return Self::smallest_covering(triples.into_iter().chain(pairs), &points);
+        }
+        let offset = (ac.cross(ad).scale_by(ab.l2_norm_sqd())
+            + ad.cross(ab).scale_by(ac.l2_norm_sqd())
+            + ab.cross(ac).scale_by(ad.l2_norm_sqd()))
+        .div_by(det);
+        Self::new(a + offset, offset.l2_norm())
+    }
+}
+
+/// `points` in a fixed pseudo-random order, which keeps Welzl's algorithm
+/// at its expected linear time on sorted or adversarial input.
+fn shuffled<V: Copy>(points: &[V]) -> Vec<V> {
+    let mut out = points.to_vec();
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    for i in (1..out.len()).rev() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        out.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+    out
+}
+
+/// Smallest circle containing every point, by Welzl's algorithm.
+/// `None` if `points` is empty.
+/// ```
+///     use xyzvec::{ball::bounding_circle, XYVec};
+///     use approx::assert_relative_eq;
+///
+///     let points = [
+///         XYVec::new([0.0f64, 0.0]),
+///         XYVec::new([4.0, 0.0]),
+///         XYVec::new([2.0, 1.0]),
+///     ];
+///     let circle = bounding_circle(&points).unwrap();
+///     assert_relative_eq!(circle.center.x(), 2.0);
+///     assert_relative_eq!(circle.center.y(), 0.0);
+///     assert_relative_eq!(circle.radius, 2.0);
+/// ```
+pub fn bounding_circle<T: VecFloat>(points: &[XYVec<T>]) -> Option<Circle<T>> {
+    let points = shuffled(points);
+    let mut ball = Circle::new(*points.first()?, T::zero());
+    for i in 1..points.len() {
+        if ball.covers(points[i]) {
+            continue;
+        }
+        ball = Circle::new(points[i], T::zero());
+        for j in 0..i {
+            if ball.covers(points[j]) {
+                continue;
+            }
+            ball = Circle::through_two(points[i], points[j]);
+            for k in 0..j {
+                if !ball.covers(points[k]) {
+                    ball = Circle::through_three(points[i], points[j], points[k]);
+                }
+            }
+        }
+    }
+    Some(ball)
+}
+
+/// Smallest sphere containing every point, by Welzl's algorithm.
+/// `None` if `points` is empty.
+/// ```
+///     use xyzvec::{ball::bounding_sphere, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let points = [
+///         XYZVec::new([1.0f64, 0.0, 0.0]),
+///         XYZVec::new([-1.0, 0.0, 0.0]),
+///         XYZVec::new([0.0, 0.5, 0.5]),
+///     ];
+///     let sphere = bounding_sphere(&points).unwrap();
+///     assert_relative_eq!(sphere.radius, 1.0);
+///     assert!(sphere.contains_point(XYZVec::new([0.0, 0.0, 0.0])));
+/// ```
+pub fn bounding_sphere<T: VecFloat>(points: &[XYZVec<T>]) -> Option<Sphere<T>> {
+    let points = shuffled(points);
+    let mut ball = Sphere::new(*points.first()?, T::zero());
+    for i in 1..points.len() {
+        if ball.covers(points[i]) {
+            continue;
+        }
+        ball = Sphere::new(points[i], T::zero());
+        for j in 0..i {
+            if ball.covers(points[j]) {
+                continue;
+            }
+            ball = Sphere::through_two(points[i], points[j]);
+            for k in 0..j {
+                if ball.covers(points[k]) {
+                    continue;
+                }
+                ball = Sphere::through_three(points[i], points[j], points[k]);
+                for l in 0..k {
+                    if !ball.covers(points[l]) {
+                        ball = Sphere::through_four(points[i], points[j], points[k], points[l]);
+                    }
+                }
+            }
+        }
+    }
+    Some(ball)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn pseudo_random(n: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn circle_contains_all_f64() {
+        let r = pseudo_random(400, 7);
+        let points: Vec<XYVec<f64>> = r.chunks(2).map(|c| XYVec::new([c[0], c[1]])).collect();
+        let circle = bounding_circle(&points).unwrap();
+        for &p in points.iter() {
+            assert!(circle.covers(p));
+        }
+        // Minimal: at least two points lie on the boundary.
+        let on_boundary = points
+            .iter()
+            .filter(|&&p| ((p - circle.center).l2_norm() - circle.radius).abs() < 1e-9)
+            .count();
+        assert!(on_boundary >= 2);
+        assert!(circle.radius <= 2.0f64.sqrt());
+    }
+
+    #[test]
+    fn circle_degenerate_f32() {
+        assert!(bounding_circle::<f32>(&[]).is_none());
+        let p = XYVec::new([1.0f32, 2.0]);
+        assert_eq!(bounding_circle(&[p, p, p]), Some(Circle::new(p, 0.0)));
+        let collinear: Vec<XYVec<f32>> = (0..10).map(|i| XYVec::new([i as f32, 0.0])).collect();
+        let circle = bounding_circle(&collinear).unwrap();
+        assert_relative_eq!(circle.center.x(), 4.5);
+        assert_relative_eq!(circle.radius, 4.5);
+    }
+
+    #[test]
+    fn circle_triangle_f64() {
+        // An acute triangle is bounded by its circumcircle.
+        let points = [
+            XYVec::new([0.0f64, 0.0]),
+            XYVec::new([2.0, 0.0]),
+            XYVec::new([1.0, 1.5]),
+        ];
+        let circle = bounding_circle(&points).unwrap();
+        assert_relative_eq!(circle.center.x(), 1.0);
+        assert_relative_eq!(circle.center.y(), 5.0 / 12.0);
+        assert_relative_eq!(circle.radius, 13.0 / 12.0);
+    }
+
+    #[test]
+    fn sphere_contains_all_f64() {
+        let r = pseudo_random(600, 11);
+        let points: Vec<XYZVec<f64>> = r
+            .chunks(3)
+            .map(|c| XYZVec::new([c[0], c[1], c[2]]))
+            .collect();
+        let sphere = bounding_sphere(&points).unwrap();
+        for &p in points.iter() {
+            assert!(sphere.covers(p));
+        }
+        let on_boundary = points
+            .iter()
+            .filter(|&&p| ((p - sphere.center).l2_norm() - sphere.radius).abs() < 1e-9)
+            .count();
+        assert!(on_boundary >= 2);
+        assert!(sphere.radius <= 3.0f64.sqrt());
+    }
+
+    #[test]
+    fn sphere_regular_tetrahedron_f64() {
+        let points = [
+            XYZVec::new([1.0f64, 1.0, 1.0]),
+            XYZVec::new([1.0, -1.0, -1.0]),
+            XYZVec::new([-1.0, 1.0, -1.0]),
+            XYZVec::new([-1.0, -1.0, 1.0]),
+            XYZVec::new([0.1, 0.2, -0.3]),
+        ];
+        let sphere = bounding_sphere(&points).unwrap();
+        assert_relative_eq!(sphere.center.l2_norm(), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(sphere.radius, 3.0f64.sqrt(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn sphere_coplanar_f32() {
+        // A square in the z = 1 plane: four cospherical, coplanar points.
+        let points = [
+            XYZVec::new([1.0f32, 1.0, 1.0]),
+            XYZVec::new([-1.0, 1.0, 1.0]),
+            XYZVec::new([-1.0, -1.0, 1.0]),
+            XYZVec::new([1.0, -1.0, 1.0]),
+        ];
+        let sphere = bounding_sphere(&points).unwrap();
+        assert_relative_eq!(sphere.center.z(), 1.0, epsilon = 1e-6);
+        assert_relative_eq!(sphere.radius, 2.0f32.sqrt(), epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,118 @@
+//! Orthonormal bases. See also [`XYZVec::any_orthonormal_pair`](crate::XYZVec::any_orthonormal_pair)
+//! for completing a basis from a single direction.
+
+use crate::{VecFloat, VecNum, Vector};
+
+/// Orthonormalizes `vectors` in order with modified Gram-Schmidt: the first
+/// output is the first input normalized, and each later output is its input
+/// with the components along the earlier outputs removed.
+///
+/// Returns `None` if the inputs are linearly dependent, i.e. if some input
+/// loses all but a `sqrt(epsilon)` fraction of its length to the earlier
+/// ones (or is zero).
+/// ```
+///     use xyzvec::{basis::gram_schmidt, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let [u, v] = gram_schmidt([
+///         XYZVec::new([2.0f64, 0.0, 0.0]),
+///         XYZVec::new([1.0, 1.0, 0.0]),
+///     ])
+///     .unwrap();
+///     assert_relative_eq!(u.x(), 1.0);
+///     assert_relative_eq!(v.x(), 0.0);
+///     assert_relative_eq!(v.y(), 1.0);
+///
+///     let parallel = [XYZVec::new([1.0f64, 2.0, 3.0]), XYZVec::new([2.0, 4.0, 6.0])];
+///     assert!(gram_schmidt(parallel).is_none());
+/// ```
+pub fn gram_schmidt<V, const N: usize>(vectors: [V; N]) -> Option<[V; N]>
+where
+    V: Vector,
+    V::Scalar: VecFloat,
+{
+    let mut out = vectors;
+    for i in 0..N {
+        let original_len_sqd = out[i].l2_norm_sqd();
+        let mut v = out[i];
+        for u in out[..i].iter() {
+            v -= u.scale_by(v.dot_prod(*u));
+        }
+        let len_sqd = v.l2_norm_sqd();
+        if len_sqd <= original_len_sqd * V::Scalar::epsilon() || len_sqd == V::Scalar::zero() {
+            return None;
+        }
+        out[i] = v.div_by(len_sqd.sqrt());
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::gram_schmidt;
+    use crate::{Vector, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+
+    fn assert_orthonormal<V: Vector<Scalar = f64>>(vs: &[V]) {
+        for (i, a) in vs.iter().enumerate() {
+            for (j, b) in vs.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert_relative_eq!(a.dot_prod(*b), expected, epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn any_orthonormal_pair_f64() {
+        let normals = [
+            XYZVec::new([0.0f64, 0.0, 1.0]),
+            XYZVec::new([0.0, 0.0, -1.0]),
+            XYZVec::new([1.0, 0.0, 0.0]),
+            XYZVec::new([0.3, -0.4, 1e-9]),
+            XYZVec::new([-2.0, 5.0, -3.0]),
+            XYZVec::new([0.01, 0.02, -0.99]),
+        ];
+        for n in normals {
+            let (t, b) = n.any_orthonormal_pair();
+            let unit = n.div_by(n.l2_norm());
+            assert_orthonormal(&[t, b, unit]);
+            let c = t.cross(b);
+            assert_relative_eq!(c.x(), unit.x(), epsilon = 1e-12);
+            assert_relative_eq!(c.y(), unit.y(), epsilon = 1e-12);
+            assert_relative_eq!(c.z(), unit.z(), epsilon = 1e-12);
+            assert_eq!(n.any_orthonormal(), t);
+        }
+    }
+
+    #[test]
+    fn any_orthonormal_f32() {
+        let n = XYZVec::new([3.0f32, -1.0, 0.5]);
+        let t = n.any_orthonormal();
+        assert_relative_eq!(t.dot_prod(n), 0.0, epsilon = 1e-6);
+        assert_relative_eq!(Vector::l2_norm(&t), 1.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn gram_schmidt_3d_f64() {
+        let basis = gram_schmidt([
+            XYZVec::new([1.0f64, 1.0, 0.0]),
+            XYZVec::new([1.0, 0.0, 1.0]),
+            XYZVec::new([0.0, 1.0, 1.0]),
+        ])
+        .unwrap();
+        assert_orthonormal(&basis);
+        // The first direction is kept.
+        assert_relative_eq!(basis[0].x(), 0.5f64.sqrt());
+        assert_relative_eq!(basis[0].y(), 0.5f64.sqrt());
+    }
+
+    #[test]
+    fn gram_schmidt_dependent_f32() {
+        let a = XYZVec::new([1.0f32, 0.0, 0.0]);
+        let b = XYZVec::new([0.0f32, 1.0, 0.0]);
+        assert!(gram_schmidt([a, b, a + b]).is_none());
+        assert!(gram_schmidt([a, XYZVec::<f32>::zeroes()]).is_none());
+        assert!(gram_schmidt([XYVec::new([1.0f32, 0.0]), XYVec::new([1.0, 1e-6])]).is_none());
+        assert!(gram_schmidt([XYVec::new([1.0f32, 0.0]), XYVec::new([1.0, 1e-2])]).is_some());
+    }
+}
@@ -0,0 +1,347 @@
+//! Quadratic and cubic Bézier curves over [`XYVec`](crate::XYVec) or
+//! [`XYZVec`](crate::XYZVec) control points.
+
+use crate::{queries::closest_point_on_segment, VecFloat, VecNum, Vector};
+
+/// Maximum subdivision depth when flattening, bounding output to 2^16 segments.
+const MAX_FLATTEN_DEPTH: u32 = 16;
+
+/// Gauss-Legendre nodes on `[-1, 1]` and their weights.
+const GAUSS_LEGENDRE_5: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_888_9),
+    (-0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (0.538_469_310_105_683_1, 0.478_628_670_499_366_5),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189_1),
+    (0.906_179_845_938_664, 0.236_926_885_056_189_1),
+];
+
+/// Number of equal pieces `arc_length` integrates over.
+const ARC_LENGTH_PIECES: usize = 8;
+
+fn half<T: VecNum>() -> T {
+    T::one() / (T::one() + T::one())
+}
+
+/// Integrates `|derivative(t)|` over `[0, 1]`.
+fn integrate_speed<V, F>(derivative: F) -> V::Scalar
+where
+    V: Vector,
+    V::Scalar: VecFloat,
+    F: Fn(V::Scalar) -> V,
+{
+    let pieces = V::Scalar::from_f64(ARC_LENGTH_PIECES as f64);
+    let mut total = V::Scalar::zero();
+    for i in 0..ARC_LENGTH_PIECES {
+        let start = V::Scalar::from_f64(i as f64) / pieces;
+        for (node, weight) in GAUSS_LEGENDRE_5 {
+            let t = start + V::Scalar::from_f64((node + 1.0) / 2.0) / pieces;
+            total += derivative(t).l2_norm() * V::Scalar::from_f64(weight / 2.0) / pieces;
+        }
+    }
+    total
+}
+
+/// Whether each control point lies within `tolerance` of the chord `a`-`b`.
+fn is_flat<V>(a: V, b: V, controls: &[V], tolerance_sqd: V::Scalar) -> bool
+where
+    V: Vector,
+    V::Scalar: VecNum,
+{
+    controls.iter().all(|&c| {
+        let (q, _) = closest_point_on_segment(c, a, b);
+        (c - q).l2_norm_sqd() <= tolerance_sqd
+    })
+}
+
+/// Bézier curve with control points `p0`, `p1`, `p2`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct QuadraticBezier<V> {
+    pub p0: V,
+    pub p1: V,
+    pub p2: V,
+}
+
+impl<V: Vector> QuadraticBezier<V>
+where
+    V::Scalar: VecNum,
+{
+    pub fn new(p0: V, p1: V, p2: V) -> Self {
+        Self { p0, p1, p2 }
+    }
+
+    /// Point on the curve at `t` in `[0, 1]`.
+    /// ```
+    ///     use xyzvec::{QuadraticBezier, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let c = QuadraticBezier::new(
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([1.0, 2.0]),
+    ///         XYVec::new([2.0, 0.0]),
+    ///     );
+    ///     let p = c.eval(0.5);
+    ///     assert_relative_eq!(p.x(), 1.0);
+    ///     assert_relative_eq!(p.y(), 1.0);
+    /// ```
+    pub fn eval(&self, t: V::Scalar) -> V {
+        let a = self.p0.lerp(self.p1, t);
+        let b = self.p1.lerp(self.p2, t);
+        a.lerp(b, t)
+    }
+
+    /// Derivative with respect to `t`.
+    pub fn derivative(&self, t: V::Scalar) -> V {
+        let one = V::Scalar::one();
+        let d = (self.p1 - self.p0).scale_by(one - t) + (self.p2 - self.p1).scale_by(t);
+        d.scale_by(one + one)
+    }
+
+    /// Splits the curve at `t` into two curves covering `[0, t]` and `[t, 1]`.
+    pub fn split(&self, t: V::Scalar) -> (Self, Self) {
+        let a = self.p0.lerp(self.p1, t);
+        let b = self.p1.lerp(self.p2, t);
+        let mid = a.lerp(b, t);
+        (Self::new(self.p0, a, mid), Self::new(mid, b, self.p2))
+    }
+
+    /// Polyline approximating the curve to within `tolerance`, starting at
+    /// `p0` and ending at `p2`.
+    pub fn flatten(&self, tolerance: V::Scalar) -> Vec<V> {
+        let mut points = vec![self.p0];
+        self.flatten_into(tolerance * tolerance, MAX_FLATTEN_DEPTH, &mut points);
+        points
+    }
+
+    fn flatten_into(&self, tolerance_sqd: V::Scalar, depth: u32, points: &mut Vec<V>) {
+        if depth == 0 || is_flat(self.p0, self.p2, &[self.p1], tolerance_sqd) {
+            points.push(self.p2);
+            return;
+        }
+        let (a, b) = self.split(half());
+        a.flatten_into(tolerance_sqd, depth - 1, points);
+        b.flatten_into(tolerance_sqd, depth - 1, points);
+    }
+}
+
+impl<V: Vector> QuadraticBezier<V>
+where
+    V::Scalar: VecFloat,
+{
+    /// Arc length, approximated by Gauss-Legendre quadrature.
+    pub fn arc_length(&self) -> V::Scalar {
+        integrate_speed(|t| self.derivative(t))
+    }
+}
+
+/// Bézier curve with control points `p0`, `p1`, `p2`, `p3`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CubicBezier<V> {
+    pub p0: V,
+    pub p1: V,
+    pub p2: V,
+    pub p3: V,
+}
+
+impl<V: Vector> CubicBezier<V>
+where
+    V::Scalar: VecNum,
+{
+    pub fn new(p0: V, p1: V, p2: V, p3: V) -> Self {
+        Self { p0, p1, p2, p3 }
+    }
+
+    /// Point on the curve at `t` in `[0, 1]`.
+    /// ```
+    ///     use xyzvec::{CubicBezier, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let c = CubicBezier::new(
+    ///         XYZVec::new([0.0f64, 0.0, 0.0]),
+    ///         XYZVec::new([0.0, 1.0, 0.0]),
+    ///         XYZVec::new([1.0, 1.0, 0.0]),
+    ///         XYZVec::new([1.0, 0.0, 1.0]),
+    ///     );
+    ///     let p = c.eval(0.5);
+    ///     assert_relative_eq!(p.x(), 0.5);
+    ///     assert_relative_eq!(p.y(), 0.75);
+    ///     assert_relative_eq!(p.z(), 0.125);
+    /// ```
+    pub fn eval(&self, t: V::Scalar) -> V {
+        let a = self.p0.lerp(self.p1, t);
+        let b = self.p1.lerp(self.p2, t);
+        let c = self.p2.lerp(self.p3, t);
+        a.lerp(b, t).lerp(b.lerp(c, t), t)
+    }
+
+    /// Derivative with respect to `t`.
+    pub fn derivative(&self, t: V::Scalar) -> V {
+        let one = V::Scalar::one();
+        let two = one + one;
+        let s = one - t;
+        let d = (self.p1 - self.p0).scale_by(s * s)
+            + (self.p2 - self.p1).scale_by(two * s * t)
+            + (self.p3 - self.p2).scale_by(t * t);
+        d.scale_by(two + one)
+    }
+
+    /// Splits the curve at `t` into two curves covering `[0, t]` and `[t, 1]`.
+    pub fn split(&self, t: V::Scalar) -> (Self, Self) {
+        let a = self.p0.lerp(self.p1, t);
+        let b = self.p1.lerp(self.p2, t);
+        let c = self.p2.lerp(self.p3, t);
+        let ab = a.lerp(b, t);
+        let bc = b.lerp(c, t);
+        let mid = ab.lerp(bc, t);
+        (
+            Self::new(self.p0, a, ab, mid),
+            Self::new(mid, bc, c, self.p3),
+        )
+    }
+
+    /// Polyline approximating the curve to within `tolerance`, starting at
+    /// `p0` and ending at `p3`.
+    /// ```
+    ///     use xyzvec::{CubicBezier, XYVec};
+    ///
+    ///     let c = CubicBezier::new(
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([0.0, 1.0]),
+    ///         XYVec::new([1.0, 1.0]),
+    ///         XYVec::new([1.0, 0.0]),
+    ///     );
+    ///     let coarse = c.flatten(0.1);
+    ///     let fine = c.flatten(0.001);
+    ///     assert!(coarse.len() < fine.len());
+    ///     assert_eq!(fine[0], c.p0);
+    ///     assert_eq!(fine[fine.len() - 1], c.p3);
+    /// ```
+    pub fn flatten(&self, tolerance: V::Scalar) -> Vec<V> {
+        let mut points = vec![self.p0];
+        self.flatten_into(tolerance * tolerance, MAX_FLATTEN_DEPTH, &mut points);
+        points
+    }
+
+    fn flatten_into(&self, tolerance_sqd: V::Scalar, depth: u32, points: &mut Vec<V>) {
+        if depth == 0 || is_flat(self.p0, self.p3, &[self.p1, self.p2], tolerance_sqd) {
+            points.push(self.p3);
+            return;
+        }
+        let (a, b) = self.split(half());
+        a.flatten_into(tolerance_sqd, depth - 1, points);
+        b.flatten_into(tolerance_sqd, depth - 1, points);
+    }
+}
+
+impl<V: Vector> CubicBezier<V>
+where
+    V::Scalar: VecFloat,
+{
+    /// Arc length, approximated by Gauss-Legendre quadrature.
+    pub fn arc_length(&self) -> V::Scalar {
+        integrate_speed(|t| self.derivative(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{queries::closest_point_on_segment, CubicBezier, QuadraticBezier, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    fn arc() -> CubicBezier<XYVec<f64>> {
+        // Standard approximation of a quarter unit circle.
+        let k = 0.552_284_749_831;
+        CubicBezier::new(
+            XYVec::new([1.0, 0.0]),
+            XYVec::new([1.0, k]),
+            XYVec::new([k, 1.0]),
+            XYVec::new([0.0, 1.0]),
+        )
+    }
+
+    #[test]
+    fn split_matches_eval_f64() {
+        let c = arc();
+        let (a, b) = c.split(0.3);
+        for i in 0..=10 {
+            let s = i as f64 / 10.0;
+            let pa = a.eval(s);
+            let qa = c.eval(0.3 * s);
+            assert_relative_eq!(pa.x(), qa.x(), epsilon = 1e-12);
+            assert_relative_eq!(pa.y(), qa.y(), epsilon = 1e-12);
+            let pb = b.eval(s);
+            let qb = c.eval(0.3 + 0.7 * s);
+            assert_relative_eq!(pb.x(), qb.x(), epsilon = 1e-12);
+            assert_relative_eq!(pb.y(), qb.y(), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn derivative_f64() {
+        let c = arc();
+        let h = 1e-6;
+        for t in [0.1, 0.5, 0.9] {
+            let d = c.derivative(t);
+            let fd = (c.eval(t + h) - c.eval(t - h)).div_by(2.0 * h);
+            assert_relative_eq!(d.x(), fd.x(), epsilon = 1e-6);
+            assert_relative_eq!(d.y(), fd.y(), epsilon = 1e-6);
+        }
+        let q = QuadraticBezier::new(
+            XYZVec::new([0.0f64, 0.0, 0.0]),
+            XYZVec::new([1.0, 2.0, 3.0]),
+            XYZVec::new([2.0, 0.0, 0.0]),
+        );
+        let d = q.derivative(0.0);
+        assert_relative_eq!(d.x(), 2.0);
+        assert_relative_eq!(d.y(), 4.0);
+        assert_relative_eq!(d.z(), 6.0);
+    }
+
+    #[test]
+    fn arc_length_f64() {
+        assert_relative_eq!(
+            arc().arc_length(),
+            std::f64::consts::FRAC_PI_2,
+            epsilon = 1e-3
+        );
+        let line = QuadraticBezier::new(
+            XYVec::new([0.0f64, 0.0]),
+            XYVec::new([1.0, 1.0]),
+            XYVec::new([3.0, 3.0]),
+        );
+        assert_relative_eq!(line.arc_length(), 18.0f64.sqrt(), epsilon = 1e-9);
+    }
+
+    #[test]
+    fn flatten_within_tolerance_f32() {
+        let c = QuadraticBezier::new(
+            XYVec::new([0.0f32, 0.0]),
+            XYVec::new([5.0, 10.0]),
+            XYVec::new([10.0, 0.0]),
+        );
+        let tolerance = 0.05;
+        let points = c.flatten(tolerance);
+        for i in 0..=100 {
+            let p = c.eval(i as f32 / 100.0);
+            let nearest = points
+                .windows(2)
+                .map(|w| (p - closest_point_on_segment(p, w[0], w[1]).0).l2_norm())
+                .fold(f32::INFINITY, f32::min);
+            assert!(nearest <= tolerance * 1.01);
+        }
+    }
+
+    #[test]
+    fn eval_fixed() {
+        let f = |v: f64| I28F4::from_num(v);
+        let c = QuadraticBezier::new(
+            XYVec::new([f(0.0), f(0.0)]),
+            XYVec::new([f(2.0), f(4.0)]),
+            XYVec::new([f(4.0), f(0.0)]),
+        );
+        assert_eq!(c.eval(f(0.5)), XYVec::new([f(2.0), f(2.0)]));
+        let (a, _) = c.split(f(0.5));
+        assert_eq!(a.p1, XYVec::new([f(1.0), f(2.0)]));
+        assert_eq!(c.flatten(f(0.5)).last(), Some(&c.p2));
+    }
+}
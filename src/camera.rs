@@ -0,0 +1,426 @@
+//! Camera placement and projection.
+//!
+//! Cameras follow the right-handed OpenGL convention: in view space the
+//! camera sits at the origin looking down `-z`, with `+y` up and `+x` to
+//! the right.
+
+use crate::{pose::Pose3, Mat3, Mat4, VecFloat, VecInner, VecNum, Vector, XYVec, XYZVec};
+use std::fmt::{self, Formatter};
+
+impl<T: VecFloat> XYVec<T> {
+    /// Heading from `self` toward `target`, in radians counterclockwise
+    /// from the `+x` axis, in `(-π, π]`. Zero if the points coincide.
+    /// ```
+    ///     use xyzvec::XYVec;
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let eye = XYVec::new([1.0f64, 1.0]);
+    ///     assert_relative_eq!(eye.look_at(XYVec::new([1.0, 3.0])), std::f64::consts::FRAC_PI_2);
+    /// ```
+    pub fn look_at(&self, target: Self) -> T {
+        let d = target - *self;
+        d.y().atan2(d.x())
+    }
+}
+
+impl<T: VecFloat> Pose3<T> {
+    /// Camera-to-world pose of a camera at `eye` looking at `target`, with
+    /// `up` giving the rough direction of its `+y` axis. `None` if `eye`
+    /// and `target` coincide or `up` is parallel to the line of sight.
+    pub fn look_at(eye: XYZVec<T>, target: XYZVec<T>, up: XYZVec<T>) -> Option<Self> {
+        let forward = target - eye;
+        let side = forward.cross(up);
+        let scale = forward.l2_norm_sqd() * up.l2_norm_sqd();
+        if scale == T::zero() || side.l2_norm_sqd() <= scale * T::epsilon() * T::epsilon() {
+            return None;
+        }
+        let forward = forward.div_by(forward.l2_norm());
+        let side = side.div_by(side.l2_norm());
+        let up = side.cross(forward);
+        Some(Self::new(eye, Mat3::from_cols([side, up, -forward])))
+    }
+}
+
+impl<T: VecFloat> Mat4<T> {
+    /// View matrix taking world coordinates to the view space of a camera
+    /// at `eye` looking at `target`; the inverse of
+    /// [`Pose3::look_at`]. `None` in the same degenerate cases.
+    /// ```
+    ///     use xyzvec::{Mat4, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let eye = XYZVec::new([0.0f64, 0.0, 5.0]);
+    ///     let view = Mat4::look_at(eye, XYZVec::new([0.0, 0.0, 0.0]), XYZVec::new([0.0, 1.0, 0.0])).unwrap();
+    ///     let p = view.transform_point(XYZVec::new([1.0, 2.0, 0.0])).unwrap();
+    ///     assert_relative_eq!(p.x(), 1.0);
+    ///     assert_relative_eq!(p.y(), 2.0);
+    ///     assert_relative_eq!(p.z(), -5.0);
+    /// ```
+    pub fn look_at(eye: XYZVec<T>, target: XYZVec<T>, up: XYZVec<T>) -> Option<Self> {
+        Pose3::look_at(eye, target, up).map(|pose| pose.inverse().to_mat4())
+    }
+}
+
+/// Which way the camera looks along `z` in view space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Handedness {
+    /// Looking down `-z`, as in OpenGL and most modelling tools.
+    Right,
+    /// Looking down `+z`, as in Direct3D.
+    Left,
+}
+
+/// Range that visible depths map to in normalized device coordinates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepthRange {
+    /// `[-1, 1]`, as in OpenGL.
+    NegOneToOne,
+    /// `[0, 1]`, as in Direct3D, Vulkan, Metal, and wgpu.
+    ZeroToOne,
+}
+
+/// Conventions a projection matrix is built for. The near plane always
+/// maps to the low end of the depth range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClipConvention {
+    pub handedness: Handedness,
+    pub depth: DepthRange,
+}
+
+impl ClipConvention {
+    pub const OPENGL: Self = Self {
+        handedness: Handedness::Right,
+        depth: DepthRange::NegOneToOne,
+    };
+    pub const DIRECTX: Self = Self {
+        handedness: Handedness::Left,
+        depth: DepthRange::ZeroToOne,
+    };
+    /// Right-handed with `[0, 1]` depth, as used by wgpu and Vulkan
+    /// (Vulkan additionally points `+y` down in clip space).
+    pub const WGPU: Self = Self {
+        handedness: Handedness::Right,
+        depth: DepthRange::ZeroToOne,
+    };
+
+    /// Sign of view-space `z` in front of the camera.
+    fn forward<T: VecFloat>(&self) -> T {
+        match self.handedness {
+            Handedness::Right => -T::one(),
+            Handedness::Left => T::one(),
+        }
+    }
+}
+
+impl<T: VecFloat> Mat4<T> {
+    /// Perspective projection with vertical field of view `fov_y` radians,
+    /// width-to-height ratio `aspect`, and positive distances `near` and
+    /// `far` to the clipping planes.
+    /// ```
+    ///     use xyzvec::{camera::ClipConvention, Mat4, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let proj = Mat4::perspective(std::f64::consts::FRAC_PI_2, 2.0, 1.0, 10.0, ClipConvention::OPENGL);
+    ///     let near = proj.transform_point(XYZVec::new([2.0, 1.0, -1.0])).unwrap();
+    ///     assert_relative_eq!(near.x(), 1.0);
+    ///     assert_relative_eq!(near.y(), 1.0);
+    ///     assert_relative_eq!(near.z(), -1.0);
+    ///     let far = proj.transform_point(XYZVec::new([0.0, 0.0, -10.0])).unwrap();
+    ///     assert_relative_eq!(far.z(), 1.0, epsilon = 1e-12);
+    /// ```
+    pub fn perspective(fov_y: T, aspect: T, near: T, far: T, convention: ClipConvention) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let two = one + one;
+        let half = fov_y / two;
+        let f = half.cos() / half.sin();
+        let s = convention.forward::<T>();
+        let depth = far - near;
+        let (a, b) = match convention.depth {
+            DepthRange::NegOneToOne => (s * (far + near) / depth, -two * far * near / depth),
+            DepthRange::ZeroToOne => (s * far / depth, -far * near / depth),
+        };
+        Self::new([
+            [f / aspect, zero, zero, zero],
+            [zero, f, zero, zero],
+            [zero, zero, a, b],
+            [zero, zero, s, zero],
+        ])
+    }
+
+    /// Orthographic projection of the box bounded by `left`, `right`,
+    /// `bottom`, and `top` in view space, between positive distances
+    /// `near` and `far` in front of the camera.
+    /// ```
+    ///     use xyzvec::{camera::ClipConvention, Mat4, XYZVec};
+    ///
+    ///     let proj = Mat4::orthographic(0.0f64, 4.0, 0.0, 2.0, 1.0, 3.0, ClipConvention::DIRECTX);
+    ///     let p = proj.transform_point(XYZVec::new([4.0, 0.0, 1.0])).unwrap();
+    ///     assert_eq!(p, XYZVec::new([1.0, -1.0, 0.0]));
+    /// ```
+    pub fn orthographic(
+        left: T,
+        right: T,
+        bottom: T,
+        top: T,
+        near: T,
+        far: T,
+        convention: ClipConvention,
+    ) -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        let two = one + one;
+        let s = convention.forward::<T>();
+        let (width, height, depth) = (right - left, top - bottom, far - near);
+        let (a, b) = match convention.depth {
+            DepthRange::NegOneToOne => (s * two / depth, -(far + near) / depth),
+            DepthRange::ZeroToOne => (s / depth, -near / depth),
+        };
+        Self::new([
+            [two / width, zero, zero, -(right + left) / width],
+            [zero, two / height, zero, -(top + bottom) / height],
+            [zero, zero, a, b],
+            [zero, zero, zero, one],
+        ])
+    }
+}
+
+/// Rectangle of pixels that normalized device coordinates are drawn to.
+/// Pixel coordinates have `+y` pointing down the screen, as in most
+/// windowing systems, while NDC `+y` points up.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Viewport<T> {
+    /// Top-left corner, in pixels.
+    pub origin: XYVec<T>,
+    /// Width and height, in pixels.
+    pub size: XYVec<T>,
+}
+
+impl<T: VecNum> Viewport<T> {
+    pub fn new(origin: XYVec<T>, size: XYVec<T>) -> Self {
+        Self { origin, size }
+    }
+
+    /// Width over height, as expected by [`Mat4::perspective`].
+    pub fn aspect(&self) -> T {
+        self.size.x() / self.size.y()
+    }
+
+    /// Pixel position of NDC `(x, y)`; `(-1, 1)` is the top-left corner.
+    /// ```
+    ///     use xyzvec::{camera::Viewport, XYVec};
+    ///
+    ///     let viewport = Viewport::new(XYVec::new([0.0f64, 0.0]), XYVec::new([800.0, 600.0]));
+    ///     assert_eq!(viewport.ndc_to_screen(XYVec::new([-1.0, 1.0])), XYVec::new([0.0, 0.0]));
+    ///     assert_eq!(viewport.ndc_to_screen(XYVec::new([0.5, -1.0])), XYVec::new([600.0, 600.0]));
+    /// ```
+    pub fn ndc_to_screen(&self, ndc: XYVec<T>) -> XYVec<T> {
+        let one = T::one();
+        let half = one / (one + one);
+        XYVec::new([
+            self.origin.x() + (ndc.x() + one) * half * self.size.x(),
+            self.origin.y() + (one - ndc.y()) * half * self.size.y(),
+        ])
+    }
+
+    /// Inverse of [`ndc_to_screen`](Self::ndc_to_screen).
+    pub fn screen_to_ndc(&self, pixel: XYVec<T>) -> XYVec<T> {
+        let one = T::one();
+        let two = one + one;
+        let offset = pixel - self.origin;
+        XYVec::new([
+            offset.x() * two / self.size.x() - one,
+            one - offset.y() * two / self.size.y(),
+        ])
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Viewport<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Viewport {{ origin: {:?}, size: {:?} }}",
+            self.origin, self.size
+        )
+    }
+}
+
+/// Pixel position of world point `p` under the combined view-projection
+/// matrix `view_proj` (projection times view). `None` if `p` is behind the
+/// camera or in its plane.
+/// ```
+///     use xyzvec::{camera::{world_to_screen, ClipConvention, Viewport}, Mat4, XYVec, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let viewport = Viewport::new(XYVec::new([0.0f64, 0.0]), XYVec::new([640.0, 480.0]));
+///     let proj = Mat4::perspective(1.0, viewport.aspect(), 0.1, 100.0, ClipConvention::OPENGL);
+///     let view = Mat4::look_at(XYZVec::new([0.0, 0.0, 5.0]), XYZVec::new([0.0, 0.0, 0.0]), XYZVec::new([0.0, 1.0, 0.0])).unwrap();
+///     let center = world_to_screen(XYZVec::new([0.0, 0.0, 0.0]), &(proj * view), &viewport).unwrap();
+///     assert_relative_eq!(center.x(), 320.0);
+///     assert_relative_eq!(center.y(), 240.0);
+///     assert!(world_to_screen(XYZVec::new([0.0, 0.0, 10.0]), &(proj * view), &viewport).is_none());
+/// ```
+pub fn world_to_screen<T: VecNum>(
+    p: XYZVec<T>,
+    view_proj: &Mat4<T>,
+    viewport: &Viewport<T>,
+) -> Option<XYVec<T>> {
+    let clip = view_proj.mul_vec(p.to_homogeneous());
+    if clip.w() <= T::zero() {
+        return None;
+    }
+    let ndc = clip.xyz().div_by(clip.w());
+    Some(viewport.ndc_to_screen(XYVec::new([ndc.x(), ndc.y()])))
+}
+
+/// World point under pixel `pixel` at NDC depth `ndc_depth`, which is the
+/// near plane at the low end of the projection's [`DepthRange`] and the
+/// far plane at 1. Unprojecting at both ends gives a picking ray. `None`
+/// if `view_proj` is singular.
+pub fn screen_to_world<T: VecNum>(
+    pixel: XYVec<T>,
+    ndc_depth: T,
+    view_proj: &Mat4<T>,
+    viewport: &Viewport<T>,
+) -> Option<XYZVec<T>> {
+    let ndc = viewport.screen_to_ndc(pixel);
+    view_proj
+        .inverse()?
+        .transform_point(XYZVec::new([ndc.x(), ndc.y(), ndc_depth]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        screen_to_world, world_to_screen, ClipConvention, DepthRange, Handedness, Viewport,
+    };
+    use crate::{pose::Pose3, Mat4, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn look_at_3d_f64() {
+        let eye = XYZVec::new([3.0f64, -2.0, 1.0]);
+        let target = XYZVec::new([-1.0, 4.0, 0.5]);
+        let up = XYZVec::new([0.0, 0.0, 1.0]);
+        let view = Mat4::look_at(eye, target, up).unwrap();
+        let eye_view = view.transform_point(eye).unwrap();
+        assert_relative_eq!(eye_view.l2_norm(), 0.0, epsilon = 1e-12);
+        // The target lands straight ahead, on the -z axis.
+        let t = view.transform_point(target).unwrap();
+        assert_relative_eq!(t.x(), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(t.y(), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(t.z(), -(target - eye).l2_norm(), epsilon = 1e-12);
+        // World up stays in the upper half of the view.
+        assert!(view.transform_vector(up).y() > 0.0);
+        let pose = Pose3::look_at(eye, target, up).unwrap();
+        assert_relative_eq!(pose.rotation.determinant(), 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn degenerate_f32() {
+        let eye = XYZVec::new([1.0f32, 1.0, 1.0]);
+        let up = XYZVec::new([0.0, 1.0, 0.0]);
+        assert!(Mat4::look_at(eye, eye, up).is_none());
+        assert!(Mat4::look_at(eye, eye + up, up).is_none());
+        assert!(Mat4::look_at(eye, XYZVec::new([0.0, 0.0, 0.0]), XYZVec::new([0.0; 3])).is_none());
+        assert_relative_eq!(
+            XYVec::new([0.0f32, 0.0]).look_at(XYVec::new([-1.0, 0.0])),
+            std::f32::consts::PI
+        );
+    }
+
+    const CONVENTIONS: [ClipConvention; 4] = [
+        ClipConvention::OPENGL,
+        ClipConvention::DIRECTX,
+        ClipConvention::WGPU,
+        ClipConvention {
+            handedness: Handedness::Left,
+            depth: DepthRange::NegOneToOne,
+        },
+    ];
+
+    fn depth_bounds(convention: ClipConvention) -> (f64, f64, f64) {
+        let low = match convention.depth {
+            DepthRange::NegOneToOne => -1.0,
+            DepthRange::ZeroToOne => 0.0,
+        };
+        let forward = match convention.handedness {
+            Handedness::Right => -1.0,
+            Handedness::Left => 1.0,
+        };
+        (low, 1.0, forward)
+    }
+
+    #[test]
+    fn perspective_depth_conventions_f64() {
+        for convention in CONVENTIONS {
+            let (low, high, forward) = depth_bounds(convention);
+            let proj = Mat4::perspective(1.2f64, 1.5, 0.5, 50.0, convention);
+            let near = proj
+                .transform_point(XYZVec::new([0.0, 0.0, 0.5 * forward]))
+                .unwrap();
+            let far = proj
+                .transform_point(XYZVec::new([0.0, 0.0, 50.0 * forward]))
+                .unwrap();
+            let mid = proj
+                .transform_point(XYZVec::new([0.0, 0.0, 5.0 * forward]))
+                .unwrap();
+            assert_relative_eq!(near.z(), low, epsilon = 1e-12);
+            assert_relative_eq!(far.z(), high, epsilon = 1e-12);
+            assert!(low < mid.z() && mid.z() < high);
+            // The top edge of the frustum maps to y = 1.
+            let top = (0.6f64).tan() * 5.0;
+            let edge = proj
+                .transform_point(XYZVec::new([0.0, top, 5.0 * forward]))
+                .unwrap();
+            assert_relative_eq!(edge.y(), 1.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn orthographic_depth_conventions_f32() {
+        for convention in CONVENTIONS {
+            let (low, high, forward) = depth_bounds(convention);
+            let (low, high, forward) = (low as f32, high as f32, forward as f32);
+            let proj = Mat4::orthographic(-2.0f32, 6.0, -1.0, 3.0, 1.0, 9.0, convention);
+            let corner = proj
+                .transform_point(XYZVec::new([-2.0, 3.0, forward]))
+                .unwrap();
+            assert_relative_eq!(corner.x(), -1.0);
+            assert_relative_eq!(corner.y(), 1.0);
+            assert_relative_eq!(corner.z(), low);
+            let back = proj
+                .transform_point(XYZVec::new([6.0, -1.0, 9.0 * forward]))
+                .unwrap();
+            assert_relative_eq!(back.x(), 1.0);
+            assert_relative_eq!(back.y(), -1.0);
+            assert_relative_eq!(back.z(), high);
+        }
+    }
+
+    #[test]
+    fn screen_round_trip_f64() {
+        let viewport = Viewport::new(XYVec::new([100.0f64, 50.0]), XYVec::new([1024.0, 768.0]));
+        let pixel = XYVec::new([300.0, 700.0]);
+        let back = viewport.ndc_to_screen(viewport.screen_to_ndc(pixel));
+        assert_relative_eq!(back.x(), pixel.x());
+        assert_relative_eq!(back.y(), pixel.y());
+        // Screen y runs down while NDC y runs up.
+        assert!(viewport.screen_to_ndc(XYVec::new([100.0, 50.0])).y() > 0.0);
+
+        let proj = Mat4::perspective(0.9, viewport.aspect(), 0.5, 200.0, ClipConvention::WGPU);
+        let view = Mat4::look_at(
+            XYZVec::new([4.0, 3.0, 8.0]),
+            XYZVec::new([0.0, 0.5, 0.0]),
+            XYZVec::new([0.0, 1.0, 0.0]),
+        )
+        .unwrap();
+        let view_proj = proj * view;
+        let p = XYZVec::new([1.0, -0.5, 2.0]);
+        let screen = world_to_screen(p, &view_proj, &viewport).unwrap();
+        let near = screen_to_world(screen, 0.0, &view_proj, &viewport).unwrap();
+        let far = screen_to_world(screen, 1.0, &view_proj, &viewport).unwrap();
+        // `p` lies on the picking ray through its own pixel.
+        let ray = far - near;
+        let closest = near + ray.scale_by((p - near).dot_prod(ray) / ray.l2_norm_sqd());
+        assert_relative_eq!((closest - p).l2_norm(), 0.0, epsilon = 1e-9);
+    }
+}
@@ -0,0 +1,254 @@
+//! Comma-separated values for collections of vectors, one vector per row.
+//!
+//! Fields are plain numbers; there is no quoting. Blank lines are skipped
+//! when reading, and both `\n` and `\r\n` line endings are accepted.
+
+use crate::{VecNum, XYVec, XYZVec};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// Formatting and parsing options shared by [`write_csv`] and
+/// [`read_csv`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CsvOptions {
+    /// Write a header row, and skip the first row when reading.
+    pub header: bool,
+    /// Header names, one per component. Defaults to `x`, `y`, `z`.
+    pub column_names: Option<Vec<String>>,
+    /// Digits after the decimal point. Full precision if `None`. Ignored
+    /// for integers.
+    pub precision: Option<usize>,
+    pub delimiter: char,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            header: true,
+            column_names: None,
+            precision: None,
+            delimiter: ',',
+        }
+    }
+}
+
+/// Why a CSV string could not be parsed. Line numbers start at 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CsvError {
+    /// A row had the wrong number of fields.
+    WrongFieldCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A field could not be parsed as a number.
+    InvalidNumber { line: usize, field: String },
+}
+
+impl Display for CsvError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CsvError::WrongFieldCount {
+                line,
+                expected,
+                found,
+            } => write!(f, "line {line}: expected {expected} fields, found {found}"),
+            CsvError::InvalidNumber { line, field } => {
+                write!(f, "line {line}: invalid number `{field}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+/// Vectors that can be written as a CSV row.
+pub trait CsvRecord: Sized {
+    /// Default header names.
+    const COLUMNS: &'static [&'static str];
+
+    type Component: Display + FromStr;
+
+    fn components(&self) -> Vec<Self::Component>;
+    /// Builds a vector from exactly [`COLUMNS`](Self::COLUMNS)`.len()`
+    /// components.
+    fn from_components(components: &[Self::Component]) -> Self;
+}
+
+impl<T: VecNum + FromStr> CsvRecord for XYVec<T> {
+    const COLUMNS: &'static [&'static str] = &["x", "y"];
+
+    type Component = T;
+
+    fn components(&self) -> Vec<T> {
+        vec![self.x(), self.y()]
+    }
+
+    fn from_components(components: &[T]) -> Self {
+        XYVec::new([components[0], components[1]])
+    }
+}
+
+impl<T: VecNum + FromStr> CsvRecord for XYZVec<T> {
+    const COLUMNS: &'static [&'static str] = &["x", "y", "z"];
+
+    type Component = T;
+
+    fn components(&self) -> Vec<T> {
+        vec![self.x(), self.y(), self.z()]
+    }
+
+    fn from_components(components: &[T]) -> Self {
+        XYZVec::new([components[0], components[1], components[2]])
+    }
+}
+
+/// One row per vector, each row ending in `\n`.
+///
+/// Panics if `options.column_names` does not have one name per component.
+/// ```
+///     use xyzvec::{csv::{write_csv, CsvOptions}, XYVec};
+///
+///     let points = [XYVec::new([1.0f64, 2.5]), XYVec::new([-0.125, 3.0])];
+///     assert_eq!(write_csv(&points, &CsvOptions::default()), "x,y\n1,2.5\n-0.125,3\n");
+///     let options = CsvOptions {
+///         header: false,
+///         precision: Some(2),
+///         delimiter: ';',
+///         ..CsvOptions::default()
+///     };
+///     assert_eq!(write_csv(&points, &options), "1.00;2.50\n-0.12;3.00\n");
+/// ```
+pub fn write_csv<V: CsvRecord>(points: &[V], options: &CsvOptions) -> String {
+    let delimiter = options.delimiter.to_string();
+    let mut out = String::new();
+    if options.header {
+        let names = match &options.column_names {
+            Some(names) => {
+                assert_eq!(
+                    names.len(),
+                    V::COLUMNS.len(),
+                    "one column name per component"
+                );
+                names.join(&delimiter)
+            }
+            None => V::COLUMNS.join(&delimiter),
+        };
+        out.push_str(&names);
+        out.push('\n');
+    }
+    for p in points {
+        let fields: Vec<String> = p
+            .components()
+            .iter()
+            .map(|c| match options.precision {
+                Some(digits) => format!("{c:.digits$}"),
+                None => c.to_string(),
+            })
+            .collect();
+        out.push_str(&fields.join(&delimiter));
+        out.push('\n');
+    }
+    out
+}
+
+/// Parses rows written by [`write_csv`] with the same `options`.
+/// `precision` and `column_names` are not used.
+/// ```
+///     use xyzvec::{csv::{read_csv, CsvError, CsvOptions}, XYZVec};
+///
+///     let options = CsvOptions::default();
+///     let points: Vec<XYZVec<i32>> = read_csv("x,y,z\n1, 2, 3\n\n4,5,6\n", &options).unwrap();
+///     assert_eq!(points, vec![XYZVec::new([1, 2, 3]), XYZVec::new([4, 5, 6])]);
+///     assert_eq!(
+///         read_csv::<XYZVec<i32>>("x,y,z\n1,2\n", &options),
+///         Err(CsvError::WrongFieldCount { line: 2, expected: 3, found: 2 })
+///     );
+/// ```
+pub fn read_csv<V: CsvRecord>(s: &str, options: &CsvOptions) -> Result<Vec<V>, CsvError> {
+    let mut rows = s
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    if options.header {
+        rows.next();
+    }
+    let mut points = Vec::new();
+    let mut components = Vec::with_capacity(V::COLUMNS.len());
+    for (i, row) in rows {
+        let line = i + 1;
+        let fields: Vec<&str> = row.split(options.delimiter).map(str::trim).collect();
+        if fields.len() != V::COLUMNS.len() {
+            return Err(CsvError::WrongFieldCount {
+                line,
+                expected: V::COLUMNS.len(),
+                found: fields.len(),
+            });
+        }
+        components.clear();
+        for field in fields {
+            let c = field.parse().map_err(|_| CsvError::InvalidNumber {
+                line,
+                field: field.to_string(),
+            })?;
+            components.push(c);
+        }
+        points.push(V::from_components(&components));
+    }
+    Ok(points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_csv, write_csv, CsvError, CsvOptions};
+    use crate::{XYVec, XYZVec};
+    use fixed::types::I16F16;
+
+    #[test]
+    fn round_trip() {
+        let points: Vec<XYZVec<f32>> = (0..5)
+            .map(|i| XYZVec::new([i as f32 * 0.1, -(i as f32), 1e-3]))
+            .collect();
+        let options = CsvOptions::default();
+        let s = write_csv(&points, &options);
+        assert_eq!(read_csv(&s, &options), Ok(points));
+
+        let f = |v: f64| I16F16::from_num(v);
+        let fixed = vec![XYVec::new([f(1.5), f(-0.25)])];
+        let options = CsvOptions {
+            header: true,
+            column_names: Some(vec!["lon".to_string(), "lat".to_string()]),
+            precision: Some(3),
+            delimiter: '\t',
+        };
+        let s = write_csv(&fixed, &options);
+        assert_eq!(s, "lon\tlat\n1.500\t-0.250\n");
+        assert_eq!(read_csv(&s, &options), Ok(fixed));
+    }
+
+    #[test]
+    fn errors_report_lines() {
+        let options = CsvOptions {
+            header: false,
+            ..CsvOptions::default()
+        };
+        assert_eq!(
+            read_csv::<XYVec<f64>>("1,2\r\n\r\n3,four\r\n", &options),
+            Err(CsvError::InvalidNumber {
+                line: 3,
+                field: "four".to_string()
+            })
+        );
+        assert_eq!(
+            read_csv::<XYVec<f64>>("1,2,3\n", &options),
+            Err(CsvError::WrongFieldCount {
+                line: 1,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(read_csv::<XYVec<f64>>("", &options), Ok(Vec::new()));
+    }
+}
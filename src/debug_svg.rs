@@ -0,0 +1,252 @@
+//! SVG snapshots of 2D geometry, for eyeballing what an algorithm is doing.
+//!
+//! Add points, polylines, polygons, boxes, and circles to an [`SvgScene`],
+//! then write it out; the view box is fitted around everything drawn. The
+//! `y` axis points up, as in the math, not down as in SVG.
+//! ```
+//!     use xyzvec::{debug_svg::SvgScene, Aabb2, Circle, XYVec};
+//!
+//!     let path = [XYVec::new([0.0, 0.0]), XYVec::new([2.0, 1.0]), XYVec::new([4.0, 0.0])];
+//!     let svg = SvgScene::new()
+//!         .polyline(&path, "blue")
+//!         .points(&path, "red")
+//!         .aabb(&Aabb2::new(path[0], path[2]), "gray")
+//!         .circle(&Circle::new(path[1], 0.5), "green")
+//!         .to_svg();
+//!     assert!(svg.starts_with("<svg"));
+//! ```
+
+use crate::{Aabb2, Circle, Polygon2, VecNum, XYVec};
+use std::{fmt::Write, io, path::Path};
+
+/// Width of the written image in pixels. The height follows from the
+/// aspect ratio of the view box.
+const WIDTH_PX: f64 = 800.0;
+/// Margin around the drawing, as a fraction of its larger side.
+const MARGIN: f64 = 0.05;
+/// Radius of drawn points, as a fraction of the drawing's larger side.
+const POINT_RADIUS: f64 = 0.005;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Shape {
+    Points(Vec<XYVec<f64>>),
+    Polyline {
+        points: Vec<XYVec<f64>>,
+        closed: bool,
+    },
+    Rect {
+        min: XYVec<f64>,
+        max: XYVec<f64>,
+    },
+    Circle {
+        center: XYVec<f64>,
+        radius: f64,
+    },
+}
+
+impl Shape {
+    /// Corners of a box containing the shape.
+    fn extent(&self) -> Vec<XYVec<f64>> {
+        match self {
+            Shape::Points(points) | Shape::Polyline { points, .. } => points.clone(),
+            Shape::Rect { min, max } => vec![*min, *max],
+            Shape::Circle { center, radius } => {
+                let r = XYVec::new([*radius, *radius]);
+                vec![*center - r, *center + r]
+            }
+        }
+    }
+}
+
+/// A collection of shapes to be written as one SVG image. Each shape has a
+/// color, given as any SVG color string such as `"red"` or `"#336699"`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SvgScene {
+    shapes: Vec<(Shape, String)>,
+}
+
+/// `y` in SVG coordinates, which point down. Written as `0.0 - y` rather
+/// than `-y` so that zero prints as `0`, not `-0`.
+fn svg_y(y: f64) -> f64 {
+    0.0 - y
+}
+
+fn to_f64<T: VecNum>(v: &XYVec<T>) -> XYVec<f64> {
+    XYVec::new([v.x().to_f64(), v.y().to_f64()])
+}
+
+impl SvgScene {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push(mut self, shape: Shape, color: &str) -> Self {
+        self.shapes.push((shape, color.to_string()));
+        self
+    }
+
+    /// Each point as a dot.
+    pub fn points<T: VecNum>(self, points: &[XYVec<T>], color: &str) -> Self {
+        self.push(Shape::Points(points.iter().map(to_f64).collect()), color)
+    }
+
+    /// An open path through `points`.
+    pub fn polyline<T: VecNum>(self, points: &[XYVec<T>], color: &str) -> Self {
+        let points = points.iter().map(to_f64).collect();
+        self.push(
+            Shape::Polyline {
+                points,
+                closed: false,
+            },
+            color,
+        )
+    }
+
+    /// The outline of `polygon`.
+    pub fn polygon<T: VecNum>(self, polygon: &Polygon2<T>, color: &str) -> Self {
+        let points = polygon.vertices().iter().map(to_f64).collect();
+        self.push(
+            Shape::Polyline {
+                points,
+                closed: true,
+            },
+            color,
+        )
+    }
+
+    pub fn aabb<T: VecNum>(self, aabb: &Aabb2<T>, color: &str) -> Self {
+        let (min, max) = (to_f64(&aabb.min), to_f64(&aabb.max));
+        self.push(Shape::Rect { min, max }, color)
+    }
+
+    pub fn circle<T: VecNum>(self, circle: &Circle<T>, color: &str) -> Self {
+        let center = to_f64(&circle.center);
+        let radius = circle.radius.to_f64();
+        self.push(Shape::Circle { center, radius }, color)
+    }
+
+    /// `(min_x, min_y, width, height)` of the drawing in scene coordinates,
+    /// margin included. A drawing with no area gets a margin of 1.
+    fn view_box(&self) -> (f64, f64, f64, f64) {
+        let corners = self.shapes.iter().flat_map(|(shape, _)| shape.extent());
+        let (mut min, mut max) = (
+            XYVec::new([f64::INFINITY; 2]),
+            XYVec::new([f64::NEG_INFINITY; 2]),
+        );
+        for c in corners {
+            min = XYVec::new([min.x().min(c.x()), min.y().min(c.y())]);
+            max = XYVec::new([max.x().max(c.x()), max.y().max(c.y())]);
+        }
+        if min.x() > max.x() {
+            return (-1.0, -1.0, 2.0, 2.0);
+        }
+        let size = (max - min).x().max((max - min).y());
+        let pad = if size > 0.0 { size * MARGIN } else { 1.0 };
+        let (w, h) = (max.x() - min.x() + 2.0 * pad, max.y() - min.y() + 2.0 * pad);
+        (min.x() - pad, min.y() - pad, w, h)
+    }
+
+    /// The scene as a standalone SVG document.
+    pub fn to_svg(&self) -> String {
+        let (x0, y0, w, h) = self.view_box();
+        let dot = POINT_RADIUS * w.max(h);
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH_PX}\" height=\"{}\" \
+             viewBox=\"{x0} {} {w} {h}\">\n",
+            (WIDTH_PX * h / w).round(),
+            svg_y(y0 + h),
+        );
+        let stroke = "fill=\"none\" stroke-width=\"1\" vector-effect=\"non-scaling-stroke\"";
+        for (shape, color) in &self.shapes {
+            match shape {
+                Shape::Points(points) => {
+                    for p in points {
+                        let (x, y) = (p.x(), svg_y(p.y()));
+                        writeln!(
+                            out,
+                            "  <circle cx=\"{x}\" cy=\"{y}\" r=\"{dot}\" fill=\"{color}\"/>"
+                        )
+                        .unwrap();
+                    }
+                }
+                Shape::Polyline { points, closed } => {
+                    let tag = if *closed { "polygon" } else { "polyline" };
+                    let coords: Vec<_> = points
+                        .iter()
+                        .map(|p| format!("{},{}", p.x(), svg_y(p.y())))
+                        .collect();
+                    writeln!(
+                        out,
+                        "  <{tag} points=\"{}\" stroke=\"{color}\" {stroke}/>",
+                        coords.join(" ")
+                    )
+                    .unwrap();
+                }
+                Shape::Rect { min, max } => {
+                    let (x, y) = (min.x(), svg_y(max.y()));
+                    let (w, h) = (max.x() - min.x(), max.y() - min.y());
+                    writeln!(
+                        out,
+                        "  <rect x=\"{x}\" y=\"{y}\" width=\"{w}\" height=\"{h}\" stroke=\"{color}\" {stroke}/>"
+                    )
+                    .unwrap();
+                }
+                Shape::Circle { center, radius } => {
+                    let (x, y) = (center.x(), svg_y(center.y()));
+                    writeln!(
+                        out,
+                        "  <circle cx=\"{x}\" cy=\"{y}\" r=\"{radius}\" stroke=\"{color}\" {stroke}/>"
+                    )
+                    .unwrap();
+                }
+            }
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+
+    /// Writes [`to_svg`](Self::to_svg) to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_svg())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SvgScene;
+    use crate::{Aabb2, Circle, Polygon2, XYVec};
+
+    #[test]
+    fn fits_view_box_and_flips_y() {
+        let svg = SvgScene::new()
+            .aabb(
+                &Aabb2::new(XYVec::new([0.0, 0.0]), XYVec::new([10.0, 5.0])),
+                "gray",
+            )
+            .points(&[XYVec::new([2i32, 3])], "red")
+            .to_svg();
+        // 10 x 5 with a margin of 0.5 on every side, `y` running from -5.5.
+        assert!(svg.contains("viewBox=\"-0.5 -5.5 11 6\""), "{svg}");
+        assert!(svg.contains("height=\"436\""), "{svg}");
+        assert!(svg.contains("<rect x=\"0\" y=\"-5\" width=\"10\" height=\"5\""));
+        assert!(svg.contains("<circle cx=\"2\" cy=\"-3\""));
+    }
+
+    #[test]
+    fn circles_and_polygons_extend_the_view() {
+        let triangle = Polygon2::new(vec![
+            XYVec::new([0.0, 0.0]),
+            XYVec::new([1.0, 0.0]),
+            XYVec::new([0.0, 1.0]),
+        ]);
+        let svg = SvgScene::new()
+            .polygon(&triangle, "blue")
+            .circle(&Circle::new(XYVec::new([0.0, 0.0]), 4.0), "black")
+            .to_svg();
+        assert!(svg.contains("viewBox=\"-4.4 -4.4 8.8 8.8\""), "{svg}");
+        assert!(svg.contains("<polygon points=\"0,0 1,0 0,-1\""), "{svg}");
+        assert!(svg.contains("r=\"4\""));
+
+        assert!(SvgScene::new().to_svg().contains("viewBox=\"-1 -1 2 2\""));
+    }
+}
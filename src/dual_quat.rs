@@ -0,0 +1,276 @@
+//! Dual quaternions, for rigid motions that blend smoothly.
+
+use crate::{quat::Quat, Pose3, VecInner, VecNum, XYZVec};
+use std::{
+    fmt::{self, Formatter},
+    ops::Mul,
+};
+
+fn quat_to_f64<T: VecNum>(q: Quat<T>) -> Quat<f64> {
+    let v = q.v;
+    Quat::new(
+        q.w.to_f64(),
+        XYZVec::new([v.x(), v.y(), v.z()].map(|c| c.to_f64())),
+    )
+}
+
+fn quat_from_f64<T: VecNum>(q: Quat<f64>) -> Quat<T> {
+    let v = q.v;
+    Quat::new(
+        T::from_f64(q.w),
+        XYZVec::new([v.x(), v.y(), v.z()].map(T::from_f64)),
+    )
+}
+
+/// 3D rigid transform (an element of SE(3)) as a unit dual quaternion
+/// `real + ε dual`: `real` is the rotation, and `dual` is half the
+/// translation times `real`.
+///
+/// Unlike [`Pose3`], dual quaternions interpolate along a screw motion
+/// ([`sclerp`](Self::sclerp)), which makes them the usual choice for
+/// skinning.
+#[derive(Clone, Copy, PartialEq)]
+pub struct DualQuat<T> {
+    pub real: Quat<T>,
+    pub dual: Quat<T>,
+}
+
+impl<T: VecNum> DualQuat<T> {
+    pub fn new(real: Quat<T>, dual: Quat<T>) -> Self {
+        Self { real, dual }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(
+            Quat::identity(),
+            Quat::new(T::zero(), XYZVec::new([T::zero(); 3])),
+        )
+    }
+
+    /// Rotate by the unit quaternion `rotation`, then translate by
+    /// `translation`.
+    pub fn from_rotation_translation(rotation: Quat<T>, translation: XYZVec<T>) -> Self {
+        let t = Quat::new(T::zero(), translation);
+        Self::new(rotation, (t * rotation).scale_by(T::from_f64(0.5)))
+    }
+
+    pub fn from_pose(pose: &Pose3<T>) -> Self {
+        Self::from_rotation_translation(Quat::from_mat3(&pose.rotation), pose.translation)
+    }
+
+    pub fn to_pose(&self) -> Pose3<T> {
+        Pose3::new(self.translation(), self.real.to_mat3())
+    }
+
+    pub fn rotation(&self) -> Quat<T> {
+        self.real
+    }
+
+    pub fn translation(&self) -> XYZVec<T> {
+        let two = T::one() + T::one();
+        (self.dual * self.real.conjugate()).v.scale_by(two)
+    }
+
+    /// `v` rotated by this transform, ignoring the translation.
+    pub fn transform_vector(&self, v: XYZVec<T>) -> XYZVec<T> {
+        self.real.rotate(v)
+    }
+
+    /// `p` rotated and then translated by this transform.
+    /// ```
+    ///     use xyzvec::{dual_quat::DualQuat, quat::Quat, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let z = XYZVec::new([0.0f64, 0.0, 1.0]);
+    ///     let dq = DualQuat::from_rotation_translation(Quat::from_axis_angle(z, std::f64::consts::FRAC_PI_2), z);
+    ///     let p = dq.transform_point(XYZVec::new([1.0, 0.0, 0.0]));
+    ///     assert_relative_eq!(p.x(), 0.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(p.y(), 1.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(p.z(), 1.0, epsilon = 1e-12);
+    /// ```
+    pub fn transform_point(&self, p: XYZVec<T>) -> XYZVec<T> {
+        self.transform_vector(p) + self.translation()
+    }
+
+    /// The transform that applies `other` first and then `self`, i.e.
+    /// `self * other`.
+    pub fn compose(&self, other: Self) -> Self {
+        Self::new(
+            self.real * other.real,
+            self.real * other.dual + self.dual * other.real,
+        )
+    }
+
+    /// The transform that undoes `self`, which must be a unit dual
+    /// quaternion.
+    pub fn inverse(&self) -> Self {
+        Self::new(self.real.conjugate(), self.dual.conjugate())
+    }
+
+    /// `self` rescaled to a unit dual quaternion, correcting drift after
+    /// many compositions or a blend of several transforms. `None` if the
+    /// real part is zero.
+    pub fn normalize(&self) -> Option<Self> {
+        let (real, dual) = (quat_to_f64(self.real), quat_to_f64(self.dual));
+        let len = real.dot(real).sqrt();
+        (len > 0.0 && len.is_finite()).then(|| {
+            let (real, dual) = (real.scale_by(1.0 / len), dual.scale_by(1.0 / len));
+            // The dual part of a unit dual quaternion is orthogonal to the real part.
+            let dual = dual - real.scale_by(real.dot(dual));
+            Self::new(quat_from_f64(real), quat_from_f64(dual))
+        })
+    }
+
+    /// Screw linear interpolation from `self` (at `t = 0`) to `other` (at
+    /// `t = 1`): a constant-speed rotation about, and translation along, a
+    /// single axis, taking the shorter way round.
+    /// ```
+    ///     use xyzvec::{dual_quat::DualQuat, quat::Quat, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let z = XYZVec::new([0.0f64, 0.0, 1.0]);
+    ///     let a = DualQuat::identity();
+    ///     let b = DualQuat::from_rotation_translation(Quat::from_axis_angle(z, 2.0), z.scale_by(4.0));
+    ///     let mid = a.sclerp(b, 0.5);
+    ///     assert_relative_eq!(mid.translation().z(), 2.0, epsilon = 1e-12);
+    ///     let p = mid.transform_vector(XYZVec::new([1.0, 0.0, 0.0]));
+    ///     assert_relative_eq!(p.y().atan2(p.x()), 1.0, epsilon = 1e-12);
+    /// ```
+    pub fn sclerp(&self, other: Self, t: T) -> Self {
+        let mut diff = self.inverse() * other;
+        if diff.real.w < T::zero() {
+            diff = Self::new(-diff.real, -diff.dual);
+        }
+        let (real, dual) = (quat_to_f64(diff.real), quat_to_f64(diff.dual));
+        let t = t.to_f64();
+        let sin_half = real.v.l2_norm();
+        let (real, dual) = if sin_half < 1e-12 {
+            // No rotation: interpolate the translation alone.
+            (Quat::identity(), Quat::new(0.0, dual.v.scale_by(t)))
+        } else {
+            // Screw axis direction `l`, moment `m`, angle, and pitch.
+            let l = real.v.div_by(sin_half);
+            let angle = 2.0 * sin_half.atan2(real.w);
+            let pitch = -2.0 * dual.w / sin_half;
+            let m = (dual.v - l.scale_by(pitch / 2.0 * real.w)).div_by(sin_half);
+            let (angle, pitch) = (angle * t, pitch * t);
+            let (s, c) = ((angle / 2.0).sin(), (angle / 2.0).cos());
+            (
+                Quat::new(c, l.scale_by(s)),
+                Quat::new(
+                    -pitch / 2.0 * s,
+                    m.scale_by(s) + l.scale_by(pitch / 2.0 * c),
+                ),
+            )
+        };
+        self.compose(Self::new(quat_from_f64(real), quat_from_f64(dual)))
+    }
+}
+
+impl<T: VecNum> Mul for DualQuat<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.compose(other)
+    }
+}
+
+impl<T: VecInner> fmt::Debug for DualQuat<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "DualQuat {{ real: {:?}, dual: {:?} }}",
+            self.real, self.dual
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DualQuat;
+    use crate::{quat::Quat, Mat3, Pose3, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I16F16;
+
+    fn assert_close(a: XYZVec<f64>, b: XYZVec<f64>) {
+        assert_relative_eq!((a - b).l2_norm(), 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn matches_pose3_f64() {
+        let a = Pose3::new(
+            XYZVec::new([1.0f64, -2.0, 0.5]),
+            Mat3::from_axis_angle(XYZVec::new([1.0, 1.0, 0.0]), 0.8),
+        );
+        let b = Pose3::new(
+            XYZVec::new([0.0, 3.0, 1.0]),
+            Mat3::from_axis_angle(XYZVec::new([0.0, 0.2, 1.0]), -2.1),
+        );
+        let (da, db) = (DualQuat::from_pose(&a), DualQuat::from_pose(&b));
+        let p = XYZVec::new([0.3, 0.7, -1.1]);
+        assert_close(da.transform_point(p), a.transform_point(p));
+        assert_close((da * db).transform_point(p), (a * b).transform_point(p));
+        assert_close(da.inverse().transform_point(da.transform_point(p)), p);
+        assert_close(da.to_pose().transform_point(p), a.transform_point(p));
+        assert_close(da.translation(), a.translation);
+    }
+
+    #[test]
+    fn sclerp_endpoints_and_screw_f64() {
+        let a = DualQuat::from_rotation_translation(
+            Quat::from_axis_angle(XYZVec::new([0.3f64, -1.0, 0.2]), 0.4),
+            XYZVec::new([1.0, 2.0, 3.0]),
+        );
+        let b = DualQuat::from_rotation_translation(
+            Quat::from_axis_angle(XYZVec::new([1.0, 0.5, 0.0]), 2.5),
+            XYZVec::new([-2.0, 0.0, 1.0]),
+        );
+        let p = XYZVec::new([0.5, -0.5, 2.0]);
+        assert_close(a.sclerp(b, 0.0).transform_point(p), a.transform_point(p));
+        assert_close(a.sclerp(b, 1.0).transform_point(p), b.transform_point(p));
+        // Two half steps make the whole step.
+        let half = a.inverse() * a.sclerp(b, 0.5);
+        assert_close((a * half * half).transform_point(p), b.transform_point(p));
+        // Pure translations interpolate linearly.
+        let shift =
+            DualQuat::from_rotation_translation(Quat::identity(), XYZVec::new([4.0, 0.0, -2.0]));
+        assert_close(
+            DualQuat::identity().sclerp(shift, 0.25).translation(),
+            XYZVec::new([1.0, 0.0, -0.5]),
+        );
+    }
+
+    #[test]
+    fn normalize_f32() {
+        let dq = DualQuat::from_rotation_translation(
+            Quat::from_axis_angle(XYZVec::new([0.0f32, 1.0, 0.0]), 1.0),
+            XYZVec::new([1.0, 2.0, 3.0]),
+        );
+        let scaled = DualQuat::new(dq.real.scale_by(3.0), dq.dual.scale_by(3.0));
+        let unit = scaled.normalize().unwrap();
+        assert_relative_eq!(unit.real.norm(), 1.0, epsilon = 1e-6);
+        assert_relative_eq!(
+            (unit.translation() - dq.translation()).l2_norm(),
+            0.0,
+            epsilon = 1e-5
+        );
+        assert!(
+            DualQuat::new(Quat::new(0.0f32, XYZVec::new([0.0; 3])), dq.dual)
+                .normalize()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let z = XYZVec::new([f(0.0), f(0.0), f(1.0)]);
+        let dq = DualQuat::from_rotation_translation(
+            Quat::from_axis_angle(z, f(std::f64::consts::FRAC_PI_2)),
+            z,
+        );
+        let p = dq.transform_point(XYZVec::new([f(1.0), f(0.0), f(0.0)]));
+        assert!((p.y() - f(1.0)).abs() < f(1e-3));
+        assert!((p.z() - f(1.0)).abs() < f(1e-3));
+    }
+}
@@ -0,0 +1,44 @@
+//! Errors from fallible vector construction.
+
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VecError {
+    /// The input had the wrong number of components.
+    LengthMismatch { expected: usize, found: usize },
+    /// A flat buffer's length was not a multiple of the vector dimension.
+    RaggedLength { dim: usize, found: usize },
+}
+
+impl Display for VecError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            VecError::LengthMismatch { expected, found } => {
+                write!(f, "expected {expected} components, found {found}")
+            }
+            VecError::RaggedLength { dim, found } => {
+                write!(f, "expected a multiple of {dim} components, found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for VecError {}
+
+/// The first `N` items of `iter`, or an error giving the total count if
+/// there are not exactly `N`.
+pub(crate) fn exactly<T, const N: usize>(
+    iter: impl IntoIterator<Item = T>,
+) -> Result<[T; N], VecError> {
+    let mut iter = iter.into_iter();
+    let mut items = Vec::with_capacity(N);
+    items.extend(iter.by_ref().take(N));
+    let found = items.len() + iter.count();
+    if found != N {
+        return Err(VecError::LengthMismatch { expected: N, found });
+    }
+    items
+        .try_into()
+        .map_err(|_| VecError::LengthMismatch { expected: N, found })
+}
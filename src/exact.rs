@@ -0,0 +1,176 @@
+//! Correctly rounded dot products of `f64` vectors.
+//!
+//! Each product is split into a rounded value and its exact rounding error
+//! (Dekker's two-product), and the resulting terms are summed without loss
+//! (Knuth's two-sum) before a single final rounding. The plain
+//! [`dot_prod`](crate::XYVec::dot_prod) of nearly orthogonal vectors can
+//! be dominated by rounding error, even in sign; these cannot.
+//! ```
+//!     use xyzvec::XYZVec;
+//!
+//!     let a = XYZVec::new([1e16f64, 1.0, -1e16]);
+//!     let b = XYZVec::new([1.0, 1.0, 1.0]);
+//!     assert_eq!(a.dot_prod(b), 0.0);
+//!     assert_eq!(a.dot_prod_exact(b), 1.0);
+//! ```
+//!
+//! The guarantee holds as long as no product overflows or falls into the
+//! subnormal range. Non-finite inputs give the same result as
+//! [`dot_prod`](crate::XYVec::dot_prod).
+
+use crate::{XYVec, XYZVec};
+
+/// `2^27 + 1`, which splits an `f64` into two halves of 26 significant
+/// bits each.
+const SPLITTER: f64 = 134217729.0;
+
+/// `(hi, lo)` with `hi + lo == a` exactly and each half short enough that
+/// products of halves are exact.
+fn split(a: f64) -> (f64, f64) {
+    let c = SPLITTER * a;
+    let hi = c - (c - a);
+    (hi, a - hi)
+}
+
+/// `(p, e)` with `p = a * b` rounded and `p + e == a * b` exactly.
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    let ((ah, al), (bh, bl)) = (split(a), split(b));
+    let e = al * bl - (((p - ah * bh) - al * bh) - ah * bl);
+    (p, e)
+}
+
+/// `(s, e)` with `s = a + b` rounded and `s + e == a + b` exactly.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    let e = (a - (s - bb)) + (b - bb);
+    (s, e)
+}
+
+/// The exact sum of `terms`, correctly rounded. The running sum is kept as
+/// non-overlapping partials, smallest first, as in Shewchuk's algorithm
+/// and Python's `math.fsum`.
+fn sum_exact<const N: usize>(terms: [f64; N]) -> f64 {
+    let mut partials = [0.0; N];
+    let mut len = 0;
+    for mut x in terms {
+        let mut kept = 0;
+        for i in 0..len {
+            let (hi, lo) = two_sum(x, partials[i]);
+            if lo != 0.0 {
+                partials[kept] = lo;
+                kept += 1;
+            }
+            x = hi;
+        }
+        partials[kept] = x;
+        len = kept + 1;
+    }
+
+    // Add from the largest partial down until the sum becomes inexact.
+    let mut n = len - 1;
+    let mut hi = partials[n];
+    let mut lo = 0.0;
+    while n > 0 {
+        n -= 1;
+        (hi, lo) = two_sum(hi, partials[n]);
+        if lo != 0.0 {
+            break;
+        }
+    }
+    // `hi + lo` may sit exactly halfway between two `f64`s, with the rest
+    // of the partials deciding which way it should round.
+    if n > 0 && ((lo < 0.0 && partials[n - 1] < 0.0) || (lo > 0.0 && partials[n - 1] > 0.0)) {
+        let y = lo * 2.0;
+        let x = hi + y;
+        if x - hi == y {
+            hi = x;
+        }
+    }
+    hi
+}
+
+/// The products `a[i] * b[i]` and their rounding errors, as `2 * N` terms
+/// that sum exactly to the dot product.
+fn product_terms<const N: usize, const M: usize>(a: &[f64; N], b: &[f64; N]) -> [f64; M] {
+    let mut terms = [0.0; M];
+    for i in 0..N {
+        (terms[2 * i], terms[2 * i + 1]) = two_product(a[i], b[i]);
+    }
+    terms
+}
+
+impl XYVec<f64> {
+    /// [`dot_prod`](Self::dot_prod), correctly rounded.
+    pub fn dot_prod_exact(&self, other: Self) -> f64 {
+        let naive = self.dot_prod(other);
+        if !naive.is_finite() {
+            return naive;
+        }
+        sum_exact(product_terms::<2, 4>(self.as_ref(), other.as_ref()))
+    }
+
+    /// [`l2_norm_sqd`](Self::l2_norm_sqd), correctly rounded.
+    pub fn l2_norm_sqd_exact(&self) -> f64 {
+        self.dot_prod_exact(*self)
+    }
+}
+
+impl XYZVec<f64> {
+    /// [`dot_prod`](Self::dot_prod), correctly rounded.
+    pub fn dot_prod_exact(&self, other: Self) -> f64 {
+        let naive = self.dot_prod(other);
+        if !naive.is_finite() {
+            return naive;
+        }
+        sum_exact(product_terms::<3, 6>(self.as_ref(), other.as_ref()))
+    }
+
+    /// [`l2_norm_sqd`](Self::l2_norm_sqd), correctly rounded.
+    pub fn l2_norm_sqd_exact(&self) -> f64 {
+        self.dot_prod_exact(*self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sum_exact, two_product};
+    use crate::{XYVec, XYZVec};
+
+    #[test]
+    fn products_are_error_free() {
+        let (a, b) = (1.0 + f64::EPSILON, 1.0 - f64::EPSILON);
+        let (p, e) = two_product(a, b);
+        assert_eq!(p, 1.0);
+        assert_eq!(e, -f64::EPSILON * f64::EPSILON);
+    }
+
+    #[test]
+    fn sums_round_once() {
+        let half_ulp = f64::EPSILON / 2.0;
+        // Exactly halfway rounds to even; anything past halfway rounds up.
+        assert_eq!(sum_exact([1.0, half_ulp]), 1.0);
+        assert_eq!(sum_exact([1.0, half_ulp, 1e-30]), 1.0 + f64::EPSILON);
+        assert_eq!(sum_exact([1e100, 1.0, -1e100]), 1.0);
+        assert_eq!(sum_exact([0.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn nearly_orthogonal() {
+        // The exact dot product is -2^-104, lost entirely by `dot_prod`.
+        let a = XYVec::new([1.0 + f64::EPSILON, 1.0]);
+        let b = XYVec::new([1.0 - f64::EPSILON, -1.0]);
+        assert_eq!(a.dot_prod(b), 0.0);
+        assert_eq!(a.dot_prod_exact(b), -f64::EPSILON * f64::EPSILON);
+
+        // 1e16 + 1 is a tie that `dot_prod` rounds down; the tiny `x^2`
+        // term tips the exact result up to the next `f64`.
+        let v = XYZVec::new([1e-8, 1.0, 1e8]);
+        assert_eq!(v.l2_norm_sqd(), 1e16);
+        assert_eq!(v.l2_norm_sqd_exact(), 1e16 + 2.0);
+        assert!(XYVec::new([f64::INFINITY, 0.0])
+            .dot_prod_exact(XYVec::new([1.0, 1.0]))
+            .is_infinite());
+    }
+}
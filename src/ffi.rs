@@ -0,0 +1,231 @@
+//! C bindings for the core vector operations. Requires the `ffi` feature.
+//!
+//! Each vector type has a `#[repr(C)]` twin: `XyzvecVec2d`/`XyzvecVec3d`
+//! hold `double`s, and `XyzvecVec2q16`/`XyzvecVec3q16` hold the raw bits of
+//! Q16.16 fixed-point numbers ([`I16F16`]) in `int32_t`s. Fixed-point
+//! scalar results are raw Q16.16 bits as well. As with the Rust types,
+//! fixed-point overflow wraps in release builds and aborts in debug ones.
+//!
+//! To use them from C, link a `staticlib` or `cdylib` build of the crate
+//! with this feature enabled and generate the header with
+//! `cbindgen --config cbindgen.toml --output xyzvec.h`.
+
+use crate::{XYVec, XYZVec};
+use fixed::types::I16F16;
+use std::convert::identity;
+
+macro_rules! ffi_vec {
+    ($name:ident, $vec:ident<$t:ty>, $raw:ty, $to_raw:path, $from_raw:path, [$($c:ident),+]) => {
+        #[repr(C)]
+        #[derive(Clone, Copy, Debug, Default, PartialEq)]
+        pub struct $name {
+            $(pub $c: $raw,)+
+        }
+
+        impl From<$name> for $vec<$t> {
+            fn from(v: $name) -> Self {
+                $vec::new([$($from_raw(v.$c)),+])
+            }
+        }
+
+        impl From<$vec<$t>> for $name {
+            fn from(v: $vec<$t>) -> Self {
+                $name {
+                    $($c: $to_raw(v.$c()),)+
+                }
+            }
+        }
+    };
+}
+
+ffi_vec!(XyzvecVec2d, XYVec<f64>, f64, identity, identity, [x, y]);
+ffi_vec!(XyzvecVec3d, XYZVec<f64>, f64, identity, identity, [x, y, z]);
+ffi_vec!(
+    XyzvecVec2q16,
+    XYVec<I16F16>,
+    i32,
+    I16F16::to_bits,
+    I16F16::from_bits,
+    [x, y]
+);
+ffi_vec!(
+    XyzvecVec3q16,
+    XYZVec<I16F16>,
+    i32,
+    I16F16::to_bits,
+    I16F16::from_bits,
+    [x, y, z]
+);
+
+/// `add`, `dot`, and `norm`, which look the same in every dimension.
+macro_rules! ffi_common {
+    ($name:ident, $vec:ident<$t:ty>, $raw:ty, $to_raw:path, $add:ident, $dot:ident, $norm:ident) => {
+        #[no_mangle]
+        pub extern "C" fn $add(a: $name, b: $name) -> $name {
+            ($vec::<$t>::from(a) + $vec::from(b)).into()
+        }
+
+        #[no_mangle]
+        pub extern "C" fn $dot(a: $name, b: $name) -> $raw {
+            $to_raw($vec::<$t>::from(a).dot_prod($vec::from(b)))
+        }
+
+        #[no_mangle]
+        pub extern "C" fn $norm(v: $name) -> $raw {
+            $to_raw($vec::<$t>::from(v).l2_norm())
+        }
+    };
+}
+
+ffi_common!(
+    XyzvecVec2d,
+    XYVec<f64>,
+    f64,
+    identity,
+    xyzvec_vec2d_add,
+    xyzvec_vec2d_dot,
+    xyzvec_vec2d_norm
+);
+ffi_common!(
+    XyzvecVec3d,
+    XYZVec<f64>,
+    f64,
+    identity,
+    xyzvec_vec3d_add,
+    xyzvec_vec3d_dot,
+    xyzvec_vec3d_norm
+);
+ffi_common!(
+    XyzvecVec2q16,
+    XYVec<I16F16>,
+    i32,
+    I16F16::to_bits,
+    xyzvec_vec2q16_add,
+    xyzvec_vec2q16_dot,
+    xyzvec_vec2q16_norm
+);
+ffi_common!(
+    XyzvecVec3q16,
+    XYZVec<I16F16>,
+    i32,
+    I16F16::to_bits,
+    xyzvec_vec3q16_add,
+    xyzvec_vec3q16_dot,
+    xyzvec_vec3q16_norm
+);
+
+/// The `z` component of the 3D cross product of `a` and `b`.
+#[no_mangle]
+pub extern "C" fn xyzvec_vec2d_cross(a: XyzvecVec2d, b: XyzvecVec2d) -> f64 {
+    XYVec::<f64>::from(a).cross_prod(XYVec::from(b))
+}
+
+#[no_mangle]
+pub extern "C" fn xyzvec_vec3d_cross(a: XyzvecVec3d, b: XyzvecVec3d) -> XyzvecVec3d {
+    XYZVec::<f64>::from(a).cross(XYZVec::from(b)).into()
+}
+
+/// The `z` component of the 3D cross product of `a` and `b`.
+#[no_mangle]
+pub extern "C" fn xyzvec_vec2q16_cross(a: XyzvecVec2q16, b: XyzvecVec2q16) -> i32 {
+    XYVec::<I16F16>::from(a)
+        .cross_prod(XYVec::from(b))
+        .to_bits()
+}
+
+#[no_mangle]
+pub extern "C" fn xyzvec_vec3q16_cross(a: XyzvecVec3q16, b: XyzvecVec3q16) -> XyzvecVec3q16 {
+    XYZVec::<I16F16>::from(a).cross(XYZVec::from(b)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn float_ops() {
+        let a = XyzvecVec3d {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        };
+        let b = XyzvecVec3d {
+            x: 0.0,
+            y: 2.0,
+            z: 0.0,
+        };
+        assert_eq!(
+            xyzvec_vec3d_add(a, b),
+            XyzvecVec3d {
+                x: 1.0,
+                y: 2.0,
+                z: 0.0
+            }
+        );
+        assert_eq!(xyzvec_vec3d_dot(a, b), 0.0);
+        assert_eq!(
+            xyzvec_vec3d_cross(a, b),
+            XyzvecVec3d {
+                x: 0.0,
+                y: 0.0,
+                z: 2.0
+            }
+        );
+        assert_eq!(xyzvec_vec2d_norm(XyzvecVec2d { x: 3.0, y: 4.0 }), 5.0);
+        assert_eq!(
+            xyzvec_vec2d_cross(
+                XyzvecVec2d { x: 1.0, y: 0.0 },
+                XyzvecVec2d { x: 0.0, y: 1.0 }
+            ),
+            1.0
+        );
+    }
+
+    #[test]
+    fn fixed_ops_use_raw_bits() {
+        let q = |v: f64| I16F16::from_num(v).to_bits();
+        let a = XyzvecVec2q16 {
+            x: q(3.0),
+            y: q(4.0),
+        };
+        let b = XyzvecVec2q16 {
+            x: q(0.5),
+            y: q(-1.0),
+        };
+        assert_eq!(
+            xyzvec_vec2q16_add(a, b),
+            XyzvecVec2q16 {
+                x: q(3.5),
+                y: q(3.0)
+            }
+        );
+        assert_eq!(xyzvec_vec2q16_dot(a, b), q(-2.5));
+        assert_eq!(xyzvec_vec2q16_cross(a, b), q(-5.0));
+        let norm = I16F16::from_bits(xyzvec_vec2q16_norm(a));
+        assert!((norm - I16F16::from_num(5)).abs() < I16F16::from_num(0.01));
+
+        let c = XyzvecVec3q16 {
+            x: q(1.0),
+            y: 0,
+            z: 0,
+        };
+        let d = XyzvecVec3q16 {
+            x: 0,
+            y: q(1.0),
+            z: 0,
+        };
+        assert_eq!(
+            xyzvec_vec3q16_cross(c, d),
+            XyzvecVec3q16 {
+                x: 0,
+                y: 0,
+                z: q(1.0)
+            }
+        );
+        assert_eq!(xyzvec_vec3q16_dot(c, d), 0);
+        assert_eq!(
+            XYZVec::<I16F16>::from(xyzvec_vec3q16_add(c, d)).z(),
+            I16F16::ZERO
+        );
+    }
+}
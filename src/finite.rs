@@ -0,0 +1,138 @@
+//! Vectors known to have only finite components.
+
+use crate::{VecNum, Vector, XYVec, XYZVec};
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
+
+/// A vector with no NaN or infinite components. Because every component is
+/// comparable, it is totally ordered (lexicographically, `x` first) and can
+/// be hashed, so it works as a key in `HashMap`, `BTreeMap`, and friends.
+///
+/// `-0.0` is stored as `0.0`, keeping equality and hashing consistent.
+/// ```
+///     use std::collections::HashSet;
+///     use xyzvec::{Vector, XYVec};
+///
+///     let a = XYVec::new([0.0f64, 1.5]).try_finite().unwrap();
+///     let b = XYVec::new([-0.0f64, 1.5]).try_finite().unwrap();
+///     assert_eq!(a, b);
+///     assert_eq!(HashSet::from([a, b]).len(), 1);
+///     assert!(XYVec::new([f64::NAN, 0.0]).try_finite().is_none());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FiniteVec<V>(V);
+
+pub type FiniteXYVec<T> = FiniteVec<XYVec<T>>;
+pub type FiniteXYZVec<T> = FiniteVec<XYZVec<T>>;
+
+impl<V: Vector> FiniteVec<V>
+where
+    V::Scalar: VecNum,
+{
+    /// `v`, or `None` if any component is NaN or infinite.
+    pub fn new(v: V) -> Option<Self> {
+        let finite = (0..V::DIM).all(|i| v.component(i).to_f64().is_finite());
+        // Adding zero turns -0.0 into 0.0 and leaves everything else alone.
+        finite.then(|| FiniteVec(v.map(|c| c + V::Scalar::zero())))
+    }
+
+    pub fn into_inner(self) -> V {
+        self.0
+    }
+}
+
+impl<V> Deref for FiniteVec<V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.0
+    }
+}
+
+impl<V: Vector + PartialEq> Eq for FiniteVec<V> where V::Scalar: VecNum {}
+
+impl<V: Vector + PartialEq> Ord for FiniteVec<V>
+where
+    V::Scalar: VecNum,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        (0..V::DIM)
+            .map(|i| {
+                self.0
+                    .component(i)
+                    .partial_cmp(&other.0.component(i))
+                    .expect("finite components are comparable")
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<V: Vector + PartialEq> PartialOrd for FiniteVec<V>
+where
+    V::Scalar: VecNum,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<V: Vector> Hash for FiniteVec<V>
+where
+    V::Scalar: VecNum,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for i in 0..V::DIM {
+            self.0.component(i).to_f64().to_bits().hash(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FiniteVec, FiniteXYZVec};
+    use crate::{Vector, XYVec, XYZVec};
+    use fixed::types::I16F16;
+    use std::collections::{BTreeSet, HashMap};
+
+    #[test]
+    fn rejects_non_finite_f32() {
+        assert!(FiniteVec::new(XYZVec::new([1.0f32, f32::INFINITY, 0.0])).is_none());
+        assert!(FiniteVec::new(XYZVec::new([1.0f32, 0.0, f32::NEG_INFINITY])).is_none());
+        assert!(XYVec::new([f32::NAN, f32::NAN]).try_finite().is_none());
+        let v = XYZVec::new([f32::MAX, f32::MIN_POSITIVE, -0.0]);
+        let finite: FiniteXYZVec<f32> = FiniteVec::new(v).unwrap();
+        assert_eq!(finite.x(), f32::MAX);
+        assert!(finite.z().is_sign_positive());
+    }
+
+    #[test]
+    fn ordering_and_keys_f64() {
+        let points = [
+            XYVec::new([1.0f64, 2.0]),
+            XYVec::new([1.0, -3.0]),
+            XYVec::new([-5.0, 9.0]),
+            XYVec::new([1.0, 2.0]),
+        ];
+        let sorted: BTreeSet<_> = points.iter().filter_map(|p| p.try_finite()).collect();
+        let sorted: Vec<_> = sorted.into_iter().map(|p| p.into_inner()).collect();
+        assert_eq!(sorted, vec![points[2], points[1], points[0]]);
+        let mut counts = HashMap::new();
+        for p in points {
+            *counts.entry(p.try_finite().unwrap()).or_insert(0) += 1;
+        }
+        assert_eq!(counts[&points[0].try_finite().unwrap()], 2);
+    }
+
+    #[test]
+    fn fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let a = XYVec::new([f(1.0), f(2.0)]).try_finite().unwrap();
+        let b = XYVec::new([f(1.0), f(2.5)]).try_finite().unwrap();
+        assert!(a < b);
+        assert_eq!(a.max(b), b);
+    }
+}
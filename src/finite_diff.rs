@@ -0,0 +1,212 @@
+//! Velocity and acceleration estimates from time-stamped positions.
+//!
+//! Samples are `(time, position)` pairs in increasing time order; spacing
+//! need not be uniform. Each estimate is returned at the time of the sample
+//! it was taken at, so the output lines up index for index with the input.
+//! Interior samples use central differences that are exact for motion under
+//! constant acceleration. Noisy data can be passed through
+//! [`moving_average`] first.
+
+use crate::{VecNum, Vector};
+
+/// Velocity at each sample. Interior samples use the three-point central
+/// difference, and the two end samples a one-sided difference. Empty if
+/// there are fewer than two samples.
+/// ```
+///     use xyzvec::{finite_diff::velocities, XYVec};
+///
+///     // x = t², sampled unevenly.
+///     let samples: Vec<_> = [0.0f64, 1.0, 3.0, 4.0]
+///         .into_iter()
+///         .map(|t| (t, XYVec::new([t * t, 5.0])))
+///         .collect();
+///     let v = velocities(&samples);
+///     assert_eq!(v[1], (1.0, XYVec::new([2.0, 0.0])));
+///     assert_eq!(v[2], (3.0, XYVec::new([6.0, 0.0])));
+///     // One-sided at the ends.
+///     assert_eq!(v[3], (4.0, XYVec::new([7.0, 0.0])));
+/// ```
+pub fn velocities<V>(samples: &[(V::Scalar, V)]) -> Vec<(V::Scalar, V)>
+where
+    V: Vector,
+    V::Scalar: VecNum,
+{
+    let n = samples.len();
+    if n < 2 {
+        return Vec::new();
+    }
+    let one_sided = |(t0, p0): (V::Scalar, V), (t1, p1): (V::Scalar, V)| (p1 - p0).div_by(t1 - t0);
+    let mut out = Vec::with_capacity(n);
+    out.push((samples[0].0, one_sided(samples[0], samples[1])));
+    for w in samples.windows(3) {
+        let [(t0, p0), (t1, p1), (t2, p2)] = [w[0], w[1], w[2]];
+        let (h1, h2) = (t1 - t0, t2 - t1);
+        let v = (p2.scale_by(h1 * h1) - p0.scale_by(h2 * h2) + p1.scale_by(h2 * h2 - h1 * h1))
+            .div_by(h1 * h2 * (h1 + h2));
+        out.push((t1, v));
+    }
+    out.push((samples[n - 1].0, one_sided(samples[n - 2], samples[n - 1])));
+    out
+}
+
+/// Acceleration at each sample from the three-point second difference. The
+/// end samples repeat their neighbor's estimate. Empty if there are fewer
+/// than three samples.
+/// ```
+///     use xyzvec::{finite_diff::accelerations, XYZVec};
+///
+///     let samples: Vec<_> = [0.0f64, 0.5, 2.0, 2.5]
+///         .into_iter()
+///         .map(|t| (t, XYZVec::new([0.0, 3.0 * t, -4.9 * t * t])))
+///         .collect();
+///     for (_, a) in accelerations(&samples) {
+///         approx::assert_relative_eq!(a.z(), -9.8, epsilon = 1e-12);
+///     }
+/// ```
+pub fn accelerations<V>(samples: &[(V::Scalar, V)]) -> Vec<(V::Scalar, V)>
+where
+    V: Vector,
+    V::Scalar: VecNum,
+{
+    let n = samples.len();
+    if n < 3 {
+        return Vec::new();
+    }
+    let two = V::Scalar::one() + V::Scalar::one();
+    let interior: Vec<V> = samples
+        .windows(3)
+        .map(|w| {
+            let [(t0, p0), (t1, p1), (t2, p2)] = [w[0], w[1], w[2]];
+            let (h1, h2) = (t1 - t0, t2 - t1);
+            (p2.scale_by(h1) - p1.scale_by(h1 + h2) + p0.scale_by(h2))
+                .scale_by(two)
+                .div_by(h1 * h2 * (h1 + h2))
+        })
+        .collect();
+    samples
+        .iter()
+        .enumerate()
+        .map(|(i, &(t, _))| (t, interior[i.clamp(1, n - 2) - 1]))
+        .collect()
+}
+
+/// Positions averaged over a centered window of up to `2 * radius + 1`
+/// samples. Near the ends the window shrinks symmetrically, so the first
+/// and last samples are kept as they are. Times are unchanged.
+/// ```
+///     use xyzvec::{finite_diff::moving_average, XYVec};
+///
+///     let noisy = [(0.0f64, XYVec::new([0.0, 0.0])), (1.0, XYVec::new([3.0, 0.0])), (2.0, XYVec::new([0.0, 0.0]))];
+///     let smooth = moving_average(&noisy, 1);
+///     assert_eq!(smooth[1], (1.0, XYVec::new([1.0, 0.0])));
+///     assert_eq!(smooth[0], noisy[0]);
+/// ```
+pub fn moving_average<V>(samples: &[(V::Scalar, V)], radius: usize) -> Vec<(V::Scalar, V)>
+where
+    V: Vector,
+    V::Scalar: VecNum,
+{
+    let n = samples.len();
+    (0..n)
+        .map(|i| {
+            let r = radius.min(i).min(n - 1 - i);
+            let window = &samples[i - r..=i + r];
+            let sum = window
+                .iter()
+                .fold(V::splat(V::Scalar::zero()), |sum, &(_, p)| sum + p);
+            let count = window
+                .iter()
+                .fold(V::Scalar::zero(), |c, _| c + V::Scalar::one());
+            (samples[i].0, sum.div_by(count))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{accelerations, moving_average, velocities};
+    use crate::{XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I16F16;
+
+    #[test]
+    fn quadratic_motion_f64() {
+        let times = [0.0f64, 0.1, 0.35, 0.4, 1.0, 1.7];
+        let pos = |t: f64| XYZVec::new([2.0 * t, 1.0 - t * t, 0.5 * t * t + t]);
+        let samples: Vec<_> = times.iter().map(|&t| (t, pos(t))).collect();
+        let v = velocities(&samples);
+        let a = accelerations(&samples);
+        assert_eq!(v.len(), samples.len());
+        assert_eq!(a.len(), samples.len());
+        for i in 1..times.len() - 1 {
+            let t = times[i];
+            assert_eq!(v[i].0, t);
+            assert_relative_eq!(v[i].1.x(), 2.0, epsilon = 1e-12);
+            assert_relative_eq!(v[i].1.y(), -2.0 * t, epsilon = 1e-12);
+            assert_relative_eq!(v[i].1.z(), t + 1.0, epsilon = 1e-12);
+        }
+        for (_, acc) in a {
+            assert_relative_eq!(acc.y(), -2.0, epsilon = 1e-9);
+            assert_relative_eq!(acc.z(), 1.0, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn short_input_f32() {
+        let one = [(0.0f32, XYVec::new([1.0, 1.0]))];
+        assert!(velocities(&one).is_empty());
+        let two = [
+            (0.0f32, XYVec::new([1.0, 1.0])),
+            (0.5, XYVec::new([2.0, 0.0])),
+        ];
+        assert_eq!(
+            velocities(&two),
+            vec![
+                (0.0, XYVec::new([2.0, -2.0])),
+                (0.5, XYVec::new([2.0, -2.0]))
+            ]
+        );
+        assert!(accelerations(&two).is_empty());
+        assert_eq!(moving_average(&two, 3), two.to_vec());
+        assert!(moving_average::<XYVec<f32>>(&[], 2).is_empty());
+    }
+
+    #[test]
+    fn smoothing_f64() {
+        // Alternating noise on a straight line averages out.
+        let samples: Vec<_> = (0..20)
+            .map(|i| {
+                let t = i as f64;
+                let noise = if i % 2 == 0 { 0.5 } else { -0.5 };
+                (t, XYVec::new([t, noise]))
+            })
+            .collect();
+        let smooth = moving_average(&samples, 2);
+        for (i, &(t, p)) in smooth.iter().enumerate().take(17).skip(2) {
+            assert_eq!(t, i as f64);
+            assert_relative_eq!(p.x(), t, epsilon = 1e-12);
+            assert!(p.y().abs() <= 0.1 + 1e-12);
+        }
+        let v = velocities(&smooth);
+        assert_relative_eq!(v[10].1.x(), 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let samples: Vec<_> = (0..5)
+            .map(|i| {
+                (
+                    f(i as f64 * 0.5),
+                    XYVec::new([f(3.0 * i as f64 * 0.5), f(0.0)]),
+                )
+            })
+            .collect();
+        for (_, v) in velocities(&samples) {
+            assert_eq!(v.x(), f(3.0));
+        }
+        for (_, a) in accelerations(&samples) {
+            assert_eq!(a.x(), f(0.0));
+        }
+    }
+}
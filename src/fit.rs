@@ -0,0 +1,178 @@
+//! Least-squares line and plane fits, from the centroid and the principal
+//! axes of the covariance (see [`Mat2::principal_axes`]).
+
+use crate::{stats::centroid, Mat2, Mat3, Plane, VecFloat, VecInner, XYVec, XYZVec};
+use std::fmt::{self, Formatter};
+
+/// Best-fit line through a set of points.
+#[derive(Clone, Copy, PartialEq)]
+pub struct LineFit<T> {
+    /// Centroid of the points, which lies on the line.
+    pub point: XYVec<T>,
+    /// Unit direction of the line.
+    pub direction: XYVec<T>,
+    /// Root-mean-square perpendicular distance from the points to the line.
+    pub rms_residual: T,
+}
+
+/// Best-fit plane through a set of points.
+#[derive(Clone, Copy, PartialEq)]
+pub struct PlaneFit<T> {
+    /// Centroid of the points, which lies on the plane.
+    pub point: XYZVec<T>,
+    /// Plane with a unit normal.
+    pub plane: Plane<T>,
+    /// Root-mean-square distance from the points to the plane.
+    pub rms_residual: T,
+}
+
+fn rms<T: VecFloat>(distances: impl Iterator<Item = T>, n: usize) -> T {
+    let sum = distances.fold(T::zero(), |acc, d| acc + d * d);
+    (sum / T::from_f64(n as f64)).sqrt()
+}
+
+/// Line minimizing the sum of squared perpendicular distances to `points`
+/// (total least squares). `None` if `points` is empty. If every point
+/// coincides, the direction is arbitrary.
+/// ```
+///     use xyzvec::{fit::fit_line, XYVec};
+///     use approx::assert_relative_eq;
+///
+///     let points = [
+///         XYVec::new([0.0f64, 1.0]),
+///         XYVec::new([1.0, 3.0]),
+///         XYVec::new([2.0, 5.0]),
+///     ];
+///     let fit = fit_line(&points).unwrap();
+///     assert_relative_eq!(fit.point.y(), 3.0);
+///     assert_relative_eq!(fit.direction.y() / fit.direction.x(), 2.0);
+///     assert_relative_eq!(fit.rms_residual, 0.0, epsilon = 1e-12);
+/// ```
+pub fn fit_line<T: VecFloat>(points: &[XYVec<T>]) -> Option<LineFit<T>> {
+    let (_, axes) = Mat2::covariance(points)?.principal_axes();
+    let point = centroid(points)?;
+    let [direction, normal] = axes;
+    let rms_residual = rms(
+        points.iter().map(|&p| (p - point).dot_prod(normal)),
+        points.len(),
+    );
+    Some(LineFit {
+        point,
+        direction,
+        rms_residual,
+    })
+}
+
+/// Plane minimizing the sum of squared distances to `points`. `None` if
+/// `points` is empty. The normal is arbitrary if the points are collinear,
+/// and is only determined up to sign.
+/// ```
+///     use xyzvec::{fit::fit_plane, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let points = [
+///         XYZVec::new([0.0f64, 0.0, 2.0]),
+///         XYZVec::new([1.0, 0.0, 2.0]),
+///         XYZVec::new([0.0, 1.0, 2.0]),
+///         XYZVec::new([1.0, 1.0, 2.0]),
+///     ];
+///     let fit = fit_plane(&points).unwrap();
+///     assert_relative_eq!(fit.plane.normal.z().abs(), 1.0);
+///     assert_relative_eq!(fit.plane.signed_distance(XYZVec::new([5.0, 5.0, 2.0])), 0.0);
+///     assert_relative_eq!(fit.rms_residual, 0.0);
+/// ```
+pub fn fit_plane<T: VecFloat>(points: &[XYZVec<T>]) -> Option<PlaneFit<T>> {
+    let (_, axes) = Mat3::covariance(points)?.principal_axes();
+    let point = centroid(points)?;
+    let plane = Plane::from_point_normal(point, axes[2]);
+    let rms_residual = rms(
+        points.iter().map(|&p| plane.signed_distance(p)),
+        points.len(),
+    );
+    Some(PlaneFit {
+        point,
+        plane,
+        rms_residual,
+    })
+}
+
+impl<T: VecInner> fmt::Debug for LineFit<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LineFit {{ point: {:?}, direction: {:?}, rms_residual: {:?} }}",
+            self.point, self.direction, self.rms_residual
+        )
+    }
+}
+
+impl<T: VecInner> fmt::Debug for PlaneFit<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "PlaneFit {{ point: {:?}, plane: {:?}, rms_residual: {:?} }}",
+            self.point, self.plane, self.rms_residual
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fit_line, fit_plane};
+    use crate::{XYVec, XYZVec};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn line_residual_f64() {
+        // Pairs of points one unit above and below the x axis.
+        let points: Vec<XYVec<f64>> = (0..10)
+            .flat_map(|i| [XYVec::new([i as f64, 1.0]), XYVec::new([i as f64, -1.0])])
+            .collect();
+        let fit = fit_line(&points).unwrap();
+        assert_relative_eq!(fit.direction.x().abs(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(fit.point.x(), 4.5);
+        assert_relative_eq!(fit.point.y(), 0.0);
+        assert_relative_eq!(fit.rms_residual, 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn line_vertical_f32() {
+        let points = [
+            XYVec::new([2.0f32, -1.0]),
+            XYVec::new([2.0, 0.0]),
+            XYVec::new([2.0, 3.0]),
+        ];
+        let fit = fit_line(&points).unwrap();
+        assert_relative_eq!(fit.direction.y().abs(), 1.0);
+        assert_relative_eq!(fit.direction.x(), 0.0);
+        assert_relative_eq!(fit.rms_residual, 0.0);
+        assert!(fit_line::<f32>(&[]).is_none());
+    }
+
+    #[test]
+    fn plane_tilted_f64() {
+        let normal = XYZVec::new([1.0f64, -2.0, 2.0]).div_by(3.0);
+        let (u, w) = normal.any_orthonormal_pair();
+        let origin = XYZVec::new([4.0, 0.5, -3.0]);
+        let points: Vec<XYZVec<f64>> = (0..25)
+            .map(|i| {
+                let (a, b) = ((i % 5) as f64 - 2.0, (i / 5) as f64 - 2.0);
+                let bump = if i % 2 == 0 { 0.1 } else { -0.1 };
+                origin + u.scale_by(a) + w.scale_by(b) + normal.scale_by(bump)
+            })
+            .collect();
+        let fit = fit_plane(&points).unwrap();
+        assert_relative_eq!(fit.plane.normal.dot_prod(normal).abs(), 1.0, epsilon = 1e-9);
+        assert_relative_eq!(
+            fit.plane.signed_distance(origin).abs(),
+            0.1 / 25.0,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            fit.rms_residual,
+            0.1 * (1.0 - 1.0 / 625.0f64).sqrt(),
+            epsilon = 1e-9
+        );
+        assert!(fit_plane::<f64>(&[]).is_none());
+    }
+}
@@ -0,0 +1,177 @@
+//! Conversion between vectors and flat arrays of their components, as
+//! used by GPU uploads and C APIs: `[x0, y0, x1, y1, ...]`.
+
+use crate::{error::VecError, VecInner, Vector, XYVec, XYZVec};
+
+/// Components of each vector in turn.
+/// ```
+///     use xyzvec::{flat::flatten, XYVec};
+///
+///     assert_eq!(flatten(vec![XYVec::new([1, 2]), XYVec::new([3, 4])]), vec![1, 2, 3, 4]);
+/// ```
+pub fn flatten<V: Vector>(vectors: Vec<V>) -> Vec<V::Scalar> {
+    let mut flat = Vec::with_capacity(vectors.len() * V::DIM);
+    flat.extend(flatten_iter(vectors));
+    flat
+}
+
+/// Lazy version of [`flatten`].
+pub fn flatten_iter<V: Vector>(
+    vectors: impl IntoIterator<Item = V>,
+) -> impl Iterator<Item = V::Scalar> {
+    vectors
+        .into_iter()
+        .flat_map(|v| (0..V::DIM).map(move |i| v.component(i)))
+}
+
+/// Vectors from consecutive runs of components. Fails unless the length is
+/// a multiple of the dimension.
+/// ```
+///     use xyzvec::{error::VecError, flat::unflatten, XYZVec};
+///
+///     let points: Vec<XYZVec<f32>> = unflatten(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+///     assert_eq!(points[1], XYZVec::new([4.0, 5.0, 6.0]));
+///     assert_eq!(
+///         unflatten::<XYZVec<f32>, _>(vec![1.0; 4]),
+///         Err(VecError::RaggedLength { dim: 3, found: 4 })
+///     );
+/// ```
+pub fn unflatten<V, T>(flat: Vec<T>) -> Result<Vec<V>, VecError>
+where
+    V: Vector<Scalar = T> + FromIterator<T>,
+    T: VecInner,
+{
+    Ok(unflatten_iter(&flat)?.collect())
+}
+
+/// Lazy version of [`unflatten`], reading from a borrowed buffer. The
+/// length is checked up front.
+pub fn unflatten_iter<V, T>(flat: &[T]) -> Result<impl Iterator<Item = V> + '_, VecError>
+where
+    V: Vector<Scalar = T> + FromIterator<T>,
+    T: VecInner,
+{
+    if !flat.len().is_multiple_of(V::DIM) {
+        return Err(VecError::RaggedLength {
+            dim: V::DIM,
+            found: flat.len(),
+        });
+    }
+    Ok(flat
+        .chunks_exact(V::DIM)
+        .map(|chunk| chunk.iter().copied().collect()))
+}
+
+/// Zero-copy views between scalar slices and vector slices. These rely on
+/// the vectors being `#[repr(transparent)]` over `[T; N]`, which also makes
+/// their alignment that of `T`, so only the length needs checking.
+macro_rules! impl_cast {
+    ($($vec:ident => $n:literal),*) => {$(
+        impl<T: VecInner> $vec<T> {
+            /// Views consecutive runs of scalars as vectors without
+            /// copying. Fails unless the length is a multiple of the
+            /// dimension.
+            pub fn cast_slice(flat: &[T]) -> Result<&[Self], VecError> {
+                if !flat.len().is_multiple_of($n) {
+                    return Err(VecError::RaggedLength { dim: $n, found: flat.len() });
+                }
+                // SAFETY: `Self` is `repr(transparent)` over `[T; N]`, so
+                // `len / N` vectors occupy exactly the same bytes, with the
+                // same alignment, as `len` scalars.
+                Ok(unsafe { std::slice::from_raw_parts(flat.as_ptr().cast(), flat.len() / $n) })
+            }
+
+            /// Mutable version of [`cast_slice`](Self::cast_slice).
+            pub fn cast_slice_mut(flat: &mut [T]) -> Result<&mut [Self], VecError> {
+                if !flat.len().is_multiple_of($n) {
+                    return Err(VecError::RaggedLength { dim: $n, found: flat.len() });
+                }
+                // SAFETY: as in `cast_slice`; the borrow is exclusive.
+                Ok(unsafe {
+                    std::slice::from_raw_parts_mut(flat.as_mut_ptr().cast(), flat.len() / $n)
+                })
+            }
+
+            /// Views vectors as their components without copying.
+            pub fn as_flat(vectors: &[Self]) -> &[T] {
+                // SAFETY: the inverse of `cast_slice`.
+                unsafe { std::slice::from_raw_parts(vectors.as_ptr().cast(), vectors.len() * $n) }
+            }
+
+            /// Mutable version of [`as_flat`](Self::as_flat).
+            pub fn as_flat_mut(vectors: &mut [Self]) -> &mut [T] {
+                // SAFETY: the inverse of `cast_slice_mut`.
+                unsafe {
+                    std::slice::from_raw_parts_mut(vectors.as_mut_ptr().cast(), vectors.len() * $n)
+                }
+            }
+        }
+    )*};
+}
+
+impl_cast!(XYVec => 2, XYZVec => 3);
+
+#[cfg(test)]
+mod tests {
+    use super::{flatten, flatten_iter, unflatten, unflatten_iter};
+    use crate::{error::VecError, XYVec, XYZVec};
+    use fixed::types::I28F4;
+
+    #[test]
+    fn round_trip() {
+        let f = |v: f64| I28F4::from_num(v);
+        let points = vec![XYVec::new([f(0.5), f(-1.0)]), XYVec::new([f(2.0), f(3.25)])];
+        let flat = flatten(points.clone());
+        assert_eq!(flat, vec![f(0.5), f(-1.0), f(2.0), f(3.25)]);
+        assert_eq!(unflatten::<XYVec<I28F4>, _>(flat), Ok(points));
+
+        let xyz = [XYZVec::new([1.0f64, 2.0, 3.0]); 3];
+        let flat: Vec<f64> = flatten_iter(xyz).collect();
+        assert_eq!(flat.len(), 9);
+        let back: Vec<XYZVec<f64>> = unflatten_iter(&flat).unwrap().collect();
+        assert_eq!(back, xyz);
+        assert!(unflatten_iter::<XYZVec<f64>, _>(&flat[..8]).is_err());
+        assert_eq!(unflatten::<XYVec<i32>, _>(vec![]), Ok(vec![]));
+        assert_eq!(
+            VecError::RaggedLength { dim: 2, found: 5 }.to_string(),
+            "expected a multiple of 2 components, found 5"
+        );
+    }
+
+    #[test]
+    fn zero_copy_casts() {
+        let mut buf = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let points = XYVec::cast_slice(&buf).unwrap();
+        assert_eq!(
+            points,
+            [
+                XYVec::new([1.0, 2.0]),
+                XYVec::new([3.0, 4.0]),
+                XYVec::new([5.0, 6.0])
+            ]
+        );
+        assert_eq!(
+            XYZVec::cast_slice(&buf).unwrap()[1],
+            XYZVec::new([4.0, 5.0, 6.0])
+        );
+        assert_eq!(
+            XYZVec::cast_slice(&buf[1..]),
+            Err(VecError::RaggedLength { dim: 3, found: 5 })
+        );
+        // Unaligned starts are fine: the alignment is that of `f32`.
+        assert_eq!(
+            XYVec::cast_slice(&buf[1..5]).unwrap()[0],
+            XYVec::new([2.0, 3.0])
+        );
+
+        for p in XYVec::cast_slice_mut(&mut buf).unwrap() {
+            *p = p.scale_by(2.0);
+        }
+        assert_eq!(buf, [2.0, 4.0, 6.0, 8.0, 10.0, 12.0]);
+
+        let mut points = vec![XYZVec::new([1i64, 2, 3]), XYZVec::new([4, 5, 6])];
+        assert_eq!(XYZVec::as_flat(&points), [1, 2, 3, 4, 5, 6]);
+        XYZVec::as_flat_mut(&mut points)[4] = 50;
+        assert_eq!(points[1].y(), 50);
+    }
+}
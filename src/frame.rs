@@ -0,0 +1,219 @@
+//! Coordinate frames: an origin and a right-handed orthonormal basis, both
+//! given in world coordinates.
+//!
+//! A frame converts points and vectors between world coordinates and its
+//! own local ones, and re-expresses them from one frame in another. Unlike
+//! a [`Pose2`](crate::pose::Pose2) or [`Pose3`](crate::pose::Pose3), which
+//! store a transform, a frame stores the axes themselves, which is how
+//! sensor mounts and body-fixed frames are usually specified.
+//! ```
+//!     use xyzvec::{frame::Frame2, XYVec};
+//!     use approx::assert_relative_eq;
+//!
+//!     // A sensor at (2, 1), looking along +y.
+//!     let sensor = Frame2::new(XYVec::new([2.0f64, 1.0]), XYVec::new([0.0, 1.0])).unwrap();
+//!     let hit = sensor.to_world(XYVec::new([3.0, 0.5]));
+//!     assert_relative_eq!(hit.x(), 1.5);
+//!     assert_relative_eq!(hit.y(), 4.0);
+//!     let back = sensor.to_local(hit);
+//!     assert_relative_eq!(back.x(), 3.0);
+//!     assert_relative_eq!(back.y(), 0.5);
+//! ```
+//!
+//! Conversions only need [`VecNum`], so frames with exactly representable
+//! axes work in fixed point through `from_axes`; building a frame from
+//! arbitrary directions needs [`VecFloat`].
+
+use crate::{basis::gram_schmidt, VecFloat, VecInner, VecNum, Vector, XYVec, XYZVec};
+use std::fmt::{self, Formatter};
+
+/// 2D coordinate frame. `axes[1]` is `axes[0]` turned counterclockwise by
+/// a quarter turn.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Frame2<T> {
+    pub origin: XYVec<T>,
+    pub axes: [XYVec<T>; 2],
+}
+
+/// 3D coordinate frame. `axes[2]` is `axes[0] × axes[1]`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Frame3<T> {
+    pub origin: XYZVec<T>,
+    pub axes: [XYZVec<T>; 3],
+}
+
+macro_rules! impl_frame {
+    ($frame:ident, $vec:ident, $n:literal) => {
+        impl<T: VecNum> $frame<T> {
+            /// Frame from its origin and axes. The axes are expected to be
+            /// a right-handed orthonormal basis; this is not checked.
+            pub fn from_axes(origin: $vec<T>, axes: [$vec<T>; $n]) -> Self {
+                Self { origin, axes }
+            }
+
+            /// The world frame itself.
+            pub fn identity() -> Self {
+                let axes = std::array::from_fn(|i| {
+                    $vec::new(std::array::from_fn(|j| {
+                        if i == j {
+                            T::one()
+                        } else {
+                            T::zero()
+                        }
+                    }))
+                });
+                Self::from_axes($vec::splat(T::zero()), axes)
+            }
+
+            /// Local coordinates of the world point `p`.
+            pub fn to_local(&self, p: $vec<T>) -> $vec<T> {
+                self.vector_to_local(p - self.origin)
+            }
+
+            /// World coordinates of the local point `p`.
+            pub fn to_world(&self, p: $vec<T>) -> $vec<T> {
+                self.origin + self.vector_to_world(p)
+            }
+
+            /// Local components of the world vector `v`. Unlike
+            /// [`to_local`](Self::to_local), ignores the origin; use this
+            /// for directions and velocities.
+            pub fn vector_to_local(&self, v: $vec<T>) -> $vec<T> {
+                $vec::new(self.axes.map(|a| a.dot_prod(v)))
+            }
+
+            /// World components of the local vector `v`.
+            pub fn vector_to_world(&self, v: $vec<T>) -> $vec<T> {
+                self.axes
+                    .iter()
+                    .zip(v.iter())
+                    .fold($vec::splat(T::zero()), |acc, (a, &c)| acc + a.scale_by(c))
+            }
+
+            /// The point `p`, given in `self`'s coordinates, in `other`'s.
+            pub fn point_into(&self, other: &Self, p: $vec<T>) -> $vec<T> {
+                other.to_local(self.to_world(p))
+            }
+
+            /// The vector `v`, given in `self`'s coordinates, in `other`'s.
+            pub fn vector_into(&self, other: &Self, v: $vec<T>) -> $vec<T> {
+                other.vector_to_local(self.vector_to_world(v))
+            }
+        }
+
+        impl<T: VecInner> fmt::Debug for $frame<T> {
+            fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+                write!(
+                    f,
+                    concat!(stringify!($frame), " {{ origin: {:?}, axes: {:?} }}"),
+                    self.origin, self.axes
+                )
+            }
+        }
+    };
+}
+
+impl_frame!(Frame2, XYVec, 2);
+impl_frame!(Frame3, XYZVec, 3);
+
+impl<T: VecFloat> Frame2<T> {
+    /// Frame at `origin` whose first axis points along `x_axis`. `None` if
+    /// `x_axis` is zero or not finite.
+    pub fn new(origin: XYVec<T>, x_axis: XYVec<T>) -> Option<Self> {
+        let x = x_axis.try_normalize()?.into_inner();
+        Some(Self::from_axes(origin, [x, XYVec::new([-x.y(), x.x()])]))
+    }
+}
+
+impl<T: VecFloat> Frame3<T> {
+    /// Frame at `origin` whose first axis points along `x_axis` and whose
+    /// second axis is the part of `xy_hint` perpendicular to it. `None` if
+    /// the two are parallel or either is zero (see [`gram_schmidt`]).
+    /// ```
+    ///     use xyzvec::{frame::Frame3, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let f = Frame3::new(
+    ///         XYZVec::new([0.0f64, 0.0, 1.0]),
+    ///         XYZVec::new([0.0, 2.0, 0.0]),
+    ///         XYZVec::new([-1.0, 1.0, 0.0]),
+    ///     )
+    ///     .unwrap();
+    ///     // x along world +y and y along world -x leave z pointing up.
+    ///     assert_relative_eq!(f.axes[2].z(), 1.0);
+    ///     let p = f.to_world(XYZVec::new([1.0, 0.0, 0.0]));
+    ///     assert_relative_eq!(p.y(), 1.0);
+    ///     assert_relative_eq!(p.z(), 1.0);
+    /// ```
+    pub fn new(origin: XYZVec<T>, x_axis: XYZVec<T>, xy_hint: XYZVec<T>) -> Option<Self> {
+        let [x, y] = gram_schmidt([x_axis, xy_hint])?;
+        Some(Self::from_axes(origin, [x, y, x.cross(y)]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Frame2, Frame3};
+    use crate::{XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    #[test]
+    fn round_trips_2d_f64() {
+        let f = Frame2::new(XYVec::new([-3.0f64, 2.0]), XYVec::new([3.0, 4.0])).unwrap();
+        let p = XYVec::new([1.5, -0.25]);
+        let back = f.to_local(f.to_world(p));
+        assert_relative_eq!(back.x(), p.x(), epsilon = 1e-12);
+        assert_relative_eq!(back.y(), p.y(), epsilon = 1e-12);
+        // Vectors ignore the origin but keep their length.
+        let v = f.vector_to_world(XYVec::new([5.0, 0.0]));
+        assert_relative_eq!(v.x(), 3.0);
+        assert_relative_eq!(v.y(), 4.0);
+        assert!(Frame2::new(XYVec::new([0.0f64, 0.0]), XYVec::new([0.0, 0.0])).is_none());
+        assert_eq!(Frame2::<f64>::identity().to_local(p), p);
+    }
+
+    #[test]
+    fn reexpress_3d_f64() {
+        let z = XYZVec::new([0.0f64, 0.0, 1.0]);
+        let a = Frame3::new(
+            XYZVec::new([1.0, 0.0, 0.0]),
+            XYZVec::new([0.0, 1.0, 0.0]),
+            -z,
+        )
+        .unwrap();
+        let b = Frame3::new(
+            XYZVec::new([0.0, 0.0, 5.0]),
+            z,
+            XYZVec::new([1.0, 0.0, 0.0]),
+        )
+        .unwrap();
+        // +y then -z completes to -x.
+        assert_relative_eq!(a.axes[2].x(), -1.0);
+        for p in [XYZVec::new([1.0, 2.0, 3.0]), XYZVec::new([-0.5, 0.0, 4.0])] {
+            let direct = b.to_local(a.to_world(p));
+            let via = a.point_into(&b, p);
+            assert_relative_eq!(direct.x(), via.x(), epsilon = 1e-12);
+            assert_relative_eq!(direct.y(), via.y(), epsilon = 1e-12);
+            assert_relative_eq!(direct.z(), via.z(), epsilon = 1e-12);
+        }
+        // a's +x is world +y, which is b's +z.
+        let v = a.vector_into(&b, XYZVec::new([1.0, 0.0, 0.0]));
+        assert_relative_eq!(v.z(), 1.0);
+        assert!(Frame3::new(z, z, z.scale_by(2.0)).is_none());
+    }
+
+    #[test]
+    fn axis_aligned_fixed() {
+        let f = |v: f64| I28F4::from_num(v);
+        let (zero, one) = (f(0.0), f(1.0));
+        // A frame turned a quarter turn, at (4, -2).
+        let frame = Frame2::from_axes(
+            XYVec::new([f(4.0), f(-2.0)]),
+            [XYVec::new([zero, one]), XYVec::new([-one, zero])],
+        );
+        let p = XYVec::new([f(1.5), f(0.25)]);
+        assert_eq!(frame.to_world(p), XYVec::new([f(3.75), f(-0.5)]));
+        assert_eq!(frame.to_local(frame.to_world(p)), p);
+    }
+}
@@ -0,0 +1,185 @@
+//! Geographic coordinates on a spherical Earth.
+//!
+//! Angles are in degrees and distances in meters. Scalars only need
+//! [`VecNum`], so fixed point works too; the trigonometry itself is done in
+//! `f64`.
+
+use crate::{VecInner, VecNum, XYVec};
+use std::fmt::{self, Formatter};
+
+/// Mean Earth radius in meters (IUGG).
+pub const EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// Latitude and longitude in degrees.
+#[derive(Clone, Copy, PartialEq)]
+pub struct LatLon<T> {
+    pub lat: T,
+    pub lon: T,
+}
+
+impl<T: VecNum> LatLon<T> {
+    pub fn new(lat: T, lon: T) -> Self {
+        Self { lat, lon }
+    }
+
+    fn to_radians(self) -> (f64, f64) {
+        (
+            self.lat.to_f64().to_radians(),
+            self.lon.to_f64().to_radians(),
+        )
+    }
+
+    fn from_radians(lat: f64, lon: f64) -> Self {
+        // Wrap longitude into [-180, 180).
+        let lon = (lon.to_degrees() + 180.0).rem_euclid(360.0) - 180.0;
+        Self::new(T::from_f64(lat.to_degrees()), T::from_f64(lon))
+    }
+
+    /// Great-circle distance to `other` in meters, by the haversine formula.
+    /// ```
+    ///     use xyzvec::geo::LatLon;
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let paris = LatLon::new(48.8566f64, 2.3522);
+    ///     let london = LatLon::new(51.5074, -0.1278);
+    ///     assert_relative_eq!(paris.haversine_distance(london), 343_560.0, max_relative = 1e-3);
+    /// ```
+    pub fn haversine_distance(&self, other: Self) -> T {
+        let (lat1, lon1) = self.to_radians();
+        let (lat2, lon2) = other.to_radians();
+        let h = ((lat2 - lat1) / 2.0).sin().powi(2)
+            + lat1.cos() * lat2.cos() * ((lon2 - lon1) / 2.0).sin().powi(2);
+        T::from_f64(2.0 * EARTH_RADIUS * h.sqrt().min(1.0).asin())
+    }
+
+    /// Initial bearing of the great circle from `self` to `other`, in
+    /// degrees clockwise from north, in `[0, 360)`.
+    pub fn initial_bearing(&self, other: Self) -> T {
+        let (lat1, lon1) = self.to_radians();
+        let (lat2, lon2) = other.to_radians();
+        let dlon = lon2 - lon1;
+        let y = dlon.sin() * lat2.cos();
+        let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+        T::from_f64(y.atan2(x).to_degrees().rem_euclid(360.0))
+    }
+
+    /// Point reached by travelling `distance` meters along the great circle
+    /// leaving `self` at `bearing` degrees clockwise from north.
+    /// ```
+    ///     use xyzvec::geo::LatLon;
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let start = LatLon::new(0.0f64, 0.0);
+    ///     let quarter = std::f64::consts::FRAC_PI_2 * xyzvec::geo::EARTH_RADIUS;
+    ///     let end = start.destination(90.0, quarter);
+    ///     assert_relative_eq!(end.lat, 0.0, epsilon = 1e-9);
+    ///     assert_relative_eq!(end.lon, 90.0, epsilon = 1e-9);
+    /// ```
+    pub fn destination(&self, bearing: T, distance: T) -> Self {
+        let (lat1, lon1) = self.to_radians();
+        let bearing = bearing.to_f64().to_radians();
+        let delta = distance.to_f64() / EARTH_RADIUS;
+        let lat2 = (lat1.sin() * delta.cos() + lat1.cos() * delta.sin() * bearing.cos()).asin();
+        let lon2 = lon1
+            + (bearing.sin() * delta.sin() * lat1.cos())
+                .atan2(delta.cos() - lat1.sin() * lat2.sin());
+        Self::from_radians(lat2, lon2)
+    }
+
+    /// Offset of `self` from `origin` in meters, with `x` east and `y`
+    /// north, by the equirectangular approximation. Accurate for distances
+    /// that are small compared to the Earth's radius, away from the poles.
+    /// ```
+    ///     use xyzvec::geo::LatLon;
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let origin = LatLon::new(45.0f64, 7.0);
+    ///     let p = LatLon::new(45.001, 7.002);
+    ///     let xy = p.to_local_xy(origin);
+    ///     assert_relative_eq!(xy.l2_norm(), p.haversine_distance(origin), max_relative = 1e-4);
+    ///     let back = LatLon::from_local_xy(origin, xy);
+    ///     assert_relative_eq!(back.lat, p.lat, epsilon = 1e-12);
+    ///     assert_relative_eq!(back.lon, p.lon, epsilon = 1e-12);
+    /// ```
+    pub fn to_local_xy(&self, origin: Self) -> XYVec<T> {
+        let (lat, lon) = self.to_radians();
+        let (lat0, lon0) = origin.to_radians();
+        // Take the shorter way around across the antimeridian.
+        let dlon = (lon - lon0 + std::f64::consts::PI).rem_euclid(std::f64::consts::TAU)
+            - std::f64::consts::PI;
+        XYVec::new([
+            T::from_f64(EARTH_RADIUS * dlon * lat0.cos()),
+            T::from_f64(EARTH_RADIUS * (lat - lat0)),
+        ])
+    }
+
+    /// Inverse of [`to_local_xy`](Self::to_local_xy).
+    pub fn from_local_xy(origin: Self, xy: XYVec<T>) -> Self {
+        let (lat0, lon0) = origin.to_radians();
+        let lat = lat0 + xy.y().to_f64() / EARTH_RADIUS;
+        let lon = lon0 + xy.x().to_f64() / (EARTH_RADIUS * lat0.cos());
+        Self::from_radians(lat, lon)
+    }
+}
+
+impl<T: VecInner> fmt::Debug for LatLon<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "LatLon {{ lat: {:?}, lon: {:?} }}", self.lat, self.lon)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LatLon, EARTH_RADIUS};
+    use approx::assert_relative_eq;
+    use fixed::types::I32F32;
+
+    #[test]
+    fn bearing_f64() {
+        let a = LatLon::new(0.0f64, 0.0);
+        assert_relative_eq!(a.initial_bearing(LatLon::new(1.0, 0.0)), 0.0);
+        assert_relative_eq!(a.initial_bearing(LatLon::new(0.0, 1.0)), 90.0);
+        assert_relative_eq!(a.initial_bearing(LatLon::new(-1.0, 0.0)), 180.0);
+        assert_relative_eq!(a.initial_bearing(LatLon::new(0.0, -1.0)), 270.0);
+    }
+
+    #[test]
+    fn destination_round_trip_f64() {
+        let start = LatLon::new(-33.8688f64, 151.2093);
+        for bearing in [0.0, 37.0, 145.0, 260.0] {
+            let end = start.destination(bearing, 250_000.0);
+            assert_relative_eq!(
+                start.haversine_distance(end),
+                250_000.0,
+                max_relative = 1e-9
+            );
+            let error = (start.initial_bearing(end) - bearing + 180.0).rem_euclid(360.0) - 180.0;
+            assert_relative_eq!(error, 0.0, epsilon = 1e-9);
+        }
+        // Crossing the antimeridian wraps the longitude.
+        let east = LatLon::new(0.0f64, 179.5).destination(90.0, EARTH_RADIUS.to_radians());
+        assert_relative_eq!(east.lon, -179.5, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn local_xy_antimeridian_f32() {
+        let origin = LatLon::new(10.0f32, 179.99);
+        let p = LatLon::new(10.0f32, -179.99);
+        let xy = p.to_local_xy(origin);
+        assert!(xy.x() > 0.0);
+        assert_relative_eq!(xy.x(), p.haversine_distance(origin), max_relative = 1e-3);
+    }
+
+    #[test]
+    fn fixed() {
+        let f = |v: f64| I32F32::from_num(v);
+        let a = LatLon::new(f(52.0), f(13.0));
+        let b = LatLon::new(f(52.0), f(14.0));
+        let d = a.haversine_distance(b).to_num::<f64>();
+        let expected = LatLon::new(52.0f64, 13.0).haversine_distance(LatLon::new(52.0, 14.0));
+        assert_relative_eq!(d, expected, epsilon = 1e-6);
+        let xy = b.to_local_xy(a);
+        assert_relative_eq!(xy.x().to_num::<f64>(), expected, max_relative = 1e-4);
+        assert_eq!(xy.y(), f(0.0));
+    }
+}
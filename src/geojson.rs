@@ -0,0 +1,301 @@
+//! GeoJSON geometry objects for points, polylines, and polygons: `Point`,
+//! `LineString`, and `Polygon`.
+//!
+//! Vectors map to GeoJSON positions as `[x, y]` = `[longitude, latitude]`,
+//! with `z` as the optional altitude. Components past the vector's own, such
+//! as an altitude read into an [`XYVec`], are ignored. Polygons are written as a single
+//! closed exterior ring; polygons with holes are rejected on input, since
+//! [`Polygon2`] has none.
+
+use crate::{Polygon2, Polyline, VecNum, Vector, XYVec, XYZVec};
+use serde_json::{json, Value};
+use std::fmt::{self, Display, Formatter};
+
+/// Why a GeoJSON value could not be converted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GeoJsonError {
+    /// The `type` member was missing or was not the one expected.
+    WrongType {
+        expected: &'static str,
+        found: String,
+    },
+    /// A position had fewer components than the vector has.
+    WrongDimension { expected: usize, found: usize },
+    /// `coordinates` was missing, not nested as the type requires, or held
+    /// something other than numbers.
+    Malformed,
+    /// The geometry is valid GeoJSON but cannot be represented, such as a
+    /// polygon with holes.
+    Unsupported(&'static str),
+}
+
+impl Display for GeoJsonError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            GeoJsonError::WrongType { expected, found } => {
+                write!(f, "expected {expected} geometry, found `{found}`")
+            }
+            GeoJsonError::WrongDimension { expected, found } => {
+                write!(
+                    f,
+                    "expected at least {expected} coordinates per position, found {found}"
+                )
+            }
+            GeoJsonError::Malformed => write!(f, "malformed GeoJSON coordinates"),
+            GeoJsonError::Unsupported(what) => write!(f, "unsupported geometry: {what}"),
+        }
+    }
+}
+
+impl std::error::Error for GeoJsonError {}
+
+/// Point types that can be written as a GeoJSON position.
+pub trait GeoJsonPosition: Sized {
+    fn to_position(&self) -> Value;
+    fn from_position(value: &Value) -> Result<Self, GeoJsonError>;
+}
+
+fn parse_components<T: VecNum, const N: usize>(value: &Value) -> Result<[T; N], GeoJsonError> {
+    let parts = value.as_array().ok_or(GeoJsonError::Malformed)?;
+    if parts.len() < N {
+        return Err(GeoJsonError::WrongDimension {
+            expected: N,
+            found: parts.len(),
+        });
+    }
+    let mut values = [T::zero(); N];
+    for (v, part) in values.iter_mut().zip(parts) {
+        *v = T::from_f64(part.as_f64().ok_or(GeoJsonError::Malformed)?);
+    }
+    Ok(values)
+}
+
+impl<T: VecNum> GeoJsonPosition for XYVec<T> {
+    fn to_position(&self) -> Value {
+        json!([self.x().to_f64(), self.y().to_f64()])
+    }
+
+    fn from_position(value: &Value) -> Result<Self, GeoJsonError> {
+        parse_components(value).map(XYVec::new)
+    }
+}
+
+impl<T: VecNum> GeoJsonPosition for XYZVec<T> {
+    fn to_position(&self) -> Value {
+        json!([self.x().to_f64(), self.y().to_f64(), self.z().to_f64()])
+    }
+
+    fn from_position(value: &Value) -> Result<Self, GeoJsonError> {
+        parse_components(value).map(XYZVec::new)
+    }
+}
+
+fn geometry(kind: &str, coordinates: Value) -> Value {
+    json!({ "type": kind, "coordinates": coordinates })
+}
+
+/// The `coordinates` member of `value`, checking that its `type` is
+/// `expected`.
+fn coordinates<'a>(value: &'a Value, expected: &'static str) -> Result<&'a Value, GeoJsonError> {
+    let kind = value.get("type").and_then(Value::as_str).unwrap_or("");
+    if kind != expected {
+        return Err(GeoJsonError::WrongType {
+            expected,
+            found: kind.to_string(),
+        });
+    }
+    value.get("coordinates").ok_or(GeoJsonError::Malformed)
+}
+
+fn parse_positions<V: GeoJsonPosition>(value: &Value) -> Result<Vec<V>, GeoJsonError> {
+    value
+        .as_array()
+        .ok_or(GeoJsonError::Malformed)?
+        .iter()
+        .map(V::from_position)
+        .collect()
+}
+
+impl<T: VecNum> XYVec<T> {
+    /// `Point` geometry with `x` as longitude and `y` as latitude.
+    /// ```
+    ///     use xyzvec::XYVec;
+    ///
+    ///     let v = XYVec::new([2.35f64, 48.85]);
+    ///     let json = v.to_geojson();
+    ///     assert_eq!(json.to_string(), r#"{"coordinates":[2.35,48.85],"type":"Point"}"#);
+    ///     assert_eq!(XYVec::from_geojson(&json), Ok(v));
+    /// ```
+    pub fn to_geojson(&self) -> Value {
+        geometry("Point", self.to_position())
+    }
+
+    pub fn from_geojson(value: &Value) -> Result<Self, GeoJsonError> {
+        Self::from_position(coordinates(value, "Point")?)
+    }
+}
+
+impl<T: VecNum> XYZVec<T> {
+    /// `Point` geometry with `z` as altitude.
+    pub fn to_geojson(&self) -> Value {
+        geometry("Point", self.to_position())
+    }
+
+    pub fn from_geojson(value: &Value) -> Result<Self, GeoJsonError> {
+        Self::from_position(coordinates(value, "Point")?)
+    }
+}
+
+impl<V: Vector + GeoJsonPosition> Polyline<V>
+where
+    V::Scalar: VecNum,
+{
+    /// `LineString` geometry.
+    /// ```
+    ///     use xyzvec::{Polyline, XYVec};
+    ///
+    ///     let line = Polyline::new(vec![XYVec::new([0, 0]), XYVec::new([3, 4])]);
+    ///     let json = line.to_geojson();
+    ///     assert_eq!(json["coordinates"], serde_json::json!([[0.0, 0.0], [3.0, 4.0]]));
+    ///     assert_eq!(Polyline::from_geojson(&json), Ok(line));
+    /// ```
+    pub fn to_geojson(&self) -> Value {
+        let positions: Vec<Value> = self.points().iter().map(V::to_position).collect();
+        geometry("LineString", Value::Array(positions))
+    }
+
+    pub fn from_geojson(value: &Value) -> Result<Self, GeoJsonError> {
+        parse_positions(coordinates(value, "LineString")?).map(Self::new)
+    }
+}
+
+impl<T: VecNum> Polygon2<T> {
+    /// `Polygon` geometry with a single ring, closed by repeating the first
+    /// vertex. GeoJSON expects exterior rings to be counterclockwise; the
+    /// vertex order is written as is.
+    /// ```
+    ///     use xyzvec::{Polygon2, XYVec};
+    ///
+    ///     let tri = Polygon2::new(vec![XYVec::new([0, 0]), XYVec::new([1, 0]), XYVec::new([0, 1])]);
+    ///     let json = tri.to_geojson();
+    ///     assert_eq!(json["coordinates"][0].as_array().unwrap().len(), 4);
+    ///     assert_eq!(Polygon2::from_geojson(&json), Ok(tri));
+    /// ```
+    pub fn to_geojson(&self) -> Value {
+        let vertices = self.vertices();
+        let rings = if vertices.is_empty() {
+            Vec::new()
+        } else {
+            let ring: Vec<Value> = vertices
+                .iter()
+                .chain(vertices.first())
+                .map(XYVec::to_position)
+                .collect();
+            vec![Value::Array(ring)]
+        };
+        geometry("Polygon", Value::Array(rings))
+    }
+
+    /// Parses a `Polygon` with a single ring. A closing vertex equal to the
+    /// first is dropped.
+    pub fn from_geojson(value: &Value) -> Result<Self, GeoJsonError> {
+        let rings = coordinates(value, "Polygon")?
+            .as_array()
+            .ok_or(GeoJsonError::Malformed)?;
+        let mut vertices: Vec<XYVec<T>> = match rings.as_slice() {
+            [] => Vec::new(),
+            [ring] => parse_positions(ring)?,
+            _ => return Err(GeoJsonError::Unsupported("polygon with holes")),
+        };
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Ok(Self::new(vertices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeoJsonError;
+    use crate::{Polygon2, Polyline, XYVec, XYZVec};
+    use fixed::types::I16F16;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn points() {
+        let f = |v: f64| I16F16::from_num(v);
+        let v = XYZVec::new([f(7.5), f(-45.25), f(120.0)]);
+        assert_eq!(
+            v.to_geojson(),
+            json!({ "type": "Point", "coordinates": [7.5, -45.25, 120.0] })
+        );
+        assert_eq!(XYZVec::from_geojson(&v.to_geojson()), Ok(v));
+        let parsed: Value =
+            serde_json::from_str(r#"{"type":"Point","coordinates":[1,2]}"#).unwrap();
+        assert_eq!(XYVec::<i32>::from_geojson(&parsed), Ok(XYVec::new([1, 2])));
+        // The altitude is dropped when reading into a 2D vector.
+        let with_altitude = json!({ "type": "Point", "coordinates": [1.5, 2.5, 30.0] });
+        assert_eq!(
+            XYVec::from_geojson(&with_altitude),
+            Ok(XYVec::new([1.5f64, 2.5]))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            XYZVec::<f64>::from_geojson(&json!({ "type": "Point", "coordinates": [1.0, 2.0] })),
+            Err(GeoJsonError::WrongDimension {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            XYVec::<f64>::from_geojson(&json!({ "type": "LineString", "coordinates": [] })),
+            Err(GeoJsonError::WrongType {
+                expected: "Point",
+                found: "LineString".to_string()
+            })
+        );
+        assert_eq!(
+            XYVec::<f64>::from_geojson(&json!({ "coordinates": [1.0, 2.0] })),
+            Err(GeoJsonError::WrongType {
+                expected: "Point",
+                found: String::new()
+            })
+        );
+        assert_eq!(
+            XYVec::<f64>::from_geojson(&json!({ "type": "Point", "coordinates": ["a", 2.0] })),
+            Err(GeoJsonError::Malformed)
+        );
+        let holed = json!({
+            "type": "Polygon",
+            "coordinates": [
+                [[0, 0], [4, 0], [0, 4], [0, 0]],
+                [[1, 1], [2, 1], [1, 2], [1, 1]]
+            ]
+        });
+        assert_eq!(
+            Polygon2::<f64>::from_geojson(&holed),
+            Err(GeoJsonError::Unsupported("polygon with holes"))
+        );
+    }
+
+    #[test]
+    fn polylines_and_polygons() {
+        let line = Polyline::new(vec![
+            XYZVec::new([0.0f32, 1.0, 2.0]),
+            XYZVec::new([3.5, 4.0, 5.0]),
+        ]);
+        assert_eq!(Polyline::from_geojson(&line.to_geojson()), Ok(line));
+
+        let empty: Polyline<XYVec<f64>> = Polyline::new(Vec::new());
+        assert_eq!(empty.to_geojson()["coordinates"], json!([]));
+
+        let open = json!({ "type": "Polygon", "coordinates": [[[0, 0], [2, 0], [2, 2]]] });
+        assert_eq!(Polygon2::<i32>::from_geojson(&open).unwrap().len(), 3);
+        let none = Polygon2::<i32>::new(Vec::new()).to_geojson();
+        assert_eq!(none["coordinates"], json!([]));
+        assert_eq!(Polygon2::<i32>::from_geojson(&none).unwrap().len(), 0);
+    }
+}
@@ -0,0 +1,97 @@
+//! Support for putting `f32` vectors in GPU buffers. Requires the `gpu`
+//! feature.
+//!
+//! [`VertexAttr`] gives the `wgpu` vertex format of each vector type, for
+//! building vertex buffer layouts. The vectors also implement
+//! [`encase::ShaderType`], so they can sit in `#[derive(ShaderType)]`
+//! structs written to uniform (std140) or storage (std430) buffers, with
+//! `encase` inserting the padding WGSL expects, such as the 4 bytes after
+//! every `vec3<f32>`.
+
+use crate::{XYVec, XYZVec, XYZWVec};
+use wgpu_types::{BufferAddress, ShaderLocation, VertexAttribute, VertexFormat};
+
+/// Vectors usable as vertex attributes.
+pub trait VertexAttr {
+    const VERTEX_FORMAT: VertexFormat;
+
+    /// Attribute reading `Self` from `offset` bytes into each vertex.
+    fn vertex_attribute(shader_location: ShaderLocation, offset: BufferAddress) -> VertexAttribute {
+        VertexAttribute {
+            format: Self::VERTEX_FORMAT,
+            offset,
+            shader_location,
+        }
+    }
+}
+
+impl VertexAttr for XYVec<f32> {
+    const VERTEX_FORMAT: VertexFormat = VertexFormat::Float32x2;
+}
+
+impl VertexAttr for XYZVec<f32> {
+    const VERTEX_FORMAT: VertexFormat = VertexFormat::Float32x3;
+}
+
+impl VertexAttr for XYZWVec<f32> {
+    const VERTEX_FORMAT: VertexFormat = VertexFormat::Float32x4;
+}
+
+encase::impl_vector!(2, XYVec<f32>, f32; using AsRef AsMut From);
+encase::impl_vector!(3, XYZVec<f32>, f32; using AsRef AsMut From);
+encase::impl_vector!(4, XYZWVec<f32>, f32; using AsRef AsMut From);
+
+#[cfg(test)]
+mod tests {
+    use super::VertexAttr;
+    use crate::{XYVec, XYZVec, XYZWVec};
+    use encase::{ShaderType, StorageBuffer, UniformBuffer};
+
+    #[test]
+    fn vertex_formats_match_layout() {
+        assert_eq!(XYVec::<f32>::VERTEX_FORMAT.size(), 8);
+        assert_eq!(XYZVec::<f32>::VERTEX_FORMAT.size(), 12);
+        assert_eq!(XYZWVec::<f32>::VERTEX_FORMAT.size(), 16);
+        let attr = XYZVec::<f32>::vertex_attribute(1, 8);
+        assert_eq!((attr.shader_location, attr.offset), (1, 8));
+    }
+
+    // The derive emits per-field layout `check` fns that nothing calls.
+    #[allow(dead_code)]
+    #[test]
+    fn vec3_is_padded() {
+        #[derive(ShaderType)]
+        struct Light {
+            position: XYZVec<f32>,
+            color: XYZVec<f32>,
+            intensity: f32,
+        }
+
+        let light = Light {
+            position: XYZVec::new([1.0, 2.0, 3.0]),
+            color: XYZVec::new([0.5, 0.25, 1.0]),
+            intensity: 4.0,
+        };
+        let mut uniform = UniformBuffer::new(Vec::<u8>::new());
+        uniform.write(&light).unwrap();
+        let bytes = uniform.into_inner();
+        let at = |i: usize| f32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(at(8), 3.0);
+        assert_eq!(at(16), 0.5);
+        // `intensity` fills the padding after `color`.
+        assert_eq!(at(28), 4.0);
+
+        let mut storage = StorageBuffer::new(Vec::<u8>::new());
+        storage
+            .write(&[XYZVec::new([1.0f32, 2.0, 3.0]); 2])
+            .unwrap();
+        let bytes = storage.into_inner();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(f32::from_le_bytes(bytes[16..20].try_into().unwrap()), 1.0);
+
+        let back = StorageBuffer::new(bytes);
+        let read: [XYZVec<f32>; 2] = back.create().unwrap();
+        assert_eq!(read[1], XYZVec::new([1.0, 2.0, 3.0]));
+    }
+}
@@ -0,0 +1,281 @@
+//! Hexagonal grids in axial coordinates.
+//!
+//! A [`Hex`] is addressed by two axes `q` and `r` at 120° to each other; the
+//! implied third cube coordinate is `s = -q - r`. [`HexLayout`] maps cells
+//! to and from pixel positions for pointy- or flat-topped hexagons.
+
+use crate::{VecInner, VecNum, XYVec};
+use std::fmt::{self, Formatter};
+use std::ops::{Add, Neg, Sub};
+
+/// Hex grid cell in axial coordinates.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, Default)]
+pub struct Hex {
+    pub q: i32,
+    pub r: i32,
+}
+
+/// Axial offsets of the six neighbors, counterclockwise starting from `+q`
+/// (east, for pointy-topped layouts).
+const DIRECTIONS: [Hex; 6] = [
+    Hex { q: 1, r: 0 },
+    Hex { q: 1, r: -1 },
+    Hex { q: 0, r: -1 },
+    Hex { q: -1, r: 0 },
+    Hex { q: -1, r: 1 },
+    Hex { q: 0, r: 1 },
+];
+
+impl Hex {
+    pub fn new(q: i32, r: i32) -> Self {
+        Self { q, r }
+    }
+
+    /// Cell with cube coordinates `q`, `r`, `s`, or `None` unless they sum
+    /// to zero.
+    pub fn from_cube([q, r, s]: [i32; 3]) -> Option<Self> {
+        (q as i64 + r as i64 + s as i64 == 0).then_some(Self::new(q, r))
+    }
+
+    /// The third cube coordinate, `-q - r`.
+    pub fn s(&self) -> i32 {
+        -self.q - self.r
+    }
+
+    pub fn cube(&self) -> [i32; 3] {
+        [self.q, self.r, self.s()]
+    }
+
+    /// Number of steps between cells.
+    /// ```
+    ///     use xyzvec::hex::Hex;
+    ///
+    ///     assert_eq!(Hex::new(0, 0).distance(Hex::new(3, -1)), 3);
+    ///     assert_eq!(Hex::new(-1, 2).distance(Hex::new(2, -2)), 4);
+    /// ```
+    pub fn distance(&self, other: Self) -> i32 {
+        let d = *self - other;
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+
+    /// The six adjacent cells, counterclockwise starting from `+q`.
+    pub fn neighbors(&self) -> impl Iterator<Item = Hex> {
+        let h = *self;
+        DIRECTIONS.into_iter().map(move |d| h + d)
+    }
+
+    /// Nearest cell to fractional axial coordinates.
+    pub fn round(q: f64, r: f64) -> Self {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        // Rounding each independently can break `q + r + s = 0`; recompute
+        // the one that moved furthest.
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        Self::new(rq as i32, rr as i32)
+    }
+
+    /// Cells on the straight line from `self` to `other`, both included,
+    /// one per step.
+    /// ```
+    ///     use xyzvec::hex::Hex;
+    ///
+    ///     let line = Hex::new(0, 0).line_to(Hex::new(3, 0));
+    ///     assert_eq!(line, vec![Hex::new(0, 0), Hex::new(1, 0), Hex::new(2, 0), Hex::new(3, 0)]);
+    /// ```
+    pub fn line_to(&self, other: Self) -> Vec<Hex> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![*self];
+        }
+        // Nudge off cell edges so ties round consistently.
+        let (aq, ar) = (self.q as f64 + 1e-6, self.r as f64 + 1e-6);
+        let (bq, br) = (other.q as f64 + 1e-6, other.r as f64 + 1e-6);
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / n as f64;
+                Self::round(aq + (bq - aq) * t, ar + (br - ar) * t)
+            })
+            .collect()
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.q + other.q, self.r + other.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.q - other.q, self.r - other.r)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.q, -self.r)
+    }
+}
+
+/// Which way the hexagons point.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HexOrientation {
+    /// A vertex points along `+y`; rows of cells run along `x`.
+    PointyTop,
+    /// An edge faces `+y`; columns of cells run along `y`.
+    FlatTop,
+}
+
+/// Placement of a hex grid in pixel space: cell `(0, 0)` is centered on
+/// `origin`, and `size` is the distance from a cell's center to its
+/// vertices along each axis.
+#[derive(Clone, Copy, PartialEq)]
+pub struct HexLayout<T> {
+    pub orientation: HexOrientation,
+    pub size: XYVec<T>,
+    pub origin: XYVec<T>,
+}
+
+const SQRT_3: f64 = 1.732_050_807_568_877_2;
+
+impl<T: VecNum> HexLayout<T> {
+    pub fn new(orientation: HexOrientation, size: XYVec<T>, origin: XYVec<T>) -> Self {
+        Self {
+            orientation,
+            size,
+            origin,
+        }
+    }
+
+    /// Center of `hex`.
+    /// ```
+    ///     use xyzvec::{hex::{Hex, HexLayout, HexOrientation}, XYVec};
+    ///
+    ///     let layout = HexLayout::new(HexOrientation::FlatTop, XYVec::new([10.0f64, 10.0]), XYVec::new([0.0, 0.0]));
+    ///     let p = layout.to_pixel(Hex::new(2, -1));
+    ///     assert_eq!(p.x(), 30.0);
+    ///     assert_eq!(layout.from_pixel(p), Hex::new(2, -1));
+    /// ```
+    pub fn to_pixel(&self, hex: Hex) -> XYVec<T> {
+        let (q, r) = (hex.q as f64, hex.r as f64);
+        let (x, y) = match self.orientation {
+            HexOrientation::PointyTop => (SQRT_3 * q + SQRT_3 / 2.0 * r, 1.5 * r),
+            HexOrientation::FlatTop => (1.5 * q, SQRT_3 / 2.0 * q + SQRT_3 * r),
+        };
+        let (sx, sy) = (self.size.x().to_f64(), self.size.y().to_f64());
+        let (ox, oy) = (self.origin.x().to_f64(), self.origin.y().to_f64());
+        XYVec::new([x * sx + ox, y * sy + oy].map(T::from_f64))
+    }
+
+    /// The cell containing the pixel `p`.
+    pub fn from_pixel(&self, p: XYVec<T>) -> Hex {
+        let x = (p.x().to_f64() - self.origin.x().to_f64()) / self.size.x().to_f64();
+        let y = (p.y().to_f64() - self.origin.y().to_f64()) / self.size.y().to_f64();
+        let (q, r) = match self.orientation {
+            HexOrientation::PointyTop => (SQRT_3 / 3.0 * x - y / 3.0, 2.0 / 3.0 * y),
+            HexOrientation::FlatTop => (2.0 / 3.0 * x, -x / 3.0 + SQRT_3 / 3.0 * y),
+        };
+        Hex::round(q, r)
+    }
+}
+
+impl<T: VecInner> fmt::Debug for HexLayout<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "HexLayout {{ orientation: {:?}, size: {:?}, origin: {:?} }}",
+            self.orientation, self.size, self.origin
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Hex, HexLayout, HexOrientation};
+    use crate::XYVec;
+    use approx::assert_relative_eq;
+    use fixed::types::I20F12;
+    use std::collections::HashSet;
+
+    #[test]
+    fn coordinates_and_neighbors() {
+        let h = Hex::new(2, -5);
+        assert_eq!(h.cube(), [2, -5, 3]);
+        assert_eq!(Hex::from_cube([2, -5, 3]), Some(h));
+        assert_eq!(Hex::from_cube([2, -5, 2]), None);
+        let neighbors: HashSet<_> = h.neighbors().collect();
+        assert_eq!(neighbors.len(), 6);
+        assert!(neighbors.iter().all(|&n| h.distance(n) == 1));
+        assert_eq!(h - h, Hex::default());
+        assert_eq!(-(h + Hex::new(1, 1)), Hex::new(-3, 4));
+        // Two steps out there are twelve cells.
+        let ring2: HashSet<_> = neighbors
+            .iter()
+            .flat_map(|n| n.neighbors())
+            .filter(|&n| h.distance(n) == 2)
+            .collect();
+        assert_eq!(ring2.len(), 12);
+    }
+
+    #[test]
+    fn lines() {
+        let (a, b) = (Hex::new(-2, 3), Hex::new(4, -3));
+        let line = a.line_to(b);
+        assert_eq!(line.len() as i32, a.distance(b) + 1);
+        assert_eq!((line[0], line[line.len() - 1]), (a, b));
+        assert!(line.windows(2).all(|w| w[0].distance(w[1]) == 1));
+        assert_eq!(a.line_to(a), vec![a]);
+    }
+
+    #[test]
+    fn pixel_round_trip_f64() {
+        for orientation in [HexOrientation::PointyTop, HexOrientation::FlatTop] {
+            let layout = HexLayout::new(
+                orientation,
+                XYVec::new([8.0f64, 6.0]),
+                XYVec::new([100.0, -40.0]),
+            );
+            for q in -4..=4 {
+                for r in -4..=4 {
+                    let h = Hex::new(q, r);
+                    let center = layout.to_pixel(h);
+                    assert_eq!(layout.from_pixel(center), h);
+                    // Points well inside the cell map back to it.
+                    let nudged = center + XYVec::new([2.0, -2.0]);
+                    assert_eq!(layout.from_pixel(nudged), h);
+                }
+            }
+            // Neighbor centers are sqrt(3) * size apart for regular hexagons.
+            let square = HexLayout::new(
+                orientation,
+                XYVec::new([1.0f64, 1.0]),
+                XYVec::new([0.0, 0.0]),
+            );
+            let d = (square.to_pixel(Hex::new(1, 0)) - square.to_pixel(Hex::new(0, 0))).l2_norm();
+            assert_relative_eq!(d, 3f64.sqrt(), epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn fixed() {
+        let f = |v: f64| I20F12::from_num(v);
+        let layout = HexLayout::new(
+            HexOrientation::PointyTop,
+            XYVec::new([f(16.0), f(16.0)]),
+            XYVec::new([f(0.0), f(0.0)]),
+        );
+        let h = Hex::new(-3, 2);
+        assert_eq!(layout.from_pixel(layout.to_pixel(h)), h);
+    }
+}
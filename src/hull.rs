@@ -0,0 +1,158 @@
+use crate::{CrossSign, XYVec};
+use std::cmp::Ordering;
+
+/// Convex hull of a set of points using Andrew's monotone chain.
+///
+/// Returns the hull vertices in counter-clockwise order, starting from the
+/// point with the lowest `x` (then lowest `y`). Duplicate and collinear
+/// boundary points are dropped. Turns are decided with [`CrossSign`], so the
+/// result is exact for integer and fixed-point coordinates as long as the
+/// differences between points do not overflow `T`.
+/// ```
+///     use xyzvec::{hull::convex_hull, XYVec};
+///
+///     let points = [
+///         XYVec::new([0, 0]),
+///         XYVec::new([2, 0]),
+///         XYVec::new([1, 1]),
+///         XYVec::new([2, 2]),
+///         XYVec::new([0, 2]),
+///         XYVec::new([1, 0]),
+///     ];
+///     let hull = convex_hull(&points);
+///     assert_eq!(
+///         hull,
+///         vec![
+///             XYVec::new([0, 0]),
+///             XYVec::new([2, 0]),
+///             XYVec::new([2, 2]),
+///             XYVec::new([0, 2]),
+///         ]
+///     );
+/// ```
+pub fn convex_hull<T: CrossSign>(points: &[XYVec<T>]) -> Vec<XYVec<T>> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| {
+        a.x()
+            .partial_cmp(&b.x())
+            .unwrap_or(Ordering::Equal)
+            .then(a.y().partial_cmp(&b.y()).unwrap_or(Ordering::Equal))
+    });
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let turns_left = |hull: &[XYVec<T>], p: XYVec<T>| {
+        let a = hull[hull.len() - 2];
+        let b = hull[hull.len() - 1];
+        T::cross_sign(b - a, p - a) == Ordering::Greater
+    };
+
+    let mut hull: Vec<XYVec<T>> = Vec::with_capacity(sorted.len() + 1);
+    for &p in sorted.iter() {
+        while hull.len() >= 2 && !turns_left(&hull, p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    let lower_len = hull.len() + 1;
+    for &p in sorted.iter().rev().skip(1) {
+        while hull.len() >= lower_len && !turns_left(&hull, p) {
+            hull.pop();
+        }
+        hull.push(p);
+    }
+    // The upper chain ends on the starting point.
+    hull.pop();
+    hull
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{hull::convex_hull, Orientation, Polygon2, XYVec};
+    use fixed::types::I28F4;
+
+    fn fixed_xy(x: f64, y: f64) -> XYVec<I28F4> {
+        XYVec::new([I28F4::from_num(x), I28F4::from_num(y)])
+    }
+
+    #[test]
+    fn hull_f64() {
+        let points: Vec<XYVec<f64>> = (0..20)
+            .map(|i| {
+                let t = i as f64 * 0.7;
+                XYVec::new([t.cos() * (1.0 + (i % 3) as f64), t.sin()])
+            })
+            .collect();
+        let hull = convex_hull(&points);
+        let polygon = Polygon2::new(hull.clone());
+        assert_eq!(polygon.orientation(), Orientation::CounterClockwise);
+        for p in points.iter() {
+            assert!(polygon.contains_point(*p));
+        }
+        for i in 0..hull.len() {
+            let a = hull[i];
+            let b = hull[(i + 1) % hull.len()];
+            let c = hull[(i + 2) % hull.len()];
+            assert_eq!(Orientation::of(a, b, c), Orientation::CounterClockwise);
+        }
+    }
+
+    #[test]
+    fn hull_small_inputs_f32() {
+        assert!(convex_hull::<f32>(&[]).is_empty());
+        let p = XYVec::new([1.0f32, 2.0]);
+        assert_eq!(convex_hull(&[p, p]), vec![p]);
+        let q = XYVec::new([0.0f32, 0.0]);
+        assert_eq!(convex_hull(&[p, q]), vec![q, p]);
+    }
+
+    #[test]
+    fn hull_collinear_f32() {
+        let points = [
+            XYVec::new([0.0f32, 0.0]),
+            XYVec::new([1.0, 1.0]),
+            XYVec::new([2.0, 2.0]),
+            XYVec::new([3.0, 3.0]),
+        ];
+        let hull = convex_hull(&points);
+        assert_eq!(hull, vec![points[0], points[3]]);
+    }
+
+    #[test]
+    fn hull_i32() {
+        let points: Vec<XYVec<i32>> = (-3i32..=3)
+            .flat_map(|x| (-3i32..=3).map(move |y| XYVec::new([x, y])))
+            .filter(|p| p.x().abs() + p.y().abs() <= 3)
+            .collect();
+        let hull = convex_hull(&points);
+        assert_eq!(
+            hull,
+            vec![
+                XYVec::new([-3, 0]),
+                XYVec::new([0, -3]),
+                XYVec::new([3, 0]),
+                XYVec::new([0, 3]),
+            ]
+        );
+    }
+
+    #[test]
+    fn hull_fixed() {
+        // The top vertex rises above the bottom edge by less than I28F4
+        // multiplication can resolve, but it is still on the hull.
+        let points = [
+            fixed_xy(0.0, 0.0),
+            fixed_xy(0.25, 0.0),
+            fixed_xy(0.125, 0.0625),
+            fixed_xy(0.125, -1.0),
+            fixed_xy(0.125, -0.5),
+        ];
+        let a = points[1] - points[0];
+        let b = points[2] - points[0];
+        assert_eq!(a.cross_prod(b), 0);
+        let hull = convex_hull(&points);
+        assert_eq!(hull, vec![points[0], points[3], points[1], points[2]]);
+    }
+}
@@ -0,0 +1,196 @@
+//! Scalar interpolation and easing helpers. The matching vector operations
+//! are provided methods on [`Vector`](crate::Vector).
+
+use crate::{VecFloat, VecNum};
+
+/// `v` limited to `[lo, hi]`.
+pub fn clamp<T: VecNum>(v: T, lo: T, hi: T) -> T {
+    if v < lo {
+        lo
+    } else if v > hi {
+        hi
+    } else {
+        v
+    }
+}
+
+pub fn lerp<T: VecNum>(a: T, b: T, t: T) -> T {
+    a + (b - a) * t
+}
+
+/// Position of `x` between `edge0` and `edge1`, clamped to `[0, 1]`.
+/// Equal edges act as a step at `edge0`.
+fn ramp<T: VecNum>(edge0: T, edge1: T, x: T) -> T {
+    if edge0 == edge1 {
+        return if x < edge0 { T::zero() } else { T::one() };
+    }
+    clamp((x - edge0) / (edge1 - edge0), T::zero(), T::one())
+}
+
+/// Hermite easing of `x` from 0 at `edge0` to 1 at `edge1`, with zero
+/// slope at both edges.
+/// ```
+///     use xyzvec::interp::smoothstep;
+///     use approx::assert_relative_eq;
+///
+///     assert_relative_eq!(smoothstep(0.0f64, 2.0, -1.0), 0.0);
+///     assert_relative_eq!(smoothstep(0.0f64, 2.0, 0.5), 0.15625);
+///     assert_relative_eq!(smoothstep(0.0f64, 2.0, 1.0), 0.5);
+///     assert_relative_eq!(smoothstep(0.0f64, 2.0, 3.0), 1.0);
+/// ```
+pub fn smoothstep<T: VecNum>(edge0: T, edge1: T, x: T) -> T {
+    let t = ramp(edge0, edge1, x);
+    let two = T::one() + T::one();
+    t * t * (two + T::one() - two * t)
+}
+
+/// Like [`smoothstep`], but with zero second derivative at both edges too.
+/// ```
+///     use xyzvec::interp::smootherstep;
+///     use approx::assert_relative_eq;
+///
+///     assert_relative_eq!(smootherstep(0.0f64, 1.0, 0.25), 0.103515625);
+///     assert_relative_eq!(smootherstep(0.0f64, 1.0, 0.5), 0.5);
+/// ```
+pub fn smootherstep<T: VecNum>(edge0: T, edge1: T, x: T) -> T {
+    let t = ramp(edge0, edge1, x);
+    let one = T::one();
+    let five = one + one + one + one + one;
+    let six = five + one;
+    let ten = five + five;
+    let fifteen = ten + five;
+    t * t * t * (t * (t * six - fifteen) + ten)
+}
+
+/// Catmull-Rom cubic through `p1` (at `t = 0`) and `p2` (at `t = 1`), using
+/// `p0` and `p3` to shape the tangents.
+/// ```
+///     use xyzvec::interp::cubic;
+///     use approx::assert_relative_eq;
+///
+///     assert_relative_eq!(cubic(0.0f64, 1.0, 2.0, 3.0, 0.5), 1.5);
+///     assert_relative_eq!(cubic(0.0f64, 1.0, 1.0, 0.0, 0.5), 1.125);
+/// ```
+pub fn cubic<T: VecNum>(p0: T, p1: T, p2: T, p3: T, t: T) -> T {
+    let one = T::one();
+    let two = one + one;
+    let three = two + one;
+    let a = two * p1;
+    let b = p2 - p0;
+    let c = two * p0 - (two + three) * p1 + (two + two) * p2 - p3;
+    let d = three * (p1 - p2) + p3 - p0;
+    (a + t * (b + t * (c + t * d))) / two
+}
+
+/// `current` moved toward `target` by at most `max_delta`, landing exactly
+/// on `target` once it is within reach. A negative `max_delta` moves away.
+/// ```
+///     use xyzvec::interp::move_towards;
+///     use approx::assert_relative_eq;
+///
+///     assert_relative_eq!(move_towards(0.0f64, 10.0, 3.0), 3.0);
+///     assert_relative_eq!(move_towards(9.0f64, 10.0, 3.0), 10.0);
+///     assert_relative_eq!(move_towards(0.0f64, -10.0, 3.0), -3.0);
+/// ```
+pub fn move_towards<T: VecNum>(current: T, target: T, max_delta: T) -> T {
+    let diff = target - current;
+    if diff.abs() <= max_delta {
+        target
+    } else if diff > T::zero() {
+        current + max_delta
+    } else {
+        current - max_delta
+    }
+}
+
+/// Exponential decay of `current` toward `target` at rate `lambda` over a
+/// timestep `dt`. Unlike `lerp(current, target, k)` per frame, the result
+/// does not depend on how the elapsed time is split into steps.
+/// ```
+///     use xyzvec::interp::exp_decay;
+///     use approx::assert_relative_eq;
+///
+///     let half_life = 2.0f64.ln();
+///     assert_relative_eq!(exp_decay(0.0, 8.0, 1.0, half_life), 4.0);
+///     assert_relative_eq!(exp_decay(0.0, 8.0, 1.0, 0.0), 0.0);
+/// ```
+pub fn exp_decay<T: VecFloat>(current: T, target: T, lambda: T, dt: T) -> T {
+    target + (current - target) * (-lambda * dt).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Vector, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I16F16;
+
+    #[test]
+    fn smoothstep_degenerate_edges_f32() {
+        assert_relative_eq!(smoothstep(1.0f32, 1.0, 0.5), 0.0);
+        assert_relative_eq!(smoothstep(1.0f32, 1.0, 1.0), 1.0);
+        assert_relative_eq!(smoothstep(2.0f32, 0.0, 0.5), 0.84375);
+    }
+
+    #[test]
+    fn smoothstep_fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        assert_eq!(smoothstep(f(0.0), f(1.0), f(0.5)), 0.5);
+        assert_eq!(smootherstep(f(0.0), f(1.0), f(1.5)), 1);
+        assert_eq!(move_towards(f(1.0), f(2.0), f(0.25)), 1.25);
+        assert_eq!(move_towards(f(1.0), f(0.0), f(0.25)), 0.75);
+    }
+
+    #[test]
+    fn cubic_passes_through_f64() {
+        assert_relative_eq!(cubic(5.0f64, 1.0, -2.0, 7.0, 0.0), 1.0);
+        assert_relative_eq!(cubic(5.0f64, 1.0, -2.0, 7.0, 1.0), -2.0);
+        assert_relative_eq!(lerp(2.0f64, 4.0, 0.75), 3.5);
+    }
+
+    #[test]
+    fn vector_move_towards_f64() {
+        let a = XYVec::new([0.0f64, 0.0]);
+        let b = XYVec::new([3.0f64, 4.0]);
+        let step = a.move_towards(b, 2.5);
+        assert_relative_eq!(step.x(), 1.5);
+        assert_relative_eq!(step.y(), 2.0);
+        // Within reach snaps exactly onto the target, with no overshoot.
+        assert_eq!(step.move_towards(b, 2.5), b);
+        assert_eq!(b.move_towards(b, 1.0), b);
+    }
+
+    #[test]
+    fn exp_decay_step_independent_f32() {
+        let mut x = 1.0f32;
+        for _ in 0..10 {
+            x = exp_decay(x, -1.0, 3.0, 0.1);
+        }
+        assert_relative_eq!(x, exp_decay(1.0, -1.0, 3.0, 1.0), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn damp_f32() {
+        let a = XYZVec::new([0.0f32, 0.0, 0.0]);
+        let b = XYZVec::new([1.0f32, 2.0, 3.0]);
+        assert_eq!(a.damp(b, 5.0, 0.0), a);
+        let d = a.damp(b, 5.0, 100.0);
+        assert_relative_eq!(d.z(), 3.0);
+        let d = a.damp(b, 1.0, 1.0);
+        assert_relative_eq!(d.x(), exp_decay(0.0, 1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn vector_interpolation_f32() {
+        let a = XYZVec::new([0.0f32, 0.0, 0.0]);
+        let b = XYZVec::new([2.0f32, 4.0, -2.0]);
+        let m = a.lerp(b, 0.25);
+        assert_relative_eq!(m.y(), 1.0);
+        let s = a.smoothstep(b, 0.5);
+        assert_relative_eq!(s.x(), 1.0);
+        let s = a.smootherstep(b, 0.25);
+        assert_relative_eq!(s.y(), 4.0 * 0.103515625);
+        let c = Vector::cubic(a, a, b, b, 0.5);
+        assert_relative_eq!(c.z(), -1.0);
+    }
+}
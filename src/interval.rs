@@ -0,0 +1,276 @@
+//! Interval arithmetic with the [`Interval`] scalar, for results that are
+//! guaranteed to contain the exact answer.
+//!
+//! Every operation rounds its bounds outward, so an [`XYVec<Interval>`] or
+//! [`XYZVec<Interval>`] built from intervals containing the true inputs
+//! gives dot products, cross products, and norms containing the true
+//! outputs. When the enclosure of a predicate excludes zero, its sign is
+//! certain; otherwise the caller knows to fall back to exact arithmetic.
+//! ```
+//!     use std::cmp::Ordering;
+//!     use xyzvec::{interval::Interval, XYVec};
+//!
+//!     let v = |x: f64, y: f64| XYVec::new([Interval::point(x), Interval::point(y)]);
+//!     let turn = v(1.0, 0.0).cross_prod(v(1.0, 1e-300));
+//!     assert_eq!(turn.sign(), Some(Ordering::Greater));
+//!     let unsure = v(0.1, 0.2).cross_prod(v(0.3, 0.6));
+//!     assert_eq!(unsure.sign(), None);
+//! ```
+
+use crate::{XYVec, XYZVec};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+};
+
+/// The closed range `[lo, hi]` of `f64`s. Arithmetic rounds each bound
+/// outward by one ulp, which is enough to stay an enclosure because `f64`
+/// operations are correctly rounded to nearest.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval {
+    lo: f64,
+    hi: f64,
+}
+
+/// `[lo, hi]` widened by one ulp on each side.
+fn outward(lo: f64, hi: f64) -> Interval {
+    Interval {
+        lo: lo.next_down(),
+        hi: hi.next_up(),
+    }
+}
+
+impl Interval {
+    /// Panics if `lo > hi` or either bound is NaN.
+    pub fn new(lo: f64, hi: f64) -> Self {
+        assert!(lo <= hi, "invalid interval [{lo}, {hi}]");
+        Self { lo, hi }
+    }
+
+    /// The interval containing only `v`.
+    pub fn point(v: f64) -> Self {
+        Self::new(v, v)
+    }
+
+    /// `v` with an uncertainty of `radius` either side.
+    pub fn around(v: f64, radius: f64) -> Self {
+        outward(v - radius, v + radius)
+    }
+
+    pub fn lo(&self) -> f64 {
+        self.lo
+    }
+
+    pub fn hi(&self) -> f64 {
+        self.hi
+    }
+
+    pub fn width(&self) -> f64 {
+        self.hi - self.lo
+    }
+
+    pub fn mid(&self) -> f64 {
+        self.lo + (self.hi - self.lo) / 2.0
+    }
+
+    pub fn contains(&self, v: f64) -> bool {
+        self.lo <= v && v <= self.hi
+    }
+
+    /// The smallest interval containing both `self` and `other`.
+    pub fn hull(&self, other: Self) -> Self {
+        Self::new(self.lo.min(other.lo), self.hi.max(other.hi))
+    }
+
+    /// Sign shared by every value in the interval, or `None` if it
+    /// contains values of both signs. An interval of just zero is
+    /// `Some(Equal)`.
+    pub fn sign(&self) -> Option<Ordering> {
+        if self.lo > 0.0 {
+            Some(Ordering::Greater)
+        } else if self.hi < 0.0 {
+            Some(Ordering::Less)
+        } else if self.lo == 0.0 && self.hi == 0.0 {
+            Some(Ordering::Equal)
+        } else {
+            None
+        }
+    }
+
+    /// `self * self`, which unlike the product of two independent
+    /// intervals is never negative.
+    pub fn sqr(&self) -> Self {
+        let (lo, hi) = (self.lo * self.lo, self.hi * self.hi);
+        if self.lo >= 0.0 {
+            outward(lo, hi)
+        } else if self.hi <= 0.0 {
+            outward(hi, lo)
+        } else {
+            Self::new(0.0, lo.max(hi).next_up())
+        }
+    }
+
+    /// Square root of the non-negative part of `self`.
+    pub fn sqrt(&self) -> Self {
+        let lo = self.lo.max(0.0).sqrt().next_down().max(0.0);
+        let hi = self.hi.max(0.0).sqrt().next_up();
+        Self::new(lo, hi)
+    }
+}
+
+impl From<f64> for Interval {
+    fn from(v: f64) -> Self {
+        Self::point(v)
+    }
+}
+
+impl Add for Interval {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        outward(self.lo + other.lo, self.hi + other.hi)
+    }
+}
+
+impl AddAssign for Interval {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Interval {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        outward(self.lo - other.hi, self.hi - other.lo)
+    }
+}
+
+impl SubAssign for Interval {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl Mul for Interval {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let products = [
+            self.lo * other.lo,
+            self.lo * other.hi,
+            self.hi * other.lo,
+            self.hi * other.hi,
+        ];
+        let lo = products.iter().copied().fold(f64::INFINITY, f64::min);
+        let hi = products.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        outward(lo, hi)
+    }
+}
+
+/// Division by an interval containing zero gives the whole real line.
+impl Div for Interval {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        if other.contains(0.0) {
+            return Self::new(f64::NEG_INFINITY, f64::INFINITY);
+        }
+        let quotients = [
+            self.lo / other.lo,
+            self.lo / other.hi,
+            self.hi / other.lo,
+            self.hi / other.hi,
+        ];
+        let lo = quotients.iter().copied().fold(f64::INFINITY, f64::min);
+        let hi = quotients.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        outward(lo, hi)
+    }
+}
+
+impl Neg for Interval {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+/// `[lo, hi]`, with any precision applied to both bounds.
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "[{:.*}, {:.*}]", p, self.lo, p, self.hi),
+            None => write!(f, "[{}, {}]", self.lo, self.hi),
+        }
+    }
+}
+
+impl XYVec<Interval> {
+    /// Enclosure of the length, squaring each component with
+    /// [`Interval::sqr`] so the result stays tight around zero.
+    pub fn l2_norm(&self) -> Interval {
+        (self.x().sqr() + self.y().sqr()).sqrt()
+    }
+}
+
+impl XYZVec<Interval> {
+    /// Enclosure of the length, squaring each component with
+    /// [`Interval::sqr`] so the result stays tight around zero.
+    pub fn l2_norm(&self) -> Interval {
+        (self.x().sqr() + self.y().sqr() + self.z().sqr()).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Interval;
+    use crate::{XYVec, XYZVec};
+    use std::cmp::Ordering;
+
+    fn v3(x: f64, y: f64, z: f64) -> XYZVec<Interval> {
+        XYZVec::new([x.into(), y.into(), z.into()])
+    }
+
+    #[test]
+    fn encloses_exact_results() {
+        let third = Interval::point(1.0) / Interval::point(3.0);
+        assert!(third.lo() < third.hi());
+        let one = third * Interval::point(3.0);
+        assert!(one.contains(1.0));
+
+        // The exact dot product is just above 1, where the rounded one
+        // lands on 1 itself.
+        let dot = v3(1.0, 1e-20, 0.0).dot_prod(v3(1.0, 1.0, 0.0));
+        assert!(dot.lo() < 1.0 && dot.hi() > 1.0);
+        assert!(dot.width() < 1e-15);
+
+        let norm = XYVec::new([Interval::point(3.0), Interval::point(4.0)]).l2_norm();
+        assert!(norm.contains(5.0) && norm.width() < 1e-14);
+    }
+
+    #[test]
+    fn signs() {
+        assert_eq!(Interval::new(1.0, 2.0).sign(), Some(Ordering::Greater));
+        assert_eq!(Interval::new(-2.0, -1.0).sign(), Some(Ordering::Less));
+        assert_eq!(Interval::point(0.0).sign(), Some(Ordering::Equal));
+        assert_eq!(Interval::new(-1.0, 1.0).sign(), None);
+        assert_eq!((-Interval::new(1.0, 2.0)).sign(), Some(Ordering::Less));
+    }
+
+    #[test]
+    fn squares_and_roots() {
+        let straddling = Interval::new(-2.0, 1.0);
+        assert_eq!(straddling.sqr().lo(), 0.0);
+        assert!(straddling.sqr().contains(4.0));
+        assert!((straddling * straddling).lo() < 0.0);
+        assert_eq!(Interval::new(-1.0, 0.0).sqrt().lo(), 0.0);
+        let div = Interval::point(1.0) / Interval::new(-1.0, 1.0);
+        assert_eq!((div.lo(), div.hi()), (f64::NEG_INFINITY, f64::INFINITY));
+        assert_eq!(format!("{:.2}", Interval::new(0.25, 0.75)), "[0.25, 0.75]");
+    }
+}
@@ -0,0 +1,161 @@
+//! Iterator adapters for sequences of vectors, such as the points of a
+//! path.
+
+use crate::Vector;
+
+/// Adapters available on any iterator of vectors.
+/// ```
+///     use xyzvec::{iter::VectorIterExt, XYVec};
+///
+///     let path = [XYVec::new([0, 0]), XYVec::new([3, 4]), XYVec::new([3, 6])];
+///     let steps: Vec<_> = path.into_iter().deltas().collect();
+///     assert_eq!(steps, vec![XYVec::new([3, 4]), XYVec::new([0, 2])]);
+///     let back: Vec<_> = steps.into_iter().cumsum().collect();
+///     assert_eq!(back, path[1..]);
+/// ```
+pub trait VectorIterExt: Iterator + Sized
+where
+    Self::Item: Vector,
+{
+    /// Differences between successive items: `b - a` for each consecutive
+    /// pair `a, b`. One shorter than the input.
+    fn deltas(self) -> Deltas<Self> {
+        Deltas {
+            iter: self,
+            prev: None,
+        }
+    }
+
+    /// Running totals: the `n`th item is the sum of the first `n + 1`
+    /// inputs.
+    fn cumsum(self) -> CumSum<Self> {
+        CumSum {
+            iter: self,
+            total: None,
+        }
+    }
+
+    /// Consecutive pairs `(a, b)`, one per segment of the path through the
+    /// items.
+    fn segments(self) -> Segments<Self> {
+        Segments {
+            iter: self,
+            prev: None,
+        }
+    }
+}
+
+impl<I: Iterator> VectorIterExt for I where I::Item: Vector {}
+
+/// Iterator returned by [`VectorIterExt::deltas`].
+#[derive(Clone, Debug)]
+pub struct Deltas<I: Iterator> {
+    iter: I,
+    prev: Option<I::Item>,
+}
+
+impl<I: Iterator> Iterator for Deltas<I>
+where
+    I::Item: Vector,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let prev = match self.prev {
+            Some(p) => p,
+            None => self.iter.next()?,
+        };
+        let next = self.iter.next()?;
+        self.prev = Some(next);
+        Some(next - prev)
+    }
+}
+
+/// Iterator returned by [`VectorIterExt::cumsum`].
+#[derive(Clone, Debug)]
+pub struct CumSum<I: Iterator> {
+    iter: I,
+    total: Option<I::Item>,
+}
+
+impl<I: Iterator> Iterator for CumSum<I>
+where
+    I::Item: Vector,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let v = self.iter.next()?;
+        let total = match self.total {
+            Some(t) => t + v,
+            None => v,
+        };
+        self.total = Some(total);
+        Some(total)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Iterator returned by [`VectorIterExt::segments`].
+#[derive(Clone, Debug)]
+pub struct Segments<I: Iterator> {
+    iter: I,
+    prev: Option<I::Item>,
+}
+
+impl<I: Iterator> Iterator for Segments<I>
+where
+    I::Item: Vector,
+{
+    type Item = (I::Item, I::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = match self.prev {
+            Some(p) => p,
+            None => self.iter.next()?,
+        };
+        let b = self.iter.next()?;
+        self.prev = Some(b);
+        Some((a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VectorIterExt;
+    use crate::{XYVec, XYZVec};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn path_f64() {
+        let path = [
+            XYZVec::new([0.0f64, 0.0, 0.0]),
+            XYZVec::new([1.0, 0.0, 0.0]),
+            XYZVec::new([1.0, 2.0, 0.0]),
+            XYZVec::new([1.0, 2.0, 2.0]),
+        ];
+        let length: f64 = path.iter().copied().deltas().map(|d| d.l2_norm()).sum();
+        assert_relative_eq!(length, 5.0);
+        let segments: Vec<_> = path.iter().copied().segments().collect();
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments[1], (path[1], path[2]));
+        // Rebuilding the path from its first point and deltas.
+        let rebuilt: Vec<_> = std::iter::once(path[0])
+            .chain(path.iter().copied().deltas())
+            .cumsum()
+            .collect();
+        assert_eq!(rebuilt, path);
+    }
+
+    #[test]
+    fn short_inputs() {
+        let one = [XYVec::new([1, 1])];
+        assert_eq!(one.into_iter().deltas().count(), 0);
+        assert_eq!(one.into_iter().segments().count(), 0);
+        assert_eq!(one.into_iter().cumsum().collect::<Vec<_>>(), one);
+        assert_eq!(std::iter::empty::<XYVec<i32>>().cumsum().count(), 0);
+    }
+}
@@ -0,0 +1,662 @@
+//! Integer vectors, for grid cells, tile and voxel coordinates.
+//!
+//! Integer vectors are `Eq`, `Hash`, and totally ordered (lexicographically,
+//! `x` first), so they work directly as map keys.
+
+use crate::{Aabb2, Aabb3, VecNum, XYVec, XYZVec};
+use std::{cmp::Ordering, iter::FusedIterator};
+
+pub type IVec2 = XYVec<i32>;
+pub type IVec3 = XYZVec<i32>;
+
+macro_rules! impl_int_vec {
+    ($vec:ident, $($t:ty => $u:ty),* $(,)?) => {
+        $(
+            impl $vec<$t> {
+                /// Each component converted with `as`.
+                pub fn as_f32(&self) -> $vec<f32> {
+                    self.iter().map(|&c| c as f32).collect()
+                }
+
+                /// Each component converted with `as`.
+                pub fn as_f64(&self) -> $vec<f64> {
+                    self.iter().map(|&c| c as f64).collect()
+                }
+
+                /// The cell containing `v`: each component rounded down,
+                /// saturating at the integer range.
+                pub fn from_f32_floor(v: $vec<f32>) -> Self {
+                    v.iter().map(|c| c.floor() as $t).collect()
+                }
+
+                /// Each component rounded to the nearest integer (halves
+                /// away from zero), saturating at the integer range.
+                pub fn from_f32_round(v: $vec<f32>) -> Self {
+                    v.iter().map(|c| c.round() as $t).collect()
+                }
+
+                /// The cell containing `v`: each component rounded down,
+                /// saturating at the integer range.
+                pub fn from_f64_floor(v: $vec<f64>) -> Self {
+                    v.iter().map(|c| c.floor() as $t).collect()
+                }
+
+                /// Each component rounded to the nearest integer (halves
+                /// away from zero), saturating at the integer range.
+                pub fn from_f64_round(v: $vec<f64>) -> Self {
+                    v.iter().map(|c| c.round() as $t).collect()
+                }
+
+                /// Sum of the absolute component differences: the number of
+                /// orthogonal steps from `self` to `other`. Unsigned, so it
+                /// holds any one component's difference; the sum saturates.
+                pub fn manhattan_distance(&self, other: Self) -> $u {
+                    self.iter()
+                        .zip(other.iter())
+                        .fold(0, |acc: $u, (a, b)| acc.saturating_add(a.abs_diff(*b)))
+                }
+
+                /// Largest absolute component difference: the number of
+                /// king's moves from `self` to `other`.
+                pub fn chebyshev_distance(&self, other: Self) -> $u {
+                    self.iter()
+                        .zip(other.iter())
+                        .map(|(a, b)| a.abs_diff(*b))
+                        .max()
+                        .unwrap_or(0)
+                }
+            }
+
+            impl Ord for $vec<$t> {
+                fn cmp(&self, other: &Self) -> Ordering {
+                    self.iter().cmp(other.iter())
+                }
+            }
+
+            impl PartialOrd for $vec<$t> {
+                fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                    Some(self.cmp(other))
+                }
+            }
+        )*
+    };
+}
+
+impl_int_vec!(XYVec, i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+impl_int_vec!(XYZVec, i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128);
+
+macro_rules! impl_to_ivec {
+    ($vec:ident, $($f:ty),* $(,)?) => {
+        $(
+            impl $vec<$f> {
+                /// Each component rounded to the nearest integer (halves away
+                /// from zero), saturating at the `i32` range; NaN becomes 0.
+                pub fn round_to_ivec(&self) -> $vec<i32> {
+                    self.iter().map(|c| c.round() as i32).collect()
+                }
+
+                /// The cell containing `self`: each component rounded down,
+                /// saturating at the `i32` range; NaN becomes 0.
+                pub fn floor_to_ivec(&self) -> $vec<i32> {
+                    self.iter().map(|c| c.floor() as i32).collect()
+                }
+
+                /// Each component rounded up, saturating at the `i32` range;
+                /// NaN becomes 0.
+                pub fn ceil_to_ivec(&self) -> $vec<i32> {
+                    self.iter().map(|c| c.ceil() as i32).collect()
+                }
+
+                /// Like [`round_to_ivec`](Self::round_to_ivec), but `None`
+                /// if any rounded component is NaN or outside the `i32` range.
+                pub fn checked_round_to_ivec(&self) -> Option<$vec<i32>> {
+                    checked(self.iter().map(|c| c.round() as f64))
+                }
+
+                /// Like [`floor_to_ivec`](Self::floor_to_ivec), but `None`
+                /// if any rounded component is NaN or outside the `i32` range.
+                pub fn checked_floor_to_ivec(&self) -> Option<$vec<i32>> {
+                    checked(self.iter().map(|c| c.floor() as f64))
+                }
+
+                /// Like [`ceil_to_ivec`](Self::ceil_to_ivec), but `None`
+                /// if any rounded component is NaN or outside the `i32` range.
+                pub fn checked_ceil_to_ivec(&self) -> Option<$vec<i32>> {
+                    checked(self.iter().map(|c| c.ceil() as f64))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_ivec!(XYVec, f32, f64);
+impl_to_ivec!(XYZVec, f32, f64);
+
+/// How [`quantize`](XYVec::quantize) and [`snap_to_grid`](XYVec::snap_to_grid)
+/// pick a grid point.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Rounding {
+    /// The nearest grid point, halves away from zero.
+    #[default]
+    Nearest,
+    /// The grid point at or below, i.e. the cell containing the point.
+    Floor,
+    /// The grid point at or above.
+    Ceil,
+}
+
+impl Rounding {
+    fn apply(self, c: f64) -> f64 {
+        match self {
+            Rounding::Nearest => c.round(),
+            Rounding::Floor => c.floor(),
+            Rounding::Ceil => c.ceil(),
+        }
+    }
+}
+
+macro_rules! impl_snap {
+    ($($vec:ident),*) => {
+        $(
+            impl<T: VecNum> $vec<T> {
+                /// Index of the grid point `self` rounds to on a grid with
+                /// spacing `cell_size`, saturating at the `i32` range. With
+                /// [`Rounding::Floor`] this is the cell containing `self`.
+                pub fn quantize(&self, cell_size: T, mode: Rounding) -> $vec<i32> {
+                    let size = cell_size.to_f64();
+                    self.iter()
+                        .map(|c| mode.apply(c.to_f64() / size) as i32)
+                        .collect()
+                }
+
+                /// `self` moved onto a grid with spacing `cell_size`.
+                pub fn snap_to_grid(&self, cell_size: T, mode: Rounding) -> Self {
+                    let size = cell_size.to_f64();
+                    self.iter()
+                        .map(|c| T::from_f64(mode.apply(c.to_f64() / size) * size))
+                        .collect()
+                }
+            }
+        )*
+    };
+}
+
+impl_snap!(XYVec, XYZVec);
+
+/// Whole-valued `components` converted to `i32`, or `None` if any is NaN or
+/// out of range.
+fn checked<V: FromIterator<i32>>(components: impl Iterator<Item = f64>) -> Option<V> {
+    let cells: Option<Vec<i32>> = components
+        .map(|c| (c >= i32::MIN as f64 && c <= i32::MAX as f64).then_some(c as i32))
+        .collect();
+    cells.map(|c| c.into_iter().collect())
+}
+
+const OFFSETS_4: [[i32; 2]; 4] = [[1, 0], [0, 1], [-1, 0], [0, -1]];
+const OFFSETS_8: [[i32; 2]; 8] = [
+    [1, 0],
+    [1, 1],
+    [0, 1],
+    [-1, 1],
+    [-1, 0],
+    [-1, -1],
+    [0, -1],
+    [1, -1],
+];
+const OFFSETS_6: [[i32; 3]; 6] = [
+    [1, 0, 0],
+    [-1, 0, 0],
+    [0, 1, 0],
+    [0, -1, 0],
+    [0, 0, 1],
+    [0, 0, -1],
+];
+
+/// `[-1, 0, 1]³` without the origin.
+fn offsets_26() -> impl Iterator<Item = [i32; 3]> {
+    (-1..=1)
+        .flat_map(|x| (-1..=1).flat_map(move |y| (-1..=1).map(move |z| [x, y, z])))
+        .filter(|&o| o != [0, 0, 0])
+}
+
+/// Neighbors of a grid cell. Cells past the edge of the `i32` range are
+/// skipped, and the `_in` variants also skip cells outside `bounds`
+/// (inclusive).
+impl IVec2 {
+    /// The 4 orthogonally adjacent cells, counterclockwise from `+x`.
+    /// ```
+    ///     use xyzvec::IVec2;
+    ///
+    ///     let n: Vec<_> = IVec2::new([0, 0]).neighbors4().collect();
+    ///     assert_eq!(n, [[1, 0], [0, 1], [-1, 0], [0, -1]].map(IVec2::new));
+    /// ```
+    pub fn neighbors4(&self) -> impl Iterator<Item = IVec2> {
+        let p = *self;
+        OFFSETS_4.into_iter().filter_map(move |o| p.offset(o))
+    }
+
+    /// The 8 cells sharing an edge or corner, counterclockwise from `+x`.
+    pub fn neighbors8(&self) -> impl Iterator<Item = IVec2> {
+        let p = *self;
+        OFFSETS_8.into_iter().filter_map(move |o| p.offset(o))
+    }
+
+    /// Like [`neighbors4`](Self::neighbors4), within `bounds`.
+    /// ```
+    ///     use xyzvec::{Aabb2, IVec2};
+    ///
+    ///     let grid = Aabb2::new(IVec2::new([0, 0]), IVec2::new([9, 9]));
+    ///     assert_eq!(IVec2::new([0, 0]).neighbors4_in(&grid).count(), 2);
+    ///     assert_eq!(IVec2::new([0, 5]).neighbors8_in(&grid).count(), 5);
+    /// ```
+    pub fn neighbors4_in<'a>(&self, bounds: &'a Aabb2<i32>) -> impl Iterator<Item = IVec2> + 'a {
+        self.neighbors4().filter(|&n| bounds.contains_point(n))
+    }
+
+    /// Like [`neighbors8`](Self::neighbors8), within `bounds`.
+    pub fn neighbors8_in<'a>(&self, bounds: &'a Aabb2<i32>) -> impl Iterator<Item = IVec2> + 'a {
+        self.neighbors8().filter(|&n| bounds.contains_point(n))
+    }
+
+    fn offset(&self, [dx, dy]: [i32; 2]) -> Option<IVec2> {
+        Some(IVec2::new([
+            self.x().checked_add(dx)?,
+            self.y().checked_add(dy)?,
+        ]))
+    }
+}
+
+impl IVec3 {
+    /// The 6 face-adjacent cells: `±x`, `±y`, then `±z`.
+    pub fn neighbors6(&self) -> impl Iterator<Item = IVec3> {
+        let p = *self;
+        OFFSETS_6.into_iter().filter_map(move |o| p.offset(o))
+    }
+
+    /// The 26 cells sharing a face, edge, or corner.
+    pub fn neighbors26(&self) -> impl Iterator<Item = IVec3> {
+        let p = *self;
+        offsets_26().filter_map(move |o| p.offset(o))
+    }
+
+    /// Like [`neighbors6`](Self::neighbors6), within `bounds`.
+    pub fn neighbors6_in<'a>(&self, bounds: &'a Aabb3<i32>) -> impl Iterator<Item = IVec3> + 'a {
+        self.neighbors6().filter(|&n| bounds.contains_point(n))
+    }
+
+    /// Like [`neighbors26`](Self::neighbors26), within `bounds`.
+    pub fn neighbors26_in<'a>(&self, bounds: &'a Aabb3<i32>) -> impl Iterator<Item = IVec3> + 'a {
+        self.neighbors26().filter(|&n| bounds.contains_point(n))
+    }
+
+    fn offset(&self, [dx, dy, dz]: [i32; 3]) -> Option<IVec3> {
+        Some(IVec3::new([
+            self.x().checked_add(dx)?,
+            self.y().checked_add(dy)?,
+            self.z().checked_add(dz)?,
+        ]))
+    }
+}
+
+/// Cells on the line from `a` to `b`, both included, by Bresenham's
+/// algorithm: exactly one cell per step along the longer axis, so diagonal
+/// moves cut corners. See [`supercover_line`] for every cell touched.
+/// ```
+///     use xyzvec::{ivec::line_between, IVec2};
+///
+///     let cells: Vec<_> = line_between(IVec2::new([0, 0]), IVec2::new([5, 2])).collect();
+///     let expected = [[0, 0], [1, 0], [2, 1], [3, 1], [4, 2], [5, 2]].map(IVec2::new);
+///     assert_eq!(cells, expected);
+/// ```
+pub fn line_between(a: IVec2, b: IVec2) -> Line {
+    let (dx, dy) = (b.x() as i64 - a.x() as i64, b.y() as i64 - a.y() as i64);
+    Line {
+        cur: a,
+        end: b,
+        step: IVec2::new([dx.signum() as i32, dy.signum() as i32]),
+        dx: dx.abs(),
+        dy: -dy.abs(),
+        err: dx.abs() - dy.abs(),
+        done: false,
+    }
+}
+
+/// Iterator returned by [`line_between`].
+#[derive(Clone, Debug)]
+pub struct Line {
+    cur: IVec2,
+    end: IVec2,
+    step: IVec2,
+    dx: i64,
+    dy: i64,
+    err: i64,
+    done: bool,
+}
+
+impl Iterator for Line {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<IVec2> {
+        if self.done {
+            return None;
+        }
+        let cell = self.cur;
+        if cell == self.end {
+            self.done = true;
+            return Some(cell);
+        }
+        let e2 = 2 * self.err;
+        let [mut x, mut y] = [cell.x(), cell.y()];
+        if e2 >= self.dy {
+            self.err += self.dy;
+            x += self.step.x();
+        }
+        if e2 <= self.dx {
+            self.err += self.dx;
+            y += self.step.y();
+        }
+        self.cur = IVec2::new([x, y]);
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let dx = (self.end.x() as i64 - self.cur.x() as i64).abs();
+        let dy = (self.end.y() as i64 - self.cur.y() as i64).abs();
+        let n = dx.max(dy) as usize + 1;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for Line {}
+impl FusedIterator for Line {}
+
+/// Every cell that the segment between the centers of cells `a` and `b`
+/// passes through, in order from `a` to `b`. Where the segment crosses
+/// exactly through a cell corner, both cells beside the corner are
+/// included. Use this for visibility and collision, where
+/// [`line_between`] could slip through a diagonal gap.
+/// ```
+///     use xyzvec::{ivec::supercover_line, IVec2};
+///
+///     let cells: Vec<_> = supercover_line(IVec2::new([0, 0]), IVec2::new([2, 1])).collect();
+///     let expected = [[0, 0], [1, 0], [1, 1], [2, 1]].map(IVec2::new);
+///     assert_eq!(cells, expected);
+///     assert_eq!(supercover_line(IVec2::new([0, 0]), IVec2::new([1, 1])).count(), 4);
+/// ```
+pub fn supercover_line(a: IVec2, b: IVec2) -> SupercoverLine {
+    let (dx, dy) = (b.x() as i64 - a.x() as i64, b.y() as i64 - a.y() as i64);
+    SupercoverLine {
+        cur: a,
+        step: IVec2::new([dx.signum() as i32, dy.signum() as i32]),
+        n: [dx.abs(), dy.abs()],
+        i: [0, 0],
+        pending: [Some(a), None, None],
+    }
+}
+
+/// Iterator returned by [`supercover_line`].
+#[derive(Clone, Debug)]
+pub struct SupercoverLine {
+    cur: IVec2,
+    step: IVec2,
+    /// Steps to take, and steps taken, along each axis.
+    n: [i64; 2],
+    i: [i64; 2],
+    /// Cells found but not yet returned, in order.
+    pending: [Option<IVec2>; 3],
+}
+
+impl Iterator for SupercoverLine {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<IVec2> {
+        if let Some(slot) = self.pending.iter_mut().find(|c| c.is_some()) {
+            return slot.take();
+        }
+        let ([nx, ny], [ix, iy]) = (self.n, self.i);
+        if ix >= nx && iy >= ny {
+            return None;
+        }
+        let [x, y] = [self.cur.x(), self.cur.y()];
+        let [sx, sy] = [self.step.x(), self.step.y()];
+        // Compare where the segment next crosses a vertical and a horizontal
+        // cell edge. Spans reach 2^32, so the products need `i128`.
+        let decision = (1 + 2 * ix as i128) * ny as i128 - (1 + 2 * iy as i128) * nx as i128;
+        if decision == 0 {
+            self.cur = IVec2::new([x + sx, y + sy]);
+            self.i = [ix + 1, iy + 1];
+            self.pending = [Some(IVec2::new([x, y + sy])), Some(self.cur), None];
+            return Some(IVec2::new([x + sx, y]));
+        }
+        if decision < 0 {
+            self.cur = IVec2::new([x + sx, y]);
+            self.i[0] += 1;
+        } else {
+            self.cur = IVec2::new([x, y + sy]);
+            self.i[1] += 1;
+        }
+        Some(self.cur)
+    }
+}
+
+impl FusedIterator for SupercoverLine {}
+
+#[cfg(test)]
+mod tests {
+    use super::{line_between, supercover_line, IVec2, IVec3, Rounding};
+    use crate::{Aabb2, Aabb3};
+    use crate::{XYVec, XYZVec};
+    use fixed::types::I16F16;
+    use std::collections::{BTreeSet, HashMap};
+
+    #[test]
+    fn float_conversions() {
+        let p = XYVec::new([-0.5f32, 2.5]);
+        assert_eq!(IVec2::from_f32_floor(p), XYVec::new([-1, 2]));
+        assert_eq!(IVec2::from_f32_round(p), XYVec::new([-1, 3]));
+        assert_eq!(
+            IVec2::from_f32_floor(XYVec::new([f32::MAX, f32::NAN])),
+            XYVec::new([i32::MAX, 0])
+        );
+        let q = XYZVec::new([3.999f64, -3.001, 0.0]);
+        assert_eq!(IVec3::from_f64_floor(q), XYZVec::new([3, -4, 0]));
+        assert_eq!(IVec3::from_f64_round(q), XYZVec::new([4, -3, 0]));
+        assert_eq!(
+            XYZVec::new([1i64, -2, 3]).as_f64(),
+            XYZVec::new([1.0, -2.0, 3.0])
+        );
+        assert_eq!(XYVec::new([7i16, -1]).as_f32(), XYVec::new([7.0, -1.0]));
+    }
+
+    #[test]
+    fn snapping() {
+        let p = XYVec::new([1.3f64, -0.74]);
+        assert_eq!(p.quantize(0.5, Rounding::Nearest), XYVec::new([3, -1]));
+        assert_eq!(p.quantize(0.5, Rounding::Floor), XYVec::new([2, -2]));
+        assert_eq!(p.quantize(0.5, Rounding::Ceil), XYVec::new([3, -1]));
+        assert_eq!(
+            p.snap_to_grid(0.5, Rounding::Floor),
+            XYVec::new([1.0, -1.0])
+        );
+        let q = XYZVec::new([9.9f32, 10.0, -25.0]);
+        assert_eq!(
+            q.snap_to_grid(10.0, Rounding::default()),
+            XYZVec::new([10.0, 10.0, -30.0])
+        );
+        let f = |v: f64| I16F16::from_num(v);
+        let r = XYVec::new([f(0.3), f(-0.3)]);
+        assert_eq!(r.quantize(f(0.25), Rounding::Nearest), XYVec::new([1, -1]));
+        assert_eq!(
+            r.snap_to_grid(f(0.25), Rounding::Ceil),
+            XYVec::new([f(0.5), f(-0.25)])
+        );
+    }
+
+    #[test]
+    fn to_ivec_f32() {
+        let p = XYVec::new([-1.5f32, 0.25]);
+        assert_eq!(p.round_to_ivec(), XYVec::new([-2, 0]));
+        assert_eq!(p.floor_to_ivec(), XYVec::new([-2, 0]));
+        assert_eq!(p.ceil_to_ivec(), XYVec::new([-1, 1]));
+        assert_eq!(p.checked_ceil_to_ivec(), Some(XYVec::new([-1, 1])));
+        assert_eq!(XYVec::new([3e9f32, 0.0]).checked_floor_to_ivec(), None);
+        assert_eq!(
+            XYVec::new([3e9f32, 0.0]).floor_to_ivec(),
+            XYVec::new([i32::MAX, 0])
+        );
+        assert_eq!(XYVec::new([f32::NAN, 0.0]).checked_round_to_ivec(), None);
+    }
+
+    #[test]
+    fn to_ivec_f64() {
+        let p = XYZVec::new([2.5f64, -0.5, 7.0]);
+        assert_eq!(p.round_to_ivec(), XYZVec::new([3, -1, 7]));
+        assert_eq!(p.floor_to_ivec(), XYZVec::new([2, -1, 7]));
+        assert_eq!(p.ceil_to_ivec(), XYZVec::new([3, 0, 7]));
+        let edge = XYZVec::new([i32::MAX as f64, i32::MIN as f64, -0.4]);
+        assert_eq!(
+            edge.checked_round_to_ivec(),
+            Some(XYZVec::new([i32::MAX, i32::MIN, 0]))
+        );
+        assert_eq!(
+            edge.checked_ceil_to_ivec(),
+            Some(XYZVec::new([i32::MAX, i32::MIN, 0]))
+        );
+        let over = XYZVec::new([i32::MAX as f64 + 0.5, 0.0, 0.0]);
+        assert_eq!(
+            over.checked_floor_to_ivec(),
+            Some(XYZVec::new([i32::MAX, 0, 0]))
+        );
+        assert_eq!(over.checked_round_to_ivec(), None);
+        assert_eq!(
+            XYZVec::new([0.0, f64::NEG_INFINITY, 0.0]).checked_floor_to_ivec(),
+            None
+        );
+    }
+
+    #[test]
+    fn distances() {
+        let a = IVec2::new([1, -2]);
+        let b = IVec2::new([-3, 1]);
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        let c = XYZVec::new([0i64, 0, 0]);
+        assert_eq!(c.manhattan_distance(XYZVec::new([1, -1, 5])), 7);
+        assert_eq!(c.chebyshev_distance(XYZVec::new([1, -1, 5])), 5);
+        assert_eq!(c.chebyshev_distance(c), 0);
+        let (lo, hi) = (IVec2::new([i32::MIN; 2]), IVec2::new([i32::MAX; 2]));
+        assert_eq!(lo.chebyshev_distance(hi), u32::MAX);
+        assert_eq!(lo.manhattan_distance(hi), u32::MAX);
+    }
+
+    #[test]
+    fn keys() {
+        let cells = [
+            IVec3::new([1, 0, 0]),
+            IVec3::new([0, 5, 5]),
+            IVec3::new([0, 5, -1]),
+        ];
+        let sorted: Vec<_> = cells
+            .iter()
+            .copied()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        assert_eq!(sorted, vec![cells[2], cells[1], cells[0]]);
+        let mut tiles = HashMap::new();
+        tiles.insert(IVec2::new([2, 3]), 'x');
+        assert_eq!(tiles.get(&IVec2::new([2, 3])), Some(&'x'));
+        assert!(IVec2::new([0, 9]) < IVec2::new([1, -9]));
+    }
+
+    #[test]
+    fn bresenham_octants() {
+        let origin = IVec2::new([0, 0]);
+        for end in [
+            [5, 2],
+            [2, 5],
+            [-5, 2],
+            [-2, -5],
+            [5, -2],
+            [0, -3],
+            [4, 4],
+            [0, 0],
+        ] {
+            let end = IVec2::new(end);
+            let line = line_between(origin, end);
+            let n = line.len();
+            let cells: Vec<_> = line.collect();
+            assert_eq!(cells.len(), n);
+            assert_eq!(cells.first(), Some(&origin));
+            assert_eq!(cells.last(), Some(&end));
+            assert!(cells.windows(2).all(|w| w[0].chebyshev_distance(w[1]) == 1));
+            // The reverse line covers the same number of cells.
+            assert_eq!(line_between(end, origin).count(), n);
+        }
+        let far = line_between(IVec2::new([i32::MIN, 0]), IVec2::new([i32::MAX, 1]));
+        assert_eq!(far.len(), u32::MAX as usize + 1);
+    }
+
+    #[test]
+    fn supercover() {
+        for end in [[5, 2], [-3, 7], [4, -4], [0, 3], [-6, 0]] {
+            let (a, b) = (IVec2::new([1, -1]), IVec2::new(end));
+            let cells: Vec<_> = supercover_line(a, b).collect();
+            assert_eq!(cells.first(), Some(&a));
+            assert_eq!(cells.last(), Some(&b));
+            // Orthogonal steps, except around exact corner crossings.
+            for w in cells.windows(2) {
+                assert!(w[0].manhattan_distance(w[1]) <= 2);
+            }
+            // Everything Bresenham visits is covered.
+            assert!(line_between(a, b).all(|c| cells.contains(&c)));
+        }
+        let diagonal: Vec<_> = supercover_line(IVec2::new([0, 0]), IVec2::new([2, 2])).collect();
+        let expected = [[0, 0], [1, 0], [0, 1], [1, 1], [2, 1], [1, 2], [2, 2]].map(IVec2::new);
+        assert_eq!(diagonal, expected);
+
+        // The last corner of a full-range diagonal.
+        let mut far = supercover_line(IVec2::new([i32::MIN; 2]), IVec2::new([i32::MAX; 2]));
+        far.pending = [None; 3];
+        far.i = far.n.map(|n| n - 1);
+        far.cur = IVec2::new([i32::MAX - 1; 2]);
+        let tail: Vec<_> = far.collect();
+        let expected = [
+            [i32::MAX, i32::MAX - 1],
+            [i32::MAX - 1, i32::MAX],
+            [i32::MAX; 2],
+        ];
+        assert_eq!(tail, expected.map(IVec2::new));
+    }
+
+    #[test]
+    fn neighbors() {
+        let p = IVec2::new([3, -2]);
+        let n8: BTreeSet<_> = p.neighbors8().collect();
+        assert_eq!(n8.len(), 8);
+        assert!(n8.iter().all(|&n| p.chebyshev_distance(n) == 1));
+        assert!(p
+            .neighbors4()
+            .all(|n| n8.contains(&n) && p.manhattan_distance(n) == 1));
+        let edge = IVec2::new([i32::MAX, 0]);
+        assert_eq!(edge.neighbors4().count(), 3);
+        assert_eq!(edge.neighbors8().count(), 5);
+        let bounds = Aabb2::new(IVec2::new([0, 0]), IVec2::new([3, -2]));
+        assert_eq!(
+            p.neighbors4_in(&bounds).collect::<Vec<_>>(),
+            [IVec2::new([3, -1]), IVec2::new([2, -2])]
+        );
+
+        let q = IVec3::new([0, 0, 0]);
+        let n26: BTreeSet<_> = q.neighbors26().collect();
+        assert_eq!(n26.len(), 26);
+        assert!(!n26.contains(&q));
+        assert!(q.neighbors6().all(|n| q.manhattan_distance(n) == 1));
+        let cube = Aabb3::new(q, IVec3::new([4, 4, 4]));
+        assert_eq!(q.neighbors6_in(&cube).count(), 3);
+        assert_eq!(q.neighbors26_in(&cube).count(), 7);
+    }
+}
@@ -0,0 +1,278 @@
+//! Static k-d tree for nearest-neighbor queries over a fixed point set.
+
+use crate::{VecNum, Vector, XYVec, XYZVec};
+use std::cmp::Ordering;
+
+/// Balanced k-d tree over a copy of a slice of points. Queries return
+/// indices into that slice, nearest first where order matters. Squared
+/// distances are compared in `f64`, so integer and fixed-point coordinates
+/// work too without overflowing their own type.
+#[derive(Clone, Debug)]
+pub struct KdTree<V> {
+    points: Vec<V>,
+    /// Point indices laid out so that each subrange's middle element splits
+    /// the rest of the subrange on the axis for its depth.
+    order: Vec<usize>,
+}
+
+pub type KdTree2<T> = KdTree<XYVec<T>>;
+pub type KdTree3<T> = KdTree<XYZVec<T>>;
+
+fn cmp_scalar<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+impl<V: Vector> KdTree<V>
+where
+    V::Scalar: VecNum,
+{
+    pub fn new(points: &[V]) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        Self::build(points, &mut order, 0);
+        Self {
+            points: points.to_vec(),
+            order,
+        }
+    }
+
+    fn build(points: &[V], order: &mut [usize], depth: usize) {
+        if order.len() <= 1 {
+            return;
+        }
+        let axis = depth % V::DIM;
+        let mid = order.len() / 2;
+        order.select_nth_unstable_by(mid, |&a, &b| {
+            cmp_scalar(&points[a].component(axis), &points[b].component(axis))
+        });
+        let (left, right) = order.split_at_mut(mid);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    /// The points the tree was built from, in their original order.
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Offers the points in the subtree over `order[lo..hi]` to `collector`,
+    /// nearer half first, skipping halves that lie beyond its bound.
+    fn search<C: Collector>(&self, p: V, lo: usize, hi: usize, depth: usize, collector: &mut C) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let index = self.order[mid];
+        let node = self.points[index];
+        let diff = |axis| p.component(axis).to_f64() - node.component(axis).to_f64();
+        collector.offer(index, (0..V::DIM).map(|i| diff(i) * diff(i)).sum());
+        let diff = diff(depth % V::DIM);
+        let (near, far) = if diff < 0.0 {
+            ((lo, mid), (mid + 1, hi))
+        } else {
+            ((mid + 1, hi), (lo, mid))
+        };
+        self.search(p, near.0, near.1, depth + 1, collector);
+        if !matches!(collector.bound(), Some(b) if diff * diff > b) {
+            self.search(p, far.0, far.1, depth + 1, collector);
+        }
+    }
+
+    /// Index of the point nearest to `p`. `None` if the tree is empty.
+    /// Ties go to the lowest index.
+    /// ```
+    ///     use xyzvec::{KdTree2, XYVec};
+    ///
+    ///     let points = [XYVec::new([0.0f64, 0.0]), XYVec::new([3.0, 1.0]), XYVec::new([1.0, 2.0])];
+    ///     let tree = KdTree2::new(&points);
+    ///     assert_eq!(tree.nearest(XYVec::new([2.5, 1.5])), Some(1));
+    /// ```
+    pub fn nearest(&self, p: V) -> Option<usize> {
+        self.k_nearest(p, 1).first().copied()
+    }
+
+    /// Indices of the `k` points nearest to `p`, nearest first, with ties
+    /// broken by index. Fewer if the tree holds fewer than `k` points.
+    /// ```
+    ///     use xyzvec::{KdTree3, XYZVec};
+    ///
+    ///     let points: Vec<XYZVec<i32>> = (0..10).map(|i| XYZVec::new([i, 0, 0])).collect();
+    ///     let tree = KdTree3::new(&points);
+    ///     assert_eq!(tree.k_nearest(XYZVec::new([6, 1, 0]), 3), vec![6, 5, 7]);
+    /// ```
+    pub fn k_nearest(&self, p: V, k: usize) -> Vec<usize> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let mut nearest = KNearest {
+            k,
+            best: Vec::with_capacity(k + 1),
+        };
+        self.search(p, 0, self.order.len(), 0, &mut nearest);
+        nearest.best.into_iter().map(|(_, i)| i).collect()
+    }
+
+    /// Indices of every point within distance `r` of `p` (inclusive), in
+    /// increasing order.
+    /// ```
+    ///     use xyzvec::{KdTree2, XYVec};
+    ///
+    ///     let points = [XYVec::new([0.0f32, 0.0]), XYVec::new([1.0, 0.0]), XYVec::new([3.0, 0.0])];
+    ///     let tree = KdTree2::new(&points);
+    ///     assert_eq!(tree.within_radius(XYVec::new([0.5, 0.0]), 0.5), vec![0, 1]);
+    /// ```
+    pub fn within_radius(&self, p: V, r: V::Scalar) -> Vec<usize> {
+        let mut within = WithinRadius {
+            r_sqd: r.to_f64() * r.to_f64(),
+            found: Vec::new(),
+        };
+        self.search(p, 0, self.order.len(), 0, &mut within);
+        within.found.sort_unstable();
+        within.found
+    }
+}
+
+/// Accumulates query results during a tree search.
+trait Collector {
+    fn offer(&mut self, index: usize, dist_sqd: f64);
+    /// Squared distance beyond which no more points are wanted, if any.
+    fn bound(&self) -> Option<f64>;
+}
+
+struct KNearest {
+    k: usize,
+    /// Sorted by distance, then by index.
+    best: Vec<(f64, usize)>,
+}
+
+impl Collector for KNearest {
+    fn offer(&mut self, index: usize, dist_sqd: f64) {
+        let pos = self.best.partition_point(|&(d, i)| {
+            cmp_scalar(&d, &dist_sqd).then(i.cmp(&index)) == Ordering::Less
+        });
+        if pos < self.k {
+            self.best.insert(pos, (dist_sqd, index));
+            self.best.truncate(self.k);
+        }
+    }
+
+    fn bound(&self) -> Option<f64> {
+        (self.best.len() == self.k).then(|| self.best[self.k - 1].0)
+    }
+}
+
+struct WithinRadius {
+    r_sqd: f64,
+    found: Vec<usize>,
+}
+
+impl Collector for WithinRadius {
+    fn offer(&mut self, index: usize, dist_sqd: f64) {
+        if dist_sqd <= self.r_sqd {
+            self.found.push(index);
+        }
+    }
+
+    fn bound(&self) -> Option<f64> {
+        Some(self.r_sqd)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{KdTree2, KdTree3, XYVec, XYZVec};
+    use fixed::types::I16F16;
+
+    fn cloud(n: usize) -> Vec<XYZVec<f64>> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64;
+                XYZVec::new([
+                    (t * 1.37).sin() * 5.0,
+                    (t * 0.61).cos() * 5.0,
+                    (t * 2.9).sin(),
+                ])
+            })
+            .collect()
+    }
+
+    fn brute_k_nearest(points: &[XYZVec<f64>], p: XYZVec<f64>, k: usize) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        order.sort_by(|&a, &b| {
+            let da = (points[a] - p).l2_norm_sqd();
+            let db = (points[b] - p).l2_norm_sqd();
+            da.partial_cmp(&db).unwrap().then(a.cmp(&b))
+        });
+        order.truncate(k);
+        order
+    }
+
+    #[test]
+    fn matches_brute_force_f64() {
+        let points = cloud(500);
+        let tree = KdTree3::new(&points);
+        for q in cloud(40).iter().map(|q| q.scale_by(1.3)) {
+            assert_eq!(
+                tree.nearest(q),
+                brute_k_nearest(&points, q, 1).first().copied()
+            );
+            assert_eq!(tree.k_nearest(q, 7), brute_k_nearest(&points, q, 7));
+            let expected: Vec<usize> = (0..points.len())
+                .filter(|&i| (points[i] - q).l2_norm_sqd() <= 2.0 * 2.0)
+                .collect();
+            assert_eq!(tree.within_radius(q, 2.0), expected);
+        }
+    }
+
+    #[test]
+    fn small_and_empty_f32() {
+        let empty = KdTree2::<f32>::new(&[]);
+        assert!(empty.is_empty());
+        assert_eq!(empty.nearest(XYVec::new([0.0, 0.0])), None);
+        assert!(empty.k_nearest(XYVec::new([0.0, 0.0]), 3).is_empty());
+
+        let points = [XYVec::new([1.0f32, 1.0]), XYVec::new([1.0, 1.0])];
+        let tree = KdTree2::new(&points);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree.k_nearest(XYVec::new([0.0, 0.0]), 5), vec![0, 1]);
+        assert!(tree.k_nearest(XYVec::new([0.0, 0.0]), 0).is_empty());
+    }
+
+    #[test]
+    fn grid_fixed() {
+        let f = |v: i32| I16F16::from_num(v);
+        let points: Vec<XYVec<I16F16>> = (0..8)
+            .flat_map(|x| (0..8).map(move |y| XYVec::new([f(x), f(y)])))
+            .collect();
+        let tree = KdTree2::new(&points);
+        let q = XYVec::new([I16F16::from_num(2.4), I16F16::from_num(5.1)]);
+        assert_eq!(points[tree.nearest(q).unwrap()], XYVec::new([f(2), f(5)]));
+        assert_eq!(
+            tree.within_radius(XYVec::new([f(0), f(0)]), f(1)),
+            vec![0, 1, 8]
+        );
+    }
+
+    #[test]
+    fn distant_fixed() {
+        // Squared distances here reach 320000, far past I16F16's range.
+        let f = |v: i32| I16F16::from_num(v);
+        let points = [
+            XYZVec::new([f(200), f(200), f(200)]),
+            XYZVec::new([f(-200), f(-200), f(-200)]),
+            XYZVec::new([f(150), f(-180), f(0)]),
+        ];
+        let tree = KdTree3::new(&points);
+        let q = XYZVec::new([f(-190), f(-200), f(-210)]);
+        assert_eq!(tree.nearest(q), Some(1));
+        assert_eq!(tree.k_nearest(q, 3), vec![1, 2, 0]);
+        assert_eq!(tree.within_radius(points[0], f(450)), vec![0, 2]);
+    }
+}
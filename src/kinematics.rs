@@ -0,0 +1,272 @@
+//! Fixed-timestep integrators for a point mass moving under an acceleration
+//! that may depend on its current position and velocity, and helpers that
+//! integrate an angular velocity into an orientation.
+
+use crate::{angle::wrap_angle, lie, quat::Quat, Mat3, VecFloat, VecNum, Vector, XYZVec};
+
+/// Position and velocity of a point mass.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct State<V> {
+    pub pos: V,
+    pub vel: V,
+}
+
+impl<V: Vector> State<V>
+where
+    V::Scalar: VecNum,
+{
+    pub fn new(pos: V, vel: V) -> Self {
+        Self { pos, vel }
+    }
+
+    /// Explicit Euler: position advances with the old velocity. Simple, but
+    /// gains energy in oscillating systems.
+    pub fn step_euler<F: Fn(&Self) -> V>(&self, accel: F, dt: V::Scalar) -> Self {
+        let a = accel(self);
+        Self::new(self.pos + self.vel.scale_by(dt), self.vel + a.scale_by(dt))
+    }
+
+    /// Semi-implicit (symplectic) Euler: velocity advances first and the
+    /// position uses the new velocity. Stable for most game physics.
+    /// ```
+    ///     use xyzvec::{kinematics::State, XYVec};
+    ///
+    ///     let gravity = |_: &State<XYVec<f64>>| XYVec::new([0.0, -10.0]);
+    ///     let s = State::new(XYVec::new([0.0, 0.0]), XYVec::new([1.0, 0.0]));
+    ///     let s = s.step_semi_implicit(gravity, 0.5);
+    ///     assert_eq!(s.vel, XYVec::new([1.0, -5.0]));
+    ///     assert_eq!(s.pos, XYVec::new([0.5, -2.5]));
+    /// ```
+    pub fn step_semi_implicit<F: Fn(&Self) -> V>(&self, accel: F, dt: V::Scalar) -> Self {
+        let vel = self.vel + accel(self).scale_by(dt);
+        Self::new(self.pos + vel.scale_by(dt), vel)
+    }
+
+    /// Velocity Verlet. Exact for constant acceleration and second-order
+    /// accurate otherwise. The acceleration at the new position is evaluated
+    /// with an Euler-predicted velocity.
+    pub fn step_verlet<F: Fn(&Self) -> V>(&self, accel: F, dt: V::Scalar) -> Self {
+        let half = V::Scalar::one() / (V::Scalar::one() + V::Scalar::one());
+        let a0 = accel(self);
+        let pos = self.pos + self.vel.scale_by(dt) + a0.scale_by(half * dt * dt);
+        let a1 = accel(&Self::new(pos, self.vel + a0.scale_by(dt)));
+        Self::new(pos, self.vel + (a0 + a1).scale_by(half * dt))
+    }
+
+    /// Classic fourth-order Runge-Kutta.
+    /// ```
+    ///     use xyzvec::{kinematics::State, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     // Unit-frequency harmonic oscillator: after 2π it is back where it began.
+    ///     let spring = |s: &State<XYZVec<f64>>| -s.pos;
+    ///     let mut s = State::new(XYZVec::new([1.0, 0.0, 0.0]), XYZVec::new([0.0, 1.0, 0.0]));
+    ///     let steps = 1000;
+    ///     let dt = std::f64::consts::TAU / steps as f64;
+    ///     for _ in 0..steps {
+    ///         s = s.step_rk4(spring, dt);
+    ///     }
+    ///     assert_relative_eq!(s.pos.x(), 1.0, epsilon = 1e-9);
+    ///     assert_relative_eq!(s.vel.y(), 1.0, epsilon = 1e-9);
+    /// ```
+    pub fn step_rk4<F: Fn(&Self) -> V>(&self, accel: F, dt: V::Scalar) -> Self {
+        let one = V::Scalar::one();
+        let two = one + one;
+        let half_dt = dt / two;
+
+        let k1_pos = self.vel;
+        let k1_vel = accel(self);
+
+        let s2 = Self::new(
+            self.pos + k1_pos.scale_by(half_dt),
+            self.vel + k1_vel.scale_by(half_dt),
+        );
+        let k2_pos = s2.vel;
+        let k2_vel = accel(&s2);
+
+        let s3 = Self::new(
+            self.pos + k2_pos.scale_by(half_dt),
+            self.vel + k2_vel.scale_by(half_dt),
+        );
+        let k3_pos = s3.vel;
+        let k3_vel = accel(&s3);
+
+        let s4 = Self::new(
+            self.pos + k3_pos.scale_by(dt),
+            self.vel + k3_vel.scale_by(dt),
+        );
+        let k4_pos = s4.vel;
+        let k4_vel = accel(&s4);
+
+        let sixth_dt = dt / (two + two + two);
+        let pos = self.pos + (k1_pos + (k2_pos + k3_pos).scale_by(two) + k4_pos).scale_by(sixth_dt);
+        let vel = self.vel + (k1_vel + (k2_vel + k3_vel).scale_by(two) + k4_vel).scale_by(sixth_dt);
+        Self::new(pos, vel)
+    }
+}
+
+/// `orientation` after turning at the body-frame angular velocity `omega`
+/// (a rotation vector per unit time, as reported by a gyroscope) for `dt`.
+/// The rotation is applied exactly, not to first order, and the result is
+/// renormalized so that long integrations do not drift off unit length.
+/// ```
+///     use xyzvec::{kinematics::integrate_angular_velocity, quat::Quat, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let omega = XYZVec::new([0.0, 0.0, std::f64::consts::FRAC_PI_2]);
+///     let mut q = Quat::identity();
+///     for _ in 0..100 {
+///         q = integrate_angular_velocity(q, omega, 0.01);
+///     }
+///     let v = q.rotate(XYZVec::new([1.0, 0.0, 0.0]));
+///     assert_relative_eq!(v.y(), 1.0, epsilon = 1e-12);
+/// ```
+pub fn integrate_angular_velocity<T: VecNum>(
+    orientation: Quat<T>,
+    omega: XYZVec<T>,
+    dt: T,
+) -> Quat<T> {
+    let [x, y, z] = [omega.x(), omega.y(), omega.z()].map(|c| c.to_f64());
+    let (rate, dt) = ((x * x + y * y + z * z).sqrt(), dt.to_f64());
+    let half = rate * dt / 2.0;
+    // sin(|ω| dt / 2) / |ω|, expanded near zero to avoid dividing by it.
+    let k = if half.abs() < 1e-4 {
+        dt / 2.0 * (1.0 - half * half / 6.0)
+    } else {
+        half.sin() / rate
+    };
+    let delta = Quat::new(
+        T::from_f64(half.cos()),
+        XYZVec::new([x * k, y * k, z * k].map(T::from_f64)),
+    );
+    let turned = orientation * delta;
+    turned.normalize().unwrap_or(turned)
+}
+
+/// Like [`integrate_angular_velocity`], for an orientation stored as a
+/// rotation matrix.
+pub fn integrate_angular_velocity_mat3<T: VecNum>(
+    orientation: Mat3<T>,
+    omega: XYZVec<T>,
+    dt: T,
+) -> Mat3<T> {
+    orientation * lie::so3_exp(omega.scale_by(dt))
+}
+
+/// 2D heading after turning at `omega` radians per unit time for `dt`,
+/// wrapped into `(-π, π]`.
+pub fn integrate_heading<T: VecFloat>(heading: T, omega: T, dt: T) -> T {
+    wrap_angle(heading + omega * dt)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        integrate_angular_velocity, integrate_angular_velocity_mat3, integrate_heading, State,
+    };
+    use crate::{lie, quat::Quat, Mat3, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I16F16;
+
+    fn energy(s: &State<XYVec<f64>>) -> f64 {
+        0.5 * (s.pos.l2_norm_sqd() + s.vel.l2_norm_sqd())
+    }
+
+    #[test]
+    fn constant_acceleration_f64() {
+        let g = |_: &State<XYZVec<f64>>| XYZVec::new([0.0, 0.0, -9.8]);
+        let start = State::new(XYZVec::new([0.0, 0.0, 0.0]), XYZVec::new([1.0, 0.0, 10.0]));
+        let t = 2.0;
+        let expected_z = 10.0 * t - 0.5 * 9.8 * t * t;
+        let mut verlet = start;
+        let mut rk4 = start;
+        for _ in 0..20 {
+            verlet = verlet.step_verlet(g, 0.1);
+            rk4 = rk4.step_rk4(g, 0.1);
+        }
+        assert_relative_eq!(verlet.pos.z(), expected_z, epsilon = 1e-9);
+        assert_relative_eq!(rk4.pos.z(), expected_z, epsilon = 1e-9);
+        assert_relative_eq!(rk4.pos.x(), 2.0, epsilon = 1e-9);
+        assert_relative_eq!(verlet.vel.z(), 10.0 - 9.8 * t, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn euler_energy_drift_f64() {
+        let spring = |s: &State<XYVec<f64>>| -s.pos;
+        let start = State::new(XYVec::new([1.0, 0.0]), XYVec::new([0.0, 0.0]));
+        let mut euler = start;
+        let mut semi = start;
+        for _ in 0..1000 {
+            euler = euler.step_euler(spring, 0.01);
+            semi = semi.step_semi_implicit(spring, 0.01);
+        }
+        assert!(energy(&euler) > energy(&start) * 1.05);
+        assert_relative_eq!(energy(&semi), energy(&start), epsilon = 0.01);
+    }
+
+    #[test]
+    fn damped_f32() {
+        let drag = |s: &State<XYVec<f32>>| -s.vel;
+        let mut s = State::new(XYVec::new([0.0f32, 0.0]), XYVec::new([1.0, 0.0]));
+        for _ in 0..100 {
+            s = s.step_rk4(drag, 0.05);
+        }
+        assert_relative_eq!(s.vel.x(), (-5.0f32).exp(), epsilon = 1e-5);
+        assert_relative_eq!(s.pos.x(), 1.0 - (-5.0f32).exp(), epsilon = 1e-5);
+    }
+
+    #[test]
+    fn semi_implicit_fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let g = |_: &State<XYVec<I16F16>>| XYVec::new([f(0.0), f(-8.0)]);
+        let s = State::new(XYVec::new([f(0.0), f(0.0)]), XYVec::new([f(1.0), f(0.0)]));
+        let s = s.step_semi_implicit(g, f(0.25));
+        assert_eq!(s.vel, XYVec::new([f(1.0), f(-2.0)]));
+        assert_eq!(s.pos, XYVec::new([f(0.25), f(-0.5)]));
+    }
+
+    #[test]
+    fn angular_velocity_f64() {
+        let omega = XYZVec::new([0.3f64, -1.1, 0.7]);
+        let (steps, dt) = (1000, 0.002);
+        let mut q = Quat::identity();
+        let mut m = Mat3::identity();
+        for _ in 0..steps {
+            q = integrate_angular_velocity(q, omega, dt);
+            m = integrate_angular_velocity_mat3(m, omega, dt);
+        }
+        // A constant rate integrates to a single rotation.
+        let expected = lie::so3_exp(omega.scale_by(steps as f64 * dt));
+        let p = XYZVec::new([1.0, 2.0, -0.5]);
+        assert_relative_eq!((q.rotate(p) - expected * p).l2_norm(), 0.0, epsilon = 1e-10);
+        assert_relative_eq!((m * p - expected * p).l2_norm(), 0.0, epsilon = 1e-10);
+        assert_relative_eq!(q.norm(), 1.0, epsilon = 1e-12);
+        // Tiny rates take the small-angle path.
+        let still = integrate_angular_velocity(q, XYZVec::new([1e-12, 0.0, 0.0]), dt);
+        assert_relative_eq!(still.dot(q), 1.0, epsilon = 1e-12);
+        assert_eq!(
+            integrate_angular_velocity(q, XYZVec::new([0.0; 3]), dt),
+            q.normalize().unwrap()
+        );
+    }
+
+    #[test]
+    fn heading_f32() {
+        let mut heading = 3.0f32;
+        heading = integrate_heading(heading, 1.0, 0.5);
+        assert_relative_eq!(heading, 3.5 - std::f32::consts::TAU, epsilon = 1e-5);
+        assert_relative_eq!(integrate_heading(0.0f32, -2.0, 0.25), -0.5);
+    }
+
+    #[test]
+    fn angular_velocity_fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let mut q = Quat::identity();
+        for _ in 0..10 {
+            q = integrate_angular_velocity(q, XYZVec::new([f(0.0), f(0.0), f(1.0)]), f(0.1));
+        }
+        let v = q.rotate(XYZVec::new([f(1.0), f(0.0), f(0.0)]));
+        assert!((v.x() - f(1f64.cos())).abs() < f(1e-2));
+        assert!((v.y() - f(1f64.sin())).abs() < f(1e-2));
+    }
+}
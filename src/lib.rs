@@ -1,20 +1,136 @@
+pub mod aabb;
+pub mod angle;
+pub mod arc;
+pub mod azimuth;
+pub mod ball;
+pub mod basis;
+pub mod bezier;
+pub mod camera;
+pub mod csv;
+pub mod debug_svg;
+pub mod dual_quat;
+pub mod error;
+pub mod exact;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod finite;
+pub mod finite_diff;
+pub mod fit;
+pub mod flat;
+pub mod frame;
+pub mod geo;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+pub mod gjk;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+pub mod hex;
+pub mod hull;
+pub mod interp;
+pub mod interval;
+pub mod iter;
+pub mod ivec;
+pub mod kdtree;
+pub mod kinematics;
+pub mod lie;
+#[cfg(feature = "defmt")]
+pub mod logging;
+pub mod lut;
+mod macros;
+pub mod mat;
+pub mod morton;
+pub mod obb;
+pub mod plane;
+#[cfg(feature = "plotters")]
+pub mod plot;
+pub mod polygon;
+pub mod polyline;
+pub mod pose;
+#[cfg(feature = "postgres-types")]
+pub mod postgres;
+pub mod predicates;
+#[cfg(feature = "prost")]
+pub mod proto;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod quat;
+pub mod queries;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod ransac;
+#[cfg(feature = "num-rational")]
+pub mod rational;
+pub mod ray;
+pub mod registration;
+pub mod rotor;
+pub mod sdf;
+pub mod segment;
+pub mod spatial_hash;
+pub mod spline;
+pub mod spring;
+pub mod stats;
+pub mod strided;
+pub mod sweep;
+#[cfg(feature = "ufmt")]
+pub mod tiny_fmt;
+pub mod triangle;
+pub mod ulps;
+pub mod unit;
+#[cfg(feature = "uom")]
+pub mod units;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod wire;
+#[cfg(feature = "wkt")]
+pub mod wkt;
 pub mod xy;
 pub mod xyz;
+pub mod xyzw;
 // TODO: comments / doctest
 // TODO: tests with f64, f32, fixed point
 // TODO: checked operations
 // TODO: fixed point support
 // TODO: SIMD support
-// TODO: approximate equality for fixed point?
 // TODO: add relative_eq for tuples for simpler assertions
 
+use fixed::types::{
+    extra::{LeEqU16, LeEqU32, LeEqU64, LeEqU8},
+    I2F30,
+};
 use std::{
+    cmp::Ordering,
     fmt::{Debug, Display},
     ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
 };
 
+pub use aabb::{Aabb, Aabb2, Aabb3};
+pub use arc::Arc2;
+pub use ball::{Ball, Circle, Sphere};
+pub use bezier::{CubicBezier, QuadraticBezier};
+pub use dual_quat::DualQuat;
+pub use finite::{FiniteVec, FiniteXYVec, FiniteXYZVec};
+pub use fit::{LineFit, PlaneFit};
+pub use geo::LatLon;
+pub use ivec::{IVec2, IVec3, Rounding};
+pub use kdtree::{KdTree, KdTree2, KdTree3};
+pub use mat::{Mat2, Mat3, Mat4};
+pub use obb::{Obb2, Obb3};
+pub use plane::Plane;
+pub use polygon::{Orientation, Polygon2};
+pub use polyline::Polyline;
+pub use pose::{Pose2, Pose3};
+pub use predicates::{CrossSign, TripleSign};
+pub use quat::Quat;
+pub use ray::{Ray, Ray2, Ray3, RayHit, TriangleHit};
+pub use rotor::{Rotor2, Rotor3};
+pub use segment::{LineSegment2, SegmentIntersection};
+pub use spatial_hash::SpatialHashGrid;
+pub use spline::{CatmullRom, HermiteSegment, HermiteSpline, Parameterization};
+pub use triangle::{Triangle2, Triangle3};
+pub use unit::{Unit, UnitXYVec, UnitXYZVec};
 pub use xy::XYVec;
 pub use xyz::XYZVec;
+pub use xyzw::XYZWVec;
 
 pub trait CordicPhantomTrait {}
 impl<Frac> CordicPhantomTrait for fixed::FixedI8<Frac> {}
@@ -53,3 +169,490 @@ impl<
     > VecInner for V
 {
 }
+
+/// Operations shared by [`XYVec`] and [`XYZVec`], for code that is generic
+/// over the dimension.
+pub trait Vector:
+    Copy + Debug + Add<Output = Self> + AddAssign + Sub<Output = Self> + SubAssign + Neg<Output = Self>
+{
+    type Scalar: VecInner;
+    /// Number of components.
+    const DIM: usize;
+
+    /// Vector with every component set to `v`.
+    fn splat(v: Self::Scalar) -> Self;
+    /// Component `i`, in `x, y, z` order. Panics if `i >= DIM`.
+    fn component(&self, i: usize) -> Self::Scalar;
+    fn map<F: Fn(Self::Scalar) -> Self::Scalar>(&self, f: F) -> Self;
+    fn zip_map<F: Fn(Self::Scalar, Self::Scalar) -> Self::Scalar>(&self, other: Self, f: F)
+        -> Self;
+    fn dot_prod(&self, other: Self) -> Self::Scalar;
+    fn scale_by(&self, d: Self::Scalar) -> Self;
+    fn div_by(&self, d: Self::Scalar) -> Self;
+
+    fn l2_norm_sqd(&self) -> Self::Scalar {
+        self.dot_prod(*self)
+    }
+
+    fn l2_norm(&self) -> Self::Scalar
+    where
+        Self::Scalar: VecFloat,
+    {
+        self.l2_norm_sqd().sqrt()
+    }
+
+    /// `self` scaled to unit length. `None` if `self` is zero or its
+    /// length is not finite. See [`Unit`].
+    fn try_normalize(&self) -> Option<Unit<Self>>
+    where
+        Self::Scalar: VecFloat,
+    {
+        Unit::try_new(*self)
+    }
+
+    /// `self`, checked to have no NaN or infinite components. See
+    /// [`FiniteVec`].
+    fn try_finite(&self) -> Option<FiniteVec<Self>>
+    where
+        Self::Scalar: VecNum,
+    {
+        FiniteVec::new(*self)
+    }
+
+    /// Largest absolute difference between corresponding components, or
+    /// NaN if any difference is NaN.
+    /// ```
+    ///     use xyzvec::{Vector, XYZVec};
+    ///
+    ///     let a = XYZVec::new([1.0f64, 2.0, 3.0]);
+    ///     assert_eq!(a.max_component_diff(XYZVec::new([1.5, 2.0, 2.0])), 1.0);
+    /// ```
+    fn max_component_diff(&self, other: Self) -> Self::Scalar
+    where
+        Self::Scalar: VecNum,
+    {
+        let mut max = Self::Scalar::zero();
+        for i in 0..Self::DIM {
+            let d = (self.component(i) - other.component(i)).abs();
+            match d.partial_cmp(&max) {
+                Some(Ordering::Greater) => max = d,
+                None => return d,
+                _ => {}
+            }
+        }
+        max
+    }
+
+    /// Whether each pair of components is within `abs_eps` of each other,
+    /// or within `rel_eps` times the larger of their magnitudes. Equal
+    /// components always pass, infinities included; NaN never does. Works
+    /// the same for fixed point, where `abs_eps` of a few
+    /// [`DELTA`](fixed::FixedI32::DELTA) absorbs truncation.
+    /// ```
+    ///     use fixed::types::I16F16;
+    ///     use xyzvec::{Vector, XYVec};
+    ///
+    ///     let a = XYVec::new([1000.0f64, 1e-9]);
+    ///     let b = XYVec::new([1000.001, 0.0]);
+    ///     assert!(a.almost_eq(b, 1e-6, 1e-5));
+    ///     assert!(!a.almost_eq(b, 1e-6, 1e-7));
+    ///
+    ///     let third = XYVec::new([I16F16::ONE / 3, I16F16::ONE / 3]);
+    ///     let eps = I16F16::DELTA * 2;
+    ///     let one = XYVec::splat(I16F16::ONE);
+    ///     assert!((third + third + third).almost_eq(one, eps, I16F16::ZERO));
+    /// ```
+    fn almost_eq(&self, other: Self, abs_eps: Self::Scalar, rel_eps: Self::Scalar) -> bool
+    where
+        Self::Scalar: VecNum,
+    {
+        (0..Self::DIM).all(|i| {
+            let (a, b) = (self.component(i), other.component(i));
+            if a == b {
+                return true;
+            }
+            let diff = (a - b).abs();
+            let larger = if a.abs() > b.abs() { a.abs() } else { b.abs() };
+            diff <= abs_eps || diff <= rel_eps * larger
+        })
+    }
+
+    /// Cosine of the angle between `self` and `other`, in `[-1, 1]`. `None`
+    /// if either is zero, since the angle is then undefined.
+    /// ```
+    ///     use xyzvec::{Vector, XYVec};
+    ///
+    ///     let a = XYVec::new([3.0f64, 0.0]);
+    ///     assert_eq!(a.cosine_similarity(XYVec::new([3.0, 4.0])), Some(0.6));
+    ///     assert_eq!(a.cosine_similarity(XYVec::new([-1.0, 0.0])), Some(-1.0));
+    ///     assert_eq!(a.cosine_similarity(XYVec::new([0.0, 0.0])), None);
+    /// ```
+    fn cosine_similarity(&self, other: Self) -> Option<Self::Scalar>
+    where
+        Self::Scalar: VecFloat,
+    {
+        let zero = Self::Scalar::zero();
+        let (a, b) = (self.l2_norm(), other.l2_norm());
+        if a == zero || b == zero {
+            return None;
+        }
+        let one = Self::Scalar::one();
+        Some(interp::clamp(self.dot_prod(other) / a / b, -one, one))
+    }
+
+    /// [`cosine_similarity`](Self::cosine_similarity) for scalars without
+    /// a square root, such as fixed point and integers, as a Q2.30 fraction.
+    /// Computed in `f64`, so products that would overflow `Self::Scalar`
+    /// are fine.
+    /// ```
+    ///     use fixed::types::{I16F16, I2F30};
+    ///     use xyzvec::{Vector, XYZVec};
+    ///
+    ///     let f = I16F16::from_num;
+    ///     let a = XYZVec::new([f(300), f(0), f(0)]);
+    ///     let b = XYZVec::new([f(200), f(0), f(200)]);
+    ///     let cos = a.cosine_similarity_fixed(b).unwrap();
+    ///     assert!((cos - I2F30::SQRT_2 / 2).abs() <= I2F30::DELTA);
+    ///     assert_eq!(a.cosine_similarity_fixed(XYZVec::splat(f(0))), None);
+    /// ```
+    fn cosine_similarity_fixed(&self, other: Self) -> Option<I2F30>
+    where
+        Self::Scalar: VecNum,
+    {
+        let (mut dot, mut aa, mut bb) = (0.0, 0.0, 0.0);
+        for i in 0..Self::DIM {
+            let (a, b) = (self.component(i).to_f64(), other.component(i).to_f64());
+            dot += a * b;
+            aa += a * a;
+            bb += b * b;
+        }
+        if aa == 0.0 || bb == 0.0 {
+            return None;
+        }
+        let cos = (dot / aa.sqrt() / bb.sqrt()).clamp(-1.0, 1.0);
+        Some(I2F30::from_num(cos))
+    }
+
+    /// Linear interpolation from `self` (at `t = 0`) to `other` (at `t = 1`).
+    fn lerp(&self, other: Self, t: Self::Scalar) -> Self {
+        *self + (other - *self).scale_by(t)
+    }
+
+    /// Interpolation eased by [`interp::smoothstep`].
+    fn smoothstep(&self, other: Self, t: Self::Scalar) -> Self
+    where
+        Self::Scalar: VecNum,
+    {
+        let (zero, one) = (Self::Scalar::zero(), Self::Scalar::one());
+        self.lerp(other, interp::smoothstep(zero, one, t))
+    }
+
+    /// Interpolation eased by [`interp::smootherstep`].
+    fn smootherstep(&self, other: Self, t: Self::Scalar) -> Self
+    where
+        Self::Scalar: VecNum,
+    {
+        let (zero, one) = (Self::Scalar::zero(), Self::Scalar::one());
+        self.lerp(other, interp::smootherstep(zero, one, t))
+    }
+
+    /// Component-wise [`interp::cubic`] between `p1` and `p2`.
+    fn cubic(p0: Self, p1: Self, p2: Self, p3: Self, t: Self::Scalar) -> Self
+    where
+        Self::Scalar: VecNum,
+    {
+        let one = Self::Scalar::one();
+        let two = one + one;
+        let a = p1.scale_by(two);
+        let b = p2 - p0;
+        let c = p0.scale_by(two) - p1.scale_by(two + two + one) + p2.scale_by(two + two) - p3;
+        let d = (p1 - p2).scale_by(two + one) + p3 - p0;
+        (a + (b + (c + d.scale_by(t)).scale_by(t)).scale_by(t)).div_by(two)
+    }
+
+    /// `self` moved toward `target` by a distance of at most `max_delta`,
+    /// landing exactly on `target` once it is within reach.
+    /// ```
+    ///     use xyzvec::{Vector, XYVec};
+    ///
+    ///     let mut pos = XYVec::new([0.0f64, 0.0]);
+    ///     let target = XYVec::new([0.0, 1.0]);
+    ///     pos = pos.move_towards(target, 0.75);
+    ///     assert_eq!(pos, XYVec::new([0.0, 0.75]));
+    ///     pos = pos.move_towards(target, 0.75);
+    ///     assert_eq!(pos, target);
+    /// ```
+    fn move_towards(&self, target: Self, max_delta: Self::Scalar) -> Self
+    where
+        Self::Scalar: VecFloat,
+    {
+        let diff = target - *self;
+        let dist = diff.l2_norm();
+        if dist <= max_delta || dist == Self::Scalar::zero() {
+            return target;
+        }
+        *self + diff.scale_by(max_delta / dist)
+    }
+
+    /// Frame-rate independent smoothing toward `target`: the remaining
+    /// distance decays by a factor of `e^(-lambda * dt)`, so many short steps
+    /// land in the same place as one long one. See [`interp::exp_decay`].
+    /// ```
+    ///     use xyzvec::{Vector, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let target = XYVec::new([10.0f64, 0.0]);
+    ///     let one_step = XYVec::new([0.0, 0.0]).damp(target, 4.0, 0.5);
+    ///     let mut many_steps = XYVec::new([0.0, 0.0]);
+    ///     for _ in 0..50 {
+    ///         many_steps = many_steps.damp(target, 4.0, 0.01);
+    ///     }
+    ///     assert_relative_eq!(one_step.x(), many_steps.x(), epsilon = 1e-9);
+    /// ```
+    fn damp(&self, target: Self, lambda: Self::Scalar, dt: Self::Scalar) -> Self
+    where
+        Self::Scalar: VecFloat,
+    {
+        self.lerp(target, Self::Scalar::one() - (-lambda * dt).exp())
+    }
+
+    /// `self` wrapped into `aabb` as if opposite faces were glued together
+    /// (periodic boundary conditions). Each component lands in
+    /// `[min, max)`.
+    /// ```
+    ///     use xyzvec::{Aabb2, Vector, XYVec};
+    ///
+    ///     let world = Aabb2::new(XYVec::new([0.0f64, 0.0]), XYVec::new([10.0, 5.0]));
+    ///     assert_eq!(XYVec::new([12.5, -1.0]).wrap_into(&world), XYVec::new([2.5, 4.0]));
+    ///     assert_eq!(XYVec::new([10.0, 5.0]).wrap_into(&world), XYVec::new([0.0, 0.0]));
+    /// ```
+    fn wrap_into(&self, aabb: &Aabb<Self>) -> Self
+    where
+        Self::Scalar: VecFloat,
+    {
+        let size = aabb.size();
+        let wrapped = (*self - aabb.min).zip_map(size, |c, l| c - (c / l).floor() * l);
+        // Rounding in `c - floor(c / l) * l` can land exactly on `l`.
+        wrapped.zip_map(size, |c, l| if c >= l { Self::Scalar::zero() } else { c }) + aabb.min
+    }
+
+    /// Shortest displacement from `self` to `other` in a periodic space
+    /// with the given side lengths (the minimum image convention). Each
+    /// component lands in `[-extent / 2, extent / 2]`.
+    /// ```
+    ///     use xyzvec::{Vector, XYVec};
+    ///
+    ///     let extents = XYVec::new([10.0f64, 10.0]);
+    ///     let d = XYVec::new([9.0, 1.0]).torus_delta(XYVec::new([1.0, 2.0]), extents);
+    ///     assert_eq!(d, XYVec::new([2.0, 1.0]));
+    /// ```
+    fn torus_delta(&self, other: Self, extents: Self) -> Self
+    where
+        Self::Scalar: VecFloat,
+    {
+        (other - *self).zip_map(extents, |c, l| {
+            c - (c / l + Self::Scalar::from_f64(0.5)).floor() * l
+        })
+    }
+
+    /// Length of [`torus_delta`](Self::torus_delta): the distance between
+    /// `self` and `other` going across the seams where that is shorter.
+    fn torus_distance(&self, other: Self, extents: Self) -> Self::Scalar
+    where
+        Self::Scalar: VecFloat,
+    {
+        self.torus_delta(other, extents).l2_norm()
+    }
+}
+
+/// Ordered scalars with additive and multiplicative identities.
+/// Implemented for signed integers, floats, and fixed point.
+pub trait VecNum: VecInner + PartialOrd {
+    fn zero() -> Self;
+    /// `1`, or the largest value for fixed-point types without enough
+    /// integer bits to hold it.
+    /// ```
+    ///     use fixed::{types::extra::U31, FixedI32};
+    ///     use xyzvec::VecNum;
+    ///
+    ///     assert_eq!(<FixedI32<U31>>::one(), FixedI32::<U31>::MAX);
+    /// ```
+    fn one() -> Self;
+    /// Nearest `f64`, for conversions that go through floating point.
+    fn to_f64(self) -> f64;
+    /// Nearest representable value to `v`, saturating at the type's range.
+    /// Integers and fixed point map NaN to zero.
+    /// ```
+    ///     use fixed::types::I16F16;
+    ///     use xyzvec::VecNum;
+    ///
+    ///     assert_eq!(I16F16::from_f64(f64::NAN), I16F16::ZERO);
+    ///     assert_eq!(I16F16::from_f64(1e10), I16F16::MAX);
+    ///     assert_eq!(i32::from_f64(f64::NAN), 0);
+    /// ```
+    fn from_f64(v: f64) -> Self;
+
+    fn abs(self) -> Self {
+        if self < Self::zero() {
+            -self
+        } else {
+            self
+        }
+    }
+}
+
+macro_rules! impl_vec_num {
+    ($($t:ty => $zero:expr, $one:expr, $round:expr);* $(;)?) => {
+        $(
+            impl VecNum for $t {
+                fn zero() -> Self {
+                    $zero
+                }
+
+                fn one() -> Self {
+                    $one
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn from_f64(v: f64) -> Self {
+                    $round(v) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_vec_num! {
+    i8 => 0, 1, f64::round;
+    i16 => 0, 1, f64::round;
+    i32 => 0, 1, f64::round;
+    i64 => 0, 1, f64::round;
+    i128 => 0, 1, f64::round;
+    f32 => 0.0, 1.0, std::convert::identity;
+    f64 => 0.0, 1.0, std::convert::identity;
+}
+
+macro_rules! impl_vec_num_fixed {
+    ($($t:ident: $bound:ident),* $(,)?) => {
+        $(
+            impl<Frac: $bound> VecNum for fixed::$t<Frac> {
+                fn zero() -> Self {
+                    Self::ZERO
+                }
+
+                fn one() -> Self {
+                    Self::saturating_from_num(1)
+                }
+
+                fn to_f64(self) -> f64 {
+                    self.to_num()
+                }
+
+                fn from_f64(v: f64) -> Self {
+                    if v.is_nan() {
+                        Self::ZERO
+                    } else {
+                        Self::saturating_from_num(v)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_vec_num_fixed!(FixedI8: LeEqU8, FixedI16: LeEqU16, FixedI32: LeEqU32, FixedI64: LeEqU64);
+
+/// Floating-point scalars, for routines that need square roots or
+/// non-integer constants.
+pub trait VecFloat: VecNum {
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn floor(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan2(self, x: Self) -> Self;
+    /// Difference between `1` and the next larger representable value.
+    fn epsilon() -> Self;
+    /// IEEE 754 total order, which also ranks NaN.
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+impl VecFloat for f32 {
+    fn sqrt(self) -> Self {
+        f32::sqrt(self)
+    }
+
+    fn exp(self) -> Self {
+        f32::exp(self)
+    }
+
+    fn floor(self) -> Self {
+        f32::floor(self)
+    }
+
+    fn sin(self) -> Self {
+        f32::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f32::cos(self)
+    }
+
+    fn acos(self) -> Self {
+        f32::acos(self)
+    }
+
+    fn atan2(self, x: Self) -> Self {
+        f32::atan2(self, x)
+    }
+
+    fn epsilon() -> Self {
+        f32::EPSILON
+    }
+
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f32::total_cmp(self, other)
+    }
+}
+
+impl VecFloat for f64 {
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+
+    fn exp(self) -> Self {
+        f64::exp(self)
+    }
+
+    fn floor(self) -> Self {
+        f64::floor(self)
+    }
+
+    fn sin(self) -> Self {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> Self {
+        f64::cos(self)
+    }
+
+    fn acos(self) -> Self {
+        f64::acos(self)
+    }
+
+    fn atan2(self, x: Self) -> Self {
+        f64::atan2(self, x)
+    }
+
+    fn epsilon() -> Self {
+        f64::EPSILON
+    }
+
+    fn total_cmp(&self, other: &Self) -> Ordering {
+        f64::total_cmp(self, other)
+    }
+}
@@ -0,0 +1,248 @@
+//! Exponential and logarithm maps for 3D rotations (SO(3)) and rigid
+//! transforms (SE(3)).
+//!
+//! A rotation vector points along the rotation axis and has the rotation
+//! angle, in radians, as its length. A [`Twist`] adds a linear part, giving
+//! the constant velocity screw motion that reaches a pose in unit time.
+//! Both maps switch to Taylor expansions near zero angle, and the rotation
+//! log stays accurate near half a turn. As elsewhere, scalars only need
+//! [`VecNum`]; the arithmetic is done in `f64`.
+
+use crate::{Mat3, Pose3, VecInner, VecNum, XYZVec};
+use std::fmt::{self, Formatter};
+
+type M = [[f64; 3]; 3];
+
+/// Below this angle the Taylor expansions are used.
+const SMALL_ANGLE: f64 = 1e-4;
+
+fn to_f64<T: VecNum>(v: XYZVec<T>) -> [f64; 3] {
+    [v.x(), v.y(), v.z()].map(|c| c.to_f64())
+}
+
+fn from_f64<T: VecNum>(v: [f64; 3]) -> XYZVec<T> {
+    XYZVec::new(v.map(T::from_f64))
+}
+
+fn mat_from_f64<T: VecNum>(m: M) -> Mat3<T> {
+    Mat3::new(m.map(|row| row.map(T::from_f64)))
+}
+
+fn hat([x, y, z]: [f64; 3]) -> M {
+    [[0.0, -z, y], [z, 0.0, -x], [-y, x, 0.0]]
+}
+
+/// `I + a W + b W²`.
+fn poly(w: &M, a: f64, b: f64) -> M {
+    let mut out = [[0.0; 3]; 3];
+    for (i, row) in out.iter_mut().enumerate() {
+        for (j, c) in row.iter_mut().enumerate() {
+            let w2: f64 = (0..3).map(|k| w[i][k] * w[k][j]).sum();
+            *c = if i == j { 1.0 } else { 0.0 } + a * w[i][j] + b * w2;
+        }
+    }
+    out
+}
+
+fn mul_vec(m: &M, v: [f64; 3]) -> [f64; 3] {
+    m.map(|row| row[0] * v[0] + row[1] * v[1] + row[2] * v[2])
+}
+
+/// `sin θ / θ`, `(1 - cos θ) / θ²`, and `(θ - sin θ) / θ³`.
+fn coefficients(theta: f64) -> (f64, f64, f64) {
+    let t2 = theta * theta;
+    if theta < SMALL_ANGLE {
+        (1.0 - t2 / 6.0, 0.5 - t2 / 24.0, 1.0 / 6.0 - t2 / 120.0)
+    } else {
+        let (s, c) = theta.sin_cos();
+        (s / theta, (1.0 - c) / t2, (theta - s) / (t2 * theta))
+    }
+}
+
+fn norm(v: [f64; 3]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+/// Rotation matrix for the rotation vector `omega` (Rodrigues' formula).
+/// ```
+///     use xyzvec::{lie, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let r = lie::so3_exp(XYZVec::new([0.0f64, 0.0, std::f64::consts::FRAC_PI_2]));
+///     let v = r * XYZVec::new([1.0, 0.0, 0.0]);
+///     assert_relative_eq!(v.y(), 1.0, epsilon = 1e-12);
+///     let back = lie::so3_log(&r);
+///     assert_relative_eq!(back.z(), std::f64::consts::FRAC_PI_2, epsilon = 1e-12);
+/// ```
+pub fn so3_exp<T: VecNum>(omega: XYZVec<T>) -> Mat3<T> {
+    let omega = to_f64(omega);
+    let (a, b, _) = coefficients(norm(omega));
+    mat_from_f64(poly(&hat(omega), a, b))
+}
+
+fn so3_log_f64(r: &M) -> [f64; 3] {
+    // `R - Rᵀ = 2 sin θ [n]ₓ`.
+    let skew = [r[2][1] - r[1][2], r[0][2] - r[2][0], r[1][0] - r[0][1]];
+    let cos = ((r[0][0] + r[1][1] + r[2][2] - 1.0) / 2.0).clamp(-1.0, 1.0);
+    let theta = (norm(skew) / 2.0).atan2(cos);
+    if theta < SMALL_ANGLE {
+        let a = 1.0 - theta * theta / 6.0;
+        return skew.map(|c| c / (2.0 * a));
+    }
+    if cos > 0.0 {
+        return skew.map(|c| c * theta / (2.0 * theta.sin()));
+    }
+    // Past a quarter turn `sin θ` loses precision; read the axis off the
+    // symmetric part `cos θ I + (1 - cos θ) n nᵀ` instead.
+    let nn = |i: usize, j: usize| {
+        let sym = (r[i][j] + r[j][i]) / 2.0 - if i == j { cos } else { 0.0 };
+        sym / (1.0 - cos)
+    };
+    let k = (0..3)
+        .max_by(|&i, &j| nn(i, i).total_cmp(&nn(j, j)))
+        .unwrap_or(0);
+    let scale = nn(k, k).sqrt();
+    let mut axis = [0, 1, 2].map(|i| nn(i, k) / scale);
+    if axis[0] * skew[0] + axis[1] * skew[1] + axis[2] * skew[2] < 0.0 {
+        axis = axis.map(|c| -c);
+    }
+    axis.map(|c| c * theta)
+}
+
+/// Rotation vector of the rotation matrix `r`, with angle in `[0, π]`.
+/// Inverse of [`so3_exp`].
+pub fn so3_log<T: VecNum>(r: &Mat3<T>) -> XYZVec<T> {
+    let r = [0, 1, 2].map(|i| [0, 1, 2].map(|j| r.get(i, j).to_f64()));
+    from_f64(so3_log_f64(&r))
+}
+
+/// Element of se(3): a screw motion with velocity `linear` and rotation
+/// vector `angular`, both expressed in the moving frame.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Twist<T> {
+    pub linear: XYZVec<T>,
+    pub angular: XYZVec<T>,
+}
+
+impl<T: VecNum> Twist<T> {
+    pub fn new(linear: XYZVec<T>, angular: XYZVec<T>) -> Self {
+        Self { linear, angular }
+    }
+
+    /// The pose reached by following this twist for unit time.
+    /// ```
+    ///     use xyzvec::{lie::Twist, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     // Half a turn about z while moving along x: a half circle.
+    ///     let pi = std::f64::consts::PI;
+    ///     let twist = Twist::new(XYZVec::new([pi, 0.0, 0.0]), XYZVec::new([0.0, 0.0, pi]));
+    ///     let pose = twist.exp();
+    ///     assert_relative_eq!(pose.translation.x(), 0.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(pose.translation.y(), 2.0, epsilon = 1e-12);
+    /// ```
+    pub fn exp(&self) -> Pose3<T> {
+        let (v, omega) = (to_f64(self.linear), to_f64(self.angular));
+        let w = hat(omega);
+        let (a, b, c) = coefficients(norm(omega));
+        let rotation = poly(&w, a, b);
+        let translation = mul_vec(&poly(&w, b, c), v);
+        Pose3::new(from_f64(translation), mat_from_f64(rotation))
+    }
+
+    /// The twist whose [`exp`](Self::exp) is `pose`, with rotation angle in
+    /// `[0, π]`.
+    pub fn log(pose: &Pose3<T>) -> Self {
+        let r = [0, 1, 2].map(|i| [0, 1, 2].map(|j| pose.rotation.get(i, j).to_f64()));
+        let omega = so3_log_f64(&r);
+        let theta = norm(omega);
+        // V⁻¹ = I - W/2 + d W², with d = (1 - a / 2b) / θ².
+        let d = if theta < SMALL_ANGLE {
+            1.0 / 12.0 + theta * theta / 720.0
+        } else {
+            let (a, b, _) = coefficients(theta);
+            (1.0 - a / (2.0 * b)) / (theta * theta)
+        };
+        let v_inv = poly(&hat(omega), -0.5, d);
+        let v = mul_vec(&v_inv, to_f64(pose.translation));
+        Self::new(from_f64(v), from_f64(omega))
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Twist<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Twist {{ linear: {:?}, angular: {:?} }}",
+            self.linear, self.angular
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{so3_exp, so3_log, Twist};
+    use crate::{Mat3, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I16F16;
+    use std::f64::consts::PI;
+
+    fn assert_close(a: XYZVec<f64>, b: XYZVec<f64>, eps: f64) {
+        assert_relative_eq!((a - b).l2_norm(), 0.0, epsilon = eps);
+    }
+
+    #[test]
+    fn so3_round_trip_f64() {
+        let axis = XYZVec::new([0.3f64, -0.8, 0.5]);
+        let unit = axis.div_by(axis.l2_norm());
+        for theta in [0.0, 1e-9, 1e-5, 0.3, 1.5, 2.5, PI - 1e-4, PI - 1e-9] {
+            let omega = unit.scale_by(theta);
+            let r = so3_exp(omega);
+            let expected = Mat3::from_axis_angle(axis, theta);
+            for i in 0..3 {
+                assert_close(r.row(i), expected.row(i), 1e-12);
+            }
+            assert_close(so3_log(&r), omega, 1e-9);
+        }
+        // Half a turn has two equally valid rotation vectors.
+        let half = so3_log(&so3_exp(unit.scale_by(PI)));
+        assert_relative_eq!(half.l2_norm(), PI, epsilon = 1e-12);
+        assert_relative_eq!(half.dot_prod(unit).abs(), PI, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn se3_round_trip_f64() {
+        let twists = [
+            Twist::new(
+                XYZVec::new([1.0f64, -2.0, 0.5]),
+                XYZVec::new([0.2, 0.1, -0.4]),
+            ),
+            Twist::new(XYZVec::new([0.0, 3.0, 0.0]), XYZVec::new([0.0, 0.0, 0.0])),
+            Twist::new(XYZVec::new([0.5, 0.5, 0.5]), XYZVec::new([1e-7, 0.0, 2e-7])),
+            Twist::new(XYZVec::new([-1.0, 0.0, 2.0]), XYZVec::new([0.0, 2.8, 1.0])),
+        ];
+        for twist in twists {
+            let pose = twist.exp();
+            let back = Twist::log(&pose);
+            assert_close(back.linear, twist.linear, 1e-9);
+            assert_close(back.angular, twist.angular, 1e-9);
+            // Following a twist for two units of time is the pose squared.
+            let doubled = Twist::new(twist.linear.scale_by(2.0), twist.angular.scale_by(2.0));
+            assert_close(doubled.exp().translation, (pose * pose).translation, 1e-9);
+        }
+    }
+
+    #[test]
+    fn f32_and_fixed() {
+        let r = so3_exp(XYZVec::new([0.0f32, 1.0, 0.0]));
+        assert_relative_eq!(so3_log(&r).y(), 1.0, epsilon = 1e-6);
+        let f = |v: f64| I16F16::from_num(v);
+        let twist = Twist::new(
+            XYZVec::new([f(1.0), f(0.0), f(0.0)]),
+            XYZVec::new([f(0.0), f(0.0), f(0.5)]),
+        );
+        let back = Twist::log(&twist.exp());
+        assert!((back.linear.x() - f(1.0)).abs() < f(1e-3));
+        assert!((back.angular.z() - f(0.5)).abs() < f(1e-3));
+    }
+}
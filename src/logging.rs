@@ -0,0 +1,105 @@
+//! [`defmt::Format`] for vectors, so they can be logged from embedded
+//! targets without going through `core::fmt`. Requires the `defmt` feature.
+//!
+//! Floats and integers are sent as-is and rendered on the host. Fixed-point
+//! components are sent as `f64`, which holds every fixed-point value of up
+//! to 32 bits exactly; 64-bit values are rounded to 53 significant bits.
+
+use crate::{VecInner, XYVec, XYZVec};
+use defmt::{Format, Formatter};
+use fixed::types::extra::{LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+
+/// Scalars that can be logged as a vector component.
+pub trait FormatScalar: Copy {
+    fn format_scalar(self, f: Formatter);
+}
+
+macro_rules! impl_format_scalar {
+    ($($t:ty),*) => {$(
+        impl FormatScalar for $t {
+            fn format_scalar(self, f: Formatter) {
+                self.format(f)
+            }
+        }
+    )*};
+}
+
+impl_format_scalar!(i8, i16, i32, i64, i128, f32, f64);
+
+macro_rules! impl_format_scalar_fixed {
+    ($($t:ident: $bound:ident),*) => {$(
+        impl<Frac: $bound> FormatScalar for fixed::$t<Frac> {
+            fn format_scalar(self, f: Formatter) {
+                self.to_num::<f64>().format(f)
+            }
+        }
+    )*};
+}
+
+impl_format_scalar_fixed!(FixedI8: LeEqU8, FixedI16: LeEqU16, FixedI32: LeEqU32, FixedI64: LeEqU64);
+
+/// Adapter giving a [`FormatScalar`] the [`Format`] impl that
+/// `defmt::write!` needs for its arguments.
+struct Component<T>(T);
+
+impl<T: FormatScalar> Format for Component<T> {
+    fn format(&self, f: Formatter) {
+        self.0.format_scalar(f)
+    }
+}
+
+impl<T: VecInner + FormatScalar> Format for XYVec<T> {
+    fn format(&self, f: Formatter) {
+        defmt::write!(f, "({}, {})", Component(self.x()), Component(self.y()))
+    }
+}
+
+impl<T: VecInner + FormatScalar> Format for XYZVec<T> {
+    fn format(&self, f: Formatter) {
+        defmt::write!(
+            f,
+            "({}, {}, {})",
+            Component(self.x()),
+            Component(self.y()),
+            Component(self.z())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::XYVec;
+    use fixed::types::I16F16;
+    use std::sync::Mutex;
+
+    static FRAMES: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+    #[defmt::global_logger]
+    struct Capture;
+
+    unsafe impl defmt::Logger for Capture {
+        fn acquire() {}
+        unsafe fn flush() {}
+        unsafe fn release() {}
+        unsafe fn write(bytes: &[u8]) {
+            FRAMES.lock().unwrap().extend_from_slice(bytes);
+        }
+    }
+
+    defmt::timestamp!("");
+
+    fn logged(v: impl defmt::Format) -> Vec<u8> {
+        FRAMES.lock().unwrap().clear();
+        defmt::println!("{}", v);
+        std::mem::take(&mut *FRAMES.lock().unwrap())
+    }
+
+    #[test]
+    fn fixed_logs_as_f64() {
+        let float = logged(XYVec::new([1.5f64, -0.25]));
+        assert!(float.windows(8).any(|w| w == 1.5f64.to_le_bytes()));
+        assert!(float.windows(8).any(|w| w == (-0.25f64).to_le_bytes()));
+        let fixed = logged(XYVec::new([I16F16::from_num(1.5), I16F16::from_num(-0.25)]));
+        assert_eq!(fixed, float);
+    }
+}
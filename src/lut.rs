@@ -0,0 +1,177 @@
+//! Sine/cosine lookup tables built at compile time, for rotating
+//! fixed-point vectors where even CORDIC is too slow.
+//!
+//! A [`SinLut<N>`] divides the full turn into `N` steps; angle index `i`
+//! stands for `2π·i/N` radians, counter-clockwise. Entries are Q2.30
+//! ([`I2F30`]) and are computed by a `const fn`, so a table declared as a
+//! `static` costs nothing at startup and lives in flash. A rotation is then
+//! a table read and four multiplications.
+//! ```
+//!     use fixed::types::I16F16;
+//!     use xyzvec::{lut::SinLut, XYVec};
+//!
+//!     static LUT: SinLut<256> = SinLut::new();
+//!
+//!     let v = XYVec::new([I16F16::from_num(2), I16F16::ZERO]);
+//!     // A quarter turn is index 64 of 256.
+//!     let r = v.rotated_by_lut(&LUT, 64);
+//!     assert_eq!(r, XYVec::new([I16F16::ZERO, I16F16::from_num(2)]));
+//! ```
+//!
+//! The table entries are within `2^-31` of the true values. Converting
+//! them to a scalar with fewer fractional bits adds up to one step of that
+//! scalar, and the rotation's multiplications round as usual; the tests
+//! check these bounds for `I16F16`.
+
+use crate::{VecInner, XYVec};
+use fixed::{traits::Fixed, types::I2F30};
+use std::f64::consts::FRAC_PI_2;
+
+/// `sin(x)` for `x` in `[0, π/2]`, by its Taylor series. Accurate to well
+/// below `f64` precision over that range.
+const fn taylor_sin(x: f64) -> f64 {
+    let (mut term, mut sum) = (x, x);
+    let mut n = 1;
+    while n < 12 {
+        term = -term * x * x / ((2 * n) * (2 * n + 1)) as f64;
+        sum += term;
+        n += 1;
+    }
+    sum
+}
+
+/// `cos(x)` for `x` in `[0, π/2]`, by its Taylor series.
+const fn taylor_cos(x: f64) -> f64 {
+    let (mut term, mut sum) = (1.0, 1.0);
+    let mut n = 1;
+    while n < 12 {
+        term = -term * x * x / ((2 * n - 1) * (2 * n)) as f64;
+        sum += term;
+        n += 1;
+    }
+    sum
+}
+
+/// `v` rounded to the nearest Q2.30 value.
+const fn to_q30(v: f64) -> I2F30 {
+    let scaled = v * (1u64 << 30) as f64;
+    let bits = if scaled < 0.0 {
+        (scaled - 0.5) as i32
+    } else {
+        (scaled + 0.5) as i32
+    };
+    I2F30::from_bits(bits)
+}
+
+/// Sines of the `N` angles `2π·i/N`. `N` must be a positive multiple of 4,
+/// so that every quarter turn lands on an entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SinLut<const N: usize> {
+    sin: [I2F30; N],
+}
+
+impl<const N: usize> SinLut<N> {
+    /// Builds the table; meant to initialize a `static` or `const`. Fails
+    /// to compile there if `N` is not a positive multiple of 4.
+    pub const fn new() -> Self {
+        assert!(
+            N > 0 && N.is_multiple_of(4),
+            "SinLut size must be a positive multiple of 4"
+        );
+        let mut sin = [I2F30::ZERO; N];
+        let quarter = N / 4;
+        let mut i = 0;
+        while i < N {
+            // Reduce to the first quadrant so the series stays accurate and
+            // the table is exactly symmetric.
+            let (quadrant, step) = (i / quarter, i % quarter);
+            let x = FRAC_PI_2 * step as f64 / quarter as f64;
+            let s = match quadrant {
+                0 => taylor_sin(x),
+                1 => taylor_cos(x),
+                2 => -taylor_sin(x),
+                _ => -taylor_cos(x),
+            };
+            sin[i] = to_q30(s);
+            i += 1;
+        }
+        SinLut { sin }
+    }
+
+    /// `(sin, cos)` of angle index `i`, which wraps around every `N`.
+    pub fn sin_cos(&self, i: usize) -> (I2F30, I2F30) {
+        (self.sin[i % N], self.sin[(i + N / 4) % N])
+    }
+}
+
+impl<const N: usize> Default for SinLut<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: VecInner + Fixed> XYVec<T> {
+    /// Unit vector at angle index `angle_index` of `lut`. `T` must be able
+    /// to hold `±1`.
+    pub fn from_angle_lut<const N: usize>(lut: &SinLut<N>, angle_index: usize) -> Self {
+        let (s, c) = lut.sin_cos(angle_index);
+        Self::new([T::from_num(c), T::from_num(s)])
+    }
+
+    /// `self` rotated counter-clockwise by angle index `angle_index` of
+    /// `lut`. `T` must be able to hold `±1`.
+    pub fn rotated_by_lut<const N: usize>(&self, lut: &SinLut<N>, angle_index: usize) -> Self {
+        let (s, c) = lut.sin_cos(angle_index);
+        let (s, c) = (T::from_num(s), T::from_num(c));
+        Self::new([self.x() * c - self.y() * s, self.x() * s + self.y() * c])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SinLut;
+    use crate::XYVec;
+    use fixed::types::{I16F16, I2F30};
+    use std::f64::consts::TAU;
+
+    static LUT: SinLut<1024> = SinLut::new();
+
+    #[test]
+    fn table_error_bound() {
+        let max_err = (0..1024)
+            .map(|i| {
+                let (s, c) = LUT.sin_cos(i);
+                let theta = TAU * i as f64 / 1024.0;
+                let err_s = (s.to_num::<f64>() - theta.sin()).abs();
+                let err_c = (c.to_num::<f64>() - theta.cos()).abs();
+                err_s.max(err_c)
+            })
+            .fold(0.0, f64::max);
+        // Half a step of Q2.30, plus slack for `f64` rounding.
+        assert!(max_err <= 2f64.powi(-31) + 1e-15, "{max_err}");
+        assert_eq!(LUT.sin_cos(256), (I2F30::ONE, I2F30::ZERO));
+        assert_eq!(LUT.sin_cos(512 + 1024), (I2F30::ZERO, -I2F30::ONE));
+    }
+
+    #[test]
+    fn rotation_error_bound_i16f16() {
+        let v = XYVec::new([I16F16::from_num(100.25), I16F16::from_num(-37.5)]);
+        let (vx, vy) = (v.x().to_num::<f64>(), v.y().to_num::<f64>());
+        let len = vx.hypot(vy);
+        for i in (0..1024).step_by(7) {
+            let theta = TAU * i as f64 / 1024.0;
+            let r = v.rotated_by_lut(&LUT, i);
+            let ex = vx * theta.cos() - vy * theta.sin();
+            let ey = vx * theta.sin() + vy * theta.cos();
+            // Each of sin and cos is off by at most one I16F16 step after
+            // truncation, and each of the four products truncates once.
+            let bound = 2.0 * len * 2f64.powi(-16) + 2.0 * 2f64.powi(-16);
+            assert!((r.x().to_num::<f64>() - ex).abs() <= bound, "index {i}");
+            assert!((r.y().to_num::<f64>() - ey).abs() <= bound, "index {i}");
+        }
+        let u = XYVec::<I16F16>::from_angle_lut(&LUT, 128);
+        let half_sqrt2 = I16F16::from_num(std::f64::consts::FRAC_1_SQRT_2);
+        assert!((u.x() - half_sqrt2).abs() <= I16F16::DELTA);
+        assert_eq!(u.x(), u.y());
+    }
+}
@@ -0,0 +1,94 @@
+//! Shorthand constructors.
+
+/// Builds an [`XYVec`](crate::XYVec) from two components, or from one
+/// value repeated: `xy!(v; 2)`.
+/// ```
+///     use xyzvec::{xy, XYVec};
+///
+///     assert_eq!(xy!(1.0, 2.0), XYVec::new([1.0, 2.0]));
+///     assert_eq!(xy!(0; 2), XYVec::new([0, 0]));
+/// ```
+#[macro_export]
+macro_rules! xy {
+    ($v:expr; $n:expr) => {
+        $crate::XYVec::new([$v; $n])
+    };
+    ($x:expr, $y:expr $(,)?) => {
+        $crate::XYVec::new([$x, $y])
+    };
+}
+
+/// Builds an [`XYZVec`](crate::XYZVec) from three components, or from one
+/// value repeated: `xyz!(v; 3)`.
+/// ```
+///     use xyzvec::{xyz, XYZVec};
+///
+///     assert_eq!(xyz!(1, 2, 3), XYZVec::new([1, 2, 3]));
+///     assert_eq!(xyz!(0.5f32; 3), XYZVec::new([0.5, 0.5, 0.5]));
+/// ```
+#[macro_export]
+macro_rules! xyz {
+    ($v:expr; $n:expr) => {
+        $crate::XYZVec::new([$v; $n])
+    };
+    ($x:expr, $y:expr, $z:expr $(,)?) => {
+        $crate::XYZVec::new([$x, $y, $z])
+    };
+}
+
+/// Like [`xy!`], but converts each component to the fixed-point type given
+/// first with `from_num`.
+/// ```
+///     use fixed::types::I16F16;
+///     use xyzvec::{xy_fixed, XYVec};
+///
+///     let v = xy_fixed!(I16F16; 1.5, -2);
+///     assert_eq!(v, XYVec::new([I16F16::from_num(1.5), I16F16::from_num(-2)]));
+///     assert_eq!(xy_fixed!(I16F16; 0.25; 2), XYVec::new([I16F16::from_num(0.25); 2]));
+/// ```
+#[macro_export]
+macro_rules! xy_fixed {
+    ($t:ty; $v:expr; $n:expr) => {
+        $crate::XYVec::new([<$t>::from_num($v); $n])
+    };
+    ($t:ty; $x:expr, $y:expr $(,)?) => {
+        $crate::XYVec::new([<$t>::from_num($x), <$t>::from_num($y)])
+    };
+}
+
+/// Like [`xyz!`], but converts each component to the fixed-point type
+/// given first with `from_num`.
+/// ```
+///     use fixed::types::I28F4;
+///     use xyzvec::{xyz_fixed, XYZVec};
+///
+///     let v = xyz_fixed!(I28F4; 1, 0.5, -3.25);
+///     assert_eq!(v.y(), I28F4::from_num(0.5));
+/// ```
+#[macro_export]
+macro_rules! xyz_fixed {
+    ($t:ty; $v:expr; $n:expr) => {
+        $crate::XYZVec::new([<$t>::from_num($v); $n])
+    };
+    ($t:ty; $x:expr, $y:expr, $z:expr $(,)?) => {
+        $crate::XYZVec::new([<$t>::from_num($x), <$t>::from_num($y), <$t>::from_num($z)])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{XYVec, XYZVec};
+    use fixed::types::I16F16;
+
+    #[test]
+    fn macros() {
+        assert_eq!(xy!(1.0f64, -2.0,), XYVec::new([1.0, -2.0]));
+        assert_eq!(xyz!(4; 3), XYZVec::new([4, 4, 4]));
+        let f = |v: f64| I16F16::from_num(v);
+        assert_eq!(
+            xyz_fixed!(I16F16; 1.5, 0, -0.25),
+            XYZVec::new([f(1.5), f(0.0), f(-0.25)])
+        );
+        assert_eq!(xy_fixed!(I16F16; -1; 2), XYVec::new([f(-1.0); 2]));
+    }
+}
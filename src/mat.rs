@@ -0,0 +1,818 @@
+//! Small square matrices, stored row-major as rows of [`XYVec`] or
+//! [`XYZVec`]. Matrices multiply column vectors on the right.
+
+use crate::{interp::clamp, VecFloat, VecInner, VecNum, Vector, XYVec, XYZVec};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Formatter},
+    ops::{Add, AddAssign, Mul, Sub, SubAssign},
+};
+
+/// 2x2 matrix.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Mat2<T> {
+    rows: [XYVec<T>; 2],
+}
+
+impl<T: VecInner> Mat2<T> {
+    /// Matrix from its entries, row by row.
+    pub fn new(rows: [[T; 2]; 2]) -> Self {
+        Self::from_rows(rows.map(XYVec::new))
+    }
+
+    pub fn from_rows(rows: [XYVec<T>; 2]) -> Self {
+        Self { rows }
+    }
+
+    pub fn from_cols(cols: [XYVec<T>; 2]) -> Self {
+        Self::from_rows(cols).transpose()
+    }
+
+    /// Row `i`. Panics if `i >= 2`.
+    pub fn row(&self, i: usize) -> XYVec<T> {
+        self.rows[i]
+    }
+
+    /// Column `j`. Panics if `j >= 2`.
+    pub fn col(&self, j: usize) -> XYVec<T> {
+        XYVec::new([self.get(0, j), self.get(1, j)])
+    }
+
+    /// Entry at row `i`, column `j`.
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self.rows[i].component(j)
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_rows([self.col(0), self.col(1)])
+    }
+
+    pub fn scale_by(&self, d: T) -> Self {
+        Self::from_rows(self.rows.map(|r| r.scale_by(d)))
+    }
+
+    pub fn trace(&self) -> T {
+        self.get(0, 0) + self.get(1, 1)
+    }
+
+    pub fn determinant(&self) -> T {
+        self.rows[0].cross_prod(self.rows[1])
+    }
+
+    /// Matrix-vector product `self * v`.
+    /// ```
+    ///     use xyzvec::{Mat2, XYVec};
+    ///
+    ///     let m = Mat2::new([[1, 2], [3, 4]]);
+    ///     assert_eq!(m.mul_vec(XYVec::new([1, 1])), XYVec::new([3, 7]));
+    /// ```
+    pub fn mul_vec(&self, v: XYVec<T>) -> XYVec<T> {
+        XYVec::new([self.rows[0].dot_prod(v), self.rows[1].dot_prod(v)])
+    }
+}
+
+impl<T: VecNum> Mat2<T> {
+    pub fn zeroes() -> Self {
+        Self::new([[T::zero(); 2]; 2])
+    }
+
+    pub fn identity() -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        Self::new([[one, zero], [zero, one]])
+    }
+}
+
+impl<T: VecFloat> Mat2<T> {
+    /// Population covariance of `points` about their mean. `None` if
+    /// `points` is empty.
+    /// ```
+    ///     use xyzvec::{Mat2, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let points = [XYVec::new([-1.0f64, 0.0]), XYVec::new([1.0, 0.0])];
+    ///     let cov = Mat2::covariance(&points).unwrap();
+    ///     assert_relative_eq!(cov.get(0, 0), 1.0);
+    ///     assert_relative_eq!(cov.get(1, 1), 0.0);
+    /// ```
+    pub fn covariance(points: &[XYVec<T>]) -> Option<Self> {
+        let n = T::from_f64(points.len() as f64);
+        let mean = points
+            .iter()
+            .fold(XYVec::splat(T::zero()), |acc, &p| acc + p)
+            .div_by(n);
+        let sum = points.iter().fold(Self::zeroes(), |acc, &p| {
+            let d = p - mean;
+            acc + d.outer(d)
+        });
+        (!points.is_empty()).then(|| sum.scale_by(T::one() / n))
+    }
+
+    /// Eigenvalues and unit eigenvectors of a symmetric matrix, with the
+    /// eigenvalues in decreasing order. For a covariance matrix these are
+    /// the variances along, and the directions of, the principal axes.
+    /// The eigenvectors form a right-handed basis. Only the upper triangle
+    /// of `self` is read.
+    /// ```
+    ///     use xyzvec::Mat2;
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let m = Mat2::new([[2.0f64, 1.0], [1.0, 2.0]]);
+    ///     let (values, axes) = m.principal_axes();
+    ///     assert_relative_eq!(values[0], 3.0);
+    ///     assert_relative_eq!(values[1], 1.0);
+    ///     assert_relative_eq!(axes[0].x(), 0.5f64.sqrt());
+    ///     assert_relative_eq!(axes[0].y(), 0.5f64.sqrt());
+    /// ```
+    pub fn principal_axes(&self) -> ([T; 2], [XYVec<T>; 2]) {
+        let two = T::one() + T::one();
+        let (a, b, c) = (self.get(0, 0), self.get(0, 1), self.get(1, 1));
+        let mid = (a + c) / two;
+        let half_diff = (a - c) / two;
+        let radius = (half_diff * half_diff + b * b).sqrt();
+        let theta = b.atan2(half_diff) / two;
+        let (sin, cos) = (theta.sin(), theta.cos());
+        (
+            [mid + radius, mid - radius],
+            [XYVec::new([cos, sin]), XYVec::new([-sin, cos])],
+        )
+    }
+}
+
+impl<T: VecInner> Add for Mat2<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::from_rows([self.rows[0] + other.rows[0], self.rows[1] + other.rows[1]])
+    }
+}
+
+impl<T: VecInner> AddAssign for Mat2<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: VecInner> Sub for Mat2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::from_rows([self.rows[0] - other.rows[0], self.rows[1] - other.rows[1]])
+    }
+}
+
+impl<T: VecInner> SubAssign for Mat2<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: VecInner> Mul for Mat2<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::from_cols([self.mul_vec(other.col(0)), self.mul_vec(other.col(1))])
+    }
+}
+
+impl<T: VecInner> Mul<XYVec<T>> for Mat2<T> {
+    type Output = XYVec<T>;
+
+    fn mul(self, v: XYVec<T>) -> XYVec<T> {
+        self.mul_vec(v)
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Mat2<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.rows.iter()).finish()
+    }
+}
+
+/// 3x3 matrix.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Mat3<T> {
+    rows: [XYZVec<T>; 3],
+}
+
+impl<T: VecInner> Mat3<T> {
+    /// Matrix from its entries, row by row.
+    pub fn new(rows: [[T; 3]; 3]) -> Self {
+        Self::from_rows(rows.map(XYZVec::new))
+    }
+
+    pub fn from_rows(rows: [XYZVec<T>; 3]) -> Self {
+        Self { rows }
+    }
+
+    pub fn from_cols(cols: [XYZVec<T>; 3]) -> Self {
+        Self::from_rows(cols).transpose()
+    }
+
+    /// Row `i`. Panics if `i >= 3`.
+    pub fn row(&self, i: usize) -> XYZVec<T> {
+        self.rows[i]
+    }
+
+    /// Column `j`. Panics if `j >= 3`.
+    pub fn col(&self, j: usize) -> XYZVec<T> {
+        XYZVec::new([self.get(0, j), self.get(1, j), self.get(2, j)])
+    }
+
+    /// Entry at row `i`, column `j`.
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self.rows[i].component(j)
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_rows([self.col(0), self.col(1), self.col(2)])
+    }
+
+    pub fn scale_by(&self, d: T) -> Self {
+        Self::from_rows(self.rows.map(|r| r.scale_by(d)))
+    }
+
+    pub fn trace(&self) -> T {
+        self.get(0, 0) + self.get(1, 1) + self.get(2, 2)
+    }
+
+    /// Determinant, as the scalar triple product of the rows.
+    pub fn determinant(&self) -> T {
+        self.rows[0].dot_prod(self.rows[1].cross(self.rows[2]))
+    }
+
+    /// Matrix-vector product `self * v`.
+    /// ```
+    ///     use xyzvec::{Mat3, XYZVec};
+    ///
+    ///     let m = Mat3::new([[1, 0, 0], [0, 2, 0], [1, 0, 3]]);
+    ///     assert_eq!(m.mul_vec(XYZVec::new([1, 1, 1])), XYZVec::new([1, 2, 4]));
+    /// ```
+    pub fn mul_vec(&self, v: XYZVec<T>) -> XYZVec<T> {
+        XYZVec::new([
+            self.rows[0].dot_prod(v),
+            self.rows[1].dot_prod(v),
+            self.rows[2].dot_prod(v),
+        ])
+    }
+}
+
+impl<T: VecNum> Mat3<T> {
+    pub fn zeroes() -> Self {
+        Self::new([[T::zero(); 3]; 3])
+    }
+
+    pub fn identity() -> Self {
+        let (zero, one) = (T::zero(), T::one());
+        Self::new([[one, zero, zero], [zero, one, zero], [zero, zero, one]])
+    }
+
+    /// Counterclockwise rotation by `angle` radians about `axis`, looking
+    /// down the axis toward the origin. `axis` need not be normalized; a
+    /// zero `axis` gives the identity. The trigonometry is done in `f64`.
+    /// ```
+    ///     use xyzvec::{Mat3, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let r = Mat3::from_axis_angle(XYZVec::new([0.0f64, 0.0, 2.0]), std::f64::consts::FRAC_PI_2);
+    ///     let v = r * XYZVec::new([1.0, 0.0, 0.0]);
+    ///     assert_relative_eq!(v.x(), 0.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(v.y(), 1.0, epsilon = 1e-12);
+    /// ```
+    pub fn from_axis_angle(axis: XYZVec<T>, angle: T) -> Self {
+        let [x, y, z] = [axis.x(), axis.y(), axis.z()].map(|c| c.to_f64());
+        let len = (x * x + y * y + z * z).sqrt();
+        if len == 0.0 {
+            return Self::identity();
+        }
+        let (x, y, z) = (x / len, y / len, z / len);
+        let angle = angle.to_f64();
+        let (s, c) = (angle.sin(), angle.cos());
+        let t = 1.0 - c;
+        Self::new(
+            [
+                [t * x * x + c, t * x * y - s * z, t * x * z + s * y],
+                [t * x * y + s * z, t * y * y + c, t * y * z - s * x],
+                [t * x * z - s * y, t * y * z + s * x, t * z * z + c],
+            ]
+            .map(|row| row.map(T::from_f64)),
+        )
+    }
+}
+
+impl<T: VecFloat> Mat3<T> {
+    /// Population covariance of `points` about their mean. `None` if
+    /// `points` is empty.
+    pub fn covariance(points: &[XYZVec<T>]) -> Option<Self> {
+        let n = T::from_f64(points.len() as f64);
+        let mean = points
+            .iter()
+            .fold(XYZVec::splat(T::zero()), |acc, &p| acc + p)
+            .div_by(n);
+        let sum = points.iter().fold(Self::zeroes(), |acc, &p| {
+            let d = p - mean;
+            acc + d.outer(d)
+        });
+        (!points.is_empty()).then(|| sum.scale_by(T::one() / n))
+    }
+
+    /// Eigenvalues and unit eigenvectors of a symmetric matrix, with the
+    /// eigenvalues in decreasing order. For a covariance matrix these are
+    /// the variances along, and the directions of, the principal axes.
+    /// The eigenvectors form a right-handed basis. Only the upper triangle
+    /// of `self` is read.
+    ///
+    /// The eigenvalues come from the closed-form trigonometric solution of
+    /// the characteristic cubic. The eigenvector of whichever extreme
+    /// eigenvalue is better separated is found directly; the other two are
+    /// found by solving the 2x2 problem in the plane perpendicular to it,
+    /// which stays well-conditioned when they repeat.
+    /// ```
+    ///     use xyzvec::Mat3;
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let m = Mat3::new([[2.0f64, 0.0, 0.0], [0.0, 3.0, 4.0], [0.0, 4.0, 9.0]]);
+    ///     let (values, axes) = m.principal_axes();
+    ///     assert_relative_eq!(values[0], 11.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(values[1], 2.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(values[2], 1.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(axes[1].x().abs(), 1.0, epsilon = 1e-12);
+    /// ```
+    pub fn principal_axes(&self) -> ([T; 3], [XYZVec<T>; 3]) {
+        let (zero, one) = (T::zero(), T::one());
+        let two = one + one;
+        let three = two + one;
+        let sym = Self::new([
+            [self.get(0, 0), self.get(0, 1), self.get(0, 2)],
+            [self.get(0, 1), self.get(1, 1), self.get(1, 2)],
+            [self.get(0, 2), self.get(1, 2), self.get(2, 2)],
+        ]);
+        let off_diag = sym.get(0, 1) * sym.get(0, 1)
+            + sym.get(0, 2) * sym.get(0, 2)
+            + sym.get(1, 2) * sym.get(1, 2);
+        if off_diag == zero {
+            let mut pairs = [
+                (sym.get(0, 0), XYZVec::new([one, zero, zero])),
+                (sym.get(1, 1), XYZVec::new([zero, one, zero])),
+                (sym.get(2, 2), XYZVec::new([zero, zero, one])),
+            ];
+            pairs.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+            let [(l0, v0), (l1, v1), (l2, _)] = pairs;
+            return ([l0, l1, l2], [v0, v1, v0.cross(v1)]);
+        }
+
+        let q = sym.trace() / three;
+        let shifted = sym - Self::identity().scale_by(q);
+        let p = ((shifted.get(0, 0) * shifted.get(0, 0)
+            + shifted.get(1, 1) * shifted.get(1, 1)
+            + shifted.get(2, 2) * shifted.get(2, 2)
+            + two * off_diag)
+            / (three + three))
+            .sqrt();
+        let r = clamp(shifted.scale_by(one / p).determinant() / two, -one, one);
+        let phi = r.acos() / three;
+        let third_turn = T::from_f64(2.0 * std::f64::consts::FRAC_PI_3);
+        let largest = q + two * p * phi.cos();
+        let smallest = q + two * p * (phi + third_turn).cos();
+
+        let largest_is_isolated = largest - q >= q - smallest;
+        let isolated = if largest_is_isolated {
+            largest
+        } else {
+            smallest
+        };
+        let axis = sym.eigenvector_for(isolated);
+        // Solve the remaining pair in the plane perpendicular to `axis`.
+        let (u, w) = axis.any_orthonormal_pair();
+        let (su, sw) = (sym.mul_vec(u), sym.mul_vec(w));
+        let (values, vectors) = Mat2::new([
+            [u.dot_prod(su), u.dot_prod(sw)],
+            [u.dot_prod(sw), w.dot_prod(sw)],
+        ])
+        .principal_axes();
+        let lift = |v: XYVec<T>| u.scale_by(v.x()) + w.scale_by(v.y());
+        let (first, second) = (lift(vectors[0]), lift(vectors[1]));
+        if largest_is_isolated {
+            (
+                [isolated, values[0], values[1]],
+                [axis, first, axis.cross(first)],
+            )
+        } else {
+            (
+                [values[0], values[1], isolated],
+                [first, second, first.cross(second)],
+            )
+        }
+    }
+
+    /// Unit vector in the null space of `self - lambda * I`, taken from the
+    /// largest cross product of two of its rows.
+    fn eigenvector_for(&self, lambda: T) -> XYZVec<T> {
+        let m = *self - Self::identity().scale_by(lambda);
+        let candidates = [
+            m.row(0).cross(m.row(1)),
+            m.row(0).cross(m.row(2)),
+            m.row(1).cross(m.row(2)),
+        ];
+        let best = candidates
+            .into_iter()
+            .max_by(|a, b| {
+                a.l2_norm_sqd()
+                    .partial_cmp(&b.l2_norm_sqd())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap_or(candidates[0]);
+        let len_sqd = best.l2_norm_sqd();
+        if len_sqd == T::zero() {
+            return XYZVec::new([T::one(), T::zero(), T::zero()]);
+        }
+        best.div_by(len_sqd.sqrt())
+    }
+}
+
+impl<T: VecInner> Add for Mat3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::from_rows([
+            self.rows[0] + other.rows[0],
+            self.rows[1] + other.rows[1],
+            self.rows[2] + other.rows[2],
+        ])
+    }
+}
+
+impl<T: VecInner> AddAssign for Mat3<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: VecInner> Sub for Mat3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::from_rows([
+            self.rows[0] - other.rows[0],
+            self.rows[1] - other.rows[1],
+            self.rows[2] - other.rows[2],
+        ])
+    }
+}
+
+impl<T: VecInner> SubAssign for Mat3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: VecInner> Mul for Mat3<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::from_cols([
+            self.mul_vec(other.col(0)),
+            self.mul_vec(other.col(1)),
+            self.mul_vec(other.col(2)),
+        ])
+    }
+}
+
+impl<T: VecInner> Mul<XYZVec<T>> for Mat3<T> {
+    type Output = XYZVec<T>;
+
+    fn mul(self, v: XYZVec<T>) -> XYZVec<T> {
+        self.mul_vec(v)
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Mat3<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.rows.iter()).finish()
+    }
+}
+
+/// 4x4 matrix, mostly for affine and projective transforms of 3D points.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Mat4<T> {
+    rows: [[T; 4]; 4],
+}
+
+impl<T: VecInner> Mat4<T> {
+    /// Matrix from its entries, row by row.
+    pub fn new(rows: [[T; 4]; 4]) -> Self {
+        Self { rows }
+    }
+
+    /// Entry at row `i`, column `j`.
+    pub fn get(&self, i: usize, j: usize) -> T {
+        self.rows[i][j]
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| self.rows[j][i])
+        }))
+    }
+
+    pub fn scale_by(&self, d: T) -> Self {
+        Self::new(self.rows.map(|row| row.map(|v| v * d)))
+    }
+
+    fn minors(&self) -> ([T; 6], [T; 6]) {
+        let m = &self.rows;
+        let s = [
+            m[0][0] * m[1][1] - m[1][0] * m[0][1],
+            m[0][0] * m[1][2] - m[1][0] * m[0][2],
+            m[0][0] * m[1][3] - m[1][0] * m[0][3],
+            m[0][1] * m[1][2] - m[1][1] * m[0][2],
+            m[0][1] * m[1][3] - m[1][1] * m[0][3],
+            m[0][2] * m[1][3] - m[1][2] * m[0][3],
+        ];
+        let c = [
+            m[2][0] * m[3][1] - m[3][0] * m[2][1],
+            m[2][0] * m[3][2] - m[3][0] * m[2][2],
+            m[2][0] * m[3][3] - m[3][0] * m[2][3],
+            m[2][1] * m[3][2] - m[3][1] * m[2][2],
+            m[2][1] * m[3][3] - m[3][1] * m[2][3],
+            m[2][2] * m[3][3] - m[3][2] * m[2][3],
+        ];
+        (s, c)
+    }
+
+    /// Determinant, by Laplace expansion over the 2x2 minors of the top
+    /// and bottom row pairs.
+    pub fn determinant(&self) -> T {
+        let (s, c) = self.minors();
+        s[0] * c[5] - s[1] * c[4] + s[2] * c[3] + s[3] * c[2] - s[4] * c[1] + s[5] * c[0]
+    }
+}
+
+impl<T: VecNum> Mat4<T> {
+    pub fn zeroes() -> Self {
+        Self::new([[T::zero(); 4]; 4])
+    }
+
+    pub fn identity() -> Self {
+        Self::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| if i == j { T::one() } else { T::zero() })
+        }))
+    }
+
+    /// Inverse matrix, or `None` if `self` is singular.
+    /// ```
+    ///     use xyzvec::{Mat3, Mat4, XYZVec};
+    ///
+    ///     let m = Mat4::from_affine(Mat3::new([[2.0f64, 0.0, 0.0], [0.0, 4.0, 0.0], [0.0, 0.0, 1.0]]), XYZVec::new([1.0, 2.0, 3.0]));
+    ///     assert_eq!(m * m.inverse().unwrap(), Mat4::identity());
+    ///     assert_eq!(Mat4::<f64>::zeroes().inverse(), None);
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        let det = self.determinant();
+        if det == T::zero() {
+            return None;
+        }
+        let (s, c) = self.minors();
+        let m = &self.rows;
+        let adjugate = [
+            [
+                m[1][1] * c[5] - m[1][2] * c[4] + m[1][3] * c[3],
+                -m[0][1] * c[5] + m[0][2] * c[4] - m[0][3] * c[3],
+                m[3][1] * s[5] - m[3][2] * s[4] + m[3][3] * s[3],
+                -m[2][1] * s[5] + m[2][2] * s[4] - m[2][3] * s[3],
+            ],
+            [
+                -m[1][0] * c[5] + m[1][2] * c[2] - m[1][3] * c[1],
+                m[0][0] * c[5] - m[0][2] * c[2] + m[0][3] * c[1],
+                -m[3][0] * s[5] + m[3][2] * s[2] - m[3][3] * s[1],
+                m[2][0] * s[5] - m[2][2] * s[2] + m[2][3] * s[1],
+            ],
+            [
+                m[1][0] * c[4] - m[1][1] * c[2] + m[1][3] * c[0],
+                -m[0][0] * c[4] + m[0][1] * c[2] - m[0][3] * c[0],
+                m[3][0] * s[4] - m[3][1] * s[2] + m[3][3] * s[0],
+                -m[2][0] * s[4] + m[2][1] * s[2] - m[2][3] * s[0],
+            ],
+            [
+                -m[1][0] * c[3] + m[1][1] * c[1] - m[1][2] * c[0],
+                m[0][0] * c[3] - m[0][1] * c[1] + m[0][2] * c[0],
+                -m[3][0] * s[3] + m[3][1] * s[1] - m[3][2] * s[0],
+                m[2][0] * s[3] - m[2][1] * s[1] + m[2][2] * s[0],
+            ],
+        ];
+        Some(Self::new(adjugate.map(|row| row.map(|v| v / det))))
+    }
+
+    /// Affine transform applying `linear` and then `translation`, with
+    /// `[0, 0, 0, 1]` as the last row.
+    pub fn from_affine(linear: Mat3<T>, translation: XYZVec<T>) -> Self {
+        let mut m = Self::identity();
+        for i in 0..3 {
+            for j in 0..3 {
+                m.rows[i][j] = linear.get(i, j);
+            }
+            m.rows[i][3] = translation.component(i);
+        }
+        m
+    }
+}
+
+impl<T: VecInner> Mul for Mat4<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                (1..4).fold(self.rows[i][0] * other.rows[0][j], |acc, k| {
+                    acc + self.rows[i][k] * other.rows[k][j]
+                })
+            })
+        }))
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Mat4<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.rows.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Mat2, Mat3, Mat4, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I16F16;
+
+    #[test]
+    fn mat2_ops_i32() {
+        let a = Mat2::new([[1, 2], [3, 4]]);
+        let b = Mat2::new([[0, 1], [1, 0]]);
+        assert_eq!(a * b, Mat2::new([[2, 1], [4, 3]]));
+        assert_eq!(a * Mat2::identity(), a);
+        assert_eq!(a.transpose(), Mat2::new([[1, 3], [2, 4]]));
+        assert_eq!(a.determinant(), -2);
+        assert_eq!(a.trace(), 5);
+        assert_eq!(a - a, Mat2::zeroes());
+        assert_eq!(a.col(1), XYVec::new([2, 4]));
+        assert_eq!(Mat2::from_cols([a.col(0), a.col(1)]), a);
+    }
+
+    #[test]
+    fn mat3_ops_f64() {
+        let a = Mat3::new([[2.0f64, 0.0, 1.0], [1.0, 3.0, 0.0], [0.0, 1.0, 4.0]]);
+        assert_relative_eq!(a.determinant(), 25.0);
+        assert_relative_eq!(a.trace(), 9.0);
+        assert_eq!(a * Mat3::identity(), a);
+        assert_eq!(Mat3::identity() * a, a);
+        let v = XYZVec::new([1.0, -1.0, 2.0]);
+        assert_eq!((a * a) * v, a * (a * v));
+        assert_eq!((a * a).transpose(), a.transpose() * a.transpose());
+    }
+
+    #[test]
+    fn axis_angle_zero_axis() {
+        let r = Mat3::from_axis_angle(XYZVec::new([0.0f64; 3]), 1.0);
+        assert_eq!(r, Mat3::identity());
+        let zero = I16F16::ZERO;
+        let r = Mat3::from_axis_angle(XYZVec::new([zero; 3]), I16F16::from_num(1));
+        assert_eq!(r, Mat3::identity());
+    }
+
+    #[test]
+    fn mat4_ops_i32() {
+        let m = Mat4::from_affine(
+            Mat3::new([[0, -1, 0], [1, 0, 0], [0, 0, 1]]),
+            XYZVec::new([1, 2, 3]),
+        );
+        assert_eq!(m.get(0, 1), -1);
+        assert_eq!(m.get(2, 3), 3);
+        assert_eq!(m.get(3, 3), 1);
+        assert_eq!(m * Mat4::identity(), m);
+        assert_eq!(Mat4::identity() * m, m);
+        assert_eq!(m.transpose().get(3, 0), 1);
+        assert_eq!((m * m).get(0, 3), -1);
+        assert_eq!(m.scale_by(0), Mat4::zeroes());
+        assert_eq!(m.determinant(), 1);
+        assert_eq!(Mat4::identity().scale_by(2).determinant(), 16);
+    }
+
+    #[test]
+    fn mat4_inverse_f64() {
+        let m = Mat4::new([
+            [2.0f64, 1.0, 0.0, -1.0],
+            [0.5, 3.0, 1.0, 0.0],
+            [1.0, -2.0, 4.0, 2.0],
+            [0.0, 1.0, 0.5, 1.5],
+        ]);
+        let inv = m.inverse().unwrap();
+        let id = m * inv;
+        for i in 0..4 {
+            for j in 0..4 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert_relative_eq!(id.get(i, j), expected, epsilon = 1e-12);
+            }
+        }
+        assert_relative_eq!(m.determinant() * inv.determinant(), 1.0, epsilon = 1e-12);
+        let mut singular = m;
+        singular.rows[3] = singular.rows[0];
+        assert_eq!(singular.determinant(), 0.0);
+        assert!(singular.inverse().is_none());
+    }
+
+    fn assert_eigen3(m: Mat3<f64>, eps: f64) {
+        let (values, axes) = m.principal_axes();
+        assert!(values[0] >= values[1] && values[1] >= values[2]);
+        assert_relative_eq!(values.iter().sum::<f64>(), m.trace(), epsilon = eps);
+        for (l, v) in values.iter().zip(axes.iter()) {
+            let mv = m * *v;
+            assert_relative_eq!(mv.x(), v.x() * l, epsilon = eps);
+            assert_relative_eq!(mv.y(), v.y() * l, epsilon = eps);
+            assert_relative_eq!(mv.z(), v.z() * l, epsilon = eps);
+        }
+        let basis = Mat3::from_cols(axes);
+        let gram = basis.transpose() * basis;
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert_relative_eq!(gram.get(i, j), expected, epsilon = eps);
+            }
+        }
+        assert_relative_eq!(basis.determinant(), 1.0, epsilon = eps);
+    }
+
+    #[test]
+    fn principal_axes_3d_f64() {
+        assert_eigen3(
+            Mat3::new([[4.0, 1.0, -2.0], [1.0, 3.0, 0.5], [-2.0, 0.5, 1.0]]),
+            1e-12,
+        );
+        assert_eigen3(
+            Mat3::new([[1.0, 0.0, 0.0], [0.0, 5.0, 0.0], [0.0, 0.0, 3.0]]),
+            0.0,
+        );
+        assert_eigen3(Mat3::identity().scale_by(2.0), 0.0);
+        // Repeated eigenvalues: a rank-one update of the identity.
+        let u = XYZVec::new([1.0, 2.0, 2.0]).div_by(3.0);
+        assert_eigen3(Mat3::identity() + u.outer(u).scale_by(4.0), 1e-12);
+        assert_eigen3(Mat3::identity() - u.outer(u).scale_by(0.5), 1e-12);
+        // Nearly repeated.
+        assert_eigen3(Mat3::identity() + u.outer(u).scale_by(1e-9), 1e-12);
+    }
+
+    #[test]
+    fn principal_axes_2d_f32() {
+        let (values, axes) = Mat2::new([[1.0f32, 0.0], [0.0, 4.0]]).principal_axes();
+        assert_relative_eq!(values[0], 4.0);
+        assert_relative_eq!(values[1], 1.0);
+        assert_relative_eq!(axes[0].y(), 1.0);
+        assert_relative_eq!(axes[1].x(), -1.0);
+        let (values, _) = Mat2::new([[3.0f32, 0.0], [0.0, 3.0]]).principal_axes();
+        assert_eq!(values, [3.0, 3.0]);
+    }
+
+    #[test]
+    fn covariance_pca_f64() {
+        // Points spread along (1, 1, 0), with a little spread along z.
+        let dir = XYZVec::new([1.0f64, 1.0, 0.0]).div_by(2.0f64.sqrt());
+        let points: Vec<XYZVec<f64>> = (-5..=5)
+            .flat_map(|i| {
+                [-1.0, 1.0].map(|s| {
+                    XYZVec::new([3.0, -1.0, 2.0])
+                        + dir.scale_by(i as f64)
+                        + XYZVec::new([0.0, 0.0, 0.1 * s])
+                })
+            })
+            .collect();
+        let cov = Mat3::covariance(&points).unwrap();
+        assert_eq!(cov, cov.transpose());
+        let (values, axes) = cov.principal_axes();
+        assert_relative_eq!(values[0], 10.0, epsilon = 1e-12);
+        assert_relative_eq!(values[1], 0.01, epsilon = 1e-12);
+        assert_relative_eq!(values[2], 0.0, epsilon = 1e-12);
+        assert_relative_eq!(axes[0].dot_prod(dir).abs(), 1.0, epsilon = 1e-12);
+        assert_relative_eq!(axes[1].z().abs(), 1.0, epsilon = 1e-12);
+        assert!(Mat3::<f64>::covariance(&[]).is_none());
+        assert!(Mat2::<f32>::covariance(&[]).is_none());
+    }
+
+    #[test]
+    fn outer_f32() {
+        let u = XYVec::new([1.0f32, 2.0]);
+        let v = XYVec::new([3.0f32, -1.0]);
+        let m = u.outer(v);
+        assert_eq!(m, Mat2::new([[3.0, -1.0], [6.0, -2.0]]));
+        // (u v^T) w = u (v . w)
+        let w = XYVec::new([0.5f32, 4.0]);
+        assert_eq!(m * w, u.scale_by(v.dot_prod(w)));
+        assert_eq!(v.outer(u), m.transpose());
+    }
+
+    #[test]
+    fn outer_3d_fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let u = XYZVec::new([f(1.0), f(0.5), f(-2.0)]);
+        let m = u.outer(u);
+        assert_eq!(m, m.transpose());
+        assert_eq!(m.trace(), u.l2_norm_sqd());
+        assert_eq!(m.row(2), u.scale_by(f(-2.0)));
+    }
+}
@@ -0,0 +1,214 @@
+//! Morton (Z-order) keys: the bits of each coordinate interleaved, so that
+//! sorting by key keeps nearby points close together.
+//!
+//! Signed coordinates are offset so that keys sort in the same order as the
+//! coordinates along each axis.
+
+use crate::{Aabb, VecNum, Vector, XYVec, XYZVec};
+
+/// Spreads the low 32 bits of `v` to the even bits of the result.
+fn part_1_by_1(v: u32) -> u64 {
+    let mut x = v as u64;
+    x = (x | (x << 16)) & 0x0000_FFFF_0000_FFFF;
+    x = (x | (x << 8)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x << 4)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x << 2)) & 0x3333_3333_3333_3333;
+    (x | (x << 1)) & 0x5555_5555_5555_5555
+}
+
+/// Inverse of [`part_1_by_1`].
+fn compact_1_by_1(v: u64) -> u32 {
+    let mut x = v & 0x5555_5555_5555_5555;
+    x = (x | (x >> 1)) & 0x3333_3333_3333_3333;
+    x = (x | (x >> 2)) & 0x0F0F_0F0F_0F0F_0F0F;
+    x = (x | (x >> 4)) & 0x00FF_00FF_00FF_00FF;
+    x = (x | (x >> 8)) & 0x0000_FFFF_0000_FFFF;
+    ((x | (x >> 16)) & 0xFFFF_FFFF) as u32
+}
+
+/// Spreads the low 21 bits of `v` to every third bit of the result.
+fn part_1_by_2(v: u32) -> u64 {
+    let mut x = v as u64 & 0x1F_FFFF;
+    x = (x | (x << 32)) & 0x001F_0000_0000_FFFF;
+    x = (x | (x << 16)) & 0x001F_0000_FF00_00FF;
+    x = (x | (x << 8)) & 0x100F_00F0_0F00_F00F;
+    x = (x | (x << 4)) & 0x10C3_0C30_C30C_30C3;
+    (x | (x << 2)) & 0x1249_2492_4924_9249
+}
+
+/// Inverse of [`part_1_by_2`].
+fn compact_1_by_2(v: u64) -> u32 {
+    let mut x = v & 0x1249_2492_4924_9249;
+    x = (x | (x >> 2)) & 0x10C3_0C30_C30C_30C3;
+    x = (x | (x >> 4)) & 0x100F_00F0_0F00_F00F;
+    x = (x | (x >> 8)) & 0x001F_0000_FF00_00FF;
+    x = (x | (x >> 16)) & 0x001F_0000_0000_FFFF;
+    ((x | (x >> 32)) & 0x1F_FFFF) as u32
+}
+
+/// Bits per axis in a 3D key.
+const BITS_3D: u32 = 21;
+const OFFSET_3D: i32 = 1 << (BITS_3D - 1);
+
+impl XYVec<i32> {
+    /// Morton key with `x` in the even bits and `y` in the odd bits.
+    /// ```
+    ///     use xyzvec::XYVec;
+    ///
+    ///     let v = XYVec::new([-3, 7]);
+    ///     assert_eq!(XYVec::morton_decode(v.morton_encode()), v);
+    ///     assert!(XYVec::new([0, 0]).morton_encode() < XYVec::new([1, 0]).morton_encode());
+    ///     assert!(XYVec::new([-1, 0]).morton_encode() < XYVec::new([0, 0]).morton_encode());
+    /// ```
+    pub fn morton_encode(&self) -> u64 {
+        let flip = |c: i32| (c as u32) ^ 0x8000_0000;
+        part_1_by_1(flip(self.x())) | (part_1_by_1(flip(self.y())) << 1)
+    }
+
+    pub fn morton_decode(code: u64) -> Self {
+        let unflip = |c: u32| (c ^ 0x8000_0000) as i32;
+        Self::new([
+            unflip(compact_1_by_1(code)),
+            unflip(compact_1_by_1(code >> 1)),
+        ])
+    }
+}
+
+impl XYZVec<i32> {
+    /// Morton key with 21 bits per axis, `x` lowest. Components must lie in
+    /// `[-2^20, 2^20)`; others wrap around.
+    /// ```
+    ///     use xyzvec::XYZVec;
+    ///
+    ///     let v = XYZVec::new([-5, 0, 1 << 19]);
+    ///     assert_eq!(XYZVec::morton_decode(v.morton_encode()), v);
+    /// ```
+    pub fn morton_encode(&self) -> u64 {
+        let shift = |c: i32| {
+            debug_assert!((-OFFSET_3D..OFFSET_3D).contains(&c));
+            c.wrapping_add(OFFSET_3D) as u32
+        };
+        part_1_by_2(shift(self.x()))
+            | (part_1_by_2(shift(self.y())) << 1)
+            | (part_1_by_2(shift(self.z())) << 2)
+    }
+
+    pub fn morton_decode(code: u64) -> Self {
+        let unshift = |c: u32| c as i32 - OFFSET_3D;
+        Self::new([
+            unshift(compact_1_by_2(code)),
+            unshift(compact_1_by_2(code >> 1)),
+            unshift(compact_1_by_2(code >> 2)),
+        ])
+    }
+}
+
+/// Morton key of `p` quantized to a grid spanning `bounds`, with 32 bits
+/// per axis in 2D and 21 in 3D. Points outside `bounds` are clamped to it.
+/// Works for any scalar, including floats and fixed point.
+/// ```
+///     use xyzvec::{morton::morton_encode_quantized, Aabb2, XYVec};
+///
+///     let bounds = Aabb2::new(XYVec::new([0.0f64, 0.0]), XYVec::new([1.0, 1.0]));
+///     let key = |x, y| morton_encode_quantized(XYVec::new([x, y]), &bounds);
+///     assert_eq!(key(0.0, 0.0), 0);
+///     assert_eq!(key(1.0, 1.0), u64::MAX);
+///     assert!(key(0.1, 0.1) < key(0.9, 0.1));
+/// ```
+pub fn morton_encode_quantized<V>(p: V, bounds: &Aabb<V>) -> u64
+where
+    V: Vector,
+    V::Scalar: VecNum,
+{
+    let bits = (64 / V::DIM).min(32) as u32;
+    let max_cell = ((1u64 << bits) - 1) as f64;
+    let p = bounds.clamp(p);
+    (0..V::DIM).fold(0, |code, i| {
+        let lo = bounds.min.component(i).to_f64();
+        let extent = bounds.max.component(i).to_f64() - lo;
+        let t = if extent > 0.0 {
+            (p.component(i).to_f64() - lo) / extent
+        } else {
+            0.0
+        };
+        let cell = (t * max_cell).round().clamp(0.0, max_cell) as u32;
+        let spread = if V::DIM == 2 {
+            part_1_by_1(cell)
+        } else {
+            part_1_by_2(cell)
+        };
+        code | (spread << i)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Aabb3, XYZVec};
+    use fixed::types::I16F16;
+
+    #[test]
+    fn bit_layout_2d() {
+        assert_eq!(part_1_by_1(0b1011), 0b1000101);
+        assert_eq!(part_1_by_1(u32::MAX), 0x5555_5555_5555_5555);
+        for v in [0, 1, 0xDEAD_BEEF, u32::MAX] {
+            assert_eq!(compact_1_by_1(part_1_by_1(v)), v);
+        }
+        let o = 0x8000_0000u32 as i32;
+        assert_eq!(XYVec::new([o, o]).morton_encode(), 0);
+        assert_eq!(XYVec::new([0, 0]).morton_encode(), 0b11 << 62);
+    }
+
+    #[test]
+    fn bit_layout_3d() {
+        assert_eq!(part_1_by_2(0b111), 0b1001001);
+        assert_eq!(part_1_by_2(0x1F_FFFF), 0x1249_2492_4924_9249);
+        for v in [0, 1, 0x15_5555, 0x1F_FFFF] {
+            assert_eq!(compact_1_by_2(part_1_by_2(v)), v);
+        }
+        let lo = -(1 << 20);
+        assert_eq!(XYZVec::new([lo, lo, lo]).morton_encode(), 0);
+        assert_eq!(XYZVec::new([lo + 1, lo, lo]).morton_encode(), 1);
+        assert_eq!(XYZVec::new([lo, lo, lo + 1]).morton_encode(), 4);
+    }
+
+    #[test]
+    fn round_trip_i32() {
+        for x in [-70000, -1, 0, 1, 12345, i32::MIN, i32::MAX] {
+            for y in [-2, 0, 99999, i32::MIN, i32::MAX] {
+                let v = XYVec::new([x, y]);
+                assert_eq!(XYVec::morton_decode(v.morton_encode()), v);
+            }
+        }
+        for c in [-(1 << 20), -1, 0, 7, (1 << 20) - 1] {
+            let v = XYZVec::new([c, -c / 2, c / 3]);
+            assert_eq!(XYZVec::morton_decode(v.morton_encode()), v);
+        }
+    }
+
+    #[test]
+    fn quantized_fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let bounds = Aabb3::new(
+            XYZVec::new([f(-1.0), f(-1.0), f(-1.0)]),
+            XYZVec::new([f(1.0), f(1.0), f(1.0)]),
+        );
+        assert_eq!(morton_encode_quantized(bounds.min, &bounds), 0);
+        assert_eq!(
+            morton_encode_quantized(bounds.max, &bounds),
+            (1u64 << 63) - 1
+        );
+        let outside = XYZVec::new([f(5.0), f(5.0), f(5.0)]);
+        assert_eq!(
+            morton_encode_quantized(outside, &bounds),
+            morton_encode_quantized(bounds.max, &bounds)
+        );
+        // A flat box puts everything in the first cell on that axis.
+        let flat = Aabb3::new(
+            XYZVec::new([f(0.0), f(0.0), f(0.0)]),
+            XYZVec::new([f(1.0), f(1.0), f(0.0)]),
+        );
+        let key = morton_encode_quantized(XYZVec::new([f(0.0), f(0.0), f(0.0)]), &flat);
+        assert_eq!(key, 0);
+    }
+}
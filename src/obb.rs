@@ -0,0 +1,344 @@
+//! Oriented bounding boxes: boxes with their own orthonormal axes, which fit
+//! elongated or rotated point sets much more tightly than an [`Aabb`].
+//!
+//! [`Aabb`]: crate::Aabb
+
+use crate::{
+    hull::convex_hull, CrossSign, Mat3, VecFloat, VecInner, VecNum, Vector, XYVec, XYZVec,
+};
+use std::fmt::{self, Formatter};
+
+/// Rectangle centered on `center`, extending `half_extents[i]` along the
+/// unit vector `axes[i]` in both directions.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Obb2<T> {
+    pub center: XYVec<T>,
+    pub axes: [XYVec<T>; 2],
+    pub half_extents: [T; 2],
+}
+
+/// Box centered on `center`, extending `half_extents[i]` along the unit
+/// vector `axes[i]` in both directions. The axes form a right-handed basis.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Obb3<T> {
+    pub center: XYZVec<T>,
+    pub axes: [XYZVec<T>; 3],
+    pub half_extents: [T; 3],
+}
+
+/// Whether the projections of two boxes onto `axis` are disjoint.
+fn separated_on<V: Vector>(
+    axis: V,
+    offset: V,
+    (a_axes, a_half): (&[V], &[V::Scalar]),
+    (b_axes, b_half): (&[V], &[V::Scalar]),
+) -> bool
+where
+    V::Scalar: VecFloat,
+{
+    let radius = |axes: &[V], half: &[V::Scalar]| {
+        axes.iter()
+            .zip(half)
+            .fold(V::Scalar::zero(), |acc, (a, &h)| {
+                acc + h * a.dot_prod(axis).abs()
+            })
+    };
+    offset.dot_prod(axis).abs() > radius(a_axes, a_half) + radius(b_axes, b_half)
+}
+
+impl<T: VecFloat> Obb2<T> {
+    pub fn new(center: XYVec<T>, axes: [XYVec<T>; 2], half_extents: [T; 2]) -> Self {
+        Self {
+            center,
+            axes,
+            half_extents,
+        }
+    }
+
+    /// Whether `p` lies inside the box or on its boundary.
+    pub fn contains_point(&self, p: XYVec<T>) -> bool {
+        let d = p - self.center;
+        (0..2).all(|i| d.dot_prod(self.axes[i]).abs() <= self.half_extents[i])
+    }
+
+    /// Whether the boxes overlap or touch, by the separating axis test.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let offset = other.center - self.center;
+        let a = (&self.axes[..], &self.half_extents[..]);
+        let b = (&other.axes[..], &other.half_extents[..]);
+        !self
+            .axes
+            .iter()
+            .chain(&other.axes)
+            .any(|&axis| separated_on(axis, offset, a, b))
+    }
+
+    /// The four corners, counterclockwise if the axes are.
+    pub fn corners(&self) -> [XYVec<T>; 4] {
+        let [u, v] = [0, 1].map(|i| self.axes[i].scale_by(self.half_extents[i]));
+        let c = self.center;
+        [c - u - v, c + u - v, c + u + v, c - u + v]
+    }
+
+    pub fn area(&self) -> T {
+        let four = T::from_f64(4.0);
+        four * self.half_extents[0] * self.half_extents[1]
+    }
+}
+
+impl<T: VecFloat> Obb3<T> {
+    pub fn new(center: XYZVec<T>, axes: [XYZVec<T>; 3], half_extents: [T; 3]) -> Self {
+        Self {
+            center,
+            axes,
+            half_extents,
+        }
+    }
+
+    /// Whether `p` lies inside the box or on its boundary.
+    pub fn contains_point(&self, p: XYZVec<T>) -> bool {
+        let d = p - self.center;
+        (0..3).all(|i| d.dot_prod(self.axes[i]).abs() <= self.half_extents[i])
+    }
+
+    /// Whether the boxes overlap or touch, by the separating axis test on
+    /// the six face normals and the nine edge-pair cross products.
+    pub fn intersects(&self, other: &Self) -> bool {
+        let offset = other.center - self.center;
+        let a = (&self.axes[..], &self.half_extents[..]);
+        let b = (&other.axes[..], &other.half_extents[..]);
+        let faces = self.axes.iter().chain(&other.axes).copied();
+        let edges = self
+            .axes
+            .iter()
+            .flat_map(|&u| other.axes.iter().map(move |&v| u.cross(v)))
+            // Parallel edges give no new axis.
+            .filter(|n| n.l2_norm_sqd() > T::epsilon());
+        !faces
+            .chain(edges)
+            .any(|axis| separated_on(axis, offset, a, b))
+    }
+
+    pub fn volume(&self) -> T {
+        let eight = T::from_f64(8.0);
+        eight * self.half_extents[0] * self.half_extents[1] * self.half_extents[2]
+    }
+}
+
+/// Tight box along the given axes: the extent of `points` projected on
+/// each.
+fn fit_to_axes<V: Vector, const N: usize>(points: &[V], axes: [V; N]) -> (V, [V::Scalar; N])
+where
+    V::Scalar: VecFloat,
+{
+    let two = V::Scalar::one() + V::Scalar::one();
+    let mut center = V::splat(V::Scalar::zero());
+    let half = axes.map(|axis| {
+        let (lo, hi) = points.iter().fold((None, None), |(lo, hi), p| {
+            let t = p.dot_prod(axis);
+            (
+                Some(lo.map_or(t, |l: V::Scalar| if t < l { t } else { l })),
+                Some(hi.map_or(t, |h: V::Scalar| if t > h { t } else { h })),
+            )
+        });
+        let (lo, hi) = (
+            lo.unwrap_or(V::Scalar::zero()),
+            hi.unwrap_or(V::Scalar::zero()),
+        );
+        center += axis.scale_by((lo + hi) / two);
+        (hi - lo) / two
+    });
+    (center, half)
+}
+
+/// Smallest-area rectangle enclosing `points`. One side of the optimal
+/// rectangle lies along an edge of the convex hull, so every hull edge is
+/// tried. `None` if `points` is empty.
+/// ```
+///     use xyzvec::{obb::obb_from_points, XYVec};
+///     use approx::assert_relative_eq;
+///
+///     // A thin diagonal strip.
+///     let points: Vec<_> = (0..10)
+///         .flat_map(|i| {
+///             let t = i as f64;
+///             [XYVec::new([t, t]), XYVec::new([t + 0.1, t - 0.1])]
+///         })
+///         .collect();
+///     let obb = obb_from_points(&points).unwrap();
+///     assert_relative_eq!(obb.area(), 9.0 * 2f64.sqrt() * 0.1 * 2f64.sqrt(), epsilon = 1e-9);
+///     assert!(points.iter().all(|&p| obb.contains_point(p + (obb.center - p).scale_by(1e-9))));
+/// ```
+pub fn obb_from_points<T: VecFloat + CrossSign>(points: &[XYVec<T>]) -> Option<Obb2<T>> {
+    let hull = convex_hull(points);
+    let first = *hull.first()?;
+    let (zero, one) = (T::zero(), T::one());
+    let mut best = {
+        let axes = [XYVec::new([one, zero]), XYVec::new([zero, one])];
+        let (center, half) = fit_to_axes(&hull, axes);
+        Obb2::new(center, axes, half)
+    };
+    if hull.len() == 1 {
+        return Some(Obb2::new(first, best.axes, [zero, zero]));
+    }
+    for (i, &a) in hull.iter().enumerate() {
+        let edge = hull[(i + 1) % hull.len()] - a;
+        let len = edge.l2_norm_sqd().sqrt();
+        let u = edge.div_by(len);
+        let axes = [u, XYVec::new([-u.y(), u.x()])];
+        let (center, half) = fit_to_axes(&hull, axes);
+        let candidate = Obb2::new(center, axes, half);
+        if candidate.area() < best.area() {
+            best = candidate;
+        }
+    }
+    Some(best)
+}
+
+/// Box enclosing `points` aligned with their principal axes (largest
+/// variance first). Not always the smallest box, but close for most
+/// elongated sets. `None` if `points` is empty.
+/// ```
+///     use xyzvec::{obb::obb3_from_points, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let dir = XYZVec::new([1.0f64, 2.0, 2.0]).scale_by(1.0 / 3.0);
+///     let points: Vec<_> = (0..=10).map(|i| dir.scale_by(i as f64)).collect();
+///     let obb = obb3_from_points(&points).unwrap();
+///     assert_relative_eq!(obb.half_extents[0], 5.0, epsilon = 1e-9);
+///     assert_relative_eq!(obb.half_extents[1], 0.0, epsilon = 1e-9);
+///     assert_relative_eq!(obb.axes[0].dot_prod(dir).abs(), 1.0, epsilon = 1e-9);
+/// ```
+pub fn obb3_from_points<T: VecFloat>(points: &[XYZVec<T>]) -> Option<Obb3<T>> {
+    let (_, axes) = Mat3::covariance(points)?.principal_axes();
+    let (center, half) = fit_to_axes(points, axes);
+    Some(Obb3::new(center, axes, half))
+}
+
+impl<T: VecInner> fmt::Debug for Obb2<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Obb2 {{ center: {:?}, axes: {:?}, half_extents: {:?} }}",
+            self.center, self.axes, self.half_extents
+        )
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Obb3<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Obb3 {{ center: {:?}, axes: {:?}, half_extents: {:?} }}",
+            self.center, self.axes, self.half_extents
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{obb3_from_points, obb_from_points, Obb2, Obb3};
+    use crate::{Mat3, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn rotated_rectangle_f64() {
+        // Corners and interior points of a 4x1 rectangle rotated by 0.6.
+        let (s, c) = 0.6f64.sin_cos();
+        let rotate = |x: f64, y: f64| XYVec::new([c * x - s * y + 3.0, s * x + c * y - 1.0]);
+        let mut points = vec![
+            rotate(0.0, 0.0),
+            rotate(4.0, 0.0),
+            rotate(4.0, 1.0),
+            rotate(0.0, 1.0),
+        ];
+        points.extend((1..8).map(|i| rotate(i as f64 * 0.5, 0.3 + (i % 3) as f64 * 0.2)));
+        let obb = obb_from_points(&points).unwrap();
+        assert_relative_eq!(obb.area(), 4.0, epsilon = 1e-9);
+        let mut half = obb.half_extents;
+        half.sort_by(|a, b| a.total_cmp(b));
+        assert_relative_eq!(half[0], 0.5, epsilon = 1e-9);
+        assert_relative_eq!(half[1], 2.0, epsilon = 1e-9);
+        let center = rotate(2.0, 0.5);
+        assert_relative_eq!((obb.center - center).l2_norm(), 0.0, epsilon = 1e-9);
+        assert!(obb.contains_point(center));
+        assert!(!obb.contains_point(rotate(4.1, 0.5)));
+        for corner in obb.corners() {
+            assert!(points.iter().any(|&p| (p - corner).l2_norm() < 1e-9));
+        }
+
+        let single = obb_from_points(&[XYVec::new([1.0f64, 2.0])]).unwrap();
+        assert_eq!(
+            (single.center, single.half_extents),
+            (XYVec::new([1.0, 2.0]), [0.0, 0.0])
+        );
+        let segment =
+            obb_from_points(&[XYVec::new([0.0f64, 0.0]), XYVec::new([3.0, 4.0])]).unwrap();
+        assert_relative_eq!(segment.area(), 0.0, epsilon = 1e-12);
+        assert!(obb_from_points::<f64>(&[]).is_none());
+    }
+
+    #[test]
+    fn separating_axes_2d_f32() {
+        let x = XYVec::new([1.0f32, 0.0]);
+        let y = XYVec::new([0.0f32, 1.0]);
+        let a = Obb2::new(XYVec::new([0.0, 0.0]), [x, y], [1.0, 1.0]);
+        let d = XYVec::new([1.0f32, 1.0]).scale_by(0.5f32.sqrt());
+        let diamond = |cx: f32| {
+            Obb2::new(
+                XYVec::new([cx, 0.0]),
+                [d, XYVec::new([-d.y(), d.x()])],
+                [0.5, 0.5],
+            )
+        };
+        // The diamond's tip reaches `0.5 * sqrt(2)` from its center.
+        assert!(a.intersects(&diamond(1.7)));
+        assert!(!a.intersects(&diamond(1.75)));
+        assert!(diamond(1.75).intersects(&diamond(2.4)));
+    }
+
+    #[test]
+    fn boxes_3d_f64() {
+        let axes = [0, 1, 2].map(|i| Mat3::<f64>::identity().row(i));
+        let unit = Obb3::new(XYZVec::new([0.0, 0.0, 0.0]), axes, [1.0, 1.0, 1.0]);
+        assert_relative_eq!(unit.volume(), 8.0);
+        assert!(unit.contains_point(XYZVec::new([1.0, -1.0, 0.5])));
+        assert!(!unit.contains_point(XYZVec::new([1.0, -1.0, 1.5])));
+        // Rotated 45° about z and moved along x: only the face normals of
+        // `turned` separate it once it is past sqrt(2) + 1.
+        let r = Mat3::from_axis_angle(XYZVec::new([0.0, 0.0, 1.0]), std::f64::consts::FRAC_PI_4);
+        let turned = |x: f64| {
+            Obb3::new(
+                XYZVec::new([x, 0.0, 0.0]),
+                [0, 1, 2].map(|i| r.col(i)),
+                [1.0, 1.0, 1.0],
+            )
+        };
+        assert!(unit.intersects(&turned(2.4)));
+        assert!(!unit.intersects(&turned(2.45)));
+        // Thin slabs tilted against each other.
+        let tilt = Mat3::from_axis_angle(XYZVec::new([1.0, 0.0, 0.0]), std::f64::consts::FRAC_PI_4);
+        let rotated = [0, 1, 2].map(|i| (tilt * r).col(i));
+        let e = Obb3::new(
+            XYZVec::new([0.0, 0.0, 0.0]),
+            [0, 1, 2].map(|i| r.col(i)),
+            [1.0, 1.0, 0.1],
+        );
+        let f = |z: f64| Obb3::new(XYZVec::new([0.0, 0.0, z]), rotated, [1.0, 1.0, 0.1]);
+        assert!(e.intersects(&f(0.5)));
+        assert!(!e.intersects(&f(3.0)));
+
+        let points: Vec<_> = (0..50)
+            .map(|i| {
+                let t = i as f64 * 0.2;
+                r * XYZVec::new([t, (t * 3.0).sin() * 0.2, (t * 5.0).cos() * 0.1])
+            })
+            .collect();
+        let obb = obb3_from_points(&points).unwrap();
+        assert!(points
+            .iter()
+            .all(|&p| obb.contains_point(p + (obb.center - p).scale_by(1e-9))));
+        assert_relative_eq!(obb.axes[0].dot_prod(r.col(0)).abs(), 1.0, epsilon = 1e-3);
+        assert!(obb3_from_points::<f64>(&[]).is_none());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{VecFloat, VecInner, VecNum, XYZVec};
+use std::fmt::{self, Formatter};
+
+/// Plane of points `p` satisfying `normal · p = offset`.
+///
+/// Distances are measured in units of `normal`'s length, so they are true
+/// distances when `normal` has unit length.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Plane<T> {
+    pub normal: XYZVec<T>,
+    pub offset: T,
+}
+
+impl<T: VecNum> Plane<T> {
+    pub fn new(normal: XYZVec<T>, offset: T) -> Self {
+        Self { normal, offset }
+    }
+
+    /// Plane through `point` with the given normal.
+    pub fn from_point_normal(point: XYZVec<T>, normal: XYZVec<T>) -> Self {
+        Self::new(normal, normal.dot_prod(point))
+    }
+
+    /// Positive on the side `normal` points to.
+    /// ```
+    ///     use xyzvec::{Plane, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let ground = Plane::new(XYZVec::new([0.0f64, 0.0, 1.0]), 1.0);
+    ///     assert_relative_eq!(ground.signed_distance(XYZVec::new([5.0, 2.0, 3.0])), 2.0);
+    ///     assert_relative_eq!(ground.signed_distance(XYZVec::new([5.0, 2.0, 0.0])), -1.0);
+    /// ```
+    pub fn signed_distance(&self, p: XYZVec<T>) -> T {
+        self.normal.dot_prod(p) - self.offset
+    }
+
+    /// Where the line through `p0` and `p1` meets the plane, with `t`
+    /// measured so that `p0` is at 0 and `p1` at 1. Check `t` against
+    /// `0..=1` to clip a segment. The parallel and contained cases are
+    /// decided by exact comparison with zero.
+    /// ```
+    ///     use xyzvec::{plane::LinePlaneIntersection, Plane, XYZVec};
+    ///
+    ///     let ground = Plane::new(XYZVec::new([0.0f64, 0.0, 1.0]), 0.0);
+    ///     let hit = ground.intersect_line(XYZVec::new([1.0, 1.0, 1.0]), XYZVec::new([3.0, 1.0, -3.0]));
+    ///     assert_eq!(
+    ///         hit,
+    ///         LinePlaneIntersection::Point { t: 0.25, point: XYZVec::new([1.5, 1.0, 0.0]) }
+    ///     );
+    ///     let flat = ground.intersect_line(XYZVec::new([0.0, 0.0, 1.0]), XYZVec::new([1.0, 0.0, 1.0]));
+    ///     assert_eq!(flat, LinePlaneIntersection::Parallel);
+    /// ```
+    pub fn intersect_line(&self, p0: XYZVec<T>, p1: XYZVec<T>) -> LinePlaneIntersection<T> {
+        let d0 = self.signed_distance(p0);
+        let denom = self.normal.dot_prod(p1 - p0);
+        if denom == T::zero() {
+            return if d0 == T::zero() {
+                LinePlaneIntersection::Contained
+            } else {
+                LinePlaneIntersection::Parallel
+            };
+        }
+        let t = -d0 / denom;
+        LinePlaneIntersection::Point {
+            t,
+            point: p0 + (p1 - p0).scale_by(t),
+        }
+    }
+}
+
+/// How a line meets a plane.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LinePlaneIntersection<T: VecInner> {
+    /// The line is parallel to the plane and off it.
+    Parallel,
+    /// The line lies in the plane.
+    Contained,
+    /// The line crosses the plane at a single point.
+    Point { t: T, point: XYZVec<T> },
+}
+
+impl<T: VecFloat> Plane<T> {
+    /// Plane through three points with a unit normal following the
+    /// right-hand rule. `None` if the points are collinear.
+    pub fn from_points(a: XYZVec<T>, b: XYZVec<T>, c: XYZVec<T>) -> Option<Self> {
+        let n = (b - a).cross(c - a);
+        let len = n.l2_norm_sqd().sqrt();
+        if len == T::zero() {
+            return None;
+        }
+        Some(Self::from_point_normal(a, n.div_by(len)))
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Plane<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Plane {{ normal: {:?}, offset: {:?} }}",
+            self.normal, self.offset
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LinePlaneIntersection;
+    use crate::{Plane, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    #[test]
+    fn from_points_f64() {
+        let p = Plane::from_points(
+            XYZVec::new([1.0f64, 0.0, 0.0]),
+            XYZVec::new([0.0, 1.0, 0.0]),
+            XYZVec::new([0.0, 0.0, 1.0]),
+        )
+        .unwrap();
+        let k = 1.0 / 3.0f64.sqrt();
+        assert_relative_eq!(p.normal.x(), k);
+        assert_relative_eq!(p.normal.y(), k);
+        assert_relative_eq!(p.normal.z(), k);
+        assert_relative_eq!(p.offset, k);
+        assert_relative_eq!(p.signed_distance(XYZVec::<f64>::zeroes()), -k);
+    }
+
+    #[test]
+    fn collinear_f32() {
+        let a = XYZVec::new([0.0f32, 0.0, 0.0]);
+        let b = XYZVec::new([1.0f32, 1.0, 1.0]);
+        assert!(Plane::from_points(a, b, b.scale_by(2.0)).is_none());
+    }
+
+    #[test]
+    fn line_fixed() {
+        let f = |v: f64| I28F4::from_num(v);
+        let p = |x: f64, y: f64, z: f64| XYZVec::new([f(x), f(y), f(z)]);
+        let wall = Plane::from_point_normal(p(2.0, 0.0, 0.0), p(1.0, 0.0, 0.0));
+        assert_eq!(
+            wall.intersect_line(p(0.0, 1.0, 1.0), p(1.0, 1.5, 1.0)),
+            LinePlaneIntersection::Point {
+                t: f(2.0),
+                point: p(2.0, 2.0, 1.0)
+            }
+        );
+        assert_eq!(
+            wall.intersect_line(p(2.0, 1.0, 1.0), p(2.0, -3.0, 5.0)),
+            LinePlaneIntersection::Contained
+        );
+        assert_eq!(
+            wall.intersect_line(p(2.5, 1.0, 1.0), p(2.5, -3.0, 5.0)),
+            LinePlaneIntersection::Parallel
+        );
+    }
+}
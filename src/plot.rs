@@ -0,0 +1,159 @@
+//! Quick scatter and path plots of point sets with [`plotters`]. Requires
+//! the `plotters` feature.
+//!
+//! The axes are fitted to the points with a small margin, so one call is
+//! enough to see what a set of points looks like.
+//! ```
+//!     use plotters::prelude::*;
+//!     use xyzvec::{plot::{draw_scatter, PlotOptions}, XYVec};
+//!
+//!     let points: Vec<_> = (0..20).map(|i| XYVec::new([i as f64, (i * i) as f64])).collect();
+//!     let mut svg = String::new();
+//!     {
+//!         let root = SVGBackend::with_string(&mut svg, (400, 300)).into_drawing_area();
+//!         let options = PlotOptions { mesh: false, ..PlotOptions::default() };
+//!         draw_scatter(&root, &points, &options).unwrap();
+//!     }
+//!     assert!(svg.contains("<circle"));
+//! ```
+
+use crate::{aabb::min_max_components, XYVec};
+use plotters::{
+    coord::{types::RangedCoordf64, Shift},
+    prelude::*,
+};
+use std::ops::Range;
+
+/// Appearance shared by [`draw_scatter`] and [`draw_path`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlotOptions {
+    /// Caption above the chart.
+    pub title: Option<String>,
+    pub color: RGBColor,
+    /// Radius of scatter points, or width of path lines, in pixels.
+    pub size: u32,
+    /// Draw axis labels and grid lines.
+    pub mesh: bool,
+    /// Margin around the points, as a fraction of each axis' span.
+    pub margin: f64,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        Self {
+            title: None,
+            color: BLUE,
+            size: 2,
+            mesh: true,
+            margin: 0.05,
+        }
+    }
+}
+
+type PlotResult<DB> = Result<(), DrawingAreaErrorKind<<DB as DrawingBackend>::ErrorType>>;
+
+/// `lo..hi` widened by `margin` of its span, or by 1 if it has none.
+fn padded(lo: f64, hi: f64, margin: f64) -> Range<f64> {
+    let pad = if hi > lo { (hi - lo) * margin } else { 1.0 };
+    lo - pad..hi + pad
+}
+
+/// Axis ranges fitting `points`, or `-1..1` on both axes if there are
+/// none.
+fn axis_ranges(points: &[XYVec<f64>], margin: f64) -> (Range<f64>, Range<f64>) {
+    match min_max_components(points.iter().copied()) {
+        Some((min, max)) => (
+            padded(min.x(), max.x(), margin),
+            padded(min.y(), max.y(), margin),
+        ),
+        None => (-1.0..1.0, -1.0..1.0),
+    }
+}
+
+type Chart<'a, DB> = ChartContext<'a, DB, Cartesian2d<RangedCoordf64, RangedCoordf64>>;
+
+fn chart<'a, DB: DrawingBackend>(
+    root: &'a DrawingArea<DB, Shift>,
+    points: &[XYVec<f64>],
+    options: &PlotOptions,
+) -> Result<Chart<'a, DB>, DrawingAreaErrorKind<DB::ErrorType>> {
+    root.fill(&WHITE)?;
+    let (x, y) = axis_ranges(points, options.margin);
+    let mut builder = ChartBuilder::on(root);
+    builder.margin(10);
+    if let Some(title) = &options.title {
+        builder.caption(title, ("sans-serif", 20));
+    }
+    if options.mesh {
+        builder.x_label_area_size(30).y_label_area_size(40);
+    }
+    let mut chart = builder.build_cartesian_2d(x, y)?;
+    if options.mesh {
+        chart.configure_mesh().draw()?;
+    }
+    Ok(chart)
+}
+
+/// Draws `points` as dots, filling `root`.
+pub fn draw_scatter<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    points: &[XYVec<f64>],
+    options: &PlotOptions,
+) -> PlotResult<DB> {
+    let mut chart = chart(root, points, options)?;
+    let style = options.color.filled();
+    chart.draw_series(
+        points
+            .iter()
+            .map(|p| Circle::new((p.x(), p.y()), options.size, style)),
+    )?;
+    root.present()
+}
+
+/// Draws `points` as a connected path, in order, filling `root`.
+pub fn draw_path<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    points: &[XYVec<f64>],
+    options: &PlotOptions,
+) -> PlotResult<DB> {
+    let mut chart = chart(root, points, options)?;
+    let style = options.color.stroke_width(options.size);
+    chart.draw_series(LineSeries::new(
+        points.iter().map(|p| (p.x(), p.y())),
+        style,
+    ))?;
+    root.present()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{axis_ranges, draw_path, PlotOptions};
+    use crate::XYVec;
+    use plotters::prelude::*;
+
+    #[test]
+    fn ranges_fit_points() {
+        let points = [XYVec::new([0.0, 5.0]), XYVec::new([10.0, 5.0])];
+        assert_eq!(axis_ranges(&points, 0.1), (-1.0..11.0, 4.0..6.0));
+        assert_eq!(axis_ranges(&[], 0.1), (-1.0..1.0, -1.0..1.0));
+    }
+
+    #[test]
+    fn path_is_drawn() {
+        let points = [
+            XYVec::new([0.0, 0.0]),
+            XYVec::new([1.0, 2.0]),
+            XYVec::new([2.0, 1.0]),
+        ];
+        let mut svg = String::new();
+        {
+            let root = SVGBackend::with_string(&mut svg, (200, 100)).into_drawing_area();
+            let options = PlotOptions {
+                mesh: false,
+                ..PlotOptions::default()
+            };
+            draw_path(&root, &points, &options).unwrap();
+        }
+        assert!(svg.contains("<polyline"), "{svg}");
+    }
+}
@@ -0,0 +1,288 @@
+use crate::{CrossSign, VecInner, VecNum, XYVec};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Formatter},
+};
+
+/// Winding direction of a polygon or of three points.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Collinear,
+}
+
+impl Orientation {
+    /// Orientation of the turn `a -> b -> c`, decided with [`CrossSign`].
+    /// ```
+    ///     use xyzvec::{Orientation, XYVec};
+    ///
+    ///     let a = XYVec::new([0.0f64, 0.0]);
+    ///     let b = XYVec::new([1.0, 0.0]);
+    ///     assert_eq!(Orientation::of(a, b, XYVec::new([1.0, 1.0])), Orientation::CounterClockwise);
+    ///     assert_eq!(Orientation::of(a, b, XYVec::new([1.0, -1.0])), Orientation::Clockwise);
+    ///     assert_eq!(Orientation::of(a, b, XYVec::new([2.0, 0.0])), Orientation::Collinear);
+    /// ```
+    pub fn of<T: CrossSign>(a: XYVec<T>, b: XYVec<T>, c: XYVec<T>) -> Self {
+        match T::cross_sign(b - a, c - a) {
+            Ordering::Greater => Orientation::CounterClockwise,
+            Ordering::Less => Orientation::Clockwise,
+            Ordering::Equal => Orientation::Collinear,
+        }
+    }
+
+    fn from_signed<T: VecNum>(v: &T) -> Self {
+        if *v > T::zero() {
+            Orientation::CounterClockwise
+        } else if *v < T::zero() {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+}
+
+/// Simple polygon in the plane, stored as its vertices in order.
+/// The closing edge from the last vertex back to the first is implicit.
+#[derive(Clone, PartialEq)]
+pub struct Polygon2<T> {
+    vertices: Vec<XYVec<T>>,
+}
+
+impl<T: VecNum> Polygon2<T> {
+    pub fn new(vertices: Vec<XYVec<T>>) -> Self {
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[XYVec<T>] {
+        &self.vertices
+    }
+
+    pub fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Iterator over the edges `(start, end)`, including the closing edge.
+    pub fn edges(&self) -> impl Iterator<Item = (XYVec<T>, XYVec<T>)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| (self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    /// Twice the signed area, accumulated without the final halving.
+    fn doubled_signed_area(&self) -> T {
+        self.edges()
+            .fold(T::zero(), |acc, (a, b)| acc + a.cross_prod(b))
+    }
+
+    /// Shoelace area, positive for counter-clockwise vertex order.
+    /// ```
+    ///     use xyzvec::{Polygon2, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let square = Polygon2::new(vec![
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([2.0, 0.0]),
+    ///         XYVec::new([2.0, 2.0]),
+    ///         XYVec::new([0.0, 2.0]),
+    ///     ]);
+    ///     assert_relative_eq!(square.signed_area(), 4.0);
+    /// ```
+    pub fn signed_area(&self) -> T {
+        self.doubled_signed_area() / (T::one() + T::one())
+    }
+
+    pub fn area(&self) -> T {
+        self.signed_area().abs()
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        Orientation::from_signed(&self.doubled_signed_area())
+    }
+
+    /// Area centroid of the polygon. `None` if the polygon has zero area.
+    /// ```
+    ///     use xyzvec::{Polygon2, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let square = Polygon2::new(vec![
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([2.0, 0.0]),
+    ///         XYVec::new([2.0, 2.0]),
+    ///         XYVec::new([0.0, 2.0]),
+    ///     ]);
+    ///     let c = square.centroid().unwrap();
+    ///     assert_relative_eq!(c.x(), 1.0);
+    ///     assert_relative_eq!(c.y(), 1.0);
+    /// ```
+    pub fn centroid(&self) -> Option<XYVec<T>> {
+        let doubled_area = self.doubled_signed_area();
+        if doubled_area == T::zero() {
+            return None;
+        }
+        let sum = self
+            .edges()
+            .fold(XYVec::new([T::zero(); 2]), |acc, (a, b)| {
+                acc + (a + b).scale_by(a.cross_prod(b))
+            });
+        let three = T::one() + T::one() + T::one();
+        Some(sum.div_by(three * doubled_area))
+    }
+}
+
+impl<T: CrossSign> Polygon2<T> {
+    /// Winding number of the polygon around `p`; zero when `p` is outside.
+    /// Edge sides are decided with [`CrossSign`], so points next to an edge
+    /// are classified exactly in fixed point too.
+    pub fn winding_number(&self, p: XYVec<T>) -> i32 {
+        self.edges().fold(0, |wn, (a, b)| {
+            let side = Orientation::of(a, b, p);
+            if a.y() <= p.y() {
+                if b.y() > p.y() && side == Orientation::CounterClockwise {
+                    return wn + 1;
+                }
+            } else if b.y() <= p.y() && side == Orientation::Clockwise {
+                return wn - 1;
+            }
+            wn
+        })
+    }
+
+    /// Whether `p` lies inside the polygon or on its boundary.
+    /// ```
+    ///     use xyzvec::{Polygon2, XYVec};
+    ///
+    ///     let l_shape = Polygon2::new(vec![
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([2.0, 0.0]),
+    ///         XYVec::new([2.0, 1.0]),
+    ///         XYVec::new([1.0, 1.0]),
+    ///         XYVec::new([1.0, 2.0]),
+    ///         XYVec::new([0.0, 2.0]),
+    ///     ]);
+    ///     assert!(l_shape.contains_point(XYVec::new([0.5, 1.5])));
+    ///     assert!(l_shape.contains_point(XYVec::new([2.0, 0.5])));
+    ///     assert!(!l_shape.contains_point(XYVec::new([1.5, 1.5])));
+    /// ```
+    pub fn contains_point(&self, p: XYVec<T>) -> bool {
+        let on_boundary = self.edges().any(|(a, b)| on_segment(a, b, p));
+        on_boundary || self.winding_number(p) != 0
+    }
+}
+
+fn on_segment<T: CrossSign>(a: XYVec<T>, b: XYVec<T>, p: XYVec<T>) -> bool {
+    if Orientation::of(a, b, p) != Orientation::Collinear {
+        return false;
+    }
+    let within = |lo: T, hi: T, v: T| (lo <= v && v <= hi) || (hi <= v && v <= lo);
+    within(a.x(), b.x(), p.x()) && within(a.y(), b.y(), p.y())
+}
+
+impl<T: VecInner> fmt::Debug for Polygon2<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.vertices.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Orientation, Polygon2, XYVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    fn fixed_xy(x: f64, y: f64) -> XYVec<I28F4> {
+        XYVec::new([I28F4::from_num(x), I28F4::from_num(y)])
+    }
+
+    fn l_shape() -> Polygon2<f64> {
+        Polygon2::new(vec![
+            XYVec::new([0.0, 0.0]),
+            XYVec::new([2.0, 0.0]),
+            XYVec::new([2.0, 1.0]),
+            XYVec::new([1.0, 1.0]),
+            XYVec::new([1.0, 2.0]),
+            XYVec::new([0.0, 2.0]),
+        ])
+    }
+
+    #[test]
+    fn area_f64() {
+        let p = l_shape();
+        assert_relative_eq!(p.signed_area(), 3.0);
+        assert_eq!(p.orientation(), Orientation::CounterClockwise);
+
+        let mut reversed = p.vertices().to_vec();
+        reversed.reverse();
+        let r = Polygon2::new(reversed);
+        assert_relative_eq!(r.signed_area(), -3.0);
+        assert_relative_eq!(r.area(), 3.0);
+        assert_eq!(r.orientation(), Orientation::Clockwise);
+    }
+
+    #[test]
+    fn centroid_f64() {
+        let c = l_shape().centroid().unwrap();
+        assert_relative_eq!(c.x(), 5.0 / 6.0, epsilon = 1e-12);
+        assert_relative_eq!(c.y(), 5.0 / 6.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn degenerate_f32() {
+        let p = Polygon2::new(vec![
+            XYVec::new([0.0f32, 0.0]),
+            XYVec::new([1.0, 1.0]),
+            XYVec::new([2.0, 2.0]),
+        ]);
+        assert_eq!(p.orientation(), Orientation::Collinear);
+        assert!(p.centroid().is_none());
+        assert!(Polygon2::<f32>::new(vec![]).centroid().is_none());
+    }
+
+    #[test]
+    fn winding_number_f64() {
+        let p = l_shape();
+        assert_eq!(p.winding_number(XYVec::new([0.5, 0.5])), 1);
+        assert_eq!(p.winding_number(XYVec::new([1.5, 1.5])), 0);
+
+        let mut reversed = p.vertices().to_vec();
+        reversed.reverse();
+        let r = Polygon2::new(reversed);
+        assert_eq!(r.winding_number(XYVec::new([0.5, 0.5])), -1);
+        assert!(r.contains_point(XYVec::new([0.5, 0.5])));
+    }
+
+    #[test]
+    fn fixed() {
+        let p = Polygon2::new(vec![
+            fixed_xy(0.0, 0.0),
+            fixed_xy(3.0, 0.0),
+            fixed_xy(3.0, 1.5),
+            fixed_xy(0.0, 1.5),
+        ]);
+        assert_eq!(p.signed_area(), 4.5);
+        let c = p.centroid().unwrap();
+        assert_eq!(c.x(), 1.5);
+        assert_eq!(c.y(), 0.75);
+        assert!(p.contains_point(fixed_xy(1.0, 1.0)));
+        assert!(p.contains_point(fixed_xy(3.0, 1.5)));
+        assert!(p.contains_point(fixed_xy(1.5, 0.0)));
+        assert!(!p.contains_point(fixed_xy(3.5, 1.0)));
+    }
+
+    #[test]
+    fn near_edge_fixed() {
+        // (0, 1/16) is just left of the edge (0, 0) -> (1/16, 1/16); the
+        // cross product, 1/256, truncates to zero in I28F4.
+        let eps = 1.0 / 16.0;
+        let p = Polygon2::new(vec![
+            fixed_xy(0.0, 0.0),
+            fixed_xy(eps, eps),
+            fixed_xy(eps, 0.0),
+        ]);
+        assert!(!p.contains_point(fixed_xy(0.0, eps)));
+        assert!(p.contains_point(fixed_xy(eps, eps)));
+    }
+}
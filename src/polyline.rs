@@ -0,0 +1,264 @@
+use crate::{queries::closest_point_on_segment, VecFloat, VecNum, Vector};
+
+/// Open path through a sequence of [`XYVec`](crate::XYVec) or
+/// [`XYZVec`](crate::XYZVec) points.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Polyline<V> {
+    points: Vec<V>,
+}
+
+impl<V: Vector> Polyline<V>
+where
+    V::Scalar: VecNum,
+{
+    pub fn new(points: Vec<V>) -> Self {
+        Self { points }
+    }
+
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Ramer–Douglas–Peucker simplification. Keeps the endpoints and every
+    /// point needed to stay within `tolerance` of the original path.
+    /// ```
+    ///     use xyzvec::{Polyline, XYVec};
+    ///
+    ///     let line = Polyline::new(vec![
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([1.0, 0.05]),
+    ///         XYVec::new([2.0, -0.05]),
+    ///         XYVec::new([3.0, 2.0]),
+    ///         XYVec::new([4.0, 4.0]),
+    ///     ]);
+    ///     let simple = line.simplify(0.1);
+    ///     assert_eq!(
+    ///         simple.points(),
+    ///         &[XYVec::new([0.0, 0.0]), XYVec::new([2.0, -0.05]), XYVec::new([4.0, 4.0])]
+    ///     );
+    /// ```
+    pub fn simplify(&self, tolerance: V::Scalar) -> Self {
+        let n = self.points.len();
+        if n < 3 {
+            return self.clone();
+        }
+        let tolerance_sqd = tolerance * tolerance;
+        let mut keep = vec![false; n];
+        keep[0] = true;
+        keep[n - 1] = true;
+        let mut stack = vec![(0, n - 1)];
+        while let Some((start, end)) = stack.pop() {
+            let (a, b) = (self.points[start], self.points[end]);
+            let mut farthest = None;
+            let mut max_dist_sqd = tolerance_sqd;
+            for i in start + 1..end {
+                let p = self.points[i];
+                let (q, _) = closest_point_on_segment(p, a, b);
+                let dist_sqd = (p - q).l2_norm_sqd();
+                if dist_sqd > max_dist_sqd {
+                    max_dist_sqd = dist_sqd;
+                    farthest = Some(i);
+                }
+            }
+            if let Some(i) = farthest {
+                keep[i] = true;
+                stack.push((start, i));
+                stack.push((i, end));
+            }
+        }
+        let points = self
+            .points
+            .iter()
+            .zip(keep)
+            .filter_map(|(&p, k)| k.then_some(p))
+            .collect();
+        Self::new(points)
+    }
+}
+
+impl<V: Vector> Polyline<V>
+where
+    V::Scalar: VecFloat,
+{
+    /// Total length of all segments.
+    /// ```
+    ///     use xyzvec::{Polyline, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let line = Polyline::new(vec![
+    ///         XYZVec::new([0.0f64, 0.0, 0.0]),
+    ///         XYZVec::new([3.0, 4.0, 0.0]),
+    ///         XYZVec::new([3.0, 4.0, 2.0]),
+    ///     ]);
+    ///     assert_relative_eq!(line.length(), 7.0);
+    /// ```
+    pub fn length(&self) -> V::Scalar {
+        self.points
+            .windows(2)
+            .fold(V::Scalar::zero(), |acc, w| acc + (w[1] - w[0]).l2_norm())
+    }
+
+    /// Point at arc length `d` from the start, clamped to the ends of the
+    /// path. `None` if the polyline is empty.
+    /// ```
+    ///     use xyzvec::{Polyline, XYVec};
+    ///
+    ///     let line = Polyline::new(vec![
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([2.0, 0.0]),
+    ///         XYVec::new([2.0, 2.0]),
+    ///     ]);
+    ///     assert_eq!(line.point_at_distance(3.0), Some(XYVec::new([2.0, 1.0])));
+    ///     assert_eq!(line.point_at_distance(10.0), Some(XYVec::new([2.0, 2.0])));
+    /// ```
+    pub fn point_at_distance(&self, d: V::Scalar) -> Option<V> {
+        let first = *self.points.first()?;
+        if d <= V::Scalar::zero() {
+            return Some(first);
+        }
+        let mut remaining = d;
+        for w in self.points.windows(2) {
+            let seg = w[1] - w[0];
+            let seg_len = seg.l2_norm();
+            if remaining <= seg_len && seg_len > V::Scalar::zero() {
+                return Some(w[0] + seg.scale_by(remaining / seg_len));
+            }
+            remaining -= seg_len;
+        }
+        self.points.last().copied()
+    }
+
+    /// Points spaced `spacing` apart along the path, starting at the first
+    /// point and always ending at the last. A non-positive spacing returns
+    /// a copy of the polyline.
+    /// ```
+    ///     use xyzvec::{Polyline, XYVec};
+    ///
+    ///     let line = Polyline::new(vec![XYVec::new([0.0f64, 0.0]), XYVec::new([2.5, 0.0])]);
+    ///     let resampled = line.resample(1.0);
+    ///     let xs: Vec<f64> = resampled.points().iter().map(|p| p.x()).collect();
+    ///     assert_eq!(xs, vec![0.0, 1.0, 2.0, 2.5]);
+    /// ```
+    pub fn resample(&self, spacing: V::Scalar) -> Self {
+        if spacing <= V::Scalar::zero() || self.points.len() < 2 {
+            return self.clone();
+        }
+        let mut out = vec![self.points[0]];
+        // Distance along the current segment at which the next sample falls.
+        let mut next = spacing;
+        for w in self.points.windows(2) {
+            let seg = w[1] - w[0];
+            let seg_len = seg.l2_norm();
+            while next < seg_len {
+                out.push(w[0] + seg.scale_by(next / seg_len));
+                next += spacing;
+            }
+            next -= seg_len;
+        }
+        let last = self.points[self.points.len() - 1];
+        if (last - out[out.len() - 1]).l2_norm_sqd() > V::Scalar::zero() {
+            out.push(last);
+        }
+        Self::new(out)
+    }
+}
+
+impl<V> From<Vec<V>> for Polyline<V> {
+    fn from(points: Vec<V>) -> Self {
+        Self { points }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Polyline, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    fn square_path() -> Polyline<XYVec<f64>> {
+        Polyline::new(vec![
+            XYVec::new([0.0, 0.0]),
+            XYVec::new([1.0, 0.0]),
+            XYVec::new([1.0, 1.0]),
+            XYVec::new([0.0, 1.0]),
+        ])
+    }
+
+    #[test]
+    fn point_at_distance_f64() {
+        let p = square_path();
+        assert_relative_eq!(p.length(), 3.0);
+        assert_eq!(p.point_at_distance(-1.0), Some(XYVec::new([0.0, 0.0])));
+        let q = p.point_at_distance(2.25).unwrap();
+        assert_relative_eq!(q.x(), 0.75);
+        assert_relative_eq!(q.y(), 1.0);
+        assert!(Polyline::<XYVec<f64>>::new(vec![])
+            .point_at_distance(1.0)
+            .is_none());
+    }
+
+    #[test]
+    fn resample_spacing_f64() {
+        let p = square_path();
+        let r = p.resample(0.4);
+        assert_eq!(r.len(), 9);
+        for w in r.points().windows(2).take(7) {
+            // Samples straddling a corner are closer than the spacing in a
+            // straight line, but exactly `spacing` apart along the path.
+            assert!((w[1] - w[0]).l2_norm() <= 0.4 + 1e-12);
+        }
+        for (i, q) in r.points().iter().enumerate().take(8) {
+            let expected = p.point_at_distance(0.4 * i as f64).unwrap();
+            assert_relative_eq!(q.x(), expected.x(), epsilon = 1e-12);
+            assert_relative_eq!(q.y(), expected.y(), epsilon = 1e-12);
+        }
+        assert_eq!(r.points()[8], XYVec::new([0.0, 1.0]));
+    }
+
+    #[test]
+    fn resample_repeated_points_f32() {
+        let p = Polyline::new(vec![
+            XYZVec::new([0.0f32, 0.0, 0.0]),
+            XYZVec::new([0.0, 0.0, 0.0]),
+            XYZVec::new([0.0, 0.0, 2.0]),
+        ]);
+        let r = p.resample(1.0);
+        assert_eq!(r.len(), 3);
+        assert_relative_eq!(r.points()[1].z(), 1.0);
+    }
+
+    #[test]
+    fn simplify_keeps_corners_f64() {
+        let p = square_path();
+        assert_eq!(p.simplify(0.1), p);
+        assert_eq!(p.simplify(2.0).len(), 2);
+    }
+
+    #[test]
+    fn simplify_fixed() {
+        let f = |v: f64| I28F4::from_num(v);
+        let p = Polyline::new(vec![
+            XYVec::new([f(0.0), f(0.0)]),
+            XYVec::new([f(1.0), f(0.125)]),
+            XYVec::new([f(2.0), f(0.0)]),
+            XYVec::new([f(3.0), f(3.0)]),
+        ]);
+        let s = p.simplify(f(0.25));
+        assert_eq!(
+            s.points(),
+            &[
+                XYVec::new([f(0.0), f(0.0)]),
+                XYVec::new([f(2.0), f(0.0)]),
+                XYVec::new([f(3.0), f(3.0)]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,293 @@
+//! Rigid transforms: a rotation followed by a translation.
+//!
+//! Scalars only need [`VecNum`], so poses work in fixed point; the sines
+//! and cosines themselves are computed in `f64`.
+
+use crate::{Mat3, Mat4, VecInner, VecNum, Vector, XYVec, XYZVec};
+use std::{
+    f64::consts::{PI, TAU},
+    fmt::{self, Formatter},
+    ops::Mul,
+};
+
+/// `theta` wrapped into `[-π, π)`.
+fn wrap(theta: f64) -> f64 {
+    (theta + PI).rem_euclid(TAU) - PI
+}
+
+/// 2D rigid transform (an element of SE(2)): rotate counterclockwise by
+/// `rotation` radians about the origin, then translate by `translation`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pose2<T> {
+    pub translation: XYVec<T>,
+    pub rotation: T,
+}
+
+impl<T: VecNum> Pose2<T> {
+    pub fn new(translation: XYVec<T>, rotation: T) -> Self {
+        Self {
+            translation,
+            rotation,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(XYVec::new([T::zero(), T::zero()]), T::zero())
+    }
+
+    fn rotate(v: XYVec<T>, theta: f64) -> XYVec<T> {
+        let (s, c) = (T::from_f64(theta.sin()), T::from_f64(theta.cos()));
+        XYVec::new([c * v.x() - s * v.y(), s * v.x() + c * v.y()])
+    }
+
+    /// `v` rotated by this pose, ignoring the translation. Use this for
+    /// directions and velocities.
+    pub fn transform_vector(&self, v: XYVec<T>) -> XYVec<T> {
+        Self::rotate(v, self.rotation.to_f64())
+    }
+
+    /// `p` rotated and then translated by this pose.
+    /// ```
+    ///     use xyzvec::{pose::Pose2, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///     use std::f64::consts::FRAC_PI_2;
+    ///
+    ///     let pose = Pose2::new(XYVec::new([1.0f64, 2.0]), FRAC_PI_2);
+    ///     let p = pose.transform_point(XYVec::new([1.0, 0.0]));
+    ///     assert_relative_eq!(p.x(), 1.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(p.y(), 3.0, epsilon = 1e-12);
+    /// ```
+    pub fn transform_point(&self, p: XYVec<T>) -> XYVec<T> {
+        self.transform_vector(p) + self.translation
+    }
+
+    /// The pose that applies `other` first and then `self`, i.e.
+    /// `self * other`. The rotation is wrapped into `[-π, π)`.
+    pub fn compose(&self, other: Self) -> Self {
+        let rotation = wrap(self.rotation.to_f64() + other.rotation.to_f64());
+        Self::new(
+            self.transform_point(other.translation),
+            T::from_f64(rotation),
+        )
+    }
+
+    /// The pose that undoes `self`.
+    /// ```
+    ///     use xyzvec::{pose::Pose2, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let pose = Pose2::new(XYVec::new([3.0f64, -1.0]), 0.7);
+    ///     let p = XYVec::new([0.5, 2.0]);
+    ///     let back = pose.inverse().transform_point(pose.transform_point(p));
+    ///     assert_relative_eq!(back.x(), p.x(), epsilon = 1e-12);
+    ///     assert_relative_eq!(back.y(), p.y(), epsilon = 1e-12);
+    /// ```
+    pub fn inverse(&self) -> Self {
+        let theta = -self.rotation.to_f64();
+        Self::new(-Self::rotate(self.translation, theta), T::from_f64(theta))
+    }
+
+    /// Interpolation from `self` (at `t = 0`) to `other` (at `t = 1`):
+    /// linear in translation, and along the shorter way round in rotation.
+    pub fn interpolate(&self, other: Self, t: T) -> Self {
+        let from = self.rotation.to_f64();
+        let turn = wrap(other.rotation.to_f64() - from);
+        Self::new(
+            self.translation.lerp(other.translation, t),
+            T::from_f64(wrap(from + turn * t.to_f64())),
+        )
+    }
+}
+
+impl<T: VecNum> Mul for Pose2<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.compose(other)
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Pose2<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Pose2 {{ translation: {:?}, rotation: {:?} }}",
+            self.translation, self.rotation
+        )
+    }
+}
+
+/// 3D rigid transform (an element of SE(3)): rotate about the origin by
+/// the rotation matrix `rotation`, then translate by `translation`.
+/// `rotation` is expected to be orthonormal with determinant 1.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Pose3<T> {
+    pub translation: XYZVec<T>,
+    pub rotation: Mat3<T>,
+}
+
+impl<T: VecNum> Pose3<T> {
+    pub fn new(translation: XYZVec<T>, rotation: Mat3<T>) -> Self {
+        Self {
+            translation,
+            rotation,
+        }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(XYZVec::new([T::zero(); 3]), Mat3::identity())
+    }
+
+    /// `v` rotated by this pose, ignoring the translation.
+    pub fn transform_vector(&self, v: XYZVec<T>) -> XYZVec<T> {
+        self.rotation * v
+    }
+
+    /// `p` rotated and then translated by this pose.
+    /// ```
+    ///     use xyzvec::{pose::Pose3, Mat3, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let z = XYZVec::new([0.0f64, 0.0, 1.0]);
+    ///     let pose = Pose3::new(z, Mat3::from_axis_angle(z, std::f64::consts::FRAC_PI_2));
+    ///     let p = pose.transform_point(XYZVec::new([1.0, 0.0, 0.0]));
+    ///     assert_relative_eq!(p.x(), 0.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(p.y(), 1.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(p.z(), 1.0, epsilon = 1e-12);
+    /// ```
+    pub fn transform_point(&self, p: XYZVec<T>) -> XYZVec<T> {
+        self.transform_vector(p) + self.translation
+    }
+
+    /// The pose that applies `other` first and then `self`, i.e.
+    /// `self * other`.
+    pub fn compose(&self, other: Self) -> Self {
+        Self::new(
+            self.transform_point(other.translation),
+            self.rotation * other.rotation,
+        )
+    }
+
+    /// The pose that undoes `self`, using the transpose as the inverse
+    /// rotation.
+    pub fn inverse(&self) -> Self {
+        let rotation = self.rotation.transpose();
+        Self::new(-(rotation * self.translation), rotation)
+    }
+
+    /// Homogeneous matrix of this pose, acting on column vectors
+    /// `[x, y, z, 1]`.
+    pub fn to_mat4(&self) -> Mat4<T> {
+        Mat4::from_affine(self.rotation, self.translation)
+    }
+}
+
+impl<T: VecNum> Mul for Pose3<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        self.compose(other)
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Pose3<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Pose3 {{ translation: {:?}, rotation: {:?} }}",
+            self.translation, self.rotation
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pose2, Pose3};
+    use crate::{Mat3, Mat4, Vector, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I16F16;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn compose_f64() {
+        let a = Pose2::new(XYVec::new([1.0f64, 0.0]), FRAC_PI_2);
+        let b = Pose2::new(XYVec::new([2.0, 0.0]), PI);
+        let p = XYVec::new([0.5, -1.5]);
+        let composed = (a * b).transform_point(p);
+        let stepwise = a.transform_point(b.transform_point(p));
+        assert_relative_eq!(composed.x(), stepwise.x(), epsilon = 1e-12);
+        assert_relative_eq!(composed.y(), stepwise.y(), epsilon = 1e-12);
+        // Rotations wrap instead of accumulating.
+        assert_relative_eq!((a * b).rotation, -FRAC_PI_2, epsilon = 1e-12);
+        let id = a * a.inverse();
+        assert_relative_eq!(id.rotation, 0.0, epsilon = 1e-12);
+        assert_relative_eq!(id.translation.l2_norm(), 0.0, epsilon = 1e-12);
+        assert_eq!(Pose2::identity().transform_point(p), p);
+    }
+
+    #[test]
+    fn interpolate_short_way_f32() {
+        let a = Pose2::new(XYVec::new([0.0f32, 0.0]), 3.0);
+        let b = Pose2::new(XYVec::new([2.0, 4.0]), -3.0);
+        let mid = a.interpolate(b, 0.5);
+        assert_eq!(mid.translation, XYVec::new([1.0, 2.0]));
+        assert_relative_eq!(mid.rotation.abs(), std::f32::consts::PI, epsilon = 1e-5);
+        assert_relative_eq!(a.interpolate(b, 1.0).rotation, -3.0, epsilon = 1e-5);
+    }
+
+    #[test]
+    fn fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let pose = Pose2::new(XYVec::new([f(10.0), f(-2.0)]), f(FRAC_PI_2));
+        let p = pose.transform_point(XYVec::new([f(3.0), f(0.0)]));
+        assert!((p.x() - f(10.0)).abs() < f(1e-3));
+        assert!((p.y() - f(1.0)).abs() < f(1e-3));
+        let v = pose.transform_vector(XYVec::new([f(0.0), f(1.0)]));
+        assert!((v.x() + f(1.0)).abs() < f(1e-3));
+    }
+
+    #[test]
+    fn pose3_f64() {
+        let a = Pose3::new(
+            XYZVec::new([1.0f64, -2.0, 0.5]),
+            Mat3::from_axis_angle(XYZVec::new([1.0, 1.0, 0.0]), 0.8),
+        );
+        let b = Pose3::new(
+            XYZVec::new([0.0, 3.0, 1.0]),
+            Mat3::from_axis_angle(XYZVec::new([0.0, 0.2, 1.0]), -2.1),
+        );
+        let p = XYZVec::new([0.3, 0.7, -1.1]);
+        let composed = (a * b).transform_point(p);
+        let stepwise = a.transform_point(b.transform_point(p));
+        let back = a.inverse().transform_point(a.transform_point(p));
+        for i in 0..3 {
+            assert_relative_eq!(
+                composed.component(i),
+                stepwise.component(i),
+                epsilon = 1e-12
+            );
+            assert_relative_eq!(back.component(i), p.component(i), epsilon = 1e-12);
+        }
+        let m = a.to_mat4();
+        for i in 0..3 {
+            let row: f64 = (0..3).map(|j| m.get(i, j) * p.component(j)).sum();
+            let expected = a.transform_point(p).component(i);
+            assert_relative_eq!(row + m.get(i, 3), expected, epsilon = 1e-12);
+        }
+        assert_eq!(Pose3::<f64>::identity().to_mat4(), Mat4::identity());
+    }
+
+    #[test]
+    fn pose3_fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let z = XYZVec::new([f(0.0), f(0.0), f(1.0)]);
+        let pose = Pose3::new(
+            XYZVec::new([f(2.0), f(0.0), f(0.0)]),
+            Mat3::from_axis_angle(z, f(PI)),
+        );
+        let p = pose.transform_point(XYZVec::new([f(1.0), f(1.0), f(5.0)]));
+        assert!((p.x() - f(1.0)).abs() < f(1e-3));
+        assert!((p.y() + f(1.0)).abs() < f(1e-3));
+        assert_eq!(p.z(), f(5.0));
+    }
+}
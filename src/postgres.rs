@@ -0,0 +1,196 @@
+//! [`ToSql`]/[`FromSql`] for PostgreSQL. Requires the `postgres-types`
+//! feature.
+//!
+//! [`XYVec<f64>`] maps to the built-in `point` type. [`XYZVec<f64>`] maps to
+//! a PostGIS `geometry` holding a `POINT Z`, sent as EWKB without an SRID;
+//! on input an SRID, if present, is skipped.
+
+use crate::{XYVec, XYZVec};
+use bytes::BytesMut;
+use postgres_types::{accepts, to_sql_checked, FromSql, IsNull, ToSql, Type};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+type BoxError = Box<dyn Error + Sync + Send>;
+
+/// Why a value from the database could not be converted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PgError {
+    /// The value did not have the number of bytes its type requires.
+    WrongLength { expected: usize, found: usize },
+    /// The value was well formed but cannot be represented, such as a
+    /// PostGIS geometry other than a 3D point.
+    Unsupported(&'static str),
+}
+
+impl Display for PgError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            PgError::WrongLength { expected, found } => {
+                write!(f, "expected {expected} bytes, found {found}")
+            }
+            PgError::Unsupported(what) => write!(f, "unsupported geometry: {what}"),
+        }
+    }
+}
+
+impl Error for PgError {}
+
+/// EWKB geometry type code for a point.
+const WKB_POINT: u32 = 1;
+/// EWKB flag marking a geometry as having `z` coordinates.
+const EWKB_Z: u32 = 0x8000_0000;
+/// EWKB flag marking a geometry as carrying an SRID.
+const EWKB_SRID: u32 = 0x2000_0000;
+
+fn read_f64s<const N: usize>(raw: &[u8], big_endian: bool) -> Result<[f64; N], PgError> {
+    if raw.len() != N * 8 {
+        return Err(PgError::WrongLength {
+            expected: N * 8,
+            found: raw.len(),
+        });
+    }
+    let mut values = [0.0; N];
+    for (v, bytes) in values.iter_mut().zip(raw.chunks_exact(8)) {
+        let bytes = bytes.try_into().unwrap();
+        *v = if big_endian {
+            f64::from_be_bytes(bytes)
+        } else {
+            f64::from_le_bytes(bytes)
+        };
+    }
+    Ok(values)
+}
+
+impl<'a> FromSql<'a> for XYVec<f64> {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        Ok(XYVec::new(read_f64s(raw, true)?))
+    }
+
+    accepts!(POINT);
+}
+
+impl ToSql for XYVec<f64> {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        out.extend_from_slice(&self.x().to_be_bytes());
+        out.extend_from_slice(&self.y().to_be_bytes());
+        Ok(IsNull::No)
+    }
+
+    accepts!(POINT);
+    to_sql_checked!();
+}
+
+fn is_geometry(ty: &Type) -> bool {
+    ty.name() == "geometry"
+}
+
+impl<'a> FromSql<'a> for XYZVec<f64> {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        let too_short = || PgError::WrongLength {
+            expected: 29,
+            found: raw.len(),
+        };
+        let (&order, rest) = raw.split_first().ok_or_else(too_short)?;
+        let big_endian = match order {
+            0 => true,
+            1 => false,
+            _ => return Err(PgError::Unsupported("unknown byte order").into()),
+        };
+        let (kind, mut rest) = rest.split_at_checked(4).ok_or_else(too_short)?;
+        let kind = kind.try_into().unwrap();
+        let kind = if big_endian {
+            u32::from_be_bytes(kind)
+        } else {
+            u32::from_le_bytes(kind)
+        };
+        if kind & EWKB_SRID != 0 {
+            rest = rest.get(4..).ok_or_else(too_short)?;
+        }
+        if kind & EWKB_Z == 0 || kind & 0xffff != WKB_POINT {
+            return Err(PgError::Unsupported("expected POINT Z").into());
+        }
+        Ok(XYZVec::new(read_f64s(rest, big_endian)?))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        is_geometry(ty)
+    }
+}
+
+impl ToSql for XYZVec<f64> {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        out.extend_from_slice(&[1]);
+        out.extend_from_slice(&(WKB_POINT | EWKB_Z).to_le_bytes());
+        for c in [self.x(), self.y(), self.z()] {
+            out.extend_from_slice(&c.to_le_bytes());
+        }
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        is_geometry(ty)
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PgError;
+    use crate::{XYVec, XYZVec};
+    use bytes::BytesMut;
+    use postgres_types::{FromSql, Kind, ToSql, Type};
+
+    fn geometry() -> Type {
+        Type::new("geometry".into(), 90_000, Kind::Simple, "public".into())
+    }
+
+    #[test]
+    fn point_round_trip() {
+        let p = XYVec::new([1.5, -2.0]);
+        let mut buf = BytesMut::new();
+        p.to_sql_checked(&Type::POINT, &mut buf).unwrap();
+        assert_eq!(buf.len(), 16);
+        assert_eq!(&buf[..8], &1.5f64.to_be_bytes());
+        assert_eq!(XYVec::from_sql(&Type::POINT, &buf).unwrap(), p);
+        assert!(p.to_sql_checked(&Type::FLOAT8, &mut buf).is_err());
+        let err = XYVec::<f64>::from_sql(&Type::POINT, &buf[..12]).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PgError>(),
+            Some(&PgError::WrongLength {
+                expected: 16,
+                found: 12
+            })
+        );
+    }
+
+    #[test]
+    fn ewkb_round_trip() {
+        let p = XYZVec::new([1.0, 2.0, 3.0]);
+        let mut buf = BytesMut::new();
+        p.to_sql_checked(&geometry(), &mut buf).unwrap();
+        assert_eq!(buf.len(), 29);
+        assert_eq!(XYZVec::from_sql(&geometry(), &buf).unwrap(), p);
+
+        // Big-endian, with SRID 4326, as PostGIS may send it.
+        let mut raw = vec![0];
+        raw.extend_from_slice(&(1u32 | 0xa000_0000).to_be_bytes());
+        raw.extend_from_slice(&4326u32.to_be_bytes());
+        for c in [4.0f64, 5.0, 6.0] {
+            raw.extend_from_slice(&c.to_be_bytes());
+        }
+        assert_eq!(
+            XYZVec::from_sql(&geometry(), &raw).unwrap(),
+            XYZVec::new([4.0, 5.0, 6.0])
+        );
+
+        // A 2D point is not a `POINT Z`.
+        let mut raw = vec![1];
+        raw.extend_from_slice(&1u32.to_le_bytes());
+        raw.extend_from_slice(&[0; 16]);
+        assert!(XYZVec::<f64>::from_sql(&geometry(), &raw).is_err());
+    }
+}
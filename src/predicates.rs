@@ -0,0 +1,389 @@
+use crate::{VecNum, XYVec, XYZVec};
+use fixed::types::extra::{LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+use std::cmp::Ordering;
+
+/// Scalars for which the sign of a 2D cross product can be decided robustly.
+///
+/// Integers and fixed point widen to `i128` so the sign is exact regardless of
+/// overflow or truncation in the scalar's own multiplication. `f32` is
+/// evaluated exactly in `f64`; `f64` is evaluated directly.
+pub trait CrossSign: VecNum {
+    /// Sign of `a.x * b.y - a.y * b.x`.
+    fn cross_sign(a: XYVec<Self>, b: XYVec<Self>) -> Ordering;
+}
+
+macro_rules! impl_cross_sign_int {
+    ($($t:ty),*) => {
+        $(
+            impl CrossSign for $t {
+                fn cross_sign(a: XYVec<Self>, b: XYVec<Self>) -> Ordering {
+                    let lhs = a.x() as i128 * b.y() as i128;
+                    let rhs = a.y() as i128 * b.x() as i128;
+                    lhs.cmp(&rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_cross_sign_int!(i8, i16, i32, i64);
+
+macro_rules! impl_cross_sign_fixed {
+    ($($t:ident: $bound:ident),*) => {
+        $(
+            impl<Frac: $bound> CrossSign for fixed::$t<Frac> {
+                fn cross_sign(a: XYVec<Self>, b: XYVec<Self>) -> Ordering {
+                    let lhs = a.x().to_bits() as i128 * b.y().to_bits() as i128;
+                    let rhs = a.y().to_bits() as i128 * b.x().to_bits() as i128;
+                    lhs.cmp(&rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_cross_sign_fixed!(FixedI8: LeEqU8, FixedI16: LeEqU16, FixedI32: LeEqU32, FixedI64: LeEqU64);
+
+impl CrossSign for f32 {
+    fn cross_sign(a: XYVec<Self>, b: XYVec<Self>) -> Ordering {
+        let lhs = a.x() as f64 * b.y() as f64;
+        let rhs = a.y() as f64 * b.x() as f64;
+        lhs.partial_cmp(&rhs).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl CrossSign for f64 {
+    fn cross_sign(a: XYVec<Self>, b: XYVec<Self>) -> Ordering {
+        a.cross_prod(b).partial_cmp(&0.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Scalars for which the sign of a 3D scalar triple product can be decided
+/// robustly.
+///
+/// Integers and fixed point are exact: up to 32 bits they widen to `i128`,
+/// and 64-bit scalars sum their 192-bit products in 64-bit limbs. Floats
+/// are evaluated in `f64`, so their sign can still be wrong when the three
+/// vectors are nearly coplanar.
+pub trait TripleSign: VecNum {
+    /// Sign of `a · (b × c)`; see [`XYZVec::scalar_triple`].
+    fn triple_sign(a: XYZVec<Self>, b: XYZVec<Self>, c: XYZVec<Self>) -> Ordering;
+}
+
+/// Sign of `a · (b × c)` for components widened to `i128`. Exact for
+/// components of up to 32 bits.
+fn triple_sign_i128(a: [i128; 3], b: [i128; 3], c: [i128; 3]) -> Ordering {
+    let det = a[0] * (b[1] * c[2] - b[2] * c[1])
+        + a[1] * (b[2] * c[0] - b[0] * c[2])
+        + a[2] * (b[0] * c[1] - b[1] * c[0]);
+    det.cmp(&0)
+}
+
+macro_rules! impl_triple_sign_int {
+    ($($t:ty),*) => {
+        $(
+            impl TripleSign for $t {
+                fn triple_sign(a: XYZVec<Self>, b: XYZVec<Self>, c: XYZVec<Self>) -> Ordering {
+                    let w = |v: XYZVec<Self>| [v.x() as i128, v.y() as i128, v.z() as i128];
+                    triple_sign_i128(w(a), w(b), w(c))
+                }
+            }
+        )*
+    };
+}
+
+impl_triple_sign_int!(i8, i16, i32);
+
+/// Sign of `a · (b × c)` for 64-bit components. Each of the six terms
+/// `± a_i b_j c_k` is split into 64-bit limbs, which are summed with carries
+/// so that nothing overflows.
+fn triple_sign_i64(a: [i64; 3], b: [i64; 3], c: [i64; 3]) -> Ordering {
+    const MASK: i128 = (1 << 64) - 1;
+    // Little-endian base-2^64 limbs; the low two stay in `0..2^64`.
+    let mut limbs = [0i128; 3];
+    let mut add = |limb: usize, v: i128| {
+        limbs[limb] += v & MASK;
+        limbs[limb + 1] += v >> 64;
+        for i in 0..2 {
+            let carry = limbs[i] >> 64;
+            limbs[i] &= MASK;
+            limbs[i + 1] += carry;
+        }
+    };
+    for (i, j, k) in [(0, 1, 2), (1, 2, 0), (2, 0, 1)] {
+        for (s, j, k) in [(1, j, k), (-1, k, j)] {
+            // |b_j c_k| <= 2^126, so its high limb is at most 2^62 and
+            // both partial products fit in `i128`.
+            let p = b[j] as i128 * c[k] as i128;
+            let f = s * a[i] as i128;
+            add(0, f * (p & MASK));
+            add(1, f * (p >> 64));
+        }
+    }
+    limbs[2].cmp(&0).then(if limbs[0] | limbs[1] != 0 {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    })
+}
+
+impl TripleSign for i64 {
+    fn triple_sign(a: XYZVec<Self>, b: XYZVec<Self>, c: XYZVec<Self>) -> Ordering {
+        triple_sign_i64(*a.as_ref(), *b.as_ref(), *c.as_ref())
+    }
+}
+
+impl<Frac: LeEqU64> TripleSign for fixed::FixedI64<Frac> {
+    fn triple_sign(a: XYZVec<Self>, b: XYZVec<Self>, c: XYZVec<Self>) -> Ordering {
+        let w = |v: XYZVec<Self>| v.as_ref().map(|x| x.to_bits());
+        triple_sign_i64(w(a), w(b), w(c))
+    }
+}
+
+macro_rules! impl_triple_sign_fixed {
+    ($($t:ident: $bound:ident),*) => {
+        $(
+            impl<Frac: $bound> TripleSign for fixed::$t<Frac> {
+                fn triple_sign(a: XYZVec<Self>, b: XYZVec<Self>, c: XYZVec<Self>) -> Ordering {
+                    let w = |v: XYZVec<Self>| v.as_ref().map(|x| x.to_bits() as i128);
+                    triple_sign_i128(w(a), w(b), w(c))
+                }
+            }
+        )*
+    };
+}
+
+impl_triple_sign_fixed!(FixedI8: LeEqU8, FixedI16: LeEqU16, FixedI32: LeEqU32);
+
+macro_rules! impl_triple_sign_float {
+    ($($t:ty),*) => {
+        $(
+            impl TripleSign for $t {
+                fn triple_sign(a: XYZVec<Self>, b: XYZVec<Self>, c: XYZVec<Self>) -> Ordering {
+                    let w = |v: XYZVec<Self>| XYZVec::new(v.as_ref().map(|x| x as f64));
+                    w(a).scalar_triple(w(b), w(c))
+                        .partial_cmp(&0.0)
+                        .unwrap_or(Ordering::Equal)
+                }
+            }
+        )*
+    };
+}
+
+impl_triple_sign_float!(f32, f64);
+
+/// Sorts `points` counterclockwise by angle around `pivot`, starting from
+/// the `+x` direction. Points in the same direction are ordered nearest
+/// first, and points equal to `pivot` come first of all. Comparisons use
+/// [`CrossSign`], so the order is exact for integer and fixed-point
+/// coordinates as long as `point - pivot` does not overflow.
+/// ```
+///     use xyzvec::{predicates::sort_by_angle_around, XYVec};
+///
+///     let mut points = [
+///         XYVec::new([0, -1]),
+///         XYVec::new([-3, 0]),
+///         XYVec::new([2, 0]),
+///         XYVec::new([1, 1]),
+///         XYVec::new([1, 0]),
+///     ];
+///     sort_by_angle_around(XYVec::new([0, 0]), &mut points);
+///     assert_eq!(
+///         points,
+///         [
+///             XYVec::new([1, 0]),
+///             XYVec::new([2, 0]),
+///             XYVec::new([1, 1]),
+///             XYVec::new([-3, 0]),
+///             XYVec::new([0, -1]),
+///         ]
+///     );
+/// ```
+pub fn sort_by_angle_around<T: CrossSign>(pivot: XYVec<T>, points: &mut [XYVec<T>]) {
+    let zero = T::zero();
+    // 0 for the pivot itself, 1 for angles in [0, π), 2 for [π, 2π).
+    let half = |d: XYVec<T>| {
+        if d.x() == zero && d.y() == zero {
+            0
+        } else if d.y() > zero || (d.y() == zero && d.x() > zero) {
+            1
+        } else {
+            2
+        }
+    };
+    points.sort_by(|&a, &b| {
+        let (da, db) = (a - pivot, b - pivot);
+        half(da)
+            .cmp(&half(db))
+            .then_with(|| T::cross_sign(db, da))
+            .then_with(|| {
+                // Same direction: the larger component is farther out.
+                let (xa, xb) = (da.x().abs(), db.x().abs());
+                let (ya, yb) = (da.y().abs(), db.y().abs());
+                xa.partial_cmp(&xb)
+                    .unwrap_or(Ordering::Equal)
+                    .then(ya.partial_cmp(&yb).unwrap_or(Ordering::Equal))
+            })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sort_by_angle_around;
+    use crate::{CrossSign, TripleSign, XYVec, XYZVec};
+    use fixed::types::{I16F16, I28F4, I32F32};
+    use std::cmp::Ordering;
+
+    #[test]
+    fn cross_sign_i32_no_overflow() {
+        let a = XYVec::new([i32::MAX, i32::MAX - 1]);
+        let b = XYVec::new([i32::MAX - 1, i32::MAX - 2]);
+        assert_eq!(i32::cross_sign(a, b), Ordering::Less);
+        assert_eq!(i32::cross_sign(b, a), Ordering::Greater);
+        assert_eq!(i32::cross_sign(a, a), Ordering::Equal);
+    }
+
+    #[test]
+    fn cross_sign_fixed_below_resolution() {
+        // The true cross product is 1/64, which I28F4 multiplication truncates to zero.
+        let a = XYVec::new([I28F4::from_num(0.125), I28F4::ZERO]);
+        let b = XYVec::new([I28F4::ZERO, I28F4::from_num(0.125)]);
+        assert_eq!(a.cross_prod(b), 0);
+        assert_eq!(I28F4::cross_sign(a, b), Ordering::Greater);
+    }
+
+    #[test]
+    fn cross_sign_fixed_large() {
+        let big = I16F16::from_num(30000);
+        let a = XYVec::new([big, big - I16F16::DELTA]);
+        let b = XYVec::new([big, big]);
+        assert_eq!(I16F16::cross_sign(a, b), Ordering::Greater);
+    }
+
+    #[test]
+    fn cross_sign_floats() {
+        let a = XYVec::new([1.0f32, 0.0]);
+        let b = XYVec::new([0.0f32, -1.0]);
+        assert_eq!(f32::cross_sign(a, b), Ordering::Less);
+        let a = XYVec::new([1.0f64, 2.0]);
+        assert_eq!(f64::cross_sign(a, a.scale_by(3.0)), Ordering::Equal);
+    }
+
+    #[test]
+    fn triple_sign_widened() {
+        // Nearly coplanar: the true triple product is 1, while every
+        // product of these components overflows `i32`.
+        let big = i32::MAX;
+        let a = XYZVec::new([big, big - 1, 0]);
+        let b = XYZVec::new([big - 1, big - 2, 0]);
+        let c = XYZVec::new([0, 0, -1]);
+        assert_eq!(i32::triple_sign(a, b, c), Ordering::Greater);
+        assert_eq!(i32::triple_sign(b, a, c), Ordering::Less);
+        assert_eq!(i32::triple_sign(a, a, c), Ordering::Equal);
+
+        // 1/512 in total, which I28F4 multiplication truncates to zero.
+        let e = I28F4::from_num(0.125);
+        let z = I28F4::ZERO;
+        let (a, b, c) = (
+            XYZVec::new([e, z, z]),
+            XYZVec::new([z, e, z]),
+            XYZVec::new([z, z, e]),
+        );
+        assert_eq!(a.scalar_triple(b, c), 0);
+        assert_eq!(I28F4::triple_sign(a, b, c), Ordering::Greater);
+        assert_eq!(I28F4::triple_sign(c, b, a), Ordering::Less);
+
+        // The same near-degenerate triple at the top of the I32F32 range,
+        // where every product overflows.
+        let (big, d) = (I32F32::MAX, I32F32::DELTA);
+        let a = XYZVec::new([big, big - d, I32F32::ZERO]);
+        let b = XYZVec::new([big - d, big - d - d, I32F32::ZERO]);
+        let c = XYZVec::new([I32F32::ZERO, I32F32::ZERO, -I32F32::ONE]);
+        assert_eq!(I32F32::triple_sign(a, b, c), Ordering::Greater);
+        assert_eq!(I32F32::triple_sign(b, a, c), Ordering::Less);
+        assert_eq!(I32F32::triple_sign(a, a, c), Ordering::Equal);
+        // And below its resolution: 2^-30 per component, 2^-90 in total.
+        let e = I32F32::from_num(2f64.powi(-30));
+        let z = I32F32::ZERO;
+        let (a, b, c) = (
+            XYZVec::new([e, z, z]),
+            XYZVec::new([z, e, z]),
+            XYZVec::new([z, z, e]),
+        );
+        assert_eq!(a.scalar_triple(b, c), 0);
+        assert_eq!(I32F32::triple_sign(a, b, c), Ordering::Greater);
+        assert_eq!(I32F32::triple_sign(b, a, c), Ordering::Less);
+        // Extremes of `i64` agree with the sign worked out by hand.
+        let (max, min) = (i64::MAX, i64::MIN);
+        let x = XYZVec::new([max, 0, 0]);
+        let y = XYZVec::new([0, max, 0]);
+        assert_eq!(
+            i64::triple_sign(x, y, XYZVec::new([0, 0, max])),
+            Ordering::Greater
+        );
+        assert_eq!(
+            i64::triple_sign(x, y, XYZVec::new([min, min, min])),
+            Ordering::Less
+        );
+        let m = XYZVec::new([min, max, min]);
+        assert_eq!(
+            i64::triple_sign(m, m, XYZVec::new([1, 2, 3])),
+            Ordering::Equal
+        );
+        // Where `i128` suffices, both paths agree.
+        for t in 0..50i64 {
+            let v = |k: i64| (t * 31 + k).wrapping_mul(0x9E37_79B9_7F4A_7C15u64 as i64) >> 23;
+            let (a, b, c) = ([v(1), v(2), v(3)], [v(4), v(5), v(6)], [v(7), v(8), v(9)]);
+            assert_eq!(
+                super::triple_sign_i64(a, b, c),
+                super::triple_sign_i128(a.map(i128::from), b.map(i128::from), c.map(i128::from))
+            );
+        }
+
+        let x = XYZVec::new([1.0f32, 0.0, 0.0]);
+        let y = XYZVec::new([0.0f32, 1.0, 0.0]);
+        assert_eq!(f32::triple_sign(x, y, x + y), Ordering::Equal);
+        assert_eq!(
+            f32::triple_sign(x, y, XYZVec::new([0.0, 0.0, 1e-30])),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn angular_sort() {
+        // Every point of a 5x5 grid around its center.
+        let pivot = XYVec::new([2i32, 2]);
+        let mut points: Vec<_> = (0..25).map(|i| XYVec::new([i % 5, i / 5])).collect();
+        points.reverse();
+        sort_by_angle_around(pivot, &mut points);
+        assert_eq!(points[0], pivot);
+        let angles: Vec<f64> = points[1..]
+            .iter()
+            .map(|p| {
+                let a = ((p.y() - 2) as f64).atan2((p.x() - 2) as f64);
+                if a < 0.0 {
+                    a + std::f64::consts::TAU
+                } else {
+                    a
+                }
+            })
+            .collect();
+        assert!(angles.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(&points[1..3], &[XYVec::new([3, 2]), XYVec::new([4, 2])]);
+
+        // Nearly parallel directions that only widening can tell apart.
+        let big = I16F16::from_num(30000);
+        let mut fixed = [
+            XYVec::new([big, big]),
+            XYVec::new([big, big - I16F16::DELTA]),
+            XYVec::new([I16F16::ZERO - big, I16F16::ZERO]),
+        ];
+        sort_by_angle_around(XYVec::new([I16F16::ZERO, I16F16::ZERO]), &mut fixed);
+        assert_eq!(fixed[0].y(), big - I16F16::DELTA);
+        assert_eq!(fixed[1].y(), big);
+
+        let mut floats = [XYVec::new([0.0f64, -1.0]), XYVec::new([-1.0, -1e-300])];
+        sort_by_angle_around(XYVec::new([0.0, 0.0]), &mut floats);
+        assert_eq!(floats[0], XYVec::new([-1.0, -1e-300]));
+    }
+}
@@ -0,0 +1,239 @@
+//! Protobuf messages for vectors, as defined in `proto/xyzvec.proto`, with
+//! conversions to and from the vector types. Requires the `prost` feature.
+//!
+//! Float vectors convert both ways infallibly. Fixed-point vectors travel
+//! as raw bits plus their number of fractional bits; decoding checks that
+//! both fit the requested type.
+//! ```
+//!     use fixed::types::I16F16;
+//!     use prost::Message;
+//!     use xyzvec::{proto::Vec2Fixed, XYVec};
+//!
+//!     let v = XYVec::new([I16F16::from_num(1.5), I16F16::from_num(-2)]);
+//!     let bytes = Vec2Fixed::from(v).encode_to_vec();
+//!     let decoded = Vec2Fixed::decode(bytes.as_slice()).unwrap();
+//!     assert_eq!(XYVec::try_from(decoded), Ok(v));
+//! ```
+
+use crate::{XYVec, XYZVec};
+use fixed::types::extra::{LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct Vec2f {
+    #[prost(float, tag = "1")]
+    pub x: f32,
+    #[prost(float, tag = "2")]
+    pub y: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct Vec2d {
+    #[prost(double, tag = "1")]
+    pub x: f64,
+    #[prost(double, tag = "2")]
+    pub y: f64,
+}
+
+/// Fixed-point components as raw bits, scaled by `2^-frac_bits`.
+#[derive(Clone, Copy, PartialEq, Eq, prost::Message)]
+pub struct Vec2Fixed {
+    #[prost(sint64, tag = "1")]
+    pub x: i64,
+    #[prost(sint64, tag = "2")]
+    pub y: i64,
+    #[prost(uint32, tag = "3")]
+    pub frac_bits: u32,
+}
+
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct Vec3f {
+    #[prost(float, tag = "1")]
+    pub x: f32,
+    #[prost(float, tag = "2")]
+    pub y: f32,
+    #[prost(float, tag = "3")]
+    pub z: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, prost::Message)]
+pub struct Vec3d {
+    #[prost(double, tag = "1")]
+    pub x: f64,
+    #[prost(double, tag = "2")]
+    pub y: f64,
+    #[prost(double, tag = "3")]
+    pub z: f64,
+}
+
+/// Fixed-point components as raw bits, scaled by `2^-frac_bits`.
+#[derive(Clone, Copy, PartialEq, Eq, prost::Message)]
+pub struct Vec3Fixed {
+    #[prost(sint64, tag = "1")]
+    pub x: i64,
+    #[prost(sint64, tag = "2")]
+    pub y: i64,
+    #[prost(sint64, tag = "3")]
+    pub z: i64,
+    #[prost(uint32, tag = "4")]
+    pub frac_bits: u32,
+}
+
+/// Why a fixed-point message could not be converted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ProtoError {
+    /// The message was scaled differently from the requested type.
+    FracBits { expected: u32, found: u32 },
+    /// A component's bits do not fit the requested type.
+    OutOfRange,
+}
+
+impl Display for ProtoError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ProtoError::FracBits { expected, found } => {
+                write!(f, "expected {expected} fractional bits, found {found}")
+            }
+            ProtoError::OutOfRange => write!(f, "fixed-point component out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ProtoError {}
+
+macro_rules! impl_float {
+    ($t:ty, $msg2:ident, $msg3:ident) => {
+        impl From<XYVec<$t>> for $msg2 {
+            fn from(v: XYVec<$t>) -> Self {
+                Self { x: v.x(), y: v.y() }
+            }
+        }
+
+        impl From<$msg2> for XYVec<$t> {
+            fn from(m: $msg2) -> Self {
+                XYVec::new([m.x, m.y])
+            }
+        }
+
+        impl From<XYZVec<$t>> for $msg3 {
+            fn from(v: XYZVec<$t>) -> Self {
+                Self {
+                    x: v.x(),
+                    y: v.y(),
+                    z: v.z(),
+                }
+            }
+        }
+
+        impl From<$msg3> for XYZVec<$t> {
+            fn from(m: $msg3) -> Self {
+                XYZVec::new([m.x, m.y, m.z])
+            }
+        }
+    };
+}
+
+impl_float!(f32, Vec2f, Vec3f);
+impl_float!(f64, Vec2d, Vec3d);
+
+macro_rules! impl_fixed {
+    ($($t:ident: $bound:ident),*) => {$(
+        impl<Frac: $bound> From<XYVec<fixed::$t<Frac>>> for Vec2Fixed {
+            fn from(v: XYVec<fixed::$t<Frac>>) -> Self {
+                Self {
+                    x: v.x().to_bits().into(),
+                    y: v.y().to_bits().into(),
+                    frac_bits: fixed::$t::<Frac>::FRAC_NBITS,
+                }
+            }
+        }
+
+        impl<Frac: $bound> TryFrom<Vec2Fixed> for XYVec<fixed::$t<Frac>> {
+            type Error = ProtoError;
+
+            fn try_from(m: Vec2Fixed) -> Result<Self, ProtoError> {
+                check_frac_bits(fixed::$t::<Frac>::FRAC_NBITS, m.frac_bits)?;
+                let c = |bits: i64| {
+                    bits.try_into()
+                        .map(fixed::$t::from_bits)
+                        .map_err(|_| ProtoError::OutOfRange)
+                };
+                Ok(XYVec::new([c(m.x)?, c(m.y)?]))
+            }
+        }
+
+        impl<Frac: $bound> From<XYZVec<fixed::$t<Frac>>> for Vec3Fixed {
+            fn from(v: XYZVec<fixed::$t<Frac>>) -> Self {
+                Self {
+                    x: v.x().to_bits().into(),
+                    y: v.y().to_bits().into(),
+                    z: v.z().to_bits().into(),
+                    frac_bits: fixed::$t::<Frac>::FRAC_NBITS,
+                }
+            }
+        }
+
+        impl<Frac: $bound> TryFrom<Vec3Fixed> for XYZVec<fixed::$t<Frac>> {
+            type Error = ProtoError;
+
+            fn try_from(m: Vec3Fixed) -> Result<Self, ProtoError> {
+                check_frac_bits(fixed::$t::<Frac>::FRAC_NBITS, m.frac_bits)?;
+                let c = |bits: i64| {
+                    bits.try_into()
+                        .map(fixed::$t::from_bits)
+                        .map_err(|_| ProtoError::OutOfRange)
+                };
+                Ok(XYZVec::new([c(m.x)?, c(m.y)?, c(m.z)?]))
+            }
+        }
+    )*};
+}
+
+fn check_frac_bits(expected: u32, found: u32) -> Result<(), ProtoError> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(ProtoError::FracBits { expected, found })
+    }
+}
+
+impl_fixed!(FixedI8: LeEqU8, FixedI16: LeEqU16, FixedI32: LeEqU32, FixedI64: LeEqU64);
+
+#[cfg(test)]
+mod tests {
+    use super::{ProtoError, Vec2Fixed, Vec3Fixed, Vec3f};
+    use crate::{XYVec, XYZVec};
+    use fixed::types::{I16F16, I4F4, I8F8};
+    use prost::Message;
+
+    #[test]
+    fn float_round_trip() {
+        let v = XYZVec::new([1.0f32, -2.5, 1e-3]);
+        let bytes = Vec3f::from(v).encode_to_vec();
+        let decoded = Vec3f::decode(bytes.as_slice()).unwrap();
+        assert_eq!(XYZVec::from(decoded), v);
+    }
+
+    #[test]
+    fn fixed_checks_scale_and_range() {
+        let v = XYZVec::new([I8F8::from_num(-1.25), I8F8::from_num(3), I8F8::MAX]);
+        let m = Vec3Fixed::from(v);
+        assert_eq!(m.frac_bits, 8);
+        assert_eq!(m.x, -320);
+        assert_eq!(XYZVec::try_from(m), Ok(v));
+        assert_eq!(
+            XYZVec::<I16F16>::try_from(m),
+            Err(ProtoError::FracBits {
+                expected: 16,
+                found: 8
+            })
+        );
+
+        let m = Vec2Fixed {
+            x: 200,
+            y: 0,
+            frac_bits: 4,
+        };
+        assert_eq!(XYVec::<I4F4>::try_from(m), Err(ProtoError::OutOfRange));
+    }
+}
@@ -0,0 +1,250 @@
+//! Python bindings through PyO3. Requires the `python` feature.
+//!
+//! [`PyVec2`] and [`PyVec3`] wrap [`XYVec<f64>`] and [`XYZVec<f64>`] as the
+//! immutable Python classes `Vec2` and `Vec3`, with arithmetic operators
+//! and the core methods. Whole point sets cross the boundary as `(n, 2)` or
+//! `(n, 3)` numpy arrays instead of one object per point.
+//!
+//! The bindings are float-only: Python code gets `f64` vectors, and the
+//! fixed-point types stay on the Rust side.
+//!
+//! The bindings are collected by [`python_module`]; an extension crate
+//! exposes them by calling it from its own `#[pymodule]` and building with
+//! PyO3's `extension-module` feature.
+
+use crate::{Vector, XYVec, XYZVec};
+use numpy::{PyArray1, PyArray2, PyArrayMethods, PyReadonlyArray2};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+#[pyclass(name = "Vec2", frozen)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PyVec2(pub XYVec<f64>);
+
+#[pyclass(name = "Vec3", frozen)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PyVec3(pub XYZVec<f64>);
+
+#[pymethods]
+impl PyVec2 {
+    #[new]
+    fn new(x: f64, y: f64) -> Self {
+        Self(XYVec::new([x, y]))
+    }
+
+    #[getter]
+    fn x(&self) -> f64 {
+        self.0.x()
+    }
+
+    #[getter]
+    fn y(&self) -> f64 {
+        self.0.y()
+    }
+
+    fn __add__(&self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+
+    fn __sub__(&self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+
+    fn __mul__(&self, d: f64) -> Self {
+        Self(self.0.scale_by(d))
+    }
+
+    fn __rmul__(&self, d: f64) -> Self {
+        Self(self.0.scale_by(d))
+    }
+
+    fn __neg__(&self) -> Self {
+        Self(-self.0)
+    }
+
+    fn __eq__(&self, other: Self) -> bool {
+        self.0 == other.0
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Vec2({}, {})", self.0.x(), self.0.y())
+    }
+
+    fn dot(&self, other: Self) -> f64 {
+        self.0.dot_prod(other.0)
+    }
+
+    /// The `z` component of the 3D cross product.
+    fn cross(&self, other: Self) -> f64 {
+        self.0.cross_prod(other.0)
+    }
+
+    fn norm(&self) -> f64 {
+        self.0.l2_norm()
+    }
+
+    /// `None` for the zero vector.
+    fn normalized(&self) -> Option<Self> {
+        self.0.try_normalize().map(|u| Self(u.into_inner()))
+    }
+
+    /// Clockwise rotation by `theta` radians, as in
+    /// [`XYVec::rotated_by`].
+    fn rotated(&self, theta: f64) -> Self {
+        Self(self.0.rotated_by(theta))
+    }
+}
+
+#[pymethods]
+impl PyVec3 {
+    #[new]
+    fn new(x: f64, y: f64, z: f64) -> Self {
+        Self(XYZVec::new([x, y, z]))
+    }
+
+    #[getter]
+    fn x(&self) -> f64 {
+        self.0.x()
+    }
+
+    #[getter]
+    fn y(&self) -> f64 {
+        self.0.y()
+    }
+
+    #[getter]
+    fn z(&self) -> f64 {
+        self.0.z()
+    }
+
+    fn __add__(&self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+
+    fn __sub__(&self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+
+    fn __mul__(&self, d: f64) -> Self {
+        Self(self.0.scale_by(d))
+    }
+
+    fn __rmul__(&self, d: f64) -> Self {
+        Self(self.0.scale_by(d))
+    }
+
+    fn __neg__(&self) -> Self {
+        Self(-self.0)
+    }
+
+    fn __eq__(&self, other: Self) -> bool {
+        self.0 == other.0
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Vec3({}, {}, {})", self.0.x(), self.0.y(), self.0.z())
+    }
+
+    fn dot(&self, other: Self) -> f64 {
+        self.0.dot_prod(other.0)
+    }
+
+    fn cross(&self, other: Self) -> Self {
+        Self(self.0.cross(other.0))
+    }
+
+    fn norm(&self) -> f64 {
+        self.0.l2_norm()
+    }
+
+    /// `None` for the zero vector.
+    fn normalized(&self) -> Option<Self> {
+        self.0.try_normalize().map(|u| Self(u.into_inner()))
+    }
+}
+
+/// `points` as an `(n, DIM)` array, one row per vector.
+pub fn to_numpy<'py, V: Vector<Scalar = f64>>(
+    py: Python<'py>,
+    points: &[V],
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let flat = crate::flat::flatten(points.to_vec());
+    PyArray1::from_vec_bound(py, flat).reshape([points.len(), V::DIM])
+}
+
+/// One vector per row of an `(n, DIM)` array. Fails with `ValueError` if
+/// the array has a different number of columns.
+pub fn from_numpy<V: Vector<Scalar = f64> + FromIterator<f64>>(
+    array: PyReadonlyArray2<f64>,
+) -> PyResult<Vec<V>> {
+    let view = array.as_array();
+    if view.ncols() != V::DIM {
+        return Err(PyValueError::new_err(format!(
+            "expected an array of shape (n, {}), found (n, {})",
+            V::DIM,
+            view.ncols()
+        )));
+    }
+    Ok(view
+        .rows()
+        .into_iter()
+        .map(|row| row.iter().copied().collect())
+        .collect())
+}
+
+#[pyfunction]
+fn vec2s_to_numpy<'py>(
+    py: Python<'py>,
+    points: Vec<PyVec2>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let points: Vec<_> = points.into_iter().map(|p| p.0).collect();
+    to_numpy(py, &points)
+}
+
+#[pyfunction]
+fn vec2s_from_numpy(array: PyReadonlyArray2<f64>) -> PyResult<Vec<PyVec2>> {
+    Ok(from_numpy(array)?.into_iter().map(PyVec2).collect())
+}
+
+#[pyfunction]
+fn vec3s_to_numpy<'py>(
+    py: Python<'py>,
+    points: Vec<PyVec3>,
+) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let points: Vec<_> = points.into_iter().map(|p| p.0).collect();
+    to_numpy(py, &points)
+}
+
+#[pyfunction]
+fn vec3s_from_numpy(array: PyReadonlyArray2<f64>) -> PyResult<Vec<PyVec3>> {
+    Ok(from_numpy(array)?.into_iter().map(PyVec3).collect())
+}
+
+/// Adds the classes and numpy conversions to `m`.
+pub fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyVec2>()?;
+    m.add_class::<PyVec3>()?;
+    m.add_function(wrap_pyfunction!(vec2s_to_numpy, m)?)?;
+    m.add_function(wrap_pyfunction!(vec2s_from_numpy, m)?)?;
+    m.add_function(wrap_pyfunction!(vec3s_to_numpy, m)?)?;
+    m.add_function(wrap_pyfunction!(vec3s_from_numpy, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PyVec2, PyVec3};
+
+    #[test]
+    fn wraps_rust_math() {
+        let v = PyVec2::new(3.0, 4.0);
+        assert_eq!(v.norm(), 5.0);
+        assert_eq!(v.__add__(PyVec2::new(1.0, 1.0)), PyVec2::new(4.0, 5.0));
+        assert_eq!(v.__repr__(), "Vec2(3, 4)");
+        assert!(PyVec2::new(0.0, 0.0).normalized().is_none());
+
+        let z = PyVec3::new(1.0, 0.0, 0.0).cross(PyVec3::new(0.0, 1.0, 0.0));
+        assert_eq!(z, PyVec3::new(0.0, 0.0, 1.0));
+        assert_eq!(z.__mul__(2.0).__neg__().z(), -2.0);
+        assert_eq!(z.__rmul__(2.0), z.__mul__(2.0));
+    }
+}
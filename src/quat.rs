@@ -0,0 +1,281 @@
+//! Quaternions, for 3D rotations.
+//!
+//! Like [`Pose3`](crate::Pose3), scalars only need [`VecNum`]: square roots
+//! and trigonometry are done in `f64`.
+
+use crate::{Mat3, VecInner, VecNum, Vector, XYZVec};
+use std::{
+    fmt::{self, Formatter},
+    ops::{Add, Mul, Neg, Sub},
+};
+
+/// Quaternion `w + xi + yj + zk`, stored as a scalar part `w` and a vector
+/// part `(x, y, z)`. Unit quaternions represent rotations; `q` and `-q`
+/// represent the same one.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Quat<T> {
+    pub w: T,
+    pub v: XYZVec<T>,
+}
+
+impl<T: VecInner> Quat<T> {
+    pub fn new(w: T, v: XYZVec<T>) -> Self {
+        Self { w, v }
+    }
+
+    /// `w - xi - yj - zk`. For a unit quaternion this is the inverse
+    /// rotation.
+    pub fn conjugate(&self) -> Self {
+        Self::new(self.w, -self.v)
+    }
+
+    pub fn dot(&self, other: Self) -> T {
+        self.w * other.w + self.v.dot_prod(other.v)
+    }
+
+    pub fn scale_by(&self, d: T) -> Self {
+        Self::new(self.w * d, self.v.scale_by(d))
+    }
+}
+
+impl<T: VecNum> Quat<T> {
+    pub fn identity() -> Self {
+        Self::new(T::one(), XYZVec::new([T::zero(); 3]))
+    }
+
+    /// Rotation counterclockwise by `angle` radians about `axis`, looking
+    /// down the axis toward the origin. `axis` need not be normalized; a
+    /// zero `axis` gives the identity.
+    /// ```
+    ///     use xyzvec::{quat::Quat, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let q = Quat::from_axis_angle(XYZVec::new([0.0f64, 0.0, 3.0]), std::f64::consts::FRAC_PI_2);
+    ///     let v = q.rotate(XYZVec::new([1.0, 0.0, 0.0]));
+    ///     assert_relative_eq!(v.x(), 0.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(v.y(), 1.0, epsilon = 1e-12);
+    /// ```
+    pub fn from_axis_angle(axis: XYZVec<T>, angle: T) -> Self {
+        let [x, y, z] = [axis.x(), axis.y(), axis.z()].map(|c| c.to_f64());
+        let len = (x * x + y * y + z * z).sqrt();
+        if len == 0.0 {
+            return Self::identity();
+        }
+        let half = angle.to_f64() / 2.0;
+        let s = half.sin() / len;
+        Self::new(
+            T::from_f64(half.cos()),
+            XYZVec::new([x * s, y * s, z * s].map(T::from_f64)),
+        )
+    }
+
+    /// The rotation of the orthonormal matrix `m`.
+    pub fn from_mat3(m: &Mat3<T>) -> Self {
+        let g = |i, j| m.get(i, j).to_f64();
+        let trace = g(0, 0) + g(1, 1) + g(2, 2);
+        // Divide by the largest of the four candidates for accuracy.
+        let [w, x, y, z] = if trace > 0.0 {
+            let s = (trace + 1.0).sqrt() * 2.0;
+            [
+                s / 4.0,
+                (g(2, 1) - g(1, 2)) / s,
+                (g(0, 2) - g(2, 0)) / s,
+                (g(1, 0) - g(0, 1)) / s,
+            ]
+        } else if g(0, 0) > g(1, 1) && g(0, 0) > g(2, 2) {
+            let s = (1.0 + g(0, 0) - g(1, 1) - g(2, 2)).sqrt() * 2.0;
+            [
+                (g(2, 1) - g(1, 2)) / s,
+                s / 4.0,
+                (g(0, 1) + g(1, 0)) / s,
+                (g(0, 2) + g(2, 0)) / s,
+            ]
+        } else if g(1, 1) > g(2, 2) {
+            let s = (1.0 + g(1, 1) - g(0, 0) - g(2, 2)).sqrt() * 2.0;
+            [
+                (g(0, 2) - g(2, 0)) / s,
+                (g(0, 1) + g(1, 0)) / s,
+                s / 4.0,
+                (g(1, 2) + g(2, 1)) / s,
+            ]
+        } else {
+            let s = (1.0 + g(2, 2) - g(0, 0) - g(1, 1)).sqrt() * 2.0;
+            [
+                (g(1, 0) - g(0, 1)) / s,
+                (g(0, 2) + g(2, 0)) / s,
+                (g(1, 2) + g(2, 1)) / s,
+                s / 4.0,
+            ]
+        };
+        Self::new(T::from_f64(w), XYZVec::new([x, y, z].map(T::from_f64)))
+    }
+
+    /// Rotation matrix of this unit quaternion.
+    pub fn to_mat3(&self) -> Mat3<T> {
+        let (w, [x, y, z]) = (self.w, [self.v.x(), self.v.y(), self.v.z()]);
+        let one = T::one();
+        let two = one + one;
+        Mat3::new([
+            [
+                one - two * (y * y + z * z),
+                two * (x * y - w * z),
+                two * (x * z + w * y),
+            ],
+            [
+                two * (x * y + w * z),
+                one - two * (x * x + z * z),
+                two * (y * z - w * x),
+            ],
+            [
+                two * (x * z - w * y),
+                two * (y * z + w * x),
+                one - two * (x * x + y * y),
+            ],
+        ])
+    }
+
+    /// `p` rotated by this unit quaternion, i.e. the vector part of
+    /// `q * p * q⁻¹`.
+    pub fn rotate(&self, p: XYZVec<T>) -> XYZVec<T> {
+        let two = T::one() + T::one();
+        let t = self.v.cross(p).scale_by(two);
+        p + t.scale_by(self.w) + self.v.cross(t)
+    }
+
+    /// Euclidean length of the four components.
+    pub fn norm(&self) -> T {
+        T::from_f64(self.dot(*self).to_f64().sqrt())
+    }
+
+    /// `self` scaled to unit length, or `None` if it is zero.
+    pub fn normalize(&self) -> Option<Self> {
+        let len = self.dot(*self).to_f64().sqrt();
+        (len > 0.0 && len.is_finite()).then(|| {
+            let s = 1.0 / len;
+            Self::new(
+                T::from_f64(self.w.to_f64() * s),
+                self.v.map(|c| T::from_f64(c.to_f64() * s)),
+            )
+        })
+    }
+}
+
+impl<T: VecInner> Add for Quat<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.w + other.w, self.v + other.v)
+    }
+}
+
+impl<T: VecInner> Sub for Quat<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.w - other.w, self.v - other.v)
+    }
+}
+
+impl<T: VecInner> Neg for Quat<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.w, -self.v)
+    }
+}
+
+/// Hamilton product: `self * other` rotates by `other` first, then `self`.
+impl<T: VecInner> Mul for Quat<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.w * other.w - self.v.dot_prod(other.v),
+            other.v.scale_by(self.w) + self.v.scale_by(other.w) + self.v.cross(other.v),
+        )
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Quat<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Quat {{ w: {:?}, v: {:?} }}", self.w, self.v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Quat;
+    use crate::{Mat3, Vector, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I16F16;
+
+    #[test]
+    fn matches_mat3_f64() {
+        let axes = [
+            XYZVec::new([1.0f64, 2.0, -0.5]),
+            XYZVec::new([1.0, 0.0, 0.0]),
+            XYZVec::new([0.0, -1.0, 0.01]),
+            XYZVec::new([0.1, 0.0, 1.0]),
+        ];
+        let p = XYZVec::new([0.3, -0.7, 1.9]);
+        for (axis, angle) in axes.into_iter().zip([0.4, 3.0, -2.9, 3.1]) {
+            let q = Quat::from_axis_angle(axis, angle);
+            let m = Mat3::from_axis_angle(axis, angle);
+            let back = Quat::from_mat3(&m);
+            let via_mat = q.to_mat3() * p;
+            for i in 0..3 {
+                assert_relative_eq!(
+                    q.rotate(p).component(i),
+                    (m * p).component(i),
+                    epsilon = 1e-12
+                );
+                assert_relative_eq!(via_mat.component(i), (m * p).component(i), epsilon = 1e-12);
+            }
+            // `back` may be `-q`; both are the same rotation.
+            assert_relative_eq!(back.dot(q).abs(), 1.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn zero_axis_is_identity() {
+        let q = Quat::from_axis_angle(XYZVec::new([0.0f64; 3]), 1.0);
+        assert_eq!(q, Quat::identity());
+        let zero = I16F16::ZERO;
+        let q = Quat::from_axis_angle(XYZVec::new([zero; 3]), I16F16::from_num(1));
+        assert_eq!(q, Quat::identity());
+    }
+
+    #[test]
+    fn compose_f32() {
+        let a = Quat::from_axis_angle(XYZVec::new([0.0f32, 0.0, 1.0]), 0.5);
+        let b = Quat::from_axis_angle(XYZVec::new([1.0, 0.0, 0.0]), -1.2);
+        let p = XYZVec::new([1.0, 2.0, 3.0]);
+        let composed = (a * b).rotate(p);
+        let stepwise = a.rotate(b.rotate(p));
+        for i in 0..3 {
+            assert_relative_eq!(composed.component(i), stepwise.component(i), epsilon = 1e-5);
+        }
+        let undone = a.conjugate().rotate(a.rotate(p));
+        assert_relative_eq!((undone - p).l2_norm(), 0.0, epsilon = 1e-5);
+        let q = Quat::new(2.0f32, XYZVec::new([0.0, 0.0, 0.0]))
+            .normalize()
+            .unwrap();
+        assert_eq!(q, Quat::identity());
+        assert!(Quat::new(0.0f32, XYZVec::new([0.0; 3]))
+            .normalize()
+            .is_none());
+    }
+
+    #[test]
+    fn fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let q = Quat::from_axis_angle(
+            XYZVec::new([f(0.0), f(0.0), f(1.0)]),
+            f(std::f64::consts::PI),
+        );
+        let p = q.rotate(XYZVec::new([f(2.0), f(1.0), f(0.0)]));
+        assert!((p.x() + f(2.0)).abs() < f(1e-3));
+        assert!((p.y() + f(1.0)).abs() < f(1e-3));
+        assert!((q.norm() - f(1.0)).abs() < f(1e-3));
+    }
+}
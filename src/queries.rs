@@ -0,0 +1,265 @@
+//! Closest-point queries against lines, segments, planes, and boxes, and
+//! where two lines in the plane meet. Closest-point line and segment
+//! queries work on both [`XYVec`] and [`XYZVec`].
+
+use crate::{interp::clamp, Aabb, Mat2, Plane, VecFloat, VecNum, Vector, XYVec, XYZVec};
+
+/// Closest point to `p` on the infinite line `origin + t * direction`,
+/// along with its parameter `t`. A zero `direction` yields `(origin, 0)`.
+/// ```
+///     use xyzvec::{queries::closest_point_on_line, XYVec};
+///     use approx::assert_relative_eq;
+///
+///     let (q, t) = closest_point_on_line(
+///         XYVec::new([3.0f64, 1.0]),
+///         XYVec::new([0.0, 0.0]),
+///         XYVec::new([2.0, 0.0]),
+///     );
+///     assert_relative_eq!(q.x(), 3.0);
+///     assert_relative_eq!(q.y(), 0.0);
+///     assert_relative_eq!(t, 1.5);
+/// ```
+pub fn closest_point_on_line<V>(p: V, origin: V, direction: V) -> (V, V::Scalar)
+where
+    V: Vector,
+    V::Scalar: VecNum,
+{
+    let dd = direction.l2_norm_sqd();
+    if dd == V::Scalar::zero() {
+        return (origin, V::Scalar::zero());
+    }
+    let t = (p - origin).dot_prod(direction) / dd;
+    (origin + direction.scale_by(t), t)
+}
+
+/// Closest point to `p` on the segment from `a` to `b`, along with its
+/// parameter `t` clamped to `[0, 1]`.
+/// ```
+///     use xyzvec::{queries::closest_point_on_segment, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let a = XYZVec::new([0.0f64, 0.0, 0.0]);
+///     let b = XYZVec::new([0.0, 0.0, 4.0]);
+///     let (q, t) = closest_point_on_segment(XYZVec::new([1.0, 1.0, 5.0]), a, b);
+///     assert_eq!(q, b);
+///     assert_relative_eq!(t, 1.0);
+/// ```
+pub fn closest_point_on_segment<V>(p: V, a: V, b: V) -> (V, V::Scalar)
+where
+    V: Vector,
+    V::Scalar: VecNum,
+{
+    let (_, t) = closest_point_on_line(p, a, b - a);
+    let t = clamp(t, V::Scalar::zero(), V::Scalar::one());
+    (a + (b - a).scale_by(t), t)
+}
+
+/// Closest point to `p` on `plane`, along with `normal · p - offset` (see
+/// [`Plane::signed_distance`]). That value is the signed distance only when
+/// `plane.normal` has unit length; otherwise it is the distance times the
+/// normal's length. The closest point does not depend on the scale.
+/// ```
+///     use xyzvec::{queries::closest_point_on_plane, Plane, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let ground = Plane::new(XYZVec::new([0.0f64, 0.0, 1.0]), 0.0);
+///     let (q, d) = closest_point_on_plane(XYZVec::new([1.0, 2.0, 3.0]), &ground);
+///     assert_relative_eq!(q.z(), 0.0);
+///     assert_relative_eq!(d, 3.0);
+/// ```
+pub fn closest_point_on_plane<T: VecNum>(p: XYZVec<T>, plane: &Plane<T>) -> (XYZVec<T>, T) {
+    let scaled = plane.signed_distance(p);
+    let nn = plane.normal.l2_norm_sqd();
+    if nn == T::zero() {
+        return (p, scaled);
+    }
+    (p - plane.normal.scale_by(scaled / nn), scaled)
+}
+
+/// Closest point to `p` in (or on) `aabb`, along with the squared distance
+/// between them, which is zero when `p` is inside.
+/// ```
+///     use xyzvec::{queries::closest_point_on_aabb, Aabb2, XYVec};
+///     use approx::assert_relative_eq;
+///
+///     let b = Aabb2::new(XYVec::new([0.0f64, 0.0]), XYVec::new([1.0, 1.0]));
+///     let (q, d2) = closest_point_on_aabb(XYVec::new([4.0, 5.0]), &b);
+///     assert_eq!(q, XYVec::new([1.0, 1.0]));
+///     assert_relative_eq!(d2, 25.0);
+/// ```
+pub fn closest_point_on_aabb<V>(p: V, aabb: &Aabb<V>) -> (V, V::Scalar)
+where
+    V: Vector,
+    V::Scalar: VecNum,
+{
+    let q = aabb.clamp(p);
+    (q, (p - q).l2_norm_sqd())
+}
+
+/// Solution `x` of `m * x = b` by Cramer's rule, or `None` if `m` is
+/// singular. The singularity test is exact, so for floats a nearly
+/// singular `m` gives a huge, inaccurate `x` rather than `None`.
+/// ```
+///     use xyzvec::{queries::solve2x2, Mat2, XYVec};
+///
+///     let m = Mat2::new([[2, 1], [1, -1]]);
+///     assert_eq!(solve2x2(&m, XYVec::new([5, 1])), Some(XYVec::new([2, 1])));
+///     assert_eq!(solve2x2(&Mat2::new([[1, 2], [2, 4]]), XYVec::new([1, 1])), None);
+/// ```
+pub fn solve2x2<T: VecNum>(m: &Mat2<T>, b: XYVec<T>) -> Option<XYVec<T>> {
+    let det = m.determinant();
+    if det == T::zero() {
+        return None;
+    }
+    let (c0, c1) = (m.col(0), m.col(1));
+    Some(XYVec::new([b.cross_prod(c1) / det, c0.cross_prod(b) / det]))
+}
+
+/// Where the infinite lines `p1 + t * d1` and `p2 + s * d2` meet. `None`
+/// if either direction is zero or the lines are parallel (including
+/// coincident) to within a few ulps of the angle between them.
+/// ```
+///     use xyzvec::{queries::intersect_lines, XYVec};
+///     use approx::assert_relative_eq;
+///
+///     let p = intersect_lines(
+///         XYVec::new([0.0f64, 0.0]),
+///         XYVec::new([1.0, 1.0]),
+///         XYVec::new([4.0, 0.0]),
+///         XYVec::new([0.0, 2.0]),
+///     )
+///     .unwrap();
+///     assert_relative_eq!(p.x(), 4.0);
+///     assert_relative_eq!(p.y(), 4.0);
+/// ```
+pub fn intersect_lines<T: VecFloat>(
+    p1: XYVec<T>,
+    d1: XYVec<T>,
+    p2: XYVec<T>,
+    d2: XYVec<T>,
+) -> Option<XYVec<T>> {
+    // |d1 × d2| = |d1| |d2| sin(angle); compare squares to skip the roots.
+    let cross = d1.cross_prod(d2);
+    let tolerance = T::epsilon() * T::from_f64(16.0);
+    let scale = d1.l2_norm_sqd() * d2.l2_norm_sqd();
+    if scale == T::zero() || cross * cross <= scale * tolerance * tolerance {
+        return None;
+    }
+    let [t, _] = *solve2x2(&Mat2::from_cols([d1, -d2]), p2 - p1)?.as_ref();
+    Some(p1 + d1.scale_by(t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Aabb3;
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    #[test]
+    fn line_3d_f64() {
+        let (q, t) = closest_point_on_line(
+            XYZVec::new([1.0f64, 1.0, 1.0]),
+            XYZVec::new([0.0, 0.0, 0.0]),
+            XYZVec::new([1.0, 1.0, 0.0]),
+        );
+        assert_relative_eq!(t, 1.0);
+        assert_relative_eq!(q.x(), 1.0);
+        assert_relative_eq!(q.y(), 1.0);
+        assert_relative_eq!(q.z(), 0.0);
+    }
+
+    #[test]
+    fn degenerate_line_f32() {
+        let o = XYVec::new([1.0f32, 2.0]);
+        let (q, t) = closest_point_on_line(XYVec::new([5.0, 5.0]), o, XYVec::<f32>::zeroes());
+        assert_eq!(q, o);
+        assert_relative_eq!(t, 0.0);
+        let (q, t) = closest_point_on_segment(XYVec::new([5.0, 5.0]), o, o);
+        assert_eq!(q, o);
+        assert_relative_eq!(t, 0.0);
+    }
+
+    #[test]
+    fn segment_clamps_f32() {
+        let a = XYVec::new([0.0f32, 0.0]);
+        let b = XYVec::new([2.0f32, 0.0]);
+        let (q, t) = closest_point_on_segment(XYVec::new([-1.0, 1.0]), a, b);
+        assert_eq!(q, a);
+        assert_relative_eq!(t, 0.0);
+        let (q, t) = closest_point_on_segment(XYVec::new([0.5, 1.0]), a, b);
+        assert_relative_eq!(q.x(), 0.5);
+        assert_relative_eq!(t, 0.25);
+    }
+
+    #[test]
+    fn segment_fixed() {
+        let f = I28F4::from_num;
+        let a = XYVec::new([f(0), f(0)]);
+        let b = XYVec::new([f(4), f(4)]);
+        let (q, t) = closest_point_on_segment(XYVec::new([f(4), f(0)]), a, b);
+        assert_eq!(q, XYVec::new([f(2), f(2)]));
+        assert_eq!(t, 0.5);
+    }
+
+    #[test]
+    fn plane_unnormalized_f64() {
+        let plane = Plane::new(XYZVec::new([0.0f64, 2.0, 0.0]), 2.0);
+        let (q, d) = closest_point_on_plane(XYZVec::new([3.0, 4.0, 1.0]), &plane);
+        assert_relative_eq!(q.x(), 3.0);
+        assert_relative_eq!(q.y(), 1.0);
+        assert_relative_eq!(q.z(), 1.0);
+        // Three units away, scaled by the normal's length of two.
+        assert_relative_eq!(d, 6.0);
+    }
+
+    #[test]
+    fn aabb_inside_f64() {
+        let b = Aabb3::new(
+            XYZVec::new([0.0f64, 0.0, 0.0]),
+            XYZVec::new([1.0, 2.0, 3.0]),
+        );
+        let p = XYZVec::new([0.5, 0.5, 0.5]);
+        let (q, d2) = closest_point_on_aabb(p, &b);
+        assert_eq!(q, p);
+        assert_relative_eq!(d2, 0.0);
+        let (q, d2) = closest_point_on_aabb(XYZVec::new([0.5, -2.0, 5.0]), &b);
+        assert_eq!(q, XYZVec::new([0.5, 0.0, 3.0]));
+        assert_relative_eq!(d2, 8.0);
+    }
+
+    #[test]
+    fn lines_meet_f64() {
+        let p = intersect_lines(
+            XYVec::new([1.0f64, 1.0]),
+            XYVec::new([2.0, 0.0]),
+            XYVec::new([3.0, -5.0]),
+            XYVec::new([-1.0, 2.0]),
+        )
+        .unwrap();
+        assert_relative_eq!(p.x(), 0.0);
+        assert_relative_eq!(p.y(), 1.0);
+        // Parallel, coincident, and degenerate lines.
+        let (o, d) = (XYVec::new([0.0f64, 0.0]), XYVec::new([1.0, 3.0]));
+        assert_eq!(intersect_lines(o, d, XYVec::new([0.0, 1.0]), d), None);
+        assert_eq!(intersect_lines(o, d, d, d.scale_by(-1e-9)), None);
+        assert_eq!(intersect_lines(o, XYVec::<f64>::zeroes(), o, d), None);
+    }
+
+    #[test]
+    fn nearly_parallel_lines_f32() {
+        let o = XYVec::new([0.0f32, 0.0]);
+        let d = XYVec::new([1.0f32, 0.0]);
+        assert_eq!(intersect_lines(o, d, o, XYVec::new([1.0, 1e-7])), None);
+        let p = intersect_lines(o, d, XYVec::new([0.0, 1.0]), XYVec::new([1.0, -1e-3]));
+        assert_relative_eq!(p.unwrap().x(), 1000.0, max_relative = 1e-4);
+    }
+
+    #[test]
+    fn solve_fixed() {
+        let f = |v: f64| I28F4::from_num(v);
+        let m = Mat2::new([[f(2.0), f(0.0)], [f(1.0), f(4.0)]]);
+        let x = solve2x2(&m, XYVec::new([f(3.0), f(-0.5)])).unwrap();
+        assert_eq!(x, XYVec::new([f(1.5), f(-0.5)]));
+    }
+}
@@ -0,0 +1,105 @@
+//! Random directions, drawn with any [`rand::Rng`]. Requires the `rand`
+//! feature.
+
+use crate::{angle::Radians, VecFloat, XYVec, XYZVec};
+use rand::Rng;
+use std::f64::consts::TAU;
+
+/// Unit vector within `half_angle` of `axis`, uniformly distributed over
+/// solid angle. `axis` need not be normalized. Sampling the polar angle
+/// uniformly instead would crowd directions toward the axis; here
+/// `cos θ` is uniform.
+/// ```
+///     use xyzvec::{angle::Degrees, random::random_in_cone, XYZVec};
+///     use rand::{rngs::SmallRng, SeedableRng};
+///
+///     let mut rng = SmallRng::seed_from_u64(7);
+///     let axis = XYZVec::new([0.0f64, 0.0, 5.0]);
+///     let d = random_in_cone(&mut rng, axis, Degrees(30.0));
+///     assert!((d.l2_norm() - 1.0).abs() < 1e-12);
+///     assert!(d.z() >= 30f64.to_radians().cos());
+/// ```
+pub fn random_in_cone<T: VecFloat, R: Rng + ?Sized>(
+    rng: &mut R,
+    axis: XYZVec<T>,
+    half_angle: impl Into<Radians<T>>,
+) -> XYZVec<T> {
+    let cos_max = half_angle.into().0.to_f64().cos();
+    let cos_theta = 1.0 - rng.gen::<f64>() * (1.0 - cos_max);
+    let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+    let phi = rng.gen::<f64>() * TAU;
+    let n = axis.div_by(axis.l2_norm_sqd().sqrt());
+    let (t, b) = n.any_orthonormal_pair();
+    let f = T::from_f64;
+    t.scale_by(f(sin_theta * phi.cos()))
+        + b.scale_by(f(sin_theta * phi.sin()))
+        + n.scale_by(f(cos_theta))
+}
+
+/// Unit vector within `half_angle` of `direction` in the plane, uniformly
+/// distributed over angle.
+/// ```
+///     use xyzvec::{random::random_in_arc, XYVec};
+///     use rand::{rngs::SmallRng, SeedableRng};
+///
+///     let mut rng = SmallRng::seed_from_u64(7);
+///     let d = random_in_arc(&mut rng, XYVec::new([0.0f32, 2.0]), 0.1);
+///     assert!(d.y() > 0.99);
+/// ```
+pub fn random_in_arc<T: VecFloat, R: Rng + ?Sized>(
+    rng: &mut R,
+    direction: XYVec<T>,
+    half_angle: impl Into<Radians<T>>,
+) -> XYVec<T> {
+    let half = half_angle.into().0.to_f64();
+    let base = direction.y().to_f64().atan2(direction.x().to_f64());
+    let angle = base + (2.0 * rng.gen::<f64>() - 1.0) * half;
+    XYVec::new([T::from_f64(angle.cos()), T::from_f64(angle.sin())])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{random_in_arc, random_in_cone};
+    use crate::{angle::Degrees, XYVec, XYZVec};
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    #[test]
+    fn cone_is_uniform_over_solid_angle() {
+        let mut rng = SmallRng::seed_from_u64(1);
+        let axis = XYZVec::new([1.0f64, -2.0, 0.5]);
+        let n = axis.div_by(axis.l2_norm());
+        let cos_max = 60f64.to_radians().cos();
+        // The cap within 60° has its inner half of solid angle where
+        // cos θ > (1 + cos 60°) / 2, so half the samples should land there.
+        let split = (1.0 + cos_max) / 2.0;
+        let samples = 20_000;
+        let mut inner = 0;
+        for _ in 0..samples {
+            let d = random_in_cone(&mut rng, axis, Degrees(60.0));
+            assert!((d.l2_norm() - 1.0).abs() < 1e-12);
+            let c = d.dot_prod(n);
+            assert!(c >= cos_max - 1e-12);
+            if c > split {
+                inner += 1;
+            }
+        }
+        let fraction = inner as f64 / samples as f64;
+        assert!((fraction - 0.5).abs() < 0.02, "{fraction}");
+    }
+
+    #[test]
+    fn arc_f32() {
+        let mut rng = SmallRng::seed_from_u64(2);
+        let dir = XYVec::new([-1.0f32, 0.0]);
+        let (mut min, mut max) = (f32::MAX, f32::MIN);
+        for _ in 0..1000 {
+            let d = random_in_arc(&mut rng, dir, Degrees(20.0));
+            assert!((d.l2_norm() - 1.0).abs() < 1e-6);
+            let off = d.y().atan2(-d.x()).to_degrees();
+            min = min.min(off);
+            max = max.max(off);
+        }
+        assert!((-20.001..-19.0).contains(&min));
+        assert!(max > 19.0 && max <= 20.001);
+    }
+}
@@ -0,0 +1,331 @@
+//! Robust model fitting by random sample consensus (RANSAC).
+//!
+//! Each round fits a model to a minimal random sample of the points and
+//! counts the inliers: the points within a distance threshold of it. The
+//! model with the most inliers wins and is then refit to all of them by
+//! least squares. Lines ([`LineFit`]), planes ([`PlaneFit`]), and circles
+//! ([`Circle`]) are supported out of the box; other shapes can implement
+//! [`RansacModel`].
+
+use crate::{
+    fit::{fit_line, fit_plane},
+    Circle, LineFit, Mat3, Plane, PlaneFit, VecFloat, Vector, XYVec, XYZVec,
+};
+
+/// A shape that can be fitted to points by [`ransac`].
+pub trait RansacModel<V: Vector>: Sized {
+    /// Number of points that determine a model.
+    const SAMPLE_SIZE: usize;
+
+    /// Model through exactly `SAMPLE_SIZE` points, or `None` if they are
+    /// degenerate (e.g. coincident or collinear).
+    fn from_sample(sample: &[V]) -> Option<Self>;
+
+    /// Unsigned distance from `p` to the model.
+    fn distance(&self, p: V) -> V::Scalar;
+
+    /// Least-squares model for a set of inliers. The default keeps the
+    /// sampled model.
+    fn refit(_inliers: &[V]) -> Option<Self> {
+        None
+    }
+}
+
+impl<T: VecFloat> RansacModel<XYVec<T>> for LineFit<T> {
+    const SAMPLE_SIZE: usize = 2;
+
+    fn from_sample(sample: &[XYVec<T>]) -> Option<Self> {
+        let (a, b) = (sample[0], sample[1]);
+        let d = b - a;
+        let len = d.l2_norm_sqd().sqrt();
+        (len > T::zero()).then(|| LineFit {
+            point: a,
+            direction: d.div_by(len),
+            rms_residual: T::zero(),
+        })
+    }
+
+    fn distance(&self, p: XYVec<T>) -> T {
+        (p - self.point).cross_prod(self.direction).abs()
+    }
+
+    fn refit(inliers: &[XYVec<T>]) -> Option<Self> {
+        fit_line(inliers)
+    }
+}
+
+impl<T: VecFloat> RansacModel<XYZVec<T>> for PlaneFit<T> {
+    const SAMPLE_SIZE: usize = 3;
+
+    fn from_sample(sample: &[XYZVec<T>]) -> Option<Self> {
+        let plane = Plane::from_points(sample[0], sample[1], sample[2])?;
+        Some(PlaneFit {
+            point: sample[0],
+            plane,
+            rms_residual: T::zero(),
+        })
+    }
+
+    fn distance(&self, p: XYZVec<T>) -> T {
+        self.plane.signed_distance(p).abs()
+    }
+
+    fn refit(inliers: &[XYZVec<T>]) -> Option<Self> {
+        fit_plane(inliers)
+    }
+}
+
+impl<T: VecFloat> RansacModel<XYVec<T>> for Circle<T> {
+    const SAMPLE_SIZE: usize = 3;
+
+    fn from_sample(sample: &[XYVec<T>]) -> Option<Self> {
+        let a = sample[0];
+        let (ab, ac) = (sample[1] - a, sample[2] - a);
+        let d = ab.cross_prod(ac) * (T::one() + T::one());
+        if d == T::zero() {
+            return None;
+        }
+        let (bb, cc) = (ab.l2_norm_sqd(), ac.l2_norm_sqd());
+        let offset = XYVec::new([
+            (ac.y() * bb - ab.y() * cc) / d,
+            (ab.x() * cc - ac.x() * bb) / d,
+        ]);
+        Some(Self::new(a + offset, offset.l2_norm_sqd().sqrt()))
+    }
+
+    fn distance(&self, p: XYVec<T>) -> T {
+        ((p - self.center).l2_norm_sqd().sqrt() - self.radius).abs()
+    }
+
+    /// Algebraic (Kåsa) fit: least squares on
+    /// `x² + y² + D x + E y + F = 0`, solved by Cramer's rule about the
+    /// centroid.
+    fn refit(inliers: &[XYVec<T>]) -> Option<Self> {
+        let n = T::from_f64(inliers.len() as f64);
+        let mean = inliers
+            .iter()
+            .fold(XYVec::new([T::zero(), T::zero()]), |acc, &p| acc + p)
+            .div_by(n);
+        let mut m = [[T::zero(); 3]; 3];
+        let mut rhs = [T::zero(); 3];
+        for &p in inliers {
+            let q = p - mean;
+            let row = [q.x(), q.y(), T::one()];
+            let z = -q.l2_norm_sqd();
+            for i in 0..3 {
+                for j in 0..3 {
+                    m[i][j] += row[i] * row[j];
+                }
+                rhs[i] += row[i] * z;
+            }
+        }
+        let det = Mat3::new(m).determinant();
+        if det == T::zero() {
+            return None;
+        }
+        let solve = |k: usize| {
+            let mut mk = m;
+            for (row, &r) in mk.iter_mut().zip(&rhs) {
+                row[k] = r;
+            }
+            Mat3::new(mk).determinant() / det
+        };
+        let two = T::one() + T::one();
+        let (d, e, f) = (solve(0), solve(1), solve(2));
+        let offset = XYVec::new([-d / two, -e / two]);
+        let r_sqd = offset.l2_norm_sqd() - f;
+        (r_sqd > T::zero()).then(|| Self::new(mean + offset, r_sqd.sqrt()))
+    }
+}
+
+/// Settings for [`ransac`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RansacOptions<T> {
+    /// Points within this distance of a model are its inliers.
+    pub threshold: T,
+    /// Number of random samples to try.
+    pub iterations: usize,
+    /// Seed for the sampling, so results are reproducible.
+    pub seed: u64,
+}
+
+impl<T> RansacOptions<T> {
+    /// 1000 iterations with a fixed seed.
+    pub fn new(threshold: T) -> Self {
+        Self {
+            threshold,
+            iterations: 1000,
+            seed: 0,
+        }
+    }
+}
+
+/// Best model found and the indices of its inliers, in increasing order.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RansacResult<M> {
+    pub model: M,
+    pub inliers: Vec<usize>,
+}
+
+/// xorshift64, seeded so that a zero seed still works.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        Self(seed ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % n as u64) as usize
+    }
+}
+
+fn inliers_of<V: Vector, M: RansacModel<V>>(
+    model: &M,
+    points: &[V],
+    threshold: V::Scalar,
+) -> Vec<usize>
+where
+    V::Scalar: VecFloat,
+{
+    (0..points.len())
+        .filter(|&i| model.distance(points[i]) <= threshold)
+        .collect()
+}
+
+/// Fits a model of type `M` to `points` while ignoring outliers. `None` if
+/// there are fewer points than a model needs or every sample was
+/// degenerate.
+/// ```
+///     use xyzvec::{ransac::{ransac, RansacOptions}, LineFit, XYVec};
+///     use approx::assert_relative_eq;
+///
+///     let mut points: Vec<_> = (0..20).map(|i| XYVec::new([i as f64, 2.0 * i as f64 + 1.0])).collect();
+///     points.push(XYVec::new([3.0, 40.0]));
+///     points.push(XYVec::new([15.0, -8.0]));
+///     let fit = ransac::<_, LineFit<f64>>(&points, &RansacOptions::new(0.1)).unwrap();
+///     assert_eq!(fit.inliers, (0..20).collect::<Vec<_>>());
+///     assert_relative_eq!(fit.model.direction.y() / fit.model.direction.x(), 2.0, epsilon = 1e-9);
+/// ```
+pub fn ransac<V, M>(points: &[V], options: &RansacOptions<V::Scalar>) -> Option<RansacResult<M>>
+where
+    V: Vector,
+    V::Scalar: VecFloat,
+    M: RansacModel<V>,
+{
+    let k = M::SAMPLE_SIZE;
+    if points.len() < k {
+        return None;
+    }
+    let mut rng = Rng::new(options.seed);
+    let mut best: Option<RansacResult<M>> = None;
+    let mut sample_idx = Vec::with_capacity(k);
+    let mut sample = Vec::with_capacity(k);
+    for _ in 0..options.iterations {
+        sample_idx.clear();
+        while sample_idx.len() < k {
+            let i = rng.below(points.len());
+            if !sample_idx.contains(&i) {
+                sample_idx.push(i);
+            }
+        }
+        sample.clear();
+        sample.extend(sample_idx.iter().map(|&i| points[i]));
+        let Some(model) = M::from_sample(&sample) else {
+            continue;
+        };
+        let inliers = inliers_of(&model, points, options.threshold);
+        if best
+            .as_ref()
+            .is_none_or(|b| inliers.len() > b.inliers.len())
+        {
+            best = Some(RansacResult { model, inliers });
+        }
+    }
+    let best = best?;
+    let subset: Vec<V> = best.inliers.iter().map(|&i| points[i]).collect();
+    // Keep the refit only if it doesn't lose inliers.
+    if let Some(model) = M::refit(&subset) {
+        let inliers = inliers_of(&model, points, options.threshold);
+        if inliers.len() >= best.inliers.len() {
+            return Some(RansacResult { model, inliers });
+        }
+    }
+    Some(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ransac, RansacModel, RansacOptions};
+    use crate::{Circle, LineFit, PlaneFit, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn plane_with_outliers_f64() {
+        let mut points = Vec::new();
+        for i in 0..10 {
+            for j in 0..10 {
+                let (x, y) = (i as f64, j as f64);
+                let noise = if (i + j) % 2 == 0 { 0.01 } else { -0.01 };
+                points.push(XYZVec::new([x, y, 0.5 * x - 0.25 * y + 3.0 + noise]));
+            }
+        }
+        // A third of the points are junk.
+        for i in 0..50 {
+            let t = i as f64;
+            points.push(XYZVec::new([
+                (t * 1.7) % 9.0,
+                (t * 2.3) % 9.0,
+                20.0 + (t * 0.9).sin() * 10.0,
+            ]));
+        }
+        let fit = ransac::<_, PlaneFit<f64>>(&points, &RansacOptions::new(0.05)).unwrap();
+        assert_eq!(fit.inliers, (0..100).collect::<Vec<_>>());
+        let n = fit.model.plane.normal;
+        assert_relative_eq!(n.x() / n.z(), -0.5, epsilon = 1e-4);
+        assert_relative_eq!(n.y() / n.z(), 0.25, epsilon = 1e-4);
+        assert!(fit.model.rms_residual < 0.011);
+    }
+
+    #[test]
+    fn circle_f32() {
+        let mut points: Vec<XYVec<f32>> = (0..36)
+            .map(|i| {
+                let a = i as f32 * 10f32.to_radians();
+                XYVec::new([2.0 + 5.0 * a.cos(), -1.0 + 5.0 * a.sin()])
+            })
+            .collect();
+        points.extend([
+            XYVec::new([2.0, -1.0]),
+            XYVec::new([0.0, 0.0]),
+            XYVec::new([9.0, 9.0]),
+        ]);
+        let options = RansacOptions {
+            iterations: 200,
+            seed: 42,
+            ..RansacOptions::new(0.01)
+        };
+        let fit = ransac::<_, Circle<f32>>(&points, &options).unwrap();
+        assert_eq!(fit.inliers.len(), 36);
+        assert_relative_eq!(fit.model.radius, 5.0, epsilon = 1e-4);
+        assert_relative_eq!(fit.model.center.x(), 2.0, epsilon = 1e-4);
+        assert_relative_eq!(fit.model.center.y(), -1.0, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn degenerate() {
+        let line = [
+            XYVec::new([0.0f64, 0.0]),
+            XYVec::new([1.0, 1.0]),
+            XYVec::new([2.0, 2.0]),
+        ];
+        assert!(Circle::from_sample(&line).is_none());
+        assert!(ransac::<_, Circle<f64>>(&line, &RansacOptions::new(0.1)).is_none());
+        assert!(ransac::<_, LineFit<f64>>(&line[..1], &RansacOptions::new(0.1)).is_none());
+        let same = [XYVec::new([1.0f64, 1.0]); 4];
+        assert!(ransac::<_, LineFit<f64>>(&same, &RansacOptions::new(0.1)).is_none());
+    }
+}
@@ -0,0 +1,157 @@
+//! Vectors of exact, non-`Copy` scalars such as [`BigRational`]. Requires
+//! the `num-rational` feature.
+//!
+//! Most of the API passes components by value, which needs `Copy`. The
+//! operations here borrow instead: `+`, `-`, and unary `-` are implemented
+//! on references, and the `_ref` methods take `&self` and `&other`. Any
+//! [`RefScalar`] works, so the same code also runs on plain floats and
+//! integers.
+//! ```
+//!     use num_rational::BigRational;
+//!     use xyzvec::XYVec;
+//!
+//!     let r = |n: i64, d: i64| BigRational::new(n.into(), d.into());
+//!     let a = XYVec::new([r(1, 3), r(2, 3)]);
+//!     let b = XYVec::new([r(1, 2), r(-1, 4)]);
+//!     assert_eq!(a.dot_prod_ref(&b), r(0, 1));
+//!     assert_eq!(&a + &b, XYVec::new([r(5, 6), r(5, 12)]));
+//! ```
+//!
+//! [`BigRational`]: num_rational::BigRational
+
+use crate::{XYVec, XYZVec};
+use num_traits::NumRef;
+use std::ops::{Add, Neg, Sub};
+
+/// Scalars whose arithmetic can take the right-hand side by reference, so
+/// no component is cloned more than once per operation. Implemented for
+/// every such type, including [`BigRational`](num_rational::BigRational).
+pub trait RefScalar: Clone + NumRef + Neg<Output = Self> {}
+
+impl<T: Clone + NumRef + Neg<Output = T>> RefScalar for T {}
+
+macro_rules! impl_ref_ops {
+    ($($vec:ident),*) => {$(
+        impl<T: RefScalar> $vec<T> {
+            fn zip_ref(&self, other: &Self, f: impl Fn(&T, &T) -> T) -> Self {
+                let (a, b) = (self.as_ref(), other.as_ref());
+                $vec::new(std::array::from_fn(|i| f(&a[i], &b[i])))
+            }
+
+            fn map_ref(&self, f: impl Fn(&T) -> T) -> Self {
+                let a = self.as_ref();
+                $vec::new(std::array::from_fn(|i| f(&a[i])))
+            }
+
+            /// [`dot_prod`](Self::dot_prod) on borrowed vectors.
+            pub fn dot_prod_ref(&self, other: &Self) -> T {
+                self.as_ref()
+                    .iter()
+                    .zip(other.as_ref())
+                    .fold(T::zero(), |acc, (a, b)| acc + a.clone() * b)
+            }
+
+            /// [`l2_norm_sqd`](Self::l2_norm_sqd) on a borrowed vector.
+            pub fn l2_norm_sqd_ref(&self) -> T {
+                self.dot_prod_ref(self)
+            }
+
+            pub fn scale_by_ref(&self, d: &T) -> Self {
+                self.map_ref(|c| c.clone() * d)
+            }
+
+            pub fn div_by_ref(&self, d: &T) -> Self {
+                self.map_ref(|c| c.clone() / d)
+            }
+        }
+
+        impl<'a, T: RefScalar> Add<&'a $vec<T>> for &'a $vec<T> {
+            type Output = $vec<T>;
+
+            fn add(self, other: Self) -> $vec<T> {
+                self.zip_ref(other, |a, b| a.clone() + b)
+            }
+        }
+
+        impl<'a, T: RefScalar> Sub<&'a $vec<T>> for &'a $vec<T> {
+            type Output = $vec<T>;
+
+            fn sub(self, other: Self) -> $vec<T> {
+                self.zip_ref(other, |a, b| a.clone() - b)
+            }
+        }
+
+        impl<T: RefScalar> Neg for &$vec<T> {
+            type Output = $vec<T>;
+
+            fn neg(self) -> $vec<T> {
+                self.map_ref(|c| -c.clone())
+            }
+        }
+    )*};
+}
+
+impl_ref_ops!(XYVec, XYZVec);
+
+impl<T: RefScalar> XYVec<T> {
+    /// The `z` component of the 3D cross product, as in
+    /// [`cross_prod`](XYVec::cross_prod).
+    pub fn cross_prod_ref(&self, other: &Self) -> T {
+        let [x, y] = self.as_ref();
+        let [ox, oy] = other.as_ref();
+        x.clone() * oy - y.clone() * ox
+    }
+}
+
+impl<T: RefScalar> XYZVec<T> {
+    /// The right-handed cross product `self × other`, with components in
+    /// `x, y, z` order.
+    pub fn cross_ref(&self, other: &Self) -> Self {
+        let [x, y, z] = self.as_ref();
+        let [ox, oy, oz] = other.as_ref();
+        XYZVec::new([
+            y.clone() * oz - z.clone() * oy,
+            z.clone() * ox - x.clone() * oz,
+            x.clone() * oy - y.clone() * ox,
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{XYVec, XYZVec};
+    use num_rational::BigRational;
+
+    fn r(n: i64, d: i64) -> BigRational {
+        BigRational::new(n.into(), d.into())
+    }
+
+    #[test]
+    fn exact_2d() {
+        let a = XYVec::new([r(1, 3), r(1, 7)]);
+        let b = XYVec::new([r(3, 1), r(-7, 2)]);
+        assert_eq!(a.dot_prod_ref(&b), r(1, 2));
+        assert_eq!(a.cross_prod_ref(&b), r(-67, 42));
+        assert_eq!(a.l2_norm_sqd_ref(), r(58, 441));
+        assert_eq!(&a - &a, XYVec::new([r(0, 1), r(0, 1)]));
+        assert_eq!(-&b, XYVec::new([r(-3, 1), r(7, 2)]));
+        assert_eq!(a.scale_by_ref(&r(21, 1)).div_by_ref(&r(21, 1)), a);
+    }
+
+    #[test]
+    fn exact_3d_cross_is_right_handed() {
+        let x = XYZVec::new([r(1, 2), r(0, 1), r(0, 1)]);
+        let y = XYZVec::new([r(0, 1), r(1, 3), r(0, 1)]);
+        assert_eq!(x.cross_ref(&y), XYZVec::new([r(0, 1), r(0, 1), r(1, 6)]));
+        assert_eq!(x.dot_prod_ref(&y), r(0, 1));
+    }
+
+    #[test]
+    fn copy_scalars_work_too() {
+        let a = XYZVec::new([1i64, 2, 3]);
+        let b = XYZVec::new([4, 5, 6]);
+        assert_eq!(-&a, XYZVec::new([-1, -2, -3]));
+        assert_eq!(a.dot_prod_ref(&b), 32);
+        assert_eq!(a.cross_ref(&b), XYZVec::new([-3, 6, -3]));
+    }
+}
@@ -0,0 +1,359 @@
+//! Rays and their intersections with other shapes.
+
+use crate::{
+    plane::LinePlaneIntersection, Aabb, Ball, Plane, Triangle3, VecFloat, VecNum, Vector, XYVec,
+    XYZVec,
+};
+
+/// Half-line of points `origin + t * direction` for `t >= 0`. `direction`
+/// need not be normalized; hit parameters are in units of its length.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Ray<V> {
+    pub origin: V,
+    pub direction: V,
+}
+
+pub type Ray2<T> = Ray<XYVec<T>>;
+pub type Ray3<T> = Ray<XYZVec<T>>;
+
+/// Where a ray meets a surface: the parameter `t` and the point there.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RayHit<V: Vector> {
+    pub t: V::Scalar,
+    pub point: V,
+}
+
+/// Crossings of a ray with a circle or sphere boundary, nearest first.
+#[derive(Clone, Copy, Debug)]
+pub enum BallHits<V: Vector> {
+    None,
+    /// The ray is tangent, or starts inside and only leaves.
+    One(RayHit<V>),
+    /// The ray enters and then leaves.
+    Two(RayHit<V>, RayHit<V>),
+}
+
+/// Where a ray meets a triangle: the parameter `t` and the barycentric
+/// weights `(u, v, w)` of the point for vertices `a`, `b`, `c`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TriangleHit<T> {
+    pub t: T,
+    pub barycentric: (T, T, T),
+}
+
+impl<V: Vector + PartialEq> PartialEq for BallHits<V>
+where
+    V::Scalar: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::None, Self::None) => true,
+            (Self::One(a), Self::One(b)) => a == b,
+            (Self::Two(a0, a1), Self::Two(b0, b1)) => a0 == b0 && a1 == b1,
+            _ => false,
+        }
+    }
+}
+
+impl<V: Vector> Ray<V>
+where
+    V::Scalar: VecNum,
+{
+    pub fn new(origin: V, direction: V) -> Self {
+        Self { origin, direction }
+    }
+
+    /// Point at parameter `t`.
+    pub fn at(&self, t: V::Scalar) -> V {
+        self.origin + self.direction.scale_by(t)
+    }
+}
+
+impl<V: Vector> Ray<V>
+where
+    V::Scalar: VecFloat,
+{
+    /// Parameters `(entry, exit)` where the ray enters and leaves `aabb`, by
+    /// the slab method, or `None` if it misses. `entry` is `0` when the
+    /// origin is inside. A zero direction component never crosses its slab,
+    /// so it hits only if the origin lies between that axis's bounds.
+    /// ```
+    ///     use xyzvec::{ray::Ray2, Aabb2, XYVec};
+    ///
+    ///     let rect = Aabb2::new(XYVec::new([1.0f64, -1.0]), XYVec::new([3.0, 1.0]));
+    ///     let ray = Ray2::new(XYVec::new([0.0, 0.5]), XYVec::new([2.0, 0.0]));
+    ///     assert_eq!(ray.intersect_aabb(&rect), Some((0.5, 1.5)));
+    ///     let above = Ray2::new(XYVec::new([0.0, 2.0]), XYVec::new([1.0, 0.0]));
+    ///     assert_eq!(above.intersect_aabb(&rect), None);
+    /// ```
+    pub fn intersect_aabb(&self, aabb: &Aabb<V>) -> Option<(V::Scalar, V::Scalar)> {
+        let mut entry = V::Scalar::zero();
+        let mut exit = V::Scalar::from_f64(f64::INFINITY);
+        for i in 0..V::DIM {
+            let (o, d) = (self.origin.component(i), self.direction.component(i));
+            let (lo, hi) = (aabb.min.component(i), aabb.max.component(i));
+            if d == V::Scalar::zero() {
+                if o < lo || o > hi {
+                    return None;
+                }
+                continue;
+            }
+            let (t0, t1) = ((lo - o) / d, (hi - o) / d);
+            let (near, far) = if t0 < t1 { (t0, t1) } else { (t1, t0) };
+            if near > entry {
+                entry = near;
+            }
+            if far < exit {
+                exit = far;
+            }
+            if entry > exit {
+                return None;
+            }
+        }
+        Some((entry, exit))
+    }
+
+    fn hit(&self, t: V::Scalar) -> RayHit<V> {
+        RayHit {
+            t,
+            point: self.at(t),
+        }
+    }
+
+    /// Where the ray crosses the boundary of `ball`. A ray starting inside
+    /// has only its exit; one starting exactly on the boundary counts that
+    /// as a hit at `t = 0`.
+    /// ```
+    ///     use xyzvec::{ray::{BallHits, Ray3}, Sphere, XYZVec};
+    ///
+    ///     let sphere = Sphere::new(XYZVec::new([0.0f64, 0.0, 5.0]), 1.0);
+    ///     let ray = Ray3::new(XYZVec::new([0.0, 0.0, 0.0]), XYZVec::new([0.0, 0.0, 1.0]));
+    ///     let BallHits::Two(near, far) = ray.intersect_ball(&sphere) else { panic!() };
+    ///     assert_eq!((near.t, far.t), (4.0, 6.0));
+    ///     assert_eq!(far.point, XYZVec::new([0.0, 0.0, 6.0]));
+    /// ```
+    pub fn intersect_ball(&self, ball: &Ball<V>) -> BallHits<V> {
+        let zero = V::Scalar::zero();
+        let oc = self.origin - ball.center;
+        let a = self.direction.l2_norm_sqd();
+        let b = oc.dot_prod(self.direction);
+        let c = oc.l2_norm_sqd() - ball.radius * ball.radius;
+        let disc = b * b - a * c;
+        if a == zero || disc < zero {
+            return BallHits::None;
+        }
+        let s = disc.sqrt();
+        let (t0, t1) = ((-b - s) / a, (-b + s) / a);
+        if t1 < zero {
+            BallHits::None
+        } else if t0 < zero {
+            BallHits::One(self.hit(t1))
+        } else if s == zero {
+            BallHits::One(self.hit(t0))
+        } else {
+            BallHits::Two(self.hit(t0), self.hit(t1))
+        }
+    }
+}
+
+impl<T: VecNum> Ray3<T> {
+    /// Where the ray crosses `plane`, or `None` if it points away or runs
+    /// parallel. A ray lying in the plane hits at its origin.
+    /// ```
+    ///     use xyzvec::{ray::Ray3, Plane, XYZVec};
+    ///
+    ///     let ground = Plane::new(XYZVec::new([0.0f64, 0.0, 1.0]), 0.0);
+    ///     let ray = Ray3::new(XYZVec::new([0.0, 0.0, 3.0]), XYZVec::new([1.0, 0.0, -2.0]));
+    ///     let hit = ray.intersect_plane(&ground).unwrap();
+    ///     assert_eq!(hit.t, 1.5);
+    ///     assert_eq!(hit.point, XYZVec::new([1.5, 0.0, 0.0]));
+    ///     let up = Ray3::new(XYZVec::new([0.0, 0.0, 3.0]), XYZVec::new([0.0, 0.0, 1.0]));
+    ///     assert!(up.intersect_plane(&ground).is_none());
+    /// ```
+    pub fn intersect_plane(&self, plane: &Plane<T>) -> Option<RayHit<XYZVec<T>>> {
+        match plane.intersect_line(self.origin, self.origin + self.direction) {
+            LinePlaneIntersection::Parallel => None,
+            LinePlaneIntersection::Contained => Some(RayHit {
+                t: T::zero(),
+                point: self.origin,
+            }),
+            LinePlaneIntersection::Point { t, point } => {
+                (t >= T::zero()).then_some(RayHit { t, point })
+            }
+        }
+    }
+}
+
+impl<T: VecFloat> Ray3<T> {
+    /// First hit on `tri` by the Möller–Trumbore method, or `None` for a
+    /// miss, a hit behind the origin, or a ray parallel to the triangle.
+    /// With `cull_backfaces`, triangles seen from the side opposite to
+    /// [`Triangle3::normal`] are skipped too.
+    /// ```
+    ///     use xyzvec::{ray::Ray3, Triangle3, XYZVec};
+    ///
+    ///     let tri = Triangle3::new(
+    ///         XYZVec::new([0.0f64, 0.0, 0.0]),
+    ///         XYZVec::new([4.0, 0.0, 0.0]),
+    ///         XYZVec::new([0.0, 4.0, 0.0]),
+    ///     );
+    ///     let ray = Ray3::new(XYZVec::new([1.0, 2.0, 5.0]), XYZVec::new([0.0, 0.0, -1.0]));
+    ///     let hit = ray.intersect_triangle(&tri, true).unwrap();
+    ///     assert_eq!(hit.t, 5.0);
+    ///     assert_eq!(hit.barycentric, (0.25, 0.25, 0.5));
+    ///     // From below, the back of the triangle faces the ray.
+    ///     let up = Ray3::new(XYZVec::new([1.0, 2.0, -5.0]), XYZVec::new([0.0, 0.0, 1.0]));
+    ///     assert!(up.intersect_triangle(&tri, true).is_none());
+    ///     assert!(up.intersect_triangle(&tri, false).is_some());
+    /// ```
+    pub fn intersect_triangle(
+        &self,
+        tri: &Triangle3<T>,
+        cull_backfaces: bool,
+    ) -> Option<TriangleHit<T>> {
+        let zero = T::zero();
+        let (e1, e2) = (tri.b - tri.a, tri.c - tri.a);
+        let p = self.direction.cross(e2);
+        let det = e1.dot_prod(p);
+        if det == zero || (cull_backfaces && det < zero) {
+            return None;
+        }
+        let s = self.origin - tri.a;
+        let v = s.dot_prod(p) / det;
+        if v < zero || v > T::one() {
+            return None;
+        }
+        let q = s.cross(e1);
+        let w = self.direction.dot_prod(q) / det;
+        if w < zero || v + w > T::one() {
+            return None;
+        }
+        let t = e2.dot_prod(q) / det;
+        (t >= zero).then_some(TriangleHit {
+            t,
+            barycentric: (T::one() - v - w, v, w),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BallHits, Ray2, Ray3};
+    use crate::{Aabb2, Aabb3, Circle, Sphere, Triangle3, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn aabb_f64() {
+        let cube = Aabb3::new(
+            XYZVec::new([-1.0f64, -1.0, -1.0]),
+            XYZVec::new([1.0, 1.0, 1.0]),
+        );
+        let diagonal = Ray3::new(
+            XYZVec::new([-3.0, -3.0, -3.0]),
+            XYZVec::new([1.0, 1.0, 1.0]),
+        );
+        assert_eq!(diagonal.intersect_aabb(&cube), Some((2.0, 4.0)));
+        // Starting inside, the entry is clamped to the origin.
+        let inside = Ray3::new(XYZVec::new([0.0, 0.5, 0.0]), XYZVec::new([0.0, 0.0, -0.5]));
+        assert_eq!(inside.intersect_aabb(&cube), Some((0.0, 2.0)));
+        // Pointing away.
+        let away = Ray3::new(XYZVec::new([2.0, 0.0, 0.0]), XYZVec::new([1.0, 0.0, 0.0]));
+        assert_eq!(away.intersect_aabb(&cube), None);
+        // Axis-parallel along a face: the zero components don't produce NaN.
+        let grazing = Ray3::new(XYZVec::new([-5.0, 1.0, -1.0]), XYZVec::new([1.0, 0.0, 0.0]));
+        assert_eq!(grazing.intersect_aabb(&cube), Some((4.0, 6.0)));
+        let outside = Ray3::new(XYZVec::new([-5.0, 1.5, 0.0]), XYZVec::new([1.0, 0.0, 0.0]));
+        assert_eq!(outside.intersect_aabb(&cube), None);
+    }
+
+    #[test]
+    fn aabb_f32() {
+        let rect = Aabb2::new(XYVec::new([0.0f32, 0.0]), XYVec::new([4.0, 2.0]));
+        let ray = Ray2::new(XYVec::new([-1.0, 3.0]), XYVec::new([1.0, -1.0]));
+        let (entry, exit) = ray.intersect_aabb(&rect).unwrap();
+        assert_relative_eq!(entry, 1.0);
+        assert_relative_eq!(exit, 3.0);
+        assert_relative_eq!(ray.at(exit).y(), 0.0);
+        // Skims past the corner.
+        let miss = Ray2::new(XYVec::new([-1.0, 7.5]), XYVec::new([1.0, -1.0]));
+        assert_eq!(miss.intersect_aabb(&rect), None);
+        let still = Ray2::new(XYVec::new([1.0, 1.0]), XYVec::new([0.0, 0.0]));
+        assert_eq!(still.intersect_aabb(&rect), Some((0.0, f32::INFINITY)));
+    }
+
+    #[test]
+    fn ball_f64() {
+        let circle = Circle::new(XYVec::new([2.0f64, 0.0]), 1.0);
+        let ray = |o: [f64; 2], d: [f64; 2]| Ray2::new(XYVec::new(o), XYVec::new(d));
+        let BallHits::Two(a, b) = ray([0.0, 0.0], [0.5, 0.0]).intersect_ball(&circle) else {
+            panic!("expected two hits");
+        };
+        assert_relative_eq!(a.t, 2.0);
+        assert_relative_eq!(b.t, 6.0);
+        assert_relative_eq!(b.point.x(), 3.0);
+        // From inside only the exit counts.
+        let BallHits::One(exit) = ray([2.0, 0.0], [0.0, 1.0]).intersect_ball(&circle) else {
+            panic!("expected one hit");
+        };
+        assert_relative_eq!(exit.point.y(), 1.0);
+        // Tangent, behind, and degenerate.
+        let BallHits::One(tangent) = ray([0.0, 1.0], [1.0, 0.0]).intersect_ball(&circle) else {
+            panic!("expected a tangent hit");
+        };
+        assert_relative_eq!(tangent.t, 2.0);
+        assert_eq!(
+            ray([4.0, 0.0], [1.0, 0.0]).intersect_ball(&circle),
+            BallHits::None
+        );
+        assert_eq!(
+            ray([0.0, 0.0], [0.0, 0.0]).intersect_ball(&circle),
+            BallHits::None
+        );
+    }
+
+    #[test]
+    fn sphere_f32() {
+        let sphere = Sphere::new(XYZVec::new([1.0f32, 1.0, 1.0]), 2.0);
+        let ray = Ray3::new(XYZVec::new([1.0, -5.0, 1.0]), XYZVec::new([0.0, 3.0, 0.0]));
+        let BallHits::Two(a, b) = ray.intersect_ball(&sphere) else {
+            panic!("expected two hits");
+        };
+        assert_relative_eq!(a.point.y(), -1.0);
+        assert_relative_eq!(b.point.y(), 3.0);
+        let off = Ray3::new(XYZVec::new([3.5, -5.0, 1.0]), XYZVec::new([0.0, 1.0, 0.0]));
+        assert_eq!(off.intersect_ball(&sphere), BallHits::None);
+    }
+
+    #[test]
+    fn triangle_f64() {
+        let tri = Triangle3::new(
+            XYZVec::new([1.0f64, 0.0, 0.0]),
+            XYZVec::new([0.0, 1.0, 0.0]),
+            XYZVec::new([0.0, 0.0, 1.0]),
+        );
+        let toward = Ray3::new(XYZVec::new([0.0, 0.0, 0.0]), XYZVec::new([2.0, 2.0, 2.0]));
+        // The origin sees the back of the triangle.
+        assert!(toward.intersect_triangle(&tri, true).is_none());
+        let hit = toward.intersect_triangle(&tri, false).unwrap();
+        assert_relative_eq!(hit.t, 1.0 / 6.0);
+        let (u, v, w) = hit.barycentric;
+        assert_relative_eq!(u, 1.0 / 3.0);
+        assert_relative_eq!(v, 1.0 / 3.0);
+        assert_relative_eq!(w, 1.0 / 3.0);
+        let p = tri.from_barycentric(u, v, w);
+        assert_relative_eq!((p - toward.at(hit.t)).l2_norm(), 0.0, epsilon = 1e-12);
+        // Through a vertex counts; just outside an edge does not.
+        let vertex = Ray3::new(XYZVec::new([2.0, 0.0, 0.0]), XYZVec::new([-1.0, 0.0, 0.0]));
+        assert_eq!(
+            vertex.intersect_triangle(&tri, true).unwrap().barycentric,
+            (1.0, 0.0, 0.0)
+        );
+        let outside = Ray3::new(XYZVec::new([0.6, 0.6, 1.0]), XYZVec::new([0.0, 0.0, -1.0]));
+        assert!(outside.intersect_triangle(&tri, false).is_none());
+        // Parallel to the plane, and behind the origin.
+        let parallel = Ray3::new(XYZVec::new([0.0, 0.0, 0.0]), XYZVec::new([1.0, -1.0, 0.0]));
+        assert!(parallel.intersect_triangle(&tri, false).is_none());
+        let behind = Ray3::new(XYZVec::new([1.0, 1.0, 1.0]), XYZVec::new([1.0, 1.0, 1.0]));
+        assert!(behind.intersect_triangle(&tri, false).is_none());
+    }
+}
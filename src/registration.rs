@@ -0,0 +1,561 @@
+//! Rigid registration of point sets.
+//!
+//! [`kabsch`] finds the rotation and translation that best map each source
+//! point onto its corresponding target point in the least-squares sense;
+//! [`umeyama`] also fits a uniform scale. The 3D solvers use Horn's
+//! quaternion method, so they never return a reflection. When the
+//! correspondences are unknown, [`icp`] aligns two scans by alternating
+//! nearest-neighbor matching with Kabsch steps. As with poses, scalars only
+//! need [`VecNum`]; the arithmetic is done in `f64`.
+
+use crate::{KdTree, Mat3, Pose2, Pose3, Quat, VecNum, Vector, XYVec, XYZVec};
+use std::ops::Mul;
+
+/// Centroid of `points` and the points relative to it, as `f64`.
+/// Components past the vector's dimension are zero.
+fn centered<V: Vector>(points: &[V]) -> ([f64; 3], Vec<[f64; 3]>)
+where
+    V::Scalar: VecNum,
+{
+    let coords: Vec<[f64; 3]> = points
+        .iter()
+        .map(|p| {
+            let mut c = [0.0; 3];
+            for (i, c) in c.iter_mut().enumerate().take(V::DIM) {
+                *c = p.component(i).to_f64();
+            }
+            c
+        })
+        .collect();
+    let n = coords.len() as f64;
+    let mut mean = [0.0; 3];
+    for c in &coords {
+        for i in 0..3 {
+            mean[i] += c[i] / n;
+        }
+    }
+    let rel = coords
+        .into_iter()
+        .map(|c| [c[0] - mean[0], c[1] - mean[1], c[2] - mean[2]])
+        .collect();
+    (mean, rel)
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Unit eigenvector for the largest eigenvalue of a symmetric 4x4 matrix,
+/// by cyclic Jacobi rotations.
+fn max_eigenvector(mut a: [[f64; 4]; 4]) -> [f64; 4] {
+    let mut v = [[0.0; 4]; 4];
+    for (i, row) in v.iter_mut().enumerate() {
+        row[i] = 1.0;
+    }
+    let total: f64 = a.iter().flatten().map(|x| x * x).sum();
+    for _ in 0..50 {
+        let off: f64 = (0..4)
+            .flat_map(|p| (0..4).filter(move |&q| q != p).map(move |q| (p, q)))
+            .map(|(p, q)| a[p][q] * a[p][q])
+            .sum();
+        if off <= total * 1e-30 {
+            break;
+        }
+        for p in 0..3 {
+            for q in p + 1..4 {
+                if a[p][q] == 0.0 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+                let (rp, rq) = (a[p], a[q]);
+                a[p] = std::array::from_fn(|k| c * rp[k] - s * rq[k]);
+                a[q] = std::array::from_fn(|k| s * rp[k] + c * rq[k]);
+            }
+        }
+    }
+    let k = (0..4)
+        .max_by(|&i, &j| a[i][i].total_cmp(&a[j][j]))
+        .unwrap_or(0);
+    v.map(|row| row[k])
+}
+
+/// Best rotation `R` (a matrix in 3D, an angle in 2D), its score
+/// `Σ (R a)·b` over the centered points, both centroids, and the source
+/// spread `Σ |a|²`.
+struct Solution<R> {
+    rotation: R,
+    score: f64,
+    source_mean: [f64; 3],
+    target_mean: [f64; 3],
+    spread: f64,
+}
+
+fn solve3<T: VecNum>(
+    source: &[XYZVec<T>],
+    target: &[XYZVec<T>],
+) -> Option<Solution<[[f64; 3]; 3]>> {
+    if source.is_empty() || source.len() != target.len() {
+        return None;
+    }
+    let (source_mean, a) = centered(source);
+    let (target_mean, b) = centered(target);
+    // Cross-covariance `S[i][j] = Σ a_i b_j`.
+    let mut s = [[0.0; 3]; 3];
+    for (a, b) in a.iter().zip(&b) {
+        for i in 0..3 {
+            for j in 0..3 {
+                s[i][j] += a[i] * b[j];
+            }
+        }
+    }
+    let [[xx, xy, xz], [yx, yy, yz], [zx, zy, zz]] = s;
+    let n = [
+        [xx + yy + zz, yz - zy, zx - xz, xy - yx],
+        [yz - zy, xx - yy - zz, xy + yx, zx + xz],
+        [zx - xz, xy + yx, -xx + yy - zz, yz + zy],
+        [xy - yx, zx + xz, yz + zy, -xx - yy + zz],
+    ];
+    let [w, x, y, z] = max_eigenvector(n);
+    let q = Quat::new(w, XYZVec::new([x, y, z]));
+    let m = q.to_mat3();
+    let rotation = [0, 1, 2].map(|i| [0, 1, 2].map(|j| m.get(i, j)));
+    let score = a
+        .iter()
+        .zip(&b)
+        .map(|(a, &b)| dot(rotation.map(|row| dot(row, *a)), b))
+        .sum();
+    let spread = a.iter().map(|a| dot(*a, *a)).sum();
+    Some(Solution {
+        rotation,
+        score,
+        source_mean,
+        target_mean,
+        spread,
+    })
+}
+
+fn pose3<T: VecNum>(sol: &Solution<[[f64; 3]; 3]>, scale: f64) -> Pose3<T> {
+    let r = sol.rotation;
+    let rotated = r.map(|row| dot(row, sol.source_mean));
+    let t = [0, 1, 2].map(|i| sol.target_mean[i] - scale * rotated[i]);
+    Pose3::new(
+        XYZVec::new(t.map(T::from_f64)),
+        Mat3::new(r.map(|row| row.map(T::from_f64))),
+    )
+}
+
+/// Rigid transform minimizing `Σ |pose(source[i]) - target[i]|²`. `None`
+/// if the slices are empty or differ in length. With fewer than three
+/// non-collinear points the rotation about the points' line is arbitrary.
+/// ```
+///     use xyzvec::{registration::kabsch, Mat3, Pose3, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let truth = Pose3::new(
+///         XYZVec::new([1.0f64, -2.0, 0.5]),
+///         Mat3::from_axis_angle(XYZVec::new([1.0, 1.0, 0.0]), 0.8),
+///     );
+///     let source = [
+///         XYZVec::new([0.0, 0.0, 0.0]),
+///         XYZVec::new([1.0, 0.0, 0.0]),
+///         XYZVec::new([0.0, 2.0, 0.0]),
+///         XYZVec::new([0.0, 0.0, 3.0]),
+///     ];
+///     let target = source.map(|p| truth.transform_point(p));
+///     let pose = kabsch(&source, &target).unwrap();
+///     for (p, q) in source.iter().zip(&target) {
+///         assert_relative_eq!((pose.transform_point(*p) - *q).l2_norm(), 0.0, epsilon = 1e-12);
+///     }
+/// ```
+pub fn kabsch<T: VecNum>(source: &[XYZVec<T>], target: &[XYZVec<T>]) -> Option<Pose3<T>> {
+    solve3(source, target).map(|sol| pose3(&sol, 1.0))
+}
+
+/// Similarity transform minimizing `Σ |s · R source[i] + t - target[i]|²`,
+/// returned as the pose `(R, t)` and the scale `s`, so that
+/// `pose.transform_point(p.scale_by(s))` maps source onto target. `None` if
+/// the slices are empty or differ in length, or every source point is the
+/// same.
+pub fn umeyama<T: VecNum>(source: &[XYZVec<T>], target: &[XYZVec<T>]) -> Option<(Pose3<T>, T)> {
+    let sol = solve3(source, target)?;
+    if sol.spread <= 0.0 {
+        return None;
+    }
+    let scale = sol.score / sol.spread;
+    Some((pose3(&sol, scale), T::from_f64(scale)))
+}
+
+fn solve2<T: VecNum>(source: &[XYVec<T>], target: &[XYVec<T>]) -> Option<Solution<f64>> {
+    if source.is_empty() || source.len() != target.len() {
+        return None;
+    }
+    let (source_mean, a) = centered(source);
+    let (target_mean, b) = centered(target);
+    let (mut sum_dot, mut sum_cross, mut spread) = (0.0, 0.0, 0.0);
+    for (a, b) in a.iter().zip(&b) {
+        sum_dot += a[0] * b[0] + a[1] * b[1];
+        sum_cross += a[0] * b[1] - a[1] * b[0];
+        spread += a[0] * a[0] + a[1] * a[1];
+    }
+    Some(Solution {
+        rotation: sum_cross.atan2(sum_dot),
+        score: sum_dot.hypot(sum_cross),
+        source_mean,
+        target_mean,
+        spread,
+    })
+}
+
+fn pose2<T: VecNum>(sol: &Solution<f64>, scale: f64) -> Pose2<T> {
+    let (s, c) = sol.rotation.sin_cos();
+    let [x, y, _] = sol.source_mean;
+    let t = [
+        sol.target_mean[0] - scale * (c * x - s * y),
+        sol.target_mean[1] - scale * (s * x + c * y),
+    ];
+    Pose2::new(XYVec::new(t.map(T::from_f64)), T::from_f64(sol.rotation))
+}
+
+/// 2D version of [`kabsch`]; the rotation is in `(-π, π]`.
+/// ```
+///     use xyzvec::{registration::kabsch2, XYVec};
+///     use approx::assert_relative_eq;
+///
+///     let source = [XYVec::new([0.0f64, 0.0]), XYVec::new([2.0, 0.0]), XYVec::new([0.0, 1.0])];
+///     let target = [XYVec::new([5.0, 5.0]), XYVec::new([5.0, 7.0]), XYVec::new([4.0, 5.0])];
+///     let pose = kabsch2(&source, &target).unwrap();
+///     assert_relative_eq!(pose.rotation, std::f64::consts::FRAC_PI_2, epsilon = 1e-12);
+///     assert_relative_eq!(pose.translation.x(), 5.0, epsilon = 1e-12);
+/// ```
+pub fn kabsch2<T: VecNum>(source: &[XYVec<T>], target: &[XYVec<T>]) -> Option<Pose2<T>> {
+    solve2(source, target).map(|sol| pose2(&sol, 1.0))
+}
+
+/// 2D version of [`umeyama`].
+pub fn umeyama2<T: VecNum>(source: &[XYVec<T>], target: &[XYVec<T>]) -> Option<(Pose2<T>, T)> {
+    let sol = solve2(source, target)?;
+    if sol.spread <= 0.0 {
+        return None;
+    }
+    let scale = sol.score / sol.spread;
+    Some((pose2(&sol, scale), T::from_f64(scale)))
+}
+
+/// Stopping and matching rules for [`icp`] and [`icp2`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct IcpOptions<T> {
+    /// Most alignment steps to take.
+    pub max_iterations: usize,
+    /// Converged once an iteration lowers the RMS error by no more than
+    /// this.
+    pub tolerance: T,
+    /// Matches farther apart than this are ignored as outliers. All
+    /// matches are used if `None`.
+    pub max_distance: Option<T>,
+}
+
+impl<T: VecNum> Default for IcpOptions<T> {
+    fn default() -> Self {
+        Self {
+            max_iterations: 50,
+            tolerance: T::from_f64(1e-9),
+            max_distance: None,
+        }
+    }
+}
+
+/// Outcome of [`icp`] or [`icp2`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct IcpResult<P, T> {
+    /// Pose mapping the source points onto the target points.
+    pub pose: P,
+    /// Root-mean-square distance between the moved source points and
+    /// their matches, over the matches used.
+    pub rms_error: T,
+    /// Alignment steps taken.
+    pub iterations: usize,
+    /// Whether the tolerance was reached before `max_iterations`.
+    pub converged: bool,
+}
+
+/// Shared ICP loop over either dimension.
+fn icp_driver<V, P>(
+    source: &[V],
+    target: &[V],
+    initial: P,
+    options: &IcpOptions<V::Scalar>,
+    solve: impl Fn(&[V], &[V]) -> Option<P>,
+    apply: impl Fn(&P, V) -> V,
+) -> Option<IcpResult<P, V::Scalar>>
+where
+    V: Vector,
+    V::Scalar: VecNum,
+    P: Copy + Mul<Output = P>,
+{
+    if source.is_empty() || target.is_empty() {
+        return None;
+    }
+    let tree = KdTree::new(target);
+    let max_sqd = options.max_distance.map(|d| d.to_f64() * d.to_f64());
+    let tolerance = options.tolerance.to_f64();
+    let mut pose = initial;
+    let mut previous: Option<f64> = None;
+    let mut iterations = 0;
+    loop {
+        let (mut from, mut to, mut sum_sqd) = (Vec::new(), Vec::new(), 0.0);
+        for &p in source {
+            let moved = apply(&pose, p);
+            let Some(i) = tree.nearest(moved) else {
+                continue;
+            };
+            let d = (target[i] - moved).l2_norm_sqd().to_f64();
+            if max_sqd.is_none_or(|m| d <= m) {
+                from.push(moved);
+                to.push(target[i]);
+                sum_sqd += d;
+            }
+        }
+        if from.is_empty() {
+            return None;
+        }
+        let rms = (sum_sqd / from.len() as f64).sqrt();
+        let converged = previous.is_some_and(|prev| prev - rms <= tolerance);
+        if converged || iterations == options.max_iterations {
+            return Some(IcpResult {
+                pose,
+                rms_error: V::Scalar::from_f64(rms),
+                iterations,
+                converged,
+            });
+        }
+        pose = solve(&from, &to)? * pose;
+        previous = Some(rms);
+        iterations += 1;
+    }
+}
+
+/// Point-to-point iterative closest point: refines `initial` until the
+/// transformed `source` points sit on `target`, re-matching each source
+/// point to its nearest target point every iteration. Converges to the
+/// nearest local minimum, so `initial` should be roughly right. `None` if
+/// either slice is empty or no matches are within `max_distance`.
+/// ```
+///     use xyzvec::{registration::{icp, IcpOptions}, Mat3, Pose3, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let target: Vec<_> = (0..200)
+///         .map(|i| {
+///             let t = i as f64 * 0.1;
+///             XYZVec::new([t.cos() * 2.0, t.sin(), t * 0.3])
+///         })
+///         .collect();
+///     let offset = Pose3::new(
+///         XYZVec::new([0.05, -0.03, 0.02]),
+///         Mat3::from_axis_angle(XYZVec::new([0.0, 0.0, 1.0]), 0.03),
+///     );
+///     let source: Vec<_> = target.iter().map(|&p| offset.transform_point(p)).collect();
+///     let result = icp(&source, &target, Pose3::identity(), &IcpOptions::default()).unwrap();
+///     assert!(result.converged);
+///     assert_relative_eq!(result.rms_error, 0.0, epsilon = 1e-6);
+/// ```
+pub fn icp<T: VecNum>(
+    source: &[XYZVec<T>],
+    target: &[XYZVec<T>],
+    initial: Pose3<T>,
+    options: &IcpOptions<T>,
+) -> Option<IcpResult<Pose3<T>, T>> {
+    icp_driver(source, target, initial, options, kabsch, |pose, p| {
+        pose.transform_point(p)
+    })
+}
+
+/// 2D version of [`icp`].
+pub fn icp2<T: VecNum>(
+    source: &[XYVec<T>],
+    target: &[XYVec<T>],
+    initial: Pose2<T>,
+    options: &IcpOptions<T>,
+) -> Option<IcpResult<Pose2<T>, T>> {
+    icp_driver(source, target, initial, options, kabsch2, |pose, p| {
+        pose.transform_point(p)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{icp, icp2, kabsch, kabsch2, umeyama, umeyama2, IcpOptions};
+    use crate::{Mat3, Pose2, Pose3, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I16F16;
+    use std::f64::consts::PI;
+
+    fn cloud() -> Vec<XYZVec<f64>> {
+        (0..40)
+            .map(|i| {
+                let t = i as f64;
+                XYZVec::new([(t * 0.7).sin() * 2.0, (t * 1.3).cos(), t * 0.05 - 1.0])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn recovers_rotations_f64() {
+        let source = cloud();
+        let axes = [
+            XYZVec::new([0.0f64, 0.0, 1.0]),
+            XYZVec::new([1.0, -2.0, 0.5]),
+            XYZVec::new([0.0, 1.0, 0.0]),
+        ];
+        for (axis, angle) in axes.into_iter().zip([0.0, 2.0, PI - 1e-3]) {
+            let truth = Pose3::new(
+                XYZVec::new([3.0, 0.0, -1.0]),
+                Mat3::from_axis_angle(axis, angle),
+            );
+            let target: Vec<_> = source.iter().map(|&p| truth.transform_point(p)).collect();
+            let pose = kabsch(&source, &target).unwrap();
+            for i in 0..3 {
+                let d = pose.rotation.row(i) - truth.rotation.row(i);
+                assert_relative_eq!(d.l2_norm(), 0.0, epsilon = 1e-9);
+            }
+            assert_relative_eq!(pose.rotation.determinant(), 1.0, epsilon = 1e-12);
+            assert_relative_eq!(
+                (pose.translation - truth.translation).l2_norm(),
+                0.0,
+                epsilon = 1e-9
+            );
+        }
+        assert!(kabsch(&source, &source[1..]).is_none());
+        assert!(kabsch::<f64>(&[], &[]).is_none());
+    }
+
+    #[test]
+    fn reflections_are_not_returned() {
+        // A mirrored planar set: the best proper rotation is still a rotation.
+        let source = [
+            XYZVec::new([1.0f64, 0.0, 0.0]),
+            XYZVec::new([0.0, 1.0, 0.0]),
+            XYZVec::new([-1.0, 0.0, 0.0]),
+            XYZVec::new([0.0, -2.0, 0.0]),
+        ];
+        let target = source.map(|p| XYZVec::new([-p.x(), p.y(), p.z()]));
+        let pose = kabsch(&source, &target).unwrap();
+        assert_relative_eq!(pose.rotation.determinant(), 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn scaled_f64() {
+        let source = cloud();
+        let truth = Pose3::new(
+            XYZVec::new([0.0, 1.0, 2.0]),
+            Mat3::from_axis_angle(XYZVec::new([1.0, 1.0, 1.0]), -1.0),
+        );
+        let target: Vec<_> = source
+            .iter()
+            .map(|&p| truth.transform_point(p.scale_by(2.5)))
+            .collect();
+        let (pose, scale) = umeyama(&source, &target).unwrap();
+        assert_relative_eq!(scale, 2.5, epsilon = 1e-12);
+        for (p, q) in source.iter().zip(&target) {
+            assert_relative_eq!(
+                (pose.transform_point(p.scale_by(scale)) - *q).l2_norm(),
+                0.0,
+                epsilon = 1e-9
+            );
+        }
+        let same = [XYZVec::new([1.0, 1.0, 1.0]); 3];
+        assert!(umeyama(&same, &target[..3]).is_none());
+    }
+
+    #[test]
+    fn planar_f32_and_fixed() {
+        let source: Vec<XYVec<f32>> = (0..10)
+            .map(|i| XYVec::new([i as f32, (i * i % 7) as f32]))
+            .collect();
+        let truth = Pose2::new(XYVec::new([-4.0f32, 2.0]), -2.5);
+        let target: Vec<_> = source
+            .iter()
+            .map(|&p| truth.transform_point(p.scale_by(0.5)))
+            .collect();
+        let (pose, scale) = umeyama2(&source, &target).unwrap();
+        assert_relative_eq!(scale, 0.5, epsilon = 1e-5);
+        assert_relative_eq!(pose.rotation, -2.5, epsilon = 1e-5);
+        assert_relative_eq!(
+            (pose.translation - truth.translation).l2_norm(),
+            0.0,
+            epsilon = 1e-4
+        );
+
+        let f = |v: f64| I16F16::from_num(v);
+        let source = [XYVec::new([f(0.0), f(0.0)]), XYVec::new([f(1.0), f(0.0)])];
+        let target = [XYVec::new([f(1.0), f(1.0)]), XYVec::new([f(1.0), f(2.0)])];
+        let pose = kabsch2(&source, &target).unwrap();
+        assert!((pose.rotation - f(PI / 2.0)).abs() < f(1e-3));
+        assert!((pose.translation - XYVec::new([f(1.0), f(1.0)])).l1_norm() < f(1e-3));
+    }
+
+    #[test]
+    fn icp_partial_overlap_f64() {
+        // The source sees only part of the target, plus a stray point.
+        let target: Vec<XYVec<f64>> = (0..120)
+            .map(|i| {
+                let t = i as f64 * 0.05;
+                XYVec::new([t * 3.0, (t * 2.0).sin()])
+            })
+            .collect();
+        let truth = Pose2::new(XYVec::new([0.1, -0.05]), 0.04);
+        let inverse = truth.inverse();
+        let mut source: Vec<_> = target[20..90]
+            .iter()
+            .map(|&p| inverse.transform_point(p))
+            .collect();
+        source.push(XYVec::new([50.0, 50.0]));
+        let options = IcpOptions {
+            max_distance: Some(1.0),
+            ..IcpOptions::default()
+        };
+        let result = icp2(&source, &target, Pose2::identity(), &options).unwrap();
+        assert!(result.converged);
+        assert!(result.iterations > 1);
+        assert_relative_eq!(result.pose.rotation, truth.rotation, epsilon = 1e-6);
+        assert_relative_eq!(
+            (result.pose.translation - truth.translation).l2_norm(),
+            0.0,
+            epsilon = 1e-6
+        );
+        // Without the outlier cut the stray point drags the fit off.
+        let loose = icp2(&source, &target, Pose2::identity(), &IcpOptions::default()).unwrap();
+        assert!(loose.rms_error > 1.0);
+    }
+
+    #[test]
+    fn icp_limits_f32() {
+        let target: Vec<XYZVec<f32>> = (0..50)
+            .map(|i| XYZVec::new([i as f32, (i % 5) as f32, (i % 3) as f32]))
+            .collect();
+        let shifted: Vec<_> = target
+            .iter()
+            .map(|&p| p + XYZVec::new([0.2, 0.1, 0.0]))
+            .collect();
+        let options = IcpOptions {
+            max_iterations: 0,
+            ..IcpOptions::default()
+        };
+        let result = icp(&shifted, &target, Pose3::identity(), &options).unwrap();
+        assert_eq!((result.iterations, result.converged), (0, false));
+        assert_eq!(result.pose, Pose3::identity());
+        let far = IcpOptions {
+            max_distance: Some(0.01),
+            ..IcpOptions::default()
+        };
+        assert!(icp(&shifted, &target, Pose3::identity(), &far).is_none());
+        assert!(icp(&[], &target, Pose3::identity(), &options).is_none());
+    }
+}
@@ -0,0 +1,325 @@
+//! Rotors: rotations in the language of geometric algebra.
+//!
+//! A rotor is a scalar plus a bivector (an oriented plane) and rotates a
+//! vector by the sandwich product `R v R̃`. Composing rotors is a single
+//! product and renormalizing one is a single division, which keeps them
+//! well behaved over long chains of small rotations.
+//!
+//! As with [`Pose2`](crate::Pose2), scalars only need [`VecNum`]; the
+//! trigonometry is done in `f64`.
+
+use crate::{angle::Radians, quat::Quat, VecInner, VecNum, XYVec, XYZVec};
+use std::{
+    fmt::{self, Formatter},
+    ops::Mul,
+};
+
+/// 2D rotor `s + b e₁₂`. The unit rotor for a counterclockwise rotation by
+/// `θ` has `s = cos(θ/2)` and `b = sin(θ/2)`; `R` and `-R` rotate alike.
+/// ```
+///     use xyzvec::{rotor::Rotor2, XYVec};
+///     use approx::assert_relative_eq;
+///
+///     let quarter = Rotor2::from_angle(std::f64::consts::FRAC_PI_4);
+///     let v = (quarter * quarter).rotate(XYVec::new([1.0, 0.0]));
+///     assert_relative_eq!(v.x(), 0.0, epsilon = 1e-12);
+///     assert_relative_eq!(v.y(), 1.0, epsilon = 1e-12);
+/// ```
+#[derive(Clone, Copy, PartialEq)]
+pub struct Rotor2<T> {
+    pub s: T,
+    pub b: T,
+}
+
+impl<T: VecInner> Rotor2<T> {
+    pub fn new(s: T, b: T) -> Self {
+        Self { s, b }
+    }
+
+    /// `s - b e₁₂`: the inverse rotation of a unit rotor.
+    pub fn reverse(&self) -> Self {
+        Self::new(self.s, -self.b)
+    }
+}
+
+impl<T: VecNum> Rotor2<T> {
+    pub fn identity() -> Self {
+        Self::new(T::one(), T::zero())
+    }
+
+    /// Counterclockwise rotation by `theta`.
+    pub fn from_angle(theta: impl Into<Radians<T>>) -> Self {
+        let half = theta.into().0.to_f64() / 2.0;
+        Self::new(T::from_f64(half.cos()), T::from_f64(half.sin()))
+    }
+
+    /// The rotation angle, in `(-π, π]`.
+    pub fn angle(&self) -> Radians<T> {
+        let (s, b) = (self.s.to_f64(), self.b.to_f64());
+        let mut theta = 2.0 * b.atan2(s);
+        if theta <= -std::f64::consts::PI {
+            theta += std::f64::consts::TAU;
+        } else if theta > std::f64::consts::PI {
+            theta -= std::f64::consts::TAU;
+        }
+        Radians(T::from_f64(theta))
+    }
+
+    /// The smallest rotation taking the direction of `from` to the direction
+    /// of `to`. `None` if either is zero.
+    pub fn from_vectors(from: XYVec<T>, to: XYVec<T>) -> Option<Self> {
+        let [ax, ay, bx, by] = [from.x(), from.y(), to.x(), to.y()].map(|c| c.to_f64());
+        let lens = (ax * ax + ay * ay).sqrt() * (bx * bx + by * by).sqrt();
+        if lens == 0.0 || !lens.is_finite() {
+            return None;
+        }
+        // (1 + cos θ, sin θ) points along (cos θ/2, sin θ/2).
+        let (s, b) = (lens + ax * bx + ay * by, ax * by - ay * bx);
+        let len = (s * s + b * b).sqrt();
+        if len <= lens * 1e-12 {
+            // Opposite directions: turn half way round.
+            return Some(Self::new(T::zero(), T::one()));
+        }
+        Some(Self::new(T::from_f64(s / len), T::from_f64(b / len)))
+    }
+
+    /// `v` rotated by this unit rotor: `R v R̃`.
+    pub fn rotate(&self, v: XYVec<T>) -> XYVec<T> {
+        let (s, b) = (self.s, self.b);
+        let two = T::one() + T::one();
+        let (c, sin) = (s * s - b * b, two * s * b);
+        XYVec::new([c * v.x() - sin * v.y(), sin * v.x() + c * v.y()])
+    }
+
+    /// `self` scaled to unit length, or `None` if it is zero.
+    pub fn normalize(&self) -> Option<Self> {
+        let (s, b) = (self.s.to_f64(), self.b.to_f64());
+        let len = (s * s + b * b).sqrt();
+        (len > 0.0 && len.is_finite())
+            .then(|| Self::new(T::from_f64(s / len), T::from_f64(b / len)))
+    }
+
+    /// The same rotation as a quaternion about the `z` axis.
+    pub fn to_quat(&self) -> Quat<T> {
+        Quat::new(self.s, XYZVec::new([T::zero(), T::zero(), self.b]))
+    }
+}
+
+/// Geometric product: `self * other` rotates by `other` first, then `self`.
+/// In 2D the order does not matter.
+impl<T: VecInner> Mul for Rotor2<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.s * other.s - self.b * other.b,
+            self.s * other.b + self.b * other.s,
+        )
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Rotor2<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Rotor2 {{ s: {:?}, b: {:?} }}", self.s, self.b)
+    }
+}
+
+/// 3D rotor `s + b₂₃ e₂₃ + b₃₁ e₃₁ + b₁₂ e₁₂`, with the bivector stored as
+/// `b = (b₂₃, b₃₁, b₁₂)`: each component names the plane normal to the
+/// matching axis. The unit rotor for a counterclockwise rotation by `θ`
+/// about the unit axis `n` has `s = cos(θ/2)` and `b = n sin(θ/2)`, so
+/// numerically it matches the corresponding [`Quat`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct Rotor3<T> {
+    pub s: T,
+    pub b: XYZVec<T>,
+}
+
+impl<T: VecInner> Rotor3<T> {
+    pub fn new(s: T, b: XYZVec<T>) -> Self {
+        Self { s, b }
+    }
+
+    /// The inverse rotation of a unit rotor.
+    pub fn reverse(&self) -> Self {
+        Self::new(self.s, -self.b)
+    }
+
+    pub fn from_quat(q: Quat<T>) -> Self {
+        Self::new(q.w, q.v)
+    }
+
+    pub fn to_quat(&self) -> Quat<T> {
+        Quat::new(self.s, self.b)
+    }
+}
+
+impl<T: VecNum> Rotor3<T> {
+    pub fn identity() -> Self {
+        Self::from_quat(Quat::identity())
+    }
+
+    /// Rotation by `angle` in the plane of the bivector `plane`, which need
+    /// not be normalized; equivalently, about the axis normal to it.
+    pub fn from_plane_angle(plane: XYZVec<T>, angle: impl Into<Radians<T>>) -> Self {
+        Self::from_quat(Quat::from_axis_angle(plane, angle.into().0))
+    }
+
+    /// The smallest rotation taking the direction of `from` to the direction
+    /// of `to`. `None` if either is zero.
+    pub fn from_vectors(from: XYZVec<T>, to: XYZVec<T>) -> Option<Self> {
+        let f = |v: XYZVec<T>| XYZVec::new([v.x(), v.y(), v.z()].map(|c| c.to_f64()));
+        let (a, b) = (f(from), f(to));
+        let lens = a.l2_norm() * b.l2_norm();
+        if lens == 0.0 || !lens.is_finite() {
+            return None;
+        }
+        let (mut s, mut plane) = (lens + a.dot_prod(b), a.cross(b));
+        let mut len = (s * s + plane.l2_norm_sqd()).sqrt();
+        if len <= lens * 1e-12 {
+            // Opposite directions: turn half way round in any plane
+            // containing `a`.
+            let other = if a.x().abs() < a.z().abs() {
+                XYZVec::new([1.0, 0.0, 0.0])
+            } else {
+                XYZVec::new([0.0, 0.0, 1.0])
+            };
+            (s, plane) = (0.0, a.cross(other));
+            len = plane.l2_norm();
+        }
+        let b = plane.div_by(len);
+        Some(Self::new(
+            T::from_f64(s / len),
+            XYZVec::new([b.x(), b.y(), b.z()].map(T::from_f64)),
+        ))
+    }
+
+    /// `v` rotated by this unit rotor: `R v R̃`.
+    pub fn rotate(&self, v: XYZVec<T>) -> XYZVec<T> {
+        self.to_quat().rotate(v)
+    }
+
+    /// `self` scaled to unit length, or `None` if it is zero.
+    pub fn normalize(&self) -> Option<Self> {
+        self.to_quat().normalize().map(Self::from_quat)
+    }
+}
+
+/// `self * other` rotates by `other` first, then `self`.
+impl<T: VecInner> Mul for Rotor3<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::from_quat(self.to_quat() * other.to_quat())
+    }
+}
+
+impl<T: VecInner> From<Quat<T>> for Rotor3<T> {
+    fn from(q: Quat<T>) -> Self {
+        Self::from_quat(q)
+    }
+}
+
+impl<T: VecInner> From<Rotor3<T>> for Quat<T> {
+    fn from(r: Rotor3<T>) -> Self {
+        r.to_quat()
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Rotor3<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Rotor3 {{ s: {:?}, b: {:?} }}", self.s, self.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Rotor2, Rotor3};
+    use crate::{angle::Degrees, quat::Quat, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I16F16;
+
+    #[test]
+    fn rotor2_f64() {
+        let r = Rotor2::from_angle(Degrees(90.0f64));
+        let v = r.rotate(XYVec::new([2.0, 1.0]));
+        assert_relative_eq!(v.x(), -1.0, epsilon = 1e-12);
+        assert_relative_eq!(v.y(), 2.0, epsilon = 1e-12);
+        assert_relative_eq!(r.angle().0, std::f64::consts::FRAC_PI_2, epsilon = 1e-12);
+        assert_relative_eq!(
+            (r * r * r).angle().0,
+            -std::f64::consts::FRAC_PI_2,
+            epsilon = 1e-12
+        );
+        let id = r * r.reverse();
+        assert_relative_eq!(id.s, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(id.b, 0.0, epsilon = 1e-12);
+        let a = XYVec::new([1.0f64, 1.0]);
+        let b = XYVec::new([-3.0, 0.0]);
+        let turn = Rotor2::from_vectors(a, b).unwrap();
+        let turned = turn.rotate(a);
+        assert_relative_eq!(turned.x(), -a.l2_norm(), epsilon = 1e-12);
+        assert_relative_eq!(turned.y(), 0.0, epsilon = 1e-12);
+        let flip = Rotor2::from_vectors(a, -a).unwrap().rotate(a);
+        assert_relative_eq!((flip + a).l2_norm(), 0.0, epsilon = 1e-12);
+        assert!(Rotor2::from_vectors(a, XYVec::new([0.0, 0.0])).is_none());
+    }
+
+    #[test]
+    fn rotor2_matches_quat_f32() {
+        let r = Rotor2::from_angle(0.7f32);
+        let q = r.to_quat();
+        let v = r.rotate(XYVec::new([1.0, -2.0]));
+        let w = q.rotate(XYZVec::new([1.0, -2.0, 0.0]));
+        assert_relative_eq!(v.x(), w.x(), epsilon = 1e-6);
+        assert_relative_eq!(v.y(), w.y(), epsilon = 1e-6);
+        let drifted = Rotor2::new(r.s * 1.01, r.b * 1.01).normalize().unwrap();
+        assert_relative_eq!(drifted.s, r.s, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn rotor3_f64() {
+        let plane = XYZVec::new([0.0f64, 0.0, 1.0]);
+        let r = Rotor3::from_plane_angle(plane, std::f64::consts::FRAC_PI_2);
+        let v = r.rotate(XYZVec::new([1.0, 0.0, 5.0]));
+        assert_relative_eq!(
+            (v - XYZVec::new([0.0, 1.0, 5.0])).l2_norm(),
+            0.0,
+            epsilon = 1e-12
+        );
+        let q: Quat<f64> = r.into();
+        assert_eq!(Rotor3::from(q), r);
+        let a = XYZVec::new([1.0, 2.0, -0.5]);
+        let b = XYZVec::new([0.0, -1.0, 3.0]);
+        let p = XYZVec::new([0.2, 0.4, 0.9]);
+        let (ra, rb) = (Rotor3::from_vectors(a, b).unwrap(), r);
+        let composed = (rb * ra).rotate(p);
+        assert_relative_eq!(
+            (composed - rb.rotate(ra.rotate(p))).l2_norm(),
+            0.0,
+            epsilon = 1e-12
+        );
+        let turned = ra.rotate(a).div_by(a.l2_norm());
+        assert_relative_eq!(
+            (turned - b.div_by(b.l2_norm())).l2_norm(),
+            0.0,
+            epsilon = 1e-12
+        );
+        for c in [a, XYZVec::new([0.0, 0.0, 2.0])] {
+            let flip = Rotor3::from_vectors(c, -c).unwrap().rotate(c);
+            assert_relative_eq!((flip + c).l2_norm(), 0.0, epsilon = 1e-12);
+        }
+        let id = ra * ra.reverse();
+        assert_relative_eq!(id.s, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(id.b.l2_norm(), 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let r = Rotor2::from_angle(f(std::f64::consts::PI));
+        let v = r.rotate(XYVec::new([f(1.5), f(0.0)]));
+        assert!((v.x() + f(1.5)).abs() < f(1e-3));
+        assert!((r.angle().0.abs() - f(std::f64::consts::PI)).abs() < f(1e-3));
+    }
+}
@@ -0,0 +1,224 @@
+//! Signed distance functions. Each returns the nearest point on the
+//! shape's surface to `p`, along with the signed distance to it: positive
+//! outside, negative inside. They work on both [`XYVec`](crate::XYVec)
+//! (circles, rectangles, 2D capsules) and [`XYZVec`](crate::XYZVec)
+//! (spheres, boxes, 3D capsules).
+
+use crate::{queries::closest_point_on_segment, Aabb, Ball, VecFloat, VecNum, Vector};
+use std::cell::Cell;
+
+/// Unit vector along axis `i`.
+fn unit_axis<V: Vector>(i: usize) -> V
+where
+    V::Scalar: VecFloat,
+{
+    // `map` visits components in order.
+    let j = Cell::new(0);
+    V::splat(V::Scalar::zero()).map(|_| {
+        let k = j.get();
+        j.set(k + 1);
+        if k == i {
+            V::Scalar::one()
+        } else {
+            V::Scalar::zero()
+        }
+    })
+}
+
+/// `v` normalized, or if it is zero, some unit vector perpendicular to
+/// `avoid`.
+fn direction_or_perpendicular<V: Vector>(v: V, avoid: V) -> V
+where
+    V::Scalar: VecFloat,
+{
+    let len = v.l2_norm();
+    if len > V::Scalar::zero() {
+        return v.div_by(len);
+    }
+    let i = (0..V::DIM)
+        .min_by(|&a, &b| {
+            let (a, b) = (avoid.component(a).abs(), avoid.component(b).abs());
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap_or(0);
+    let e: V = unit_axis(i);
+    let aa = avoid.l2_norm_sqd();
+    let perp = if aa > V::Scalar::zero() {
+        e - avoid.scale_by(e.dot_prod(avoid) / aa)
+    } else {
+        e
+    };
+    perp.div_by(perp.l2_norm())
+}
+
+/// Nearest point on the boundary of a circle or sphere. From the center,
+/// every boundary point is nearest; one is picked arbitrarily.
+/// ```
+///     use xyzvec::{sdf, Circle, XYVec};
+///
+///     let c = Circle::new(XYVec::new([1.0f64, 0.0]), 2.0);
+///     assert_eq!(sdf::ball(XYVec::new([1.0, 3.0]), &c), (XYVec::new([1.0, 2.0]), 1.0));
+///     assert_eq!(sdf::ball(XYVec::new([0.0, 0.0]), &c), (XYVec::new([-1.0, 0.0]), -1.0));
+/// ```
+pub fn ball<V: Vector>(p: V, ball: &Ball<V>) -> (V, V::Scalar)
+where
+    V::Scalar: VecFloat,
+{
+    let offset = p - ball.center;
+    let dir = direction_or_perpendicular(offset, V::splat(V::Scalar::zero()));
+    (
+        ball.center + dir.scale_by(ball.radius),
+        offset.l2_norm() - ball.radius,
+    )
+}
+
+/// Nearest boundary point, signed distance, and outward surface normal
+/// there.
+fn aabb_with_normal<V: Vector>(p: V, aabb: &Aabb<V>) -> (V, V::Scalar, V)
+where
+    V::Scalar: VecFloat,
+{
+    if !aabb.contains_point(p) {
+        let q = aabb.clamp(p);
+        let offset = p - q;
+        let dist = offset.l2_norm();
+        return (q, dist, offset.div_by(dist));
+    }
+    // Inside: push out through the nearest face.
+    let (mut best, mut axis, mut to_max) = (None, 0, false);
+    for i in 0..V::DIM {
+        let c = p.component(i);
+        for (d, up) in [
+            (c - aabb.min.component(i), false),
+            (aabb.max.component(i) - c, true),
+        ] {
+            if best.is_none_or(|b| d < b) {
+                (best, axis, to_max) = (Some(d), i, up);
+            }
+        }
+    }
+    let depth = best.unwrap_or(V::Scalar::zero());
+    let e: V = unit_axis(axis);
+    let normal = if to_max { e } else { -e };
+    (p + normal.scale_by(depth), -depth, normal)
+}
+
+/// Nearest point on the boundary of a rectangle or box. Inside, the nearest
+/// point is on the closest face.
+/// ```
+///     use xyzvec::{sdf, Aabb3, XYZVec};
+///
+///     let b = Aabb3::new(XYZVec::new([0.0f64, 0.0, 0.0]), XYZVec::new([4.0, 4.0, 2.0]));
+///     assert_eq!(sdf::aabb(XYZVec::new([7.0, 8.0, 1.0]), &b), (XYZVec::new([4.0, 4.0, 1.0]), 5.0));
+///     assert_eq!(sdf::aabb(XYZVec::new([1.0, 2.0, 1.5]), &b), (XYZVec::new([1.0, 2.0, 2.0]), -0.5));
+/// ```
+pub fn aabb<V: Vector>(p: V, aabb: &Aabb<V>) -> (V, V::Scalar)
+where
+    V::Scalar: VecFloat,
+{
+    let (q, d, _) = aabb_with_normal(p, aabb);
+    (q, d)
+}
+
+/// Nearest point on the boundary of `aabb` grown outward by `radius` with
+/// rounded edges and corners.
+pub fn rounded_box<V: Vector>(p: V, aabb: &Aabb<V>, radius: V::Scalar) -> (V, V::Scalar)
+where
+    V::Scalar: VecFloat,
+{
+    let (q, d, normal) = aabb_with_normal(p, aabb);
+    (q + normal.scale_by(radius), d - radius)
+}
+
+/// Nearest point on the segment from `a` to `b`, and the (unsigned) distance
+/// to it; a segment has no inside.
+pub fn segment<V: Vector>(p: V, a: V, b: V) -> (V, V::Scalar)
+where
+    V::Scalar: VecFloat,
+{
+    let (q, _) = closest_point_on_segment(p, a, b);
+    (q, (p - q).l2_norm())
+}
+
+/// Nearest point on the boundary of the capsule of points within `radius`
+/// of the segment from `a` to `b`.
+/// ```
+///     use xyzvec::{sdf, XYVec};
+///
+///     let (a, b) = (XYVec::new([0.0f64, 0.0]), XYVec::new([4.0, 0.0]));
+///     assert_eq!(sdf::capsule(XYVec::new([2.0, 3.0]), a, b, 1.0), (XYVec::new([2.0, 1.0]), 2.0));
+///     assert_eq!(sdf::capsule(XYVec::new([-1.5, 0.0]), a, b, 1.0), (XYVec::new([-1.0, 0.0]), 0.5));
+/// ```
+pub fn capsule<V: Vector>(p: V, a: V, b: V, radius: V::Scalar) -> (V, V::Scalar)
+where
+    V::Scalar: VecFloat,
+{
+    let (q, d) = segment(p, a, b);
+    let dir = direction_or_perpendicular(p - q, b - a);
+    (q + dir.scale_by(radius), d - radius)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{aabb, ball, capsule, rounded_box, segment};
+    use crate::{Aabb2, Aabb3, Sphere, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn sphere_and_box_f64() {
+        let s = Sphere::new(XYZVec::new([0.0f64, 0.0, 0.0]), 1.0);
+        let (q, d) = ball(XYZVec::new([2.0, 2.0, 1.0]), &s);
+        assert_relative_eq!(d, 2.0);
+        assert_relative_eq!(q.l2_norm(), 1.0);
+        let (q, d) = ball(s.center, &s);
+        assert_relative_eq!(d, -1.0);
+        assert_relative_eq!(q.l2_norm(), 1.0);
+
+        let b = Aabb3::new(
+            XYZVec::new([-1.0, -1.0, -1.0]),
+            XYZVec::new([1.0, 1.0, 1.0]),
+        );
+        let (q, d) = aabb(XYZVec::new([0.2, -0.9, 0.0]), &b);
+        assert_eq!(q, XYZVec::new([0.2, -1.0, 0.0]));
+        assert_relative_eq!(d, -0.1, epsilon = 1e-12);
+        let (q, d) = aabb(XYZVec::new([1.0, 0.0, 0.0]), &b);
+        assert_eq!((q, d), (XYZVec::new([1.0, 0.0, 0.0]), 0.0));
+    }
+
+    #[test]
+    fn rounded_box_f32() {
+        let b = Aabb2::new(XYVec::new([0.0f32, 0.0]), XYVec::new([2.0, 1.0]));
+        // Off a corner the rounding is circular.
+        let (q, d) = rounded_box(XYVec::new([5.0, 5.0]), &b, 1.0);
+        assert_relative_eq!(d, 4.0, epsilon = 1e-6);
+        assert_relative_eq!((q - XYVec::new([2.6, 1.8])).l2_norm(), 0.0, epsilon = 1e-6);
+        // Inside, the nearest face moves out by the radius.
+        let (q, d) = rounded_box(XYVec::new([1.0, 0.75]), &b, 0.5);
+        assert_eq!(q, XYVec::new([1.0, 1.5]));
+        assert_relative_eq!(d, -0.75);
+    }
+
+    #[test]
+    fn segment_and_capsule_f64() {
+        let (a, b) = (
+            XYZVec::new([0.0f64, 0.0, 0.0]),
+            XYZVec::new([0.0, 0.0, 3.0]),
+        );
+        let (q, d) = segment(XYZVec::new([0.0, 4.0, 6.0]), a, b);
+        assert_eq!(q, b);
+        assert_relative_eq!(d, 5.0);
+        // On the axis the nearest point is in some perpendicular direction.
+        let (q, d) = capsule(XYZVec::new([0.0, 0.0, 1.0]), a, b, 0.5);
+        assert_relative_eq!(d, -0.5);
+        assert_relative_eq!((q - XYZVec::new([0.0, 0.0, 1.0])).l2_norm(), 0.5);
+        assert_relative_eq!(q.z(), 1.0);
+        let diag = XYVec::new([1.0f64, 1.0]);
+        let (q, d) = capsule(XYVec::new([0.5, 0.5]), XYVec::new([0.0, 0.0]), diag, 1.0);
+        assert_relative_eq!(d, -1.0);
+        assert_relative_eq!(
+            (q - XYVec::new([0.5, 0.5])).dot_prod(diag),
+            0.0,
+            epsilon = 1e-12
+        );
+    }
+}
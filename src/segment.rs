@@ -0,0 +1,209 @@
+//! Line segments in the plane and their intersections.
+
+use crate::{CrossSign, Orientation, VecInner, VecNum, XYVec};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Formatter},
+};
+
+/// Closed segment from `start` to `end`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct LineSegment2<T> {
+    pub start: XYVec<T>,
+    pub end: XYVec<T>,
+}
+
+/// How two segments meet.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SegmentIntersection<T: VecInner> {
+    /// The segments share no point.
+    None,
+    /// The interiors cross at a single point.
+    Crossing(XYVec<T>),
+    /// The segments meet at a single point that is an endpoint of at least
+    /// one of them.
+    Touching(XYVec<T>),
+    /// The segments are collinear and share this sub-segment, of nonzero
+    /// length. Its endpoints are ordered lexicographically (`x`, then `y`).
+    Overlap(LineSegment2<T>),
+}
+
+fn lex_cmp<T: VecNum>(a: &XYVec<T>, b: &XYVec<T>) -> Ordering {
+    a.x()
+        .partial_cmp(&b.x())
+        .unwrap_or(Ordering::Equal)
+        .then(a.y().partial_cmp(&b.y()).unwrap_or(Ordering::Equal))
+}
+
+impl<T: VecNum> LineSegment2<T> {
+    pub fn new(start: XYVec<T>, end: XYVec<T>) -> Self {
+        Self { start, end }
+    }
+
+    /// Endpoints ordered lexicographically.
+    fn sorted(&self) -> (XYVec<T>, XYVec<T>) {
+        if lex_cmp(&self.start, &self.end) == Ordering::Greater {
+            (self.end, self.start)
+        } else {
+            (self.start, self.end)
+        }
+    }
+}
+
+impl<T: CrossSign> LineSegment2<T> {
+    /// Intersection of two segments. Which case applies is decided with
+    /// [`CrossSign`], so it is exact for integer and fixed-point
+    /// coordinates. Touching and overlap points are endpoints of the inputs,
+    /// and so exact too; a crossing point is computed in `f64` and rounded
+    /// to `T`. Zero-length segments are treated as points.
+    /// ```
+    ///     use xyzvec::segment::{LineSegment2, SegmentIntersection};
+    ///     use xyzvec::XYVec;
+    ///
+    ///     let s = |a: [i32; 2], b: [i32; 2]| LineSegment2::new(XYVec::new(a), XYVec::new(b));
+    ///     assert_eq!(
+    ///         s([0, 0], [4, 4]).intersect(&s([0, 4], [4, 0])),
+    ///         SegmentIntersection::Crossing(XYVec::new([2, 2]))
+    ///     );
+    ///     assert_eq!(
+    ///         s([0, 0], [4, 0]).intersect(&s([2, 0], [2, 3])),
+    ///         SegmentIntersection::Touching(XYVec::new([2, 0]))
+    ///     );
+    ///     assert_eq!(
+    ///         s([0, 0], [4, 0]).intersect(&s([6, 0], [3, 0])),
+    ///         SegmentIntersection::Overlap(s([3, 0], [4, 0]))
+    ///     );
+    ///     assert_eq!(s([0, 0], [4, 0]).intersect(&s([0, 1], [4, 1])), SegmentIntersection::None);
+    /// ```
+    pub fn intersect(&self, other: &Self) -> SegmentIntersection<T> {
+        use Orientation::Collinear;
+        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
+        let o1 = Orientation::of(a, b, c);
+        let o2 = Orientation::of(a, b, d);
+        let o3 = Orientation::of(c, d, a);
+        let o4 = Orientation::of(c, d, b);
+
+        if [o1, o2, o3, o4].iter().all(|&o| o == Collinear) {
+            let ((lo1, hi1), (lo2, hi2)) = (self.sorted(), other.sorted());
+            let lo = if lex_cmp(&lo1, &lo2) == Ordering::Less {
+                lo2
+            } else {
+                lo1
+            };
+            let hi = if lex_cmp(&hi1, &hi2) == Ordering::Less {
+                hi1
+            } else {
+                hi2
+            };
+            return match lex_cmp(&lo, &hi) {
+                Ordering::Greater => SegmentIntersection::None,
+                Ordering::Equal => SegmentIntersection::Touching(lo),
+                Ordering::Less => SegmentIntersection::Overlap(Self::new(lo, hi)),
+            };
+        }
+        if (o1 == o2 && o1 != Collinear) || (o3 == o4 && o3 != Collinear) {
+            return SegmentIntersection::None;
+        }
+        // An endpoint on the other segment's line is the meeting point.
+        for (o, p) in [(o1, c), (o2, d), (o3, a), (o4, b)] {
+            if o == Collinear {
+                return SegmentIntersection::Touching(p);
+            }
+        }
+        let f = |v: XYVec<T>| [v.x().to_f64(), v.y().to_f64()];
+        let ([ax, ay], [bx, by], [cx, cy], [dx, dy]) = (f(a), f(b), f(c), f(d));
+        let (rx, ry, sx, sy) = (bx - ax, by - ay, dx - cx, dy - cy);
+        let t = ((cx - ax) * sy - (cy - ay) * sx) / (rx * sy - ry * sx);
+        SegmentIntersection::Crossing(XYVec::new([ax + t * rx, ay + t * ry].map(T::from_f64)))
+    }
+
+    /// Whether the segments share at least one point.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersect(other) != SegmentIntersection::None
+    }
+}
+
+impl<T: VecInner> fmt::Debug for LineSegment2<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "LineSegment2 {{ start: {:?}, end: {:?} }}",
+            self.start, self.end
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LineSegment2, SegmentIntersection};
+    use crate::XYVec;
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    fn seg<T: crate::VecNum>(a: [T; 2], b: [T; 2]) -> LineSegment2<T> {
+        LineSegment2::new(XYVec::new(a), XYVec::new(b))
+    }
+
+    #[test]
+    fn cases_i64() {
+        use SegmentIntersection::*;
+        let base = seg([0i64, 0], [10, 0]);
+        // Endpoint to endpoint, and end-on-end along the same line.
+        assert_eq!(
+            base.intersect(&seg([10, 0], [12, 5])),
+            Touching(XYVec::new([10, 0]))
+        );
+        assert_eq!(
+            base.intersect(&seg([10, 0], [15, 0])),
+            Touching(XYVec::new([10, 0]))
+        );
+        assert_eq!(base.intersect(&seg([11, 0], [15, 0])), None);
+        // Containment, in either direction and orientation.
+        assert_eq!(
+            base.intersect(&seg([8, 0], [2, 0])),
+            Overlap(seg([2, 0], [8, 0]))
+        );
+        assert_eq!(
+            seg([8i64, 0], [2, 0]).intersect(&base),
+            Overlap(seg([2, 0], [8, 0]))
+        );
+        // Almost parallel but far from collinear.
+        let big = 1i64 << 40;
+        assert_eq!(
+            seg([0, 0], [big, 1]).intersect(&seg([0, 1], [big, 2])),
+            None
+        );
+        assert!(seg([0, 0], [big, big + 1]).intersects(&seg([big, 0], [0, big])));
+        // Zero-length segments behave as points.
+        assert_eq!(
+            base.intersect(&seg([3, 0], [3, 0])),
+            Touching(XYVec::new([3, 0]))
+        );
+        assert_eq!(base.intersect(&seg([3, 1], [3, 1])), None);
+        assert_eq!(
+            seg([3i64, 1], [3, 1]).intersect(&seg([3, 1], [3, 1])),
+            Touching(XYVec::new([3, 1]))
+        );
+    }
+
+    #[test]
+    fn crossing_f64_and_fixed() {
+        let hit = seg([0.0f64, 0.0], [3.0, 1.0]).intersect(&seg([1.0, -1.0], [1.0, 2.0]));
+        let SegmentIntersection::Crossing(p) = hit else {
+            panic!("expected a crossing, got {hit:?}");
+        };
+        assert_relative_eq!(p.x(), 1.0);
+        assert_relative_eq!(p.y(), 1.0 / 3.0);
+
+        let f = |v: f64| I28F4::from_num(v);
+        // Separated by a single step of resolution.
+        let a = seg([f(0.0), f(0.0)], [f(100.0), f(0.0625)]);
+        let b = seg([f(0.0), f(0.0625)], [f(100.0), f(0.125)]);
+        assert!(!a.intersects(&b));
+        let c = seg([f(50.0), f(-1.0)], [f(50.0), f(1.0)]);
+        assert_eq!(
+            a.intersect(&c),
+            SegmentIntersection::Crossing(XYVec::new([f(50.0), f(0.03125)]))
+        );
+    }
+}
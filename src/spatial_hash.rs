@@ -0,0 +1,345 @@
+//! Uniform grid of hashed cells for broad-phase neighbor queries.
+
+use crate::{VecFloat, VecNum, Vector};
+use std::{
+    collections::HashMap,
+    fmt::{self, Formatter},
+};
+
+/// Integer coordinates of a grid cell. Components past the vector's
+/// dimension are zero.
+pub type CellKey = [i64; 3];
+
+/// Key of the cell of side `cell_size` containing `p`. Points that round to
+/// the same key can be bucketed together in a `HashMap`.
+/// ```
+///     use xyzvec::{spatial_hash::quantized_key, XYVec};
+///
+///     assert_eq!(quantized_key(XYVec::new([0.49f64, -0.01]), 0.5), [0, -1, 0]);
+/// ```
+pub fn quantized_key<V: Vector>(p: V, cell_size: V::Scalar) -> CellKey
+where
+    V::Scalar: VecFloat,
+{
+    let mut key = [0; 3];
+    for (i, k) in key.iter_mut().enumerate().take(V::DIM) {
+        *k = (p.component(i) / cell_size).floor().to_f64() as i64;
+    }
+    key
+}
+
+/// Removes points within `tolerance` (which must be positive) of an earlier
+/// kept point, keeping the rest in their original order. The first point of
+/// each cluster survives, so the result only depends on the input order.
+/// Runs in expected linear time.
+/// ```
+///     use xyzvec::{spatial_hash::dedup_points, XYVec};
+///
+///     let mut points = vec![
+///         XYVec::new([0.0f64, 0.0]),
+///         XYVec::new([5.0, 5.0]),
+///         XYVec::new([0.0, 1e-9]),
+///         XYVec::new([5.0, 5.0]),
+///     ];
+///     dedup_points(&mut points, 1e-6);
+///     assert_eq!(points, vec![XYVec::new([0.0, 0.0]), XYVec::new([5.0, 5.0])]);
+/// ```
+pub fn dedup_points<V: Vector>(points: &mut Vec<V>, tolerance: V::Scalar)
+where
+    V::Scalar: VecFloat,
+{
+    let mut kept = SpatialHashGrid::new(tolerance);
+    points.retain(|&p| {
+        if kept.query_radius(p, tolerance).is_empty() {
+            kept.insert(p, ());
+            true
+        } else {
+            false
+        }
+    });
+}
+
+/// Values stored at points, bucketed into square (or cubic) cells of side
+/// `cell_size`. Queries only visit the cells overlapping the search region,
+/// so they are fast when the cell size is close to the typical query radius.
+#[derive(Clone)]
+pub struct SpatialHashGrid<V: Vector, T> {
+    cell_size: V::Scalar,
+    cells: HashMap<CellKey, Vec<(V, T)>>,
+    len: usize,
+}
+
+/// Centroid of the points in each occupied cell of side `cell_size`, one
+/// per cell, ordered by each cell's first point in `points`. Thins dense
+/// point clouds (such as lidar scans) to roughly uniform density.
+/// ```
+///     use xyzvec::{spatial_hash::voxel_downsample, XYZVec};
+///
+///     let points = [
+///         XYZVec::new([0.1f32, 0.1, 0.1]),
+///         XYZVec::new([5.5, 0.5, 0.5]),
+///         XYZVec::new([0.3, 0.5, 0.7]),
+///     ];
+///     let thinned = voxel_downsample(&points, 1.0);
+///     assert_eq!(thinned, vec![XYZVec::new([0.2, 0.3, 0.4]), XYZVec::new([5.5, 0.5, 0.5])]);
+/// ```
+pub fn voxel_downsample<V: Vector>(points: &[V], cell_size: V::Scalar) -> Vec<V>
+where
+    V::Scalar: VecFloat,
+{
+    let mut index = HashMap::new();
+    let mut sums: Vec<(V, V::Scalar)> = Vec::new();
+    for &p in points {
+        let i = *index.entry(quantized_key(p, cell_size)).or_insert_with(|| {
+            sums.push((V::splat(V::Scalar::zero()), V::Scalar::zero()));
+            sums.len() - 1
+        });
+        let (sum, count) = &mut sums[i];
+        *sum += p;
+        *count += V::Scalar::one();
+    }
+    sums.into_iter()
+        .map(|(sum, count)| sum.div_by(count))
+        .collect()
+}
+
+impl<V: Vector, T> SpatialHashGrid<V, T>
+where
+    V::Scalar: VecFloat,
+{
+    /// Empty grid. `cell_size` must be positive.
+    pub fn new(cell_size: V::Scalar) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            len: 0,
+        }
+    }
+
+    pub fn cell_size(&self) -> V::Scalar {
+        self.cell_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.len = 0;
+    }
+
+    /// Key of the cell containing `p`.
+    pub fn cell_key(&self, p: V) -> CellKey {
+        quantized_key(p, self.cell_size)
+    }
+
+    pub fn insert(&mut self, p: V, value: T) {
+        let key = self.cell_key(p);
+        self.cells.entry(key).or_default().push((p, value));
+        self.len += 1;
+    }
+
+    /// Removes one entry at `p` whose value equals `value`, returning
+    /// whether one was found. `p` must be the point it was inserted with.
+    pub fn remove(&mut self, p: V, value: &T) -> bool
+    where
+        V: PartialEq,
+        T: PartialEq,
+    {
+        let key = self.cell_key(p);
+        let Some(bucket) = self.cells.get_mut(&key) else {
+            return false;
+        };
+        let Some(i) = bucket.iter().position(|(q, v)| *q == p && v == value) else {
+            return false;
+        };
+        bucket.swap_remove(i);
+        if bucket.is_empty() {
+            self.cells.remove(&key);
+        }
+        self.len -= 1;
+        true
+    }
+
+    /// Every stored entry, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (V, &T)> + '_ {
+        self.cells
+            .values()
+            .flat_map(|bucket| bucket.iter().map(|(p, v)| (*p, v)))
+    }
+
+    /// Entries within distance `r` of `p` (inclusive), in no particular
+    /// order.
+    /// ```
+    ///     use xyzvec::{SpatialHashGrid, XYVec};
+    ///
+    ///     let mut grid = SpatialHashGrid::new(1.0f64);
+    ///     grid.insert(XYVec::new([0.5, 0.5]), "a");
+    ///     grid.insert(XYVec::new([1.2, 0.5]), "b");
+    ///     grid.insert(XYVec::new([5.0, 5.0]), "c");
+    ///     let mut near: Vec<_> = grid
+    ///         .query_radius(XYVec::new([0.8, 0.5]), 0.5)
+    ///         .into_iter()
+    ///         .map(|(_, v)| *v)
+    ///         .collect();
+    ///     near.sort();
+    ///     assert_eq!(near, vec!["a", "b"]);
+    /// ```
+    pub fn query_radius(&self, p: V, r: V::Scalar) -> Vec<(V, &T)> {
+        let lo = self.cell_key(p - V::splat(r));
+        let hi = self.cell_key(p + V::splat(r));
+        let r_sqd = r * r;
+        let mut out = Vec::new();
+        for x in lo[0]..=hi[0] {
+            for y in lo[1]..=hi[1] {
+                for z in lo[2]..=hi[2] {
+                    let Some(bucket) = self.cells.get(&[x, y, z]) else {
+                        continue;
+                    };
+                    out.extend(
+                        bucket
+                            .iter()
+                            .filter(|(q, _)| (*q - p).l2_norm_sqd() <= r_sqd)
+                            .map(|(q, v)| (*q, v)),
+                    );
+                }
+            }
+        }
+        out
+    }
+}
+
+impl<V: Vector, T: fmt::Debug> fmt::Debug for SpatialHashGrid<V, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SpatialHashGrid")
+            .field("cell_size", &self.cell_size)
+            .field("len", &self.len)
+            .field("cells", &self.cells)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dedup_points, quantized_key, voxel_downsample};
+    use crate::{SpatialHashGrid, XYVec, XYZVec};
+
+    #[test]
+    fn query_matches_brute_force_f64() {
+        let points: Vec<XYZVec<f64>> = (0..300)
+            .map(|i| {
+                let t = i as f64;
+                XYZVec::new([
+                    (t * 0.37).sin() * 4.0,
+                    (t * 0.71).cos() * 4.0,
+                    (t * 0.13).sin(),
+                ])
+            })
+            .collect();
+        let mut grid = SpatialHashGrid::new(0.75);
+        for (i, &p) in points.iter().enumerate() {
+            grid.insert(p, i);
+        }
+        assert_eq!(grid.len(), points.len());
+        for &(center, r) in [
+            (XYZVec::new([0.0, 0.0, 0.0]), 1.0),
+            (XYZVec::new([-3.5, 2.0, 0.5]), 2.3),
+            (XYZVec::new([10.0, 0.0, 0.0]), 0.5),
+        ]
+        .iter()
+        {
+            let mut found: Vec<usize> = grid
+                .query_radius(center, r)
+                .into_iter()
+                .map(|(_, &i)| i)
+                .collect();
+            found.sort();
+            let expected: Vec<usize> = (0..points.len())
+                .filter(|&i| (points[i] - center).l2_norm() <= r)
+                .collect();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn insert_remove_f32() {
+        let mut grid = SpatialHashGrid::new(2.0f32);
+        let p = XYVec::new([-0.5f32, 3.0]);
+        grid.insert(p, 'x');
+        grid.insert(p, 'y');
+        assert_eq!(grid.cell_key(p), [-1, 1, 0]);
+        assert!(!grid.remove(p, &'z'));
+        assert!(!grid.remove(XYVec::new([10.0, 10.0]), &'x'));
+        assert!(grid.remove(p, &'x'));
+        assert_eq!(grid.len(), 1);
+        assert_eq!(grid.iter().map(|(_, v)| *v).collect::<Vec<_>>(), vec!['y']);
+        assert!(grid.remove(p, &'y'));
+        assert!(grid.is_empty());
+        assert!(grid.query_radius(p, 5.0).is_empty());
+
+        // Equal values at two points of one cell: only `p`'s entry goes.
+        let q = XYVec::new([-1.5f32, 2.5]);
+        grid.insert(q, 'x');
+        grid.insert(p, 'x');
+        assert_eq!(grid.cell_key(q), grid.cell_key(p));
+        assert!(grid.remove(p, &'x'));
+        assert_eq!(grid.query_radius(q, 0.1), vec![(q, &'x')]);
+        assert!(!grid.remove(p, &'x'));
+    }
+
+    #[test]
+    fn dedup_f32() {
+        // A jittered grid sampled twice, with the copies interleaved.
+        let base: Vec<XYZVec<f32>> = (0..64)
+            .map(|i| XYZVec::new([(i % 4) as f32, (i / 4 % 4) as f32, (i / 16) as f32]))
+            .collect();
+        let mut points = Vec::new();
+        for (i, &p) in base.iter().enumerate() {
+            points.push(p);
+            let jitter = if i % 2 == 0 { 1e-4 } else { -1e-4 };
+            points.push(p + XYZVec::new([jitter, 0.0, -jitter]));
+        }
+        dedup_points(&mut points, 1e-3);
+        assert_eq!(points, base);
+        // Near points merge even across a cell boundary; points further
+        // apart than the tolerance all survive.
+        let mut close = vec![XYVec::new([0.99f32, 0.0]), XYVec::new([1.01, 0.0])];
+        dedup_points(&mut close, 0.025);
+        assert_eq!(close, vec![XYVec::new([0.99, 0.0])]);
+        let mut apart = vec![XYVec::new([0.99f32, 0.0]), XYVec::new([1.01, 0.0])];
+        dedup_points(&mut apart, 0.01);
+        assert_eq!(apart.len(), 2);
+    }
+
+    #[test]
+    fn voxel_downsample_f64() {
+        let points: Vec<XYZVec<f64>> = (0..1000)
+            .map(|i| {
+                let t = i as f64 * 0.01;
+                XYZVec::new([t.cos() * 3.0, t.sin() * 3.0, t])
+            })
+            .collect();
+        let thinned = voxel_downsample(&points, 0.5);
+        let keys: Vec<_> = thinned.iter().map(|&c| quantized_key(c, 0.5)).collect();
+        let mut distinct = keys.clone();
+        distinct.sort();
+        distinct.dedup();
+        let mut occupied: Vec<_> = points.iter().map(|&p| quantized_key(p, 0.5)).collect();
+        occupied.sort();
+        occupied.dedup();
+        // One point per occupied cell. The helix is convex within a cell,
+        // so each centroid stays near it.
+        assert_eq!(distinct, occupied);
+        assert_eq!(keys.len(), distinct.len());
+        for c in &thinned {
+            let r = (c.x() * c.x() + c.y() * c.y()).sqrt();
+            assert!((r - 3.0).abs() < 0.05);
+        }
+        assert!(voxel_downsample::<XYZVec<f64>>(&[], 1.0).is_empty());
+    }
+}
@@ -0,0 +1,375 @@
+//! Interpolating splines through [`XYVec`](crate::XYVec) or
+//! [`XYZVec`](crate::XYZVec) points.
+//!
+//! Splines through `n` points are evaluated at `t` in `[0, n - 1]`: segment
+//! `i` covers `[i, i + 1]` and passes through point `i` at `t = i`.
+
+use crate::{VecFloat, VecNum, Vector};
+
+/// Cubic Hermite curve from `p0` to `p1` with tangents `m0` and `m1`,
+/// evaluated at `t` in `[0, 1]`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct HermiteSegment<V> {
+    pub p0: V,
+    pub m0: V,
+    pub p1: V,
+    pub m1: V,
+}
+
+impl<V: Vector> HermiteSegment<V>
+where
+    V::Scalar: VecNum,
+{
+    pub fn new(p0: V, m0: V, p1: V, m1: V) -> Self {
+        Self { p0, m0, p1, m1 }
+    }
+
+    /// ```
+    ///     use xyzvec::{HermiteSegment, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let s = HermiteSegment::new(
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([1.0, 1.0]),
+    ///         XYVec::new([1.0, 0.0]),
+    ///         XYVec::new([1.0, -1.0]),
+    ///     );
+    ///     assert_eq!(s.eval(0.0), s.p0);
+    ///     assert_eq!(s.eval(1.0), s.p1);
+    ///     assert_relative_eq!(s.eval(0.5).y(), 0.25);
+    /// ```
+    pub fn eval(&self, t: V::Scalar) -> V {
+        let one = V::Scalar::one();
+        let two = one + one;
+        let three = two + one;
+        let t2 = t * t;
+        let t3 = t2 * t;
+        let h00 = two * t3 - three * t2 + one;
+        let h10 = t3 - two * t2 + t;
+        let h01 = three * t2 - two * t3;
+        let h11 = t3 - t2;
+        self.p0.scale_by(h00)
+            + self.m0.scale_by(h10)
+            + self.p1.scale_by(h01)
+            + self.m1.scale_by(h11)
+    }
+
+    /// Derivative with respect to `t`.
+    pub fn tangent(&self, t: V::Scalar) -> V {
+        let one = V::Scalar::one();
+        let two = one + one;
+        let three = two + one;
+        let six = three + three;
+        let t2 = t * t;
+        let h00 = six * t2 - six * t;
+        let h10 = three * t2 - (two + two) * t + one;
+        let h01 = six * t - six * t2;
+        let h11 = three * t2 - two * t;
+        self.p0.scale_by(h00)
+            + self.m0.scale_by(h10)
+            + self.p1.scale_by(h01)
+            + self.m1.scale_by(h11)
+    }
+}
+
+/// Segment index and local parameter for global `t` over `segments` segments.
+fn locate<T: VecFloat>(t: T, segments: usize) -> (usize, T) {
+    let i = t.to_f64().floor().clamp(0.0, (segments - 1) as f64) as usize;
+    (i, t - T::from_f64(i as f64))
+}
+
+/// Piecewise cubic Hermite spline through `points` with the given tangents.
+#[derive(Clone, PartialEq, Debug)]
+pub struct HermiteSpline<V> {
+    points: Vec<V>,
+    tangents: Vec<V>,
+}
+
+impl<V: Vector> HermiteSpline<V>
+where
+    V::Scalar: VecFloat,
+{
+    /// `None` unless there are at least two points and one tangent per point.
+    pub fn new(points: Vec<V>, tangents: Vec<V>) -> Option<Self> {
+        if points.len() < 2 || points.len() != tangents.len() {
+            return None;
+        }
+        Some(Self { points, tangents })
+    }
+
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    pub fn tangents(&self) -> &[V] {
+        &self.tangents
+    }
+
+    pub fn segment(&self, i: usize) -> HermiteSegment<V> {
+        HermiteSegment::new(
+            self.points[i],
+            self.tangents[i],
+            self.points[i + 1],
+            self.tangents[i + 1],
+        )
+    }
+
+    pub fn eval(&self, t: V::Scalar) -> V {
+        let (i, u) = locate(t, self.points.len() - 1);
+        self.segment(i).eval(u)
+    }
+
+    /// Derivative with respect to `t`.
+    pub fn tangent(&self, t: V::Scalar) -> V {
+        let (i, u) = locate(t, self.points.len() - 1);
+        self.segment(i).tangent(u)
+    }
+}
+
+/// Knot spacing used by [`CatmullRom`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Parameterization {
+    /// Equal knot spacing; the classic Catmull-Rom spline.
+    Uniform,
+    /// Knot spacing by the square root of the chord length, which avoids
+    /// cusps and self-intersections within a segment.
+    Centripetal,
+}
+
+/// Catmull-Rom spline passing through every point.
+///
+/// The end segments use mirrored phantom points, so the spline starts at the
+/// first point and ends at the last.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CatmullRom<V> {
+    points: Vec<V>,
+    parameterization: Parameterization,
+}
+
+impl<V: Vector> CatmullRom<V>
+where
+    V::Scalar: VecFloat,
+{
+    /// `None` if there are fewer than two points.
+    pub fn new(points: Vec<V>, parameterization: Parameterization) -> Option<Self> {
+        if points.len() < 2 {
+            return None;
+        }
+        Some(Self {
+            points,
+            parameterization,
+        })
+    }
+
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    fn point(&self, i: isize) -> V {
+        let n = self.points.len() as isize;
+        if i < 0 {
+            let two = V::Scalar::one() + V::Scalar::one();
+            self.points[0].scale_by(two) - self.points[1]
+        } else if i >= n {
+            let two = V::Scalar::one() + V::Scalar::one();
+            self.points[(n - 1) as usize].scale_by(two) - self.points[(n - 2) as usize]
+        } else {
+            self.points[i as usize]
+        }
+    }
+
+    fn knot_interval(&self, a: V, b: V) -> V::Scalar {
+        let dt = match self.parameterization {
+            Parameterization::Uniform => V::Scalar::one(),
+            Parameterization::Centripetal => (b - a).l2_norm().sqrt(),
+        };
+        if dt > V::Scalar::zero() {
+            dt
+        } else {
+            V::Scalar::one()
+        }
+    }
+
+    /// Segment `i` (from point `i` to point `i + 1`) as a Hermite curve.
+    /// ```
+    ///     use xyzvec::{CatmullRom, Parameterization, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let points = vec![
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([1.0, 1.0]),
+    ///         XYVec::new([2.0, 0.0]),
+    ///         XYVec::new([3.0, 1.0]),
+    ///     ];
+    ///     let spline = CatmullRom::new(points, Parameterization::Uniform).unwrap();
+    ///     let s = spline.segment(1);
+    ///     assert_relative_eq!(s.m0.x(), 1.0);
+    ///     assert_relative_eq!(s.m0.y(), 0.0);
+    /// ```
+    pub fn segment(&self, i: usize) -> HermiteSegment<V> {
+        let i = i as isize;
+        let (p0, p1, p2, p3) = (
+            self.point(i - 1),
+            self.point(i),
+            self.point(i + 1),
+            self.point(i + 2),
+        );
+        let d01 = self.knot_interval(p0, p1);
+        let d12 = self.knot_interval(p1, p2);
+        let d23 = self.knot_interval(p2, p3);
+        let m1 = ((p1 - p0).div_by(d01) - (p2 - p0).div_by(d01 + d12) + (p2 - p1).div_by(d12))
+            .scale_by(d12);
+        let m2 = ((p2 - p1).div_by(d12) - (p3 - p1).div_by(d12 + d23) + (p3 - p2).div_by(d23))
+            .scale_by(d12);
+        HermiteSegment::new(p1, m1, p2, m2)
+    }
+
+    /// ```
+    ///     use xyzvec::{CatmullRom, Parameterization, XYZVec};
+    ///
+    ///     let points = vec![
+    ///         XYZVec::new([0.0f64, 0.0, 0.0]),
+    ///         XYZVec::new([1.0, 2.0, 0.0]),
+    ///         XYZVec::new([3.0, 2.0, 1.0]),
+    ///     ];
+    ///     let spline = CatmullRom::new(points.clone(), Parameterization::Centripetal).unwrap();
+    ///     assert_eq!(spline.eval(0.0), points[0]);
+    ///     assert_eq!(spline.eval(1.0), points[1]);
+    ///     assert_eq!(spline.eval(2.0), points[2]);
+    /// ```
+    pub fn eval(&self, t: V::Scalar) -> V {
+        let (i, u) = locate(t, self.points.len() - 1);
+        self.segment(i).eval(u)
+    }
+
+    /// Derivative with respect to `t`. With centripetal parameterization
+    /// its length may jump at the points, but its direction is continuous.
+    pub fn tangent(&self, t: V::Scalar) -> V {
+        let (i, u) = locate(t, self.points.len() - 1);
+        self.segment(i).tangent(u)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CatmullRom, HermiteSegment, HermiteSpline, Parameterization, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    fn zigzag() -> Vec<XYVec<f64>> {
+        vec![
+            XYVec::new([0.0, 0.0]),
+            XYVec::new([1.0, 2.0]),
+            XYVec::new([1.5, 2.0]),
+            XYVec::new([4.0, -1.0]),
+            XYVec::new([5.0, 0.0]),
+        ]
+    }
+
+    #[test]
+    fn interpolates_points_f64() {
+        for p in [Parameterization::Uniform, Parameterization::Centripetal] {
+            let points = zigzag();
+            let spline = CatmullRom::new(points.clone(), p).unwrap();
+            for (i, q) in points.iter().enumerate() {
+                let e = spline.eval(i as f64);
+                assert_relative_eq!(e.x(), q.x(), epsilon = 1e-12);
+                assert_relative_eq!(e.y(), q.y(), epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn continuous_tangent_f64() {
+        let spline = CatmullRom::new(zigzag(), Parameterization::Uniform).unwrap();
+        for i in 1..4 {
+            let before = spline.segment(i - 1).tangent(1.0);
+            let after = spline.segment(i).tangent(0.0);
+            assert_relative_eq!(before.x(), after.x(), epsilon = 1e-9);
+            assert_relative_eq!(before.y(), after.y(), epsilon = 1e-9);
+        }
+        // Centripetal segments are rescaled to unit parameter length, so only
+        // the tangent direction is continuous.
+        let spline = CatmullRom::new(zigzag(), Parameterization::Centripetal).unwrap();
+        for i in 1..4 {
+            let before = spline.segment(i - 1).tangent(1.0);
+            let after = spline.segment(i).tangent(0.0);
+            assert_relative_eq!(before.cross_prod(after), 0.0, epsilon = 1e-9);
+            assert!(before.dot_prod(after) > 0.0);
+        }
+    }
+
+    #[test]
+    fn tangent_matches_finite_difference_f64() {
+        let spline = CatmullRom::new(zigzag(), Parameterization::Centripetal).unwrap();
+        let h = 1e-6;
+        for t in [0.3, 1.7, 3.2] {
+            let d = spline.tangent(t);
+            let fd = (spline.eval(t + h) - spline.eval(t - h)).div_by(2.0 * h);
+            assert_relative_eq!(d.x(), fd.x(), epsilon = 1e-5);
+            assert_relative_eq!(d.y(), fd.y(), epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn collinear_is_straight_f32() {
+        let points = vec![
+            XYZVec::new([0.0f32, 0.0, 0.0]),
+            XYZVec::new([1.0, 1.0, 1.0]),
+            XYZVec::new([2.0, 2.0, 2.0]),
+        ];
+        let spline = CatmullRom::new(points, Parameterization::Uniform).unwrap();
+        let p = spline.eval(0.5);
+        assert_relative_eq!(p.x(), 0.5);
+        assert_relative_eq!(p.y(), 0.5);
+        assert_relative_eq!(p.z(), 0.5);
+        assert_relative_eq!(spline.eval(2.0).x(), 2.0);
+    }
+
+    #[test]
+    fn repeated_points_f64() {
+        let points = vec![
+            XYVec::new([0.0f64, 0.0]),
+            XYVec::new([0.0, 0.0]),
+            XYVec::new([1.0, 0.0]),
+        ];
+        let spline = CatmullRom::new(points, Parameterization::Centripetal).unwrap();
+        assert!(spline.eval(0.5).x().is_finite());
+        assert!(
+            CatmullRom::new(vec![XYVec::new([0.0f64, 0.0])], Parameterization::Uniform).is_none()
+        );
+    }
+
+    #[test]
+    fn hermite_spline_f64() {
+        let spline = HermiteSpline::new(
+            vec![
+                XYVec::new([0.0f64, 0.0]),
+                XYVec::new([1.0, 0.0]),
+                XYVec::new([2.0, 0.0]),
+            ],
+            vec![
+                XYVec::new([1.0, 1.0]),
+                XYVec::new([1.0, 0.0]),
+                XYVec::new([1.0, -1.0]),
+            ],
+        )
+        .unwrap();
+        assert_eq!(spline.eval(1.0), XYVec::new([1.0, 0.0]));
+        assert_relative_eq!(spline.tangent(2.0).y(), -1.0);
+        assert!(HermiteSpline::new(spline.points().to_vec(), vec![]).is_none());
+    }
+
+    #[test]
+    fn hermite_segment_fixed() {
+        let f = |v: f64| I28F4::from_num(v);
+        let s = HermiteSegment::new(
+            XYVec::new([f(0.0), f(0.0)]),
+            XYVec::new([f(2.0), f(0.0)]),
+            XYVec::new([f(2.0), f(0.0)]),
+            XYVec::new([f(2.0), f(0.0)]),
+        );
+        assert_eq!(s.eval(f(0.5)), XYVec::new([f(1.0), f(0.0)]));
+        assert_eq!(s.tangent(f(0.5)), XYVec::new([f(2.0), f(0.0)]));
+    }
+}
@@ -0,0 +1,145 @@
+//! Spring-damper smoothing of a position toward a target.
+
+use crate::{kinematics::State, VecFloat, VecNum, Vector};
+
+/// Damped spring pulling a point mass toward `target`, with acceleration
+/// `stiffness * (target - pos) - damping * vel`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Spring<V: Vector> {
+    pub stiffness: V::Scalar,
+    pub damping: V::Scalar,
+    pub target: V,
+}
+
+impl<V: Vector> Spring<V>
+where
+    V::Scalar: VecNum,
+{
+    pub fn new(stiffness: V::Scalar, damping: V::Scalar, target: V) -> Self {
+        Self {
+            stiffness,
+            damping,
+            target,
+        }
+    }
+
+    pub fn accel(&self, state: &State<V>) -> V {
+        (self.target - state.pos).scale_by(self.stiffness) - state.vel.scale_by(self.damping)
+    }
+
+    /// Advances `state` by `dt` with semi-implicit Euler. Stiff springs need
+    /// `dt` well below `1 / sqrt(stiffness)`; see [`smooth_damp`] for an
+    /// unconditionally stable alternative.
+    /// ```
+    ///     use xyzvec::{kinematics::State, spring::Spring, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let spring = Spring::critically_damped(100.0, XYVec::new([1.0f64, 1.0]));
+    ///     let mut state = State::new(XYVec::new([0.0, 0.0]), XYVec::new([0.0, 0.0]));
+    ///     for _ in 0..200 {
+    ///         state = spring.step(state, 0.01);
+    ///     }
+    ///     assert_relative_eq!(state.pos.x(), 1.0, epsilon = 1e-3);
+    /// ```
+    pub fn step(&self, state: State<V>, dt: V::Scalar) -> State<V> {
+        state.step_semi_implicit(|s| self.accel(s), dt)
+    }
+}
+
+impl<V: Vector> Spring<V>
+where
+    V::Scalar: VecFloat,
+{
+    /// Spring with the damping that returns to `target` fastest without
+    /// overshooting: `2 * sqrt(stiffness)`.
+    pub fn critically_damped(stiffness: V::Scalar, target: V) -> Self {
+        let two = V::Scalar::one() + V::Scalar::one();
+        Self::new(stiffness, two * stiffness.sqrt(), target)
+    }
+}
+
+/// Exact critically-damped motion toward `target` over `dt`, reaching it in
+/// roughly `smooth_time`. Being closed-form, the result is independent of
+/// how time is split into steps and never overshoots from rest.
+/// ```
+///     use xyzvec::{kinematics::State, spring::smooth_damp, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let target = XYZVec::new([0.0f64, 5.0, 0.0]);
+///     let start = State::new(XYZVec::new([0.0, 0.0, 0.0]), XYZVec::new([0.0, 0.0, 0.0]));
+///     let one_step = smooth_damp(start, target, 0.3, 0.2);
+///     let mut many = start;
+///     for _ in 0..20 {
+///         many = smooth_damp(many, target, 0.3, 0.01);
+///     }
+///     assert_relative_eq!(one_step.pos.y(), many.pos.y(), epsilon = 1e-9);
+///     assert_relative_eq!(one_step.vel.y(), many.vel.y(), epsilon = 1e-9);
+/// ```
+pub fn smooth_damp<V>(state: State<V>, target: V, smooth_time: V::Scalar, dt: V::Scalar) -> State<V>
+where
+    V: Vector,
+    V::Scalar: VecFloat,
+{
+    let two = V::Scalar::one() + V::Scalar::one();
+    let omega = two / smooth_time;
+    let decay = (-omega * dt).exp();
+    let offset = state.pos - target;
+    let temp = (state.vel + offset.scale_by(omega)).scale_by(dt);
+    State::new(
+        target + (offset + temp).scale_by(decay),
+        (state.vel - temp.scale_by(omega)).scale_by(decay),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{smooth_damp, Spring};
+    use crate::{kinematics::State, XYVec};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn critically_damped_no_overshoot_f64() {
+        let spring = Spring::critically_damped(25.0, XYVec::new([1.0f64, 0.0]));
+        assert_relative_eq!(spring.damping, 10.0);
+        let mut state = State::new(XYVec::new([0.0, 0.0]), XYVec::new([0.0, 0.0]));
+        for _ in 0..3000 {
+            state = spring.step(state, 0.001);
+            assert!(state.pos.x() <= 1.0);
+        }
+        assert_relative_eq!(state.pos.x(), 1.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn underdamped_overshoots_f32() {
+        let spring = Spring::new(100.0, 2.0, XYVec::new([1.0f32, 0.0]));
+        let mut state = State::new(XYVec::new([0.0f32, 0.0]), XYVec::new([0.0, 0.0]));
+        let mut max_x = 0.0f32;
+        for _ in 0..300 {
+            state = spring.step(state, 0.005);
+            max_x = max_x.max(state.pos.x());
+        }
+        assert!(max_x > 1.5);
+    }
+
+    #[test]
+    fn smooth_damp_matches_analytic_f64() {
+        let target = XYVec::new([0.0f64, 0.0]);
+        let start = State::new(XYVec::new([1.0, 0.0]), XYVec::new([0.0, 0.0]));
+        let omega = 2.0 / 0.5;
+        let t = 0.7;
+        let s = smooth_damp(start, target, 0.5, t);
+        assert_relative_eq!(
+            s.pos.x(),
+            (1.0 + omega * t) * (-omega * t).exp(),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            s.vel.x(),
+            -omega * omega * t * (-omega * t).exp(),
+            epsilon = 1e-12
+        );
+        let settled = smooth_damp(start, target, 0.5, 100.0);
+        assert_relative_eq!(settled.pos.x(), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(settled.vel.x(), 0.0, epsilon = 1e-12);
+    }
+}
@@ -0,0 +1,163 @@
+//! Reductions over collections of [`XYVec`](crate::XYVec) or
+//! [`XYZVec`](crate::XYZVec). Each returns `None` for empty input.
+//! Spreads are population statistics, computed per component.
+
+use crate::{VecFloat, VecNum, Vector};
+
+/// Component-wise mean of `points`.
+/// ```
+///     use xyzvec::{stats::mean, XYVec};
+///
+///     let points = [XYVec::new([0.0f64, 1.0]), XYVec::new([2.0, 3.0])];
+///     assert_eq!(mean(points), Some(XYVec::new([1.0, 2.0])));
+///     assert_eq!(mean(Vec::<XYVec<f64>>::new()), None);
+/// ```
+pub fn mean<V, I>(points: I) -> Option<V>
+where
+    I: IntoIterator<Item = V>,
+    V: Vector,
+    V::Scalar: VecFloat,
+{
+    let mut iter = points.into_iter();
+    let first = iter.next()?;
+    let one = V::Scalar::one();
+    let (sum, n) = iter.fold((first, one), |(sum, n), p| (sum + p, n + one));
+    Some(sum.div_by(n))
+}
+
+/// Centroid of a set of points, i.e. their [`mean`].
+pub fn centroid<V>(points: &[V]) -> Option<V>
+where
+    V: Vector,
+    V::Scalar: VecFloat,
+{
+    mean(points.iter().copied())
+}
+
+/// Component-wise variance of `points`, accumulated in a single pass with
+/// Welford's method.
+/// ```
+///     use xyzvec::{stats::variance, XYZVec};
+///     use approx::assert_relative_eq;
+///
+///     let points = [XYZVec::new([1.0f64, 5.0, 0.0]), XYZVec::new([3.0, 5.0, 4.0])];
+///     let var = variance(points).unwrap();
+///     assert_relative_eq!(var.x(), 1.0);
+///     assert_relative_eq!(var.y(), 0.0);
+///     assert_relative_eq!(var.z(), 4.0);
+/// ```
+pub fn variance<V, I>(points: I) -> Option<V>
+where
+    I: IntoIterator<Item = V>,
+    V: Vector,
+    V::Scalar: VecFloat,
+{
+    let mut iter = points.into_iter();
+    let first = iter.next()?;
+    let (zero, one) = (V::Scalar::zero(), V::Scalar::one());
+    let (mut mean, mut m2, mut n) = (first, V::splat(zero), one);
+    for p in iter {
+        n += one;
+        let delta = p - mean;
+        mean += delta.div_by(n);
+        m2 += delta.zip_map(p - mean, |a, b| a * b);
+    }
+    Some(m2.div_by(n))
+}
+
+/// Component-wise standard deviation of `points`.
+pub fn std_dev<V, I>(points: I) -> Option<V>
+where
+    I: IntoIterator<Item = V>,
+    V: Vector,
+    V::Scalar: VecFloat,
+{
+    variance(points).map(|v| v.map(|c| c.sqrt()))
+}
+
+/// Smallest value of each component over `points`.
+/// ```
+///     use xyzvec::{stats::{max_components, min_components}, XYVec};
+///
+///     let points = [XYVec::new([1, 5]), XYVec::new([-2, 7]), XYVec::new([0, 6])];
+///     assert_eq!(min_components(points), Some(XYVec::new([-2, 5])));
+///     assert_eq!(max_components(points), Some(XYVec::new([1, 7])));
+/// ```
+pub fn min_components<V, I>(points: I) -> Option<V>
+where
+    I: IntoIterator<Item = V>,
+    V: Vector,
+    V::Scalar: VecNum,
+{
+    points
+        .into_iter()
+        .reduce(|acc, p| acc.zip_map(p, |a, b| if b < a { b } else { a }))
+}
+
+/// Largest value of each component over `points`.
+pub fn max_components<V, I>(points: I) -> Option<V>
+where
+    I: IntoIterator<Item = V>,
+    V: Vector,
+    V::Scalar: VecNum,
+{
+    points
+        .into_iter()
+        .reduce(|acc, p| acc.zip_map(p, |a, b| if b > a { b } else { a }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    #[test]
+    fn mean_variance_f64() {
+        let points: Vec<XYZVec<f64>> = (0..100)
+            .map(|i| XYZVec::new([i as f64, 1e9 + (i % 2) as f64, -3.0]))
+            .collect();
+        let c = centroid(&points).unwrap();
+        assert_relative_eq!(c.x(), 49.5);
+        assert_relative_eq!(c.y(), 1e9 + 0.5);
+        let var = variance(points.iter().copied()).unwrap();
+        assert_relative_eq!(var.x(), (100.0 * 100.0 - 1.0) / 12.0, epsilon = 1e-9);
+        // Welford stays accurate despite the large offset.
+        assert_relative_eq!(var.y(), 0.25, epsilon = 1e-9);
+        assert_relative_eq!(var.z(), 0.0);
+        let sd = std_dev(points).unwrap();
+        assert_relative_eq!(sd.y(), 0.5, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn empty_and_single_f32() {
+        let none: [XYVec<f32>; 0] = [];
+        assert!(mean(none).is_none());
+        assert!(centroid(&none).is_none());
+        assert!(variance(none).is_none());
+        assert!(std_dev(none).is_none());
+        assert!(min_components(none).is_none());
+        assert!(max_components(none).is_none());
+        let p = XYVec::new([1.5f32, -2.0]);
+        assert_eq!(mean([p]), Some(p));
+        assert_eq!(variance([p]), Some(XYVec::new([0.0, 0.0])));
+    }
+
+    #[test]
+    fn min_max_fixed() {
+        let f = |v: f64| I28F4::from_num(v);
+        let points = [
+            XYZVec::new([f(1.0), f(-0.5), f(3.0)]),
+            XYZVec::new([f(0.25), f(2.0), f(3.0)]),
+        ];
+        assert_eq!(
+            min_components(points),
+            Some(XYZVec::new([f(0.25), f(-0.5), f(3.0)]))
+        );
+        assert_eq!(
+            max_components(points),
+            Some(XYZVec::new([f(1.0), f(2.0), f(3.0)]))
+        );
+    }
+}
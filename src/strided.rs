@@ -0,0 +1,123 @@
+//! Vectors stored inside interleaved buffers, such as vertex arrays where
+//! each vertex is `stride` scalars and its position starts `offset`
+//! scalars in. These view or update the vectors in place, without
+//! deinterleaving the buffer first.
+//!
+//! Vertices run from `offset`, `offset + stride`, ... for as long as a
+//! whole vector fits in the buffer. All functions panic if `stride` is zero.
+
+use crate::{VecInner, Vector};
+
+/// Start index of each whole vector of dimension `dim` in a buffer of
+/// `len` scalars.
+fn starts(len: usize, stride: usize, offset: usize, dim: usize) -> impl Iterator<Item = usize> {
+    assert!(stride > 0, "stride must be positive");
+    (offset..)
+        .step_by(stride)
+        .take_while(move |&start| start + dim <= len)
+}
+
+/// The vectors of an interleaved buffer, read out by value.
+/// ```
+///     use xyzvec::{strided::strided, XYVec, XYZVec};
+///
+///     // Position (x, y, z) followed by texture coordinates (u, v).
+///     let vertices = [0.0f32, 1.0, 2.0, 0.5, 0.5, 3.0, 4.0, 5.0, 1.0, 0.0];
+///     let positions: Vec<XYZVec<f32>> = strided(&vertices, 5, 0).collect();
+///     assert_eq!(positions[1], XYZVec::new([3.0, 4.0, 5.0]));
+///     let uvs: Vec<XYVec<f32>> = strided(&vertices, 5, 3).collect();
+///     assert_eq!(uvs, vec![XYVec::new([0.5, 0.5]), XYVec::new([1.0, 0.0])]);
+/// ```
+pub fn strided<V, T>(buf: &[T], stride: usize, offset: usize) -> impl Iterator<Item = V> + '_
+where
+    V: Vector<Scalar = T> + FromIterator<T>,
+    T: VecInner,
+{
+    starts(buf.len(), stride, offset, V::DIM)
+        .map(move |i| buf[i..i + V::DIM].iter().copied().collect())
+}
+
+/// Dot product of each vector in an interleaved buffer with `other`.
+/// ```
+///     use xyzvec::{strided::dot_strided, XYZVec};
+///
+///     let normals = [0.0f32, 0.0, 1.0, 9.0, 1.0, 0.0, 0.0, 9.0];
+///     let light = XYZVec::new([0.6f32, 0.0, 0.8]);
+///     let shade: Vec<f32> = dot_strided(&normals, 4, 0, light).collect();
+///     assert_eq!(shade, vec![0.8, 0.6]);
+/// ```
+pub fn dot_strided<V, T>(
+    buf: &[T],
+    stride: usize,
+    offset: usize,
+    other: V,
+) -> impl Iterator<Item = T> + '_
+where
+    V: Vector<Scalar = T> + FromIterator<T> + 'static,
+    T: VecInner,
+{
+    strided(buf, stride, offset).map(move |v: V| v.dot_prod(other))
+}
+
+/// Replaces each vector in an interleaved buffer with `f` of it, leaving
+/// the other scalars untouched.
+/// ```
+///     use xyzvec::{strided::map_strided, XYVec};
+///
+///     let mut vertices = [1.0f64, 2.0, 7.0, 3.0, 4.0, 8.0];
+///     map_strided(&mut vertices, 3, 0, |p: XYVec<f64>| p.scale_by(10.0));
+///     assert_eq!(vertices, [10.0, 20.0, 7.0, 30.0, 40.0, 8.0]);
+/// ```
+pub fn map_strided<V, T, F>(buf: &mut [T], stride: usize, offset: usize, mut f: F)
+where
+    V: Vector<Scalar = T> + FromIterator<T>,
+    T: VecInner,
+    F: FnMut(V) -> V,
+{
+    for i in starts(buf.len(), stride, offset, V::DIM) {
+        let slot = &mut buf[i..i + V::DIM];
+        let v = f(slot.iter().copied().collect());
+        for (k, c) in slot.iter_mut().enumerate() {
+            *c = v.component(k);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{dot_strided, map_strided, strided};
+    use crate::{XYVec, XYZVec};
+    use fixed::types::I28F4;
+
+    #[test]
+    fn partial_tail_and_fixed() {
+        let f = |v: f64| I28F4::from_num(v);
+        // The last vertex has only two of its three position scalars.
+        let buf: Vec<I28F4> = [1.0, 2.0, 3.0, 0.0, 4.0, 5.0, 6.0, 0.0, 7.0, 8.0]
+            .into_iter()
+            .map(f)
+            .collect();
+        let positions: Vec<XYZVec<I28F4>> = strided(&buf, 4, 0).collect();
+        assert_eq!(positions.len(), 2);
+        assert_eq!(positions[1], XYZVec::new([f(4.0), f(5.0), f(6.0)]));
+        let dots: Vec<I28F4> =
+            dot_strided(&buf, 4, 0, XYZVec::new([f(1.0), f(0.0), f(0.5)])).collect();
+        assert_eq!(dots, vec![f(2.5), f(7.0)]);
+        // ...but its first two scalars still fit a 2D view.
+        assert_eq!(strided::<XYVec<I28F4>, _>(&buf, 4, 0).count(), 3);
+        assert_eq!(strided::<XYVec<I28F4>, _>(&buf, 4, 20).count(), 0);
+    }
+
+    #[test]
+    fn map_in_place_i32() {
+        let mut buf = [1, 2, 3, -1, 4, 5, 6, -1];
+        map_strided(&mut buf, 4, 1, |v: XYZVec<i32>| -v);
+        assert_eq!(buf, [1, -2, -3, 1, 4, -5, -6, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_stride() {
+        let _ = strided::<XYVec<f32>, _>(&[1.0, 2.0], 0, 0).count();
+    }
+}
@@ -0,0 +1,379 @@
+//! Continuous collision: when a moving circle or sphere first touches an
+//! obstacle, and how the two bounce apart.
+//!
+//! The moving ball's center travels from `center` to `center + velocity`
+//! over one step, so `velocity` is the displacement per step and impact
+//! times lie in `0..=1`. To sweep two moving balls against each other, pass
+//! the difference of their velocities. A ball that already touches or
+//! overlaps the obstacle has an impact at time `0`.
+
+use crate::{
+    ray::BallHits, Aabb2, Ball, Circle, LineSegment2, Plane, Ray, Sphere, VecFloat, VecNum, Vector,
+    XYVec, XYZVec,
+};
+
+/// First contact during a sweep: the fraction of the step at which it
+/// happens and the unit contact normal, pointing from the obstacle toward
+/// the moving ball.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Impact<V: Vector> {
+    pub time: V::Scalar,
+    pub normal: V,
+}
+
+/// `v` scaled to unit length, or `fallback` if it is zero.
+fn unit_or<V: Vector>(v: V, fallback: V) -> V
+where
+    V::Scalar: VecFloat,
+{
+    let len = v.l2_norm_sqd().sqrt();
+    if len == V::Scalar::zero() {
+        fallback
+    } else {
+        v.div_by(len)
+    }
+}
+
+/// Earliest time in `0..=1` at which a point moving by `velocity` from
+/// `origin` reaches the boundary of `ball`, if it starts outside.
+fn first_ball_hit<V: Vector>(origin: V, velocity: V, ball: &Ball<V>) -> Option<V::Scalar>
+where
+    V::Scalar: VecFloat,
+{
+    match Ray::new(origin, velocity).intersect_ball(ball) {
+        BallHits::Two(near, _) if near.t <= V::Scalar::one() => Some(near.t),
+        _ => None,
+    }
+}
+
+/// Moving ball against a stationary one, in 2D or 3D.
+/// ```
+///     use xyzvec::{sweep::ball_vs_ball, Circle, XYVec};
+///
+///     let puck = Circle::new(XYVec::new([0.0f64, 0.0]), 1.0);
+///     let post = Circle::new(XYVec::new([10.0, 0.0]), 1.0);
+///     let impact = ball_vs_ball(&puck, XYVec::new([16.0, 0.0]), &post).unwrap();
+///     assert_eq!(impact.time, 0.5);
+///     assert_eq!(impact.normal, XYVec::new([-1.0, 0.0]));
+///     assert!(ball_vs_ball(&puck, XYVec::new([4.0, 0.0]), &post).is_none());
+/// ```
+pub fn ball_vs_ball<V: Vector>(moving: &Ball<V>, velocity: V, other: &Ball<V>) -> Option<Impact<V>>
+where
+    V::Scalar: VecFloat,
+{
+    let reach = Ball::new(other.center, moving.radius + other.radius);
+    let time = if reach.contains_point(moving.center) {
+        V::Scalar::zero()
+    } else {
+        first_ball_hit(moving.center, velocity, &reach)?
+    };
+    let contact = moving.center + velocity.scale_by(time);
+    Some(Impact {
+        time,
+        normal: unit_or(contact - other.center, -unit_or(velocity, velocity)),
+    })
+}
+
+/// Moving circle against a line segment.
+/// ```
+///     use xyzvec::{sweep::circle_vs_segment, Circle, LineSegment2, XYVec};
+///
+///     let ball = Circle::new(XYVec::new([0.0f64, 5.0]), 1.0);
+///     let floor = LineSegment2::new(XYVec::new([-2.0, 0.0]), XYVec::new([2.0, 0.0]));
+///     let impact = circle_vs_segment(&ball, XYVec::new([0.0, -8.0]), &floor).unwrap();
+///     assert_eq!(impact.time, 0.5);
+///     assert_eq!(impact.normal, XYVec::new([0.0, 1.0]));
+/// ```
+pub fn circle_vs_segment<T: VecFloat>(
+    moving: &Circle<T>,
+    velocity: XYVec<T>,
+    segment: &LineSegment2<T>,
+) -> Option<Impact<XYVec<T>>> {
+    let (zero, one) = (T::zero(), T::one());
+    let (a, c, r) = (segment.start, moving.center, moving.radius);
+    let edge = segment.end - a;
+    let len_sqd = edge.l2_norm_sqd();
+    let closest_at = |p: XYVec<T>| {
+        if len_sqd == zero {
+            return a;
+        }
+        let s = (p - a).dot_prod(edge) / len_sqd;
+        a + edge.scale_by(if s < zero {
+            zero
+        } else if s > one {
+            one
+        } else {
+            s
+        })
+    };
+    let start = closest_at(c);
+    if (c - start).l2_norm_sqd() <= r * r {
+        return Some(Impact {
+            time: zero,
+            normal: unit_or(c - start, -unit_or(velocity, velocity)),
+        });
+    }
+    // The circle's center hits the segment grown by `r`: a capsule made of
+    // two offset sides and two end caps.
+    let mut best: Option<T> = None;
+    let mut consider = |t: T| {
+        if best.is_none_or(|b| t < b) {
+            best = Some(t);
+        }
+    };
+    if len_sqd > zero {
+        let n = unit_or(XYVec::new([-edge.y(), edge.x()]), edge);
+        let side = if (c - a).dot_prod(n) < zero { -n } else { n };
+        let approach = -velocity.dot_prod(side);
+        if approach > zero {
+            let t = ((c - a).dot_prod(side) - r) / approach;
+            let s = (c + velocity.scale_by(t) - a).dot_prod(edge) / len_sqd;
+            if t <= one && zero <= s && s <= one {
+                consider(t);
+            }
+        }
+    }
+    for cap in [segment.start, segment.end] {
+        if let Some(t) = first_ball_hit(c, velocity, &Circle::new(cap, r)) {
+            consider(t);
+        }
+    }
+    let time = best?;
+    let contact = c + velocity.scale_by(time);
+    Some(Impact {
+        time,
+        normal: unit_or(contact - closest_at(contact), -unit_or(velocity, velocity)),
+    })
+}
+
+/// Moving circle against an axis-aligned rectangle.
+/// ```
+///     use xyzvec::{sweep::circle_vs_aabb, Aabb2, Circle, XYVec};
+///
+///     let ball = Circle::new(XYVec::new([-3.0f64, 0.5]), 1.0);
+///     let wall = Aabb2::new(XYVec::new([0.0, 0.0]), XYVec::new([1.0, 4.0]));
+///     let impact = circle_vs_aabb(&ball, XYVec::new([4.0, 0.0]), &wall).unwrap();
+///     assert_eq!(impact.time, 0.5);
+///     assert_eq!(impact.normal, XYVec::new([-1.0, 0.0]));
+/// ```
+pub fn circle_vs_aabb<T: VecFloat>(
+    moving: &Circle<T>,
+    velocity: XYVec<T>,
+    aabb: &Aabb2<T>,
+) -> Option<Impact<XYVec<T>>> {
+    let (c, r) = (moving.center, moving.radius);
+    let nearest = aabb.clamp(c);
+    if (c - nearest).l2_norm_sqd() <= r * r {
+        return Some(Impact {
+            time: T::zero(),
+            normal: unit_or(c - nearest, -unit_or(velocity, velocity)),
+        });
+    }
+    // The center hits the box grown by `r` with rounded corners: first the
+    // grown box, then the corner circle if the entry point is past a corner.
+    let grown = Aabb2::new(aabb.min - XYVec::splat(r), aabb.max + XYVec::splat(r));
+    let ray = Ray::new(c, velocity);
+    let (entry, _) = ray.intersect_aabb(&grown)?;
+    if entry > T::one() {
+        return None;
+    }
+    let p = ray.at(entry);
+    let corner = aabb.clamp(p);
+    let outside = (p - corner).iter().filter(|&d| *d != T::zero()).count();
+    let time = if outside == 2 {
+        first_ball_hit(c, velocity, &Circle::new(corner, r))?
+    } else {
+        entry
+    };
+    let contact = ray.at(time);
+    Some(Impact {
+        time,
+        normal: unit_or(contact - aabb.clamp(contact), -unit_or(velocity, velocity)),
+    })
+}
+
+/// Moving sphere against a plane, approaching from either side.
+/// ```
+///     use xyzvec::{sweep::sphere_vs_plane, Plane, Sphere, XYZVec};
+///
+///     let ball = Sphere::new(XYZVec::new([0.0f64, 0.0, 3.0]), 1.0);
+///     let ground = Plane::new(XYZVec::new([0.0, 0.0, 2.0]), 0.0);
+///     let impact = sphere_vs_plane(&ball, XYZVec::new([1.0, 0.0, -4.0]), &ground).unwrap();
+///     assert_eq!(impact.time, 0.5);
+///     assert_eq!(impact.normal, XYZVec::new([0.0, 0.0, 1.0]));
+/// ```
+pub fn sphere_vs_plane<T: VecFloat>(
+    moving: &Sphere<T>,
+    velocity: XYZVec<T>,
+    plane: &Plane<T>,
+) -> Option<Impact<XYZVec<T>>> {
+    let zero = T::zero();
+    let len = plane.normal.l2_norm_sqd().sqrt();
+    if len == zero {
+        return None;
+    }
+    let n = plane.normal.div_by(len);
+    let dist = plane.signed_distance(moving.center) / len;
+    let normal = if dist < zero { -n } else { n };
+    let height = dist.abs() - moving.radius;
+    if height <= zero {
+        return Some(Impact { time: zero, normal });
+    }
+    let approach = -velocity.dot_prod(normal);
+    (approach > zero && height <= approach).then(|| Impact {
+        time: height / approach,
+        normal,
+    })
+}
+
+/// Velocities of two bodies after an impulse along the contact `normal`,
+/// which has unit length and points from `b` toward `a` (as in
+/// [`Impact::normal`]). `restitution` is `1` for a perfectly elastic
+/// bounce and `0` for no bounce. A mass of infinity makes that body
+/// immovable. Bodies already separating along the normal are unchanged.
+/// ```
+///     use xyzvec::{sweep::resolve_contact, XYVec};
+///
+///     // Equal masses swap velocities in an elastic head-on collision.
+///     let (a, b) = resolve_contact(
+///         (XYVec::new([2.0f64, 0.0]), 1.0),
+///         (XYVec::new([-1.0, 0.0]), 1.0),
+///         XYVec::new([-1.0, 0.0]),
+///         1.0,
+///     );
+///     assert_eq!((a, b), (XYVec::new([-1.0, 0.0]), XYVec::new([2.0, 0.0])));
+/// ```
+pub fn resolve_contact<V: Vector>(
+    (vel_a, mass_a): (V, V::Scalar),
+    (vel_b, mass_b): (V, V::Scalar),
+    normal: V,
+    restitution: V::Scalar,
+) -> (V, V)
+where
+    V::Scalar: VecFloat,
+{
+    let one = V::Scalar::one();
+    let closing = (vel_a - vel_b).dot_prod(normal);
+    let (inv_a, inv_b) = (one / mass_a, one / mass_b);
+    if closing >= V::Scalar::zero() || inv_a + inv_b == V::Scalar::zero() {
+        return (vel_a, vel_b);
+    }
+    let impulse = -(one + restitution) * closing / (inv_a + inv_b);
+    (
+        vel_a + normal.scale_by(impulse * inv_a),
+        vel_b - normal.scale_by(impulse * inv_b),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ball_vs_ball, circle_vs_aabb, circle_vs_segment, resolve_contact, sphere_vs_plane,
+    };
+    use crate::{Aabb2, Circle, LineSegment2, Plane, Sphere, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn circle_f64() {
+        let ball = Circle::new(XYVec::new([0.0f64, 0.0]), 1.0);
+        // Glancing off the end cap of a segment.
+        let post = LineSegment2::new(XYVec::new([5.0, 0.5]), XYVec::new([5.0, 10.0]));
+        let hit = circle_vs_segment(&ball, XYVec::new([10.0, 0.0]), &post).unwrap();
+        assert_relative_eq!(hit.time * 10.0, 5.0 - 0.75f64.sqrt(), epsilon = 1e-12);
+        assert_relative_eq!(hit.normal.l2_norm(), 1.0, epsilon = 1e-12);
+        assert!(hit.normal.y() < 0.0);
+        // Passing beside it misses.
+        assert!(circle_vs_segment(&ball, XYVec::new([10.0, -3.0]), &post).is_none());
+        // Too slow to arrive this step.
+        assert!(circle_vs_segment(&ball, XYVec::new([3.0, 0.0]), &post).is_none());
+        // Degenerate segment behaves as a point.
+        let point = LineSegment2::new(XYVec::new([3.0, 0.0]), XYVec::new([3.0, 0.0]));
+        let hit = circle_vs_segment(&ball, XYVec::new([4.0, 0.0]), &point).unwrap();
+        assert_relative_eq!(hit.time, 0.5);
+
+        // Into the corner of a box, and straight into a face.
+        let crate_box = Aabb2::new(XYVec::new([2.0, 2.0]), XYVec::new([4.0, 4.0]));
+        let hit = circle_vs_aabb(&ball, XYVec::new([4.0, 4.0]), &crate_box).unwrap();
+        let corner_t = (2.0 * 2f64.sqrt() - 1.0) / (4.0 * 2f64.sqrt());
+        assert_relative_eq!(hit.time, corner_t, epsilon = 1e-12);
+        assert_relative_eq!(hit.normal.x(), -(0.5f64.sqrt()), epsilon = 1e-12);
+        // Grazing past the corner, inside the grown box but off the disk.
+        let ball2 = Circle::new(XYVec::new([0.0, 3.2]), 0.5);
+        assert!(circle_vs_aabb(&ball2, XYVec::new([2.5, -2.5]), &crate_box).is_none());
+        let hit = circle_vs_aabb(
+            &ball,
+            XYVec::new([0.0, 4.0]),
+            &Aabb2::new(XYVec::new([-1.0, 3.0]), XYVec::new([1.0, 5.0])),
+        )
+        .unwrap();
+        assert_relative_eq!(hit.time, 0.5);
+        assert_eq!(hit.normal, XYVec::new([0.0, -1.0]));
+        // Already overlapping.
+        let inside = Circle::new(XYVec::new([2.5, 3.0]), 1.0);
+        let hit = circle_vs_aabb(&inside, XYVec::new([1.0, 0.0]), &crate_box).unwrap();
+        assert_eq!(hit.time, 0.0);
+    }
+
+    #[test]
+    fn sphere_f32() {
+        let ball = Sphere::new(XYZVec::new([0.0f32, 0.0, 0.0]), 1.0);
+        let other = Sphere::new(XYZVec::new([0.0, 6.0, 0.0]), 2.0);
+        let hit = ball_vs_ball(&ball, XYZVec::new([0.0, 6.0, 0.0]), &other).unwrap();
+        assert_relative_eq!(hit.time, 0.5);
+        assert_relative_eq!(hit.normal.y(), -1.0);
+        assert!(ball_vs_ball(&ball, XYZVec::new([0.0, -6.0, 0.0]), &other).is_none());
+
+        // From below the plane, moving up.
+        let plane = Plane::new(XYZVec::new([0.0, 0.0, 1.0]), 5.0);
+        let hit = sphere_vs_plane(&ball, XYZVec::new([0.0, 0.0, 8.0]), &plane).unwrap();
+        assert_relative_eq!(hit.time, 0.5);
+        assert_relative_eq!(hit.normal.z(), -1.0);
+        assert!(sphere_vs_plane(&ball, XYZVec::new([3.0, 0.0, 0.0]), &plane).is_none());
+        let touching = Sphere::new(XYZVec::new([0.0, 0.0, 4.5]), 1.0);
+        assert_eq!(
+            sphere_vs_plane(&touching, XYZVec::new([0.0, 0.0, 0.0]), &plane)
+                .unwrap()
+                .time,
+            0.0
+        );
+    }
+
+    #[test]
+    fn contact_response_f64() {
+        let a = Circle::new(XYVec::new([0.0f64, 0.0]), 1.0);
+        let b = Circle::new(XYVec::new([3.0, 1.0]), 1.0);
+        let (va, vb) = (XYVec::new([4.0, 1.0]), XYVec::new([-1.0, 0.5]));
+        let (ma, mb) = (2.0, 3.0);
+        let impact = ball_vs_ball(&a, va - vb, &b).unwrap();
+        let (va2, vb2) = resolve_contact((va, ma), (vb, mb), impact.normal, 0.5);
+        // Momentum is conserved and the bodies now separate along the normal.
+        let momentum = |va: XYVec<f64>, vb: XYVec<f64>| va.scale_by(ma) + vb.scale_by(mb);
+        assert_relative_eq!(
+            (momentum(va2, vb2) - momentum(va, vb)).l2_norm(),
+            0.0,
+            epsilon = 1e-12
+        );
+        let closing = (va - vb).dot_prod(impact.normal);
+        assert_relative_eq!(
+            (va2 - vb2).dot_prod(impact.normal),
+            -0.5 * closing,
+            epsilon = 1e-12
+        );
+        // Tangential motion is untouched.
+        let tangent = XYVec::new([-impact.normal.y(), impact.normal.x()]);
+        assert_relative_eq!(va2.dot_prod(tangent), va.dot_prod(tangent), epsilon = 1e-12);
+        // Separating bodies pass through unchanged; walls don't move.
+        assert_eq!(
+            resolve_contact((va, ma), (vb, mb), -impact.normal, 1.0),
+            (va, vb)
+        );
+        let (ball, wall) = resolve_contact(
+            (XYVec::new([3.0, -2.0]), 1.0),
+            (XYVec::new([0.0, 0.0]), f64::INFINITY),
+            XYVec::new([0.0, 1.0]),
+            1.0,
+        );
+        assert_eq!(ball, XYVec::new([3.0, 2.0]));
+        assert_eq!(wall, XYVec::new([0.0, 0.0]));
+    }
+}
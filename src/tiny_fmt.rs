@@ -0,0 +1,152 @@
+//! [`ufmt`] formatting for vectors, for `no_std` targets where pulling in
+//! `core::fmt` costs too much flash. Requires the `ufmt` feature.
+//!
+//! `ufmt` has no float support, so float and fixed-point components are
+//! written here with three decimal places, matching the vectors'
+//! [`Display`](std::fmt::Display) output. `uDebug` and `uDisplay` agree.
+
+use crate::{VecInner, XYVec, XYZVec};
+use fixed::types::extra::{LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+use ufmt::{uDebug, uDisplay, uWrite, uwrite, Formatter};
+
+/// Scalars that can be written as a vector component.
+pub trait UfmtScalar: Copy {
+    fn fmt_scalar<W: uWrite + ?Sized>(self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>;
+}
+
+macro_rules! impl_ufmt_scalar_int {
+    ($($t:ty),*) => {$(
+        impl UfmtScalar for $t {
+            fn fmt_scalar<W: uWrite + ?Sized>(
+                self,
+                f: &mut Formatter<'_, W>,
+            ) -> Result<(), W::Error> {
+                uwrite!(f, "{}", self)
+            }
+        }
+    )*};
+}
+
+impl_ufmt_scalar_int!(i8, i16, i32, i64, i128);
+
+impl UfmtScalar for f32 {
+    fn fmt_scalar<W: uWrite + ?Sized>(self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        write_decimal(f, self as f64)
+    }
+}
+
+impl UfmtScalar for f64 {
+    fn fmt_scalar<W: uWrite + ?Sized>(self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        write_decimal(f, self)
+    }
+}
+
+macro_rules! impl_ufmt_scalar_fixed {
+    ($($t:ident: $bound:ident),*) => {$(
+        impl<Frac: $bound> UfmtScalar for fixed::$t<Frac> {
+            fn fmt_scalar<W: uWrite + ?Sized>(
+                self,
+                f: &mut Formatter<'_, W>,
+            ) -> Result<(), W::Error> {
+                write_decimal(f, self.to_num())
+            }
+        }
+    )*};
+}
+
+impl_ufmt_scalar_fixed!(FixedI8: LeEqU8, FixedI16: LeEqU16, FixedI32: LeEqU32, FixedI64: LeEqU64);
+
+/// `v` rounded to three decimal places. Magnitudes beyond `u64::MAX / 1000`
+/// saturate.
+fn write_decimal<W: uWrite + ?Sized>(f: &mut Formatter<'_, W>, v: f64) -> Result<(), W::Error> {
+    if v.is_nan() {
+        return f.write_str("NaN");
+    }
+    if v.is_infinite() {
+        return f.write_str(if v < 0.0 { "-inf" } else { "inf" });
+    }
+    let millis = (v.abs() * 1000.0).round() as u64;
+    if v.is_sign_negative() {
+        f.write_str("-")?;
+    }
+    let frac = millis % 1000;
+    uwrite!(f, "{}.", millis / 1000)?;
+    if frac < 100 {
+        f.write_str("0")?;
+    }
+    if frac < 10 {
+        f.write_str("0")?;
+    }
+    uwrite!(f, "{}", frac)
+}
+
+/// Adapter giving a [`UfmtScalar`] the [`uDisplay`] impl that `uwrite!`
+/// needs for its arguments.
+struct Component<T>(T);
+
+impl<T: UfmtScalar> uDisplay for Component<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        self.0.fmt_scalar(f)
+    }
+}
+
+impl<T: VecInner + UfmtScalar> uDisplay for XYVec<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uwrite!(f, "({}, {})", Component(self.x()), Component(self.y()))
+    }
+}
+
+impl<T: VecInner + UfmtScalar> uDebug for XYVec<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uDisplay::fmt(self, f)
+    }
+}
+
+impl<T: VecInner + UfmtScalar> uDisplay for XYZVec<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uwrite!(
+            f,
+            "({}, {}, {})",
+            Component(self.x()),
+            Component(self.y()),
+            Component(self.z())
+        )
+    }
+}
+
+impl<T: VecInner + UfmtScalar> uDebug for XYZVec<T> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        uDisplay::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{XYVec, XYZVec};
+    use fixed::types::I16F16;
+
+    #[test]
+    fn matches_display() {
+        let mut s = String::new();
+        ufmt::uwrite!(s, "{}", XYVec::new([1.5f64, -0.0004])).unwrap();
+        assert_eq!(s, format!("{}", XYVec::new([1.5f64, -0.0004])));
+        assert_eq!(s, "(1.500, -0.000)");
+
+        let mut s = String::new();
+        let v = XYZVec::new([
+            I16F16::from_num(-2.25),
+            I16F16::from_num(0.125),
+            I16F16::ZERO,
+        ]);
+        ufmt::uwrite!(s, "{:?}", v).unwrap();
+        assert_eq!(s, "(-2.250, 0.125, 0.000)");
+
+        let mut s = String::new();
+        ufmt::uwrite!(s, "{}", XYVec::new([-7i32, 12])).unwrap();
+        assert_eq!(s, "(-7, 12)");
+
+        let mut s = String::new();
+        ufmt::uwrite!(s, "{}", XYVec::new([f32::NAN, f32::NEG_INFINITY])).unwrap();
+        assert_eq!(s, "(NaN, -inf)");
+    }
+}
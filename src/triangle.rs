@@ -0,0 +1,328 @@
+use crate::{VecFloat, VecInner, VecNum, XYVec, XYZVec};
+use std::fmt::{self, Formatter};
+
+/// Triangle in the plane with vertices `a`, `b`, `c`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Triangle2<T> {
+    pub a: XYVec<T>,
+    pub b: XYVec<T>,
+    pub c: XYVec<T>,
+}
+
+impl<T: VecNum> Triangle2<T> {
+    pub fn new(a: XYVec<T>, b: XYVec<T>, c: XYVec<T>) -> Self {
+        Self { a, b, c }
+    }
+
+    /// Area of the triangle, positive when `a`, `b`, `c` wind counter-clockwise.
+    /// ```
+    ///     use xyzvec::{Triangle2, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let t = Triangle2::new(
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([2.0, 0.0]),
+    ///         XYVec::new([0.0, 2.0]),
+    ///     );
+    ///     assert_relative_eq!(t.signed_area(), 2.0);
+    ///     assert_relative_eq!(Triangle2::new(t.a, t.c, t.b).signed_area(), -2.0);
+    /// ```
+    pub fn signed_area(&self) -> T {
+        (self.b - self.a).cross_prod(self.c - self.a) / (T::one() + T::one())
+    }
+
+    pub fn area(&self) -> T {
+        self.signed_area().abs()
+    }
+
+    pub fn centroid(&self) -> XYVec<T> {
+        (self.a + self.b + self.c).div_by(T::one() + T::one() + T::one())
+    }
+
+    /// Whether `p` lies inside the triangle or on its boundary.
+    /// Degenerate triangles contain no points.
+    /// ```
+    ///     use xyzvec::{Triangle2, XYVec};
+    ///
+    ///     let t = Triangle2::new(
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([2.0, 0.0]),
+    ///         XYVec::new([0.0, 2.0]),
+    ///     );
+    ///     assert!(t.contains_point(XYVec::new([0.5, 0.5])));
+    ///     assert!(t.contains_point(XYVec::new([1.0, 1.0])));
+    ///     assert!(!t.contains_point(XYVec::new([1.5, 1.5])));
+    /// ```
+    pub fn contains_point(&self, p: XYVec<T>) -> bool {
+        let zero = T::zero();
+        if (self.b - self.a).cross_prod(self.c - self.a) == zero {
+            return false;
+        }
+        let d1 = (self.b - self.a).cross_prod(p - self.a);
+        let d2 = (self.c - self.b).cross_prod(p - self.b);
+        let d3 = (self.a - self.c).cross_prod(p - self.c);
+        let has_neg = d1 < zero || d2 < zero || d3 < zero;
+        let has_pos = d1 > zero || d2 > zero || d3 > zero;
+        !(has_neg && has_pos)
+    }
+
+    /// Barycentric coordinates `(u, v, w)` of `p`, such that
+    /// `p = u * a + v * b + w * c`. `None` if the triangle is degenerate.
+    /// ```
+    ///     use xyzvec::{Triangle2, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let t = Triangle2::new(
+    ///         XYVec::new([0.0f64, 0.0]),
+    ///         XYVec::new([2.0, 0.0]),
+    ///         XYVec::new([0.0, 2.0]),
+    ///     );
+    ///     let (u, v, w) = t.barycentric(XYVec::new([0.5, 1.0])).unwrap();
+    ///     assert_relative_eq!(u, 0.25);
+    ///     assert_relative_eq!(v, 0.25);
+    ///     assert_relative_eq!(w, 0.5);
+    /// ```
+    pub fn barycentric(&self, p: XYVec<T>) -> Option<(T, T, T)> {
+        let ab = self.b - self.a;
+        let ac = self.c - self.a;
+        let ap = p - self.a;
+        let denom = ab.cross_prod(ac);
+        if denom == T::zero() {
+            return None;
+        }
+        let v = ap.cross_prod(ac) / denom;
+        let w = ab.cross_prod(ap) / denom;
+        Some((T::one() - v - w, v, w))
+    }
+
+    pub fn from_barycentric(&self, u: T, v: T, w: T) -> XYVec<T> {
+        self.a.scale_by(u) + self.b.scale_by(v) + self.c.scale_by(w)
+    }
+}
+
+/// Triangle in space with vertices `a`, `b`, `c`.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Triangle3<T> {
+    pub a: XYZVec<T>,
+    pub b: XYZVec<T>,
+    pub c: XYZVec<T>,
+}
+
+impl<T: VecNum> Triangle3<T> {
+    pub fn new(a: XYZVec<T>, b: XYZVec<T>, c: XYZVec<T>) -> Self {
+        Self { a, b, c }
+    }
+
+    /// Unnormalized normal `(b - a) × (c - a)`, whose length is twice the area.
+    /// ```
+    ///     use xyzvec::{Triangle3, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let t = Triangle3::new(
+    ///         XYZVec::new([0.0f64, 0.0, 0.0]),
+    ///         XYZVec::new([1.0, 0.0, 0.0]),
+    ///         XYZVec::new([0.0, 1.0, 0.0]),
+    ///     );
+    ///     let n = t.normal();
+    ///     assert_relative_eq!(n.x(), 0.0);
+    ///     assert_relative_eq!(n.y(), 0.0);
+    ///     assert_relative_eq!(n.z(), 1.0);
+    /// ```
+    pub fn normal(&self) -> XYZVec<T> {
+        (self.b - self.a).cross(self.c - self.a)
+    }
+
+    pub fn centroid(&self) -> XYZVec<T> {
+        (self.a + self.b + self.c).div_by(T::one() + T::one() + T::one())
+    }
+
+    /// Barycentric coordinates `(u, v, w)` of `p` projected onto the plane of
+    /// the triangle. `None` if the triangle is degenerate.
+    pub fn barycentric(&self, p: XYZVec<T>) -> Option<(T, T, T)> {
+        let v0 = self.b - self.a;
+        let v1 = self.c - self.a;
+        let v2 = p - self.a;
+        let d00 = v0.dot_prod(v0);
+        let d01 = v0.dot_prod(v1);
+        let d11 = v1.dot_prod(v1);
+        let d20 = v2.dot_prod(v0);
+        let d21 = v2.dot_prod(v1);
+        let denom = d00 * d11 - d01 * d01;
+        if denom == T::zero() {
+            return None;
+        }
+        let v = (d11 * d20 - d01 * d21) / denom;
+        let w = (d00 * d21 - d01 * d20) / denom;
+        Some((T::one() - v - w, v, w))
+    }
+
+    pub fn from_barycentric(&self, u: T, v: T, w: T) -> XYZVec<T> {
+        self.a.scale_by(u) + self.b.scale_by(v) + self.c.scale_by(w)
+    }
+
+    /// Whether `p`, projected onto the plane of the triangle, lies inside it
+    /// or on its boundary. Degenerate triangles contain no points.
+    /// ```
+    ///     use xyzvec::{Triangle3, XYZVec};
+    ///
+    ///     let t = Triangle3::new(
+    ///         XYZVec::new([0.0f64, 0.0, 0.0]),
+    ///         XYZVec::new([1.0, 0.0, 0.0]),
+    ///         XYZVec::new([0.0, 1.0, 0.0]),
+    ///     );
+    ///     assert!(t.contains_point(XYZVec::new([0.25, 0.25, 3.0])));
+    ///     assert!(!t.contains_point(XYZVec::new([1.0, 1.0, 0.0])));
+    /// ```
+    pub fn contains_point(&self, p: XYZVec<T>) -> bool {
+        let zero = T::zero();
+        match self.barycentric(p) {
+            Some((u, v, w)) => u >= zero && v >= zero && w >= zero,
+            None => false,
+        }
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Triangle2<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "[{:?}, {:?}, {:?}]", self.a, self.b, self.c)
+    }
+}
+
+impl<T: VecInner> fmt::Debug for Triangle3<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "[{:?}, {:?}, {:?}]", self.a, self.b, self.c)
+    }
+}
+
+impl<T: VecFloat> Triangle3<T> {
+    pub fn area(&self) -> T {
+        self.normal().l2_norm_sqd().sqrt() / (T::one() + T::one())
+    }
+
+    /// Normal of unit length, following the right-hand rule over `a`, `b`, `c`.
+    pub fn unit_normal(&self) -> XYZVec<T> {
+        let n = self.normal();
+        n.div_by(n.l2_norm_sqd().sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Triangle2, Triangle3, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+    use fixed::types::I28F4;
+
+    fn fixed_xy(x: f64, y: f64) -> XYVec<I28F4> {
+        XYVec::new([I28F4::from_num(x), I28F4::from_num(y)])
+    }
+
+    #[test]
+    fn area_f32() {
+        let t = Triangle2::new(
+            XYVec::new([1.0f32, 1.0]),
+            XYVec::new([4.0, 1.0]),
+            XYVec::new([1.0, 3.0]),
+        );
+        assert_relative_eq!(t.signed_area(), 3.0);
+        assert_relative_eq!(Triangle2::new(t.a, t.c, t.b).area(), 3.0);
+    }
+
+    #[test]
+    fn centroid_f64() {
+        let t = Triangle2::new(
+            XYVec::new([0.0f64, 0.0]),
+            XYVec::new([3.0, 0.0]),
+            XYVec::new([0.0, 3.0]),
+        );
+        let c = t.centroid();
+        assert_relative_eq!(c.x(), 1.0);
+        assert_relative_eq!(c.y(), 1.0);
+    }
+
+    #[test]
+    fn barycentric_round_trip_f64() {
+        let t = Triangle2::new(
+            XYVec::new([-1.0f64, 0.5]),
+            XYVec::new([3.0, -2.0]),
+            XYVec::new([0.5, 4.0]),
+        );
+        let p = XYVec::new([0.7, 0.9]);
+        let (u, v, w) = t.barycentric(p).unwrap();
+        assert_relative_eq!(u + v + w, 1.0);
+        let q = t.from_barycentric(u, v, w);
+        assert_relative_eq!(q.x(), p.x(), epsilon = 1e-12);
+        assert_relative_eq!(q.y(), p.y(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn degenerate_f64() {
+        let t = Triangle2::new(
+            XYVec::new([0.0f64, 0.0]),
+            XYVec::new([1.0, 1.0]),
+            XYVec::new([2.0, 2.0]),
+        );
+        assert!(t.barycentric(XYVec::new([0.5, 0.5])).is_none());
+        assert!(!t.contains_point(XYVec::new([0.5, 0.5])));
+    }
+
+    #[test]
+    fn contains_point_clockwise_f32() {
+        let t = Triangle2::new(
+            XYVec::new([0.0f32, 0.0]),
+            XYVec::new([0.0, 2.0]),
+            XYVec::new([2.0, 0.0]),
+        );
+        assert!(t.contains_point(XYVec::new([0.5, 0.5])));
+        assert!(t.contains_point(XYVec::new([0.0, 0.0])));
+        assert!(!t.contains_point(XYVec::new([-0.1, 0.5])));
+    }
+
+    #[test]
+    fn area_fixed() {
+        let t = Triangle2::new(fixed_xy(0.0, 0.0), fixed_xy(3.0, 0.0), fixed_xy(0.0, 1.5));
+        assert_eq!(t.signed_area(), 2.25);
+        assert_eq!(Triangle2::new(t.b, t.a, t.c).area(), 2.25);
+    }
+
+    #[test]
+    fn contains_point_fixed() {
+        let t = Triangle2::new(fixed_xy(0.0, 0.0), fixed_xy(4.0, 0.0), fixed_xy(0.0, 4.0));
+        assert!(t.contains_point(fixed_xy(1.0, 1.0)));
+        assert!(t.contains_point(fixed_xy(2.0, 2.0)));
+        assert!(!t.contains_point(fixed_xy(2.5, 2.0)));
+        let (u, v, w) = t.barycentric(fixed_xy(1.0, 2.0)).unwrap();
+        assert_eq!(u, 0.25);
+        assert_eq!(v, 0.25);
+        assert_eq!(w, 0.5);
+    }
+
+    #[test]
+    fn area_and_normal_3d_f64() {
+        let t = Triangle3::new(
+            XYZVec::new([0.0f64, 0.0, 0.0]),
+            XYZVec::new([0.0, 2.0, 0.0]),
+            XYZVec::new([0.0, 0.0, 2.0]),
+        );
+        assert_relative_eq!(t.area(), 2.0);
+        let n = t.unit_normal();
+        assert_relative_eq!(n.x(), 1.0);
+        assert_relative_eq!(n.y(), 0.0);
+        assert_relative_eq!(n.z(), 0.0);
+    }
+
+    #[test]
+    fn barycentric_3d_f32() {
+        let t = Triangle3::new(
+            XYZVec::new([1.0f32, 0.0, 0.0]),
+            XYZVec::new([0.0, 1.0, 0.0]),
+            XYZVec::new([0.0, 0.0, 1.0]),
+        );
+        let c = t.centroid();
+        let (u, v, w) = t.barycentric(c).unwrap();
+        assert_relative_eq!(u, 1.0 / 3.0, epsilon = 1e-6);
+        assert_relative_eq!(v, 1.0 / 3.0, epsilon = 1e-6);
+        assert_relative_eq!(w, 1.0 / 3.0, epsilon = 1e-6);
+        assert!(t.contains_point(c));
+        assert!(!t.contains_point(XYZVec::new([1.0, 1.0, 0.0])));
+    }
+}
@@ -0,0 +1,107 @@
+//! Distances between float vectors in units in the last place (ulps).
+//!
+//! Two floats are `n` ulps apart when there are `n - 1` representable
+//! values strictly between them. Unlike an absolute or relative tolerance,
+//! this measures rounding differences the same way at every magnitude,
+//! which makes it the natural check when comparing two implementations of
+//! the same arithmetic.
+//! ```
+//!     use xyzvec::XYVec;
+//!
+//!     let a = XYVec::new([1.0f64, -0.0]);
+//!     let b = XYVec::new([1.0 + 2.0 * f64::EPSILON, 0.0]);
+//!     assert_eq!(a.ulp_diff(b), [2, 0]);
+//!     assert!(a.eq_within_ulps(b, 2));
+//!     assert!(!a.eq_within_ulps(b, 1));
+//! ```
+
+use crate::{VecInner, XYVec, XYZVec};
+
+/// Floats that can be compared by ulp distance.
+pub trait UlpScalar: VecInner {
+    /// Number of representable values from `self` to `other`. `0.0` and
+    /// `-0.0` are the same value; NaN is `u64::MAX` from everything.
+    fn ulp_diff(self, other: Self) -> u64;
+}
+
+macro_rules! impl_ulp_scalar {
+    ($($t:ty => $int:ty),*) => {$(
+        impl UlpScalar for $t {
+            fn ulp_diff(self, other: Self) -> u64 {
+                if self.is_nan() || other.is_nan() {
+                    return u64::MAX;
+                }
+                // Map the sign-magnitude bits onto a single integer line,
+                // with negative floats below zero and both zeros at zero.
+                let ordered = |v: $t| {
+                    let bits = v.to_bits() as $int;
+                    if bits < 0 {
+                        -(bits & <$int>::MAX)
+                    } else {
+                        bits
+                    }
+                };
+                ordered(self).abs_diff(ordered(other)) as u64
+            }
+        }
+    )*};
+}
+
+impl_ulp_scalar!(f32 => i32, f64 => i64);
+
+impl<T: UlpScalar> XYVec<T> {
+    /// Ulp distance between each pair of components.
+    pub fn ulp_diff(&self, other: Self) -> [u64; 2] {
+        [self.x().ulp_diff(other.x()), self.y().ulp_diff(other.y())]
+    }
+
+    /// Whether every pair of components is at most `max_ulps` apart.
+    pub fn eq_within_ulps(&self, other: Self, max_ulps: u64) -> bool {
+        self.ulp_diff(other).iter().all(|&d| d <= max_ulps)
+    }
+}
+
+impl<T: UlpScalar> XYZVec<T> {
+    /// Ulp distance between each pair of components.
+    pub fn ulp_diff(&self, other: Self) -> [u64; 3] {
+        [
+            self.x().ulp_diff(other.x()),
+            self.y().ulp_diff(other.y()),
+            self.z().ulp_diff(other.z()),
+        ]
+    }
+
+    /// Whether every pair of components is at most `max_ulps` apart.
+    pub fn eq_within_ulps(&self, other: Self, max_ulps: u64) -> bool {
+        self.ulp_diff(other).iter().all(|&d| d <= max_ulps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UlpScalar;
+    use crate::XYZVec;
+
+    #[test]
+    fn scalar_distances() {
+        assert_eq!(1.0f64.ulp_diff(1.0f64.next_up()), 1);
+        assert_eq!(0.0f32.ulp_diff(-0.0), 0);
+        // Across zero: the smallest subnormal on each side of it.
+        assert_eq!(f64::from_bits(1).ulp_diff(-f64::from_bits(1)), 2);
+        assert_eq!(f32::MAX.ulp_diff(f32::INFINITY), 1);
+        assert_eq!(
+            f64::NEG_INFINITY.ulp_diff(f64::INFINITY),
+            2 * 0x7ff0_0000_0000_0000
+        );
+        assert_eq!(f64::NAN.ulp_diff(f64::NAN), u64::MAX);
+    }
+
+    #[test]
+    fn vectors() {
+        let a = XYZVec::new([1.0f32, 2.0, -3.0]);
+        let b = XYZVec::new([1.0f32.next_up(), 2.0, (-3.0f32).next_down()]);
+        assert_eq!(a.ulp_diff(b), [1, 0, 1]);
+        assert!(a.eq_within_ulps(b, 1));
+        assert!(!a.eq_within_ulps(XYZVec::new([f32::NAN, 2.0, -3.0]), u64::MAX - 1));
+    }
+}
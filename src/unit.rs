@@ -0,0 +1,131 @@
+//! Vectors known to have unit length.
+
+use crate::{angle::Radians, Mat3, VecFloat, VecNum, Vector, XYVec, XYZVec};
+use std::ops::{Deref, Neg};
+
+/// A vector of length one, up to rounding. Dereferences to the underlying
+/// vector, and only offers operations that keep the length at one.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Unit<V>(V);
+
+pub type UnitXYVec<T> = Unit<XYVec<T>>;
+pub type UnitXYZVec<T> = Unit<XYZVec<T>>;
+
+impl<V: Vector> Unit<V>
+where
+    V::Scalar: VecFloat,
+{
+    /// `v` scaled to unit length. `None` if `v` is zero or has a
+    /// non-finite length.
+    /// ```
+    ///     use xyzvec::{unit::Unit, XYVec};
+    ///
+    ///     let u = Unit::try_new(XYVec::new([3.0f64, 4.0])).unwrap();
+    ///     assert_eq!(*u, XYVec::new([0.6, 0.8]));
+    ///     assert!(Unit::try_new(XYVec::new([0.0f64, 0.0])).is_none());
+    /// ```
+    pub fn try_new(v: V) -> Option<Self> {
+        let len = v.l2_norm();
+        let finite = len.to_f64().is_finite();
+        (finite && len > V::Scalar::zero()).then(|| Unit(v.div_by(len)))
+    }
+
+    /// Wraps `v` without checking its length. The caller promises it is
+    /// already one.
+    pub fn new_unchecked(v: V) -> Self {
+        Unit(v)
+    }
+
+    pub fn into_inner(self) -> V {
+        self.0
+    }
+}
+
+impl<V> Deref for Unit<V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.0
+    }
+}
+
+impl<V: Vector> Neg for Unit<V> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Unit(-self.0)
+    }
+}
+
+impl<T: VecFloat> Unit<XYVec<T>> {
+    /// Unit vector at angle `theta` counterclockwise from the `+x` axis.
+    pub fn from_angle(theta: impl Into<Radians<T>>) -> Self {
+        Unit(XYVec::from_angle(theta))
+    }
+
+    /// `self` turned counterclockwise by `theta`.
+    /// ```
+    ///     use xyzvec::{angle::Degrees, unit::UnitXYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let u = UnitXYVec::from_angle(0.0f64).rotated(Degrees(90.0));
+    ///     assert_relative_eq!(u.x(), 0.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(u.y(), 1.0);
+    /// ```
+    pub fn rotated(&self, theta: impl Into<Radians<T>>) -> Self {
+        let theta = theta.into();
+        let (s, c) = (theta.sin(), theta.cos());
+        let v = self.0;
+        Unit(XYVec::new([v.x() * c - v.y() * s, v.x() * s + v.y() * c]))
+    }
+}
+
+impl<T: VecFloat> Unit<XYZVec<T>> {
+    /// `self` turned by `theta` about `axis`, counterclockwise looking down
+    /// the axis toward the origin.
+    /// ```
+    ///     use xyzvec::{unit::UnitXYZVec, XYZVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let x = UnitXYZVec::try_new(XYZVec::new([1.0f64, 0.0, 0.0])).unwrap();
+    ///     let z = UnitXYZVec::try_new(XYZVec::new([0.0, 0.0, 1.0])).unwrap();
+    ///     let y = x.rotated_about(z, std::f64::consts::FRAC_PI_2);
+    ///     assert_relative_eq!(y.y(), 1.0);
+    /// ```
+    pub fn rotated_about(&self, axis: Self, theta: impl Into<Radians<T>>) -> Self {
+        Unit(Mat3::from_axis_angle(axis.0, theta.into().0) * self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Unit, UnitXYVec, UnitXYZVec};
+    use crate::{angle::Degrees, Vector, XYVec, XYZVec};
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn construction_f64() {
+        assert!(Unit::try_new(XYZVec::new([f64::NAN, 0.0, 1.0])).is_none());
+        assert!(Unit::try_new(XYZVec::new([f64::INFINITY, 0.0, 1.0])).is_none());
+        let u = XYZVec::new([1.0f64, -2.0, 2.0]).try_normalize().unwrap();
+        assert_relative_eq!(u.l2_norm(), 1.0);
+        assert_relative_eq!(u.z(), 2.0 / 3.0);
+        assert_eq!((-u).into_inner(), -u.into_inner());
+        let tiny = XYVec::new([1e-100f64, 0.0]).try_normalize().unwrap();
+        assert_eq!(*tiny, XYVec::new([1.0, 0.0]));
+    }
+
+    #[test]
+    fn rotations_keep_length_f32() {
+        let mut u = UnitXYVec::from_angle(Degrees(10.0f32));
+        for _ in 0..100 {
+            u = u.rotated(0.37);
+        }
+        assert_relative_eq!(u.l2_norm(), 1.0, epsilon = 1e-5);
+        let axis = UnitXYZVec::try_new(XYZVec::new([1.0f32, 1.0, 1.0])).unwrap();
+        let v = UnitXYZVec::try_new(XYZVec::new([1.0f32, 0.0, 0.0])).unwrap();
+        let turned = v.rotated_about(axis, Degrees(120.0));
+        assert_relative_eq!(turned.y(), 1.0, epsilon = 1e-6);
+        assert_relative_eq!(turned.l2_norm(), 1.0, epsilon = 1e-6);
+    }
+}
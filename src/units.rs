@@ -0,0 +1,222 @@
+//! Vectors of physical quantities, checked by [`uom`].
+//!
+//! [`f32::Position2`], [`f64::Velocity3`], and friends store their
+//! components in SI base units and only combine in dimensionally sound
+//! ways: positions and velocities add to their own kind, a velocity times a
+//! time is a position delta, and so on. Mixing them up is a compile error.
+//! ```
+//!     use uom::si::f64::Time;
+//!     use uom::si::length::meter;
+//!     use uom::si::time::second;
+//!     use uom::si::velocity::meter_per_second;
+//!     use uom::si::f64::Velocity;
+//!     use xyzvec::units::f64::{Position2, Velocity2};
+//!
+//!     let pos = Position2::from_si(xyzvec::XYVec::new([1.0, 0.0]));
+//!     let vel = Velocity2::new([
+//!         Velocity::new::<meter_per_second>(2.0),
+//!         Velocity::new::<meter_per_second>(0.5),
+//!     ]);
+//!     let later = pos + vel * Time::new::<second>(2.0);
+//!     assert_eq!(later.components()[0].get::<meter>(), 5.0);
+//!     // `pos + vel` does not compile.
+//! ```
+
+macro_rules! quantity_vector {
+    ($name:ident, $vec:ident, $n:literal, $float:ident, $q:ident, $unit:path) => {
+        #[doc = concat!("Vector of `", stringify!($q), "` components, stored in SI base units.")]
+        #[derive(Clone, Copy, PartialEq, Debug)]
+        pub struct $name($vec<$float>);
+
+        impl $name {
+            pub fn new(components: [$q; $n]) -> Self {
+                Self($vec::new(components.map(|c| c.get::<$unit>())))
+            }
+
+            /// Vector whose components are already in SI base units.
+            pub fn from_si(v: $vec<$float>) -> Self {
+                Self(v)
+            }
+
+            /// The components in SI base units, as a plain vector.
+            pub fn as_si(&self) -> $vec<$float> {
+                self.0
+            }
+
+            pub fn components(&self) -> [$q; $n] {
+                std::array::from_fn(|i| $q::new::<$unit>(self.0.component(i)))
+            }
+
+            /// Euclidean length.
+            pub fn norm(&self) -> $q {
+                $q::new::<$unit>(self.0.l2_norm())
+            }
+        }
+
+        impl Add for $name {
+            type Output = Self;
+
+            fn add(self, other: Self) -> Self {
+                Self(self.0 + other.0)
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, other: Self) {
+                self.0 += other.0;
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, other: Self) -> Self {
+                Self(self.0 - other.0)
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, other: Self) {
+                self.0 -= other.0;
+            }
+        }
+
+        impl Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                Self(-self.0)
+            }
+        }
+
+        impl Mul<$float> for $name {
+            type Output = Self;
+
+            fn mul(self, d: $float) -> Self {
+                Self(self.0.scale_by(d))
+            }
+        }
+
+        impl Div<$float> for $name {
+            type Output = Self;
+
+            fn div(self, d: $float) -> Self {
+                Self(self.0.div_by(d))
+            }
+        }
+    };
+}
+
+/// `$rate * time = $amount` and `$amount / time = $rate`.
+macro_rules! time_derivative {
+    ($amount:ident, $rate:ident) => {
+        impl Mul<Time> for $rate {
+            type Output = $amount;
+
+            fn mul(self, dt: Time) -> $amount {
+                $amount(self.0.scale_by(dt.get::<second>()))
+            }
+        }
+
+        impl Div<Time> for $amount {
+            type Output = $rate;
+
+            fn div(self, dt: Time) -> $rate {
+                $rate(self.0.div_by(dt.get::<second>()))
+            }
+        }
+    };
+}
+
+macro_rules! quantity_vectors {
+    ($float:ident) => {
+        #[doc = concat!("Quantity vectors with `", stringify!($float), "` storage.")]
+        pub mod $float {
+            use crate::{Vector, XYVec, XYZVec};
+            use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+            use uom::si::{
+                acceleration::meter_per_second_squared,
+                length::meter,
+                time::second,
+                velocity::meter_per_second,
+                $float::{Acceleration, Length, Time, Velocity},
+            };
+
+            quantity_vector!(Position2, XYVec, 2, $float, Length, meter);
+            quantity_vector!(Position3, XYZVec, 3, $float, Length, meter);
+            quantity_vector!(Velocity2, XYVec, 2, $float, Velocity, meter_per_second);
+            quantity_vector!(Velocity3, XYZVec, 3, $float, Velocity, meter_per_second);
+            quantity_vector!(
+                Acceleration2,
+                XYVec,
+                2,
+                $float,
+                Acceleration,
+                meter_per_second_squared
+            );
+            quantity_vector!(
+                Acceleration3,
+                XYZVec,
+                3,
+                $float,
+                Acceleration,
+                meter_per_second_squared
+            );
+
+            time_derivative!(Position2, Velocity2);
+            time_derivative!(Position3, Velocity3);
+            time_derivative!(Velocity2, Acceleration2);
+            time_derivative!(Velocity3, Acceleration3);
+        }
+    };
+}
+
+quantity_vectors!(f32);
+quantity_vectors!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::f64::{Acceleration3, Position3, Velocity3};
+    use crate::XYZVec;
+    use approx::assert_relative_eq;
+    use uom::si::{
+        f64::{Length, Time},
+        length::{kilometer, meter},
+        time::{millisecond, second},
+    };
+
+    #[test]
+    fn kinematics_f64() {
+        let gravity = Acceleration3::from_si(XYZVec::new([0.0, 0.0, -9.81]));
+        let mut vel = Velocity3::from_si(XYZVec::new([3.0, 0.0, 0.0]));
+        let mut pos = Position3::new([
+            Length::new::<kilometer>(1.0),
+            Length::new::<meter>(0.0),
+            Length::new::<meter>(10.0),
+        ]);
+        let dt = Time::new::<millisecond>(100.0);
+        for _ in 0..10 {
+            vel += gravity * dt;
+            pos += vel * dt;
+        }
+        assert_relative_eq!(pos.as_si().x(), 1003.0, epsilon = 1e-9);
+        assert!(pos.as_si().z() < 10.0);
+        let displacement = pos - Position3::from_si(XYZVec::new([1000.0, 0.0, 10.0]));
+        let mean_vel = displacement / Time::new::<second>(1.0);
+        assert_relative_eq!(mean_vel.as_si().x(), 3.0, epsilon = 1e-9);
+        assert_relative_eq!(
+            (-displacement * 2.0 / 2.0).norm().get::<meter>(),
+            displacement.norm().get::<meter>()
+        );
+    }
+
+    #[test]
+    fn components_f32() {
+        use super::f32::Position2;
+        use uom::si::{f32::Length, length::centimeter};
+        let p = Position2::new([Length::new::<centimeter>(150.0), Length::new::<meter>(-2.0)]);
+        assert_eq!(p.as_si(), crate::XYVec::new([1.5, -2.0]));
+        assert_relative_eq!(p.components()[0].get::<centimeter>(), 150.0);
+        assert_relative_eq!(p.norm().get::<meter>(), 2.5);
+    }
+}
@@ -0,0 +1,193 @@
+//! JavaScript bindings for `f64` vectors through `wasm-bindgen`. Requires
+//! the `wasm` feature.
+//!
+//! [`Vec2`] and [`Vec3`] wrap [`XYVec<f64>`] and [`XYZVec<f64>`] and show up
+//! in JavaScript as immutable classes: every operation returns a new
+//! vector, computed by the same code the Rust types use.
+//! ```js
+//! const v = new Vec2(3, 4);
+//! v.add(new Vec2(1, 0)).length(); // 5.656...
+//! ```
+
+use crate::{Vector, XYVec, XYZVec};
+use wasm_bindgen::prelude::wasm_bindgen;
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vec2(XYVec<f64>);
+
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vec3(XYZVec<f64>);
+
+impl From<XYVec<f64>> for Vec2 {
+    fn from(v: XYVec<f64>) -> Self {
+        Self(v)
+    }
+}
+
+impl From<Vec2> for XYVec<f64> {
+    fn from(v: Vec2) -> Self {
+        v.0
+    }
+}
+
+impl From<XYZVec<f64>> for Vec3 {
+    fn from(v: XYZVec<f64>) -> Self {
+        Self(v)
+    }
+}
+
+impl From<Vec3> for XYZVec<f64> {
+    fn from(v: Vec3) -> Self {
+        v.0
+    }
+}
+
+#[wasm_bindgen]
+impl Vec2 {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: f64, y: f64) -> Vec2 {
+        Self(XYVec::new([x, y]))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> f64 {
+        self.0.x()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn y(&self) -> f64 {
+        self.0.y()
+    }
+
+    pub fn add(&self, other: &Vec2) -> Vec2 {
+        Self(self.0 + other.0)
+    }
+
+    pub fn sub(&self, other: &Vec2) -> Vec2 {
+        Self(self.0 - other.0)
+    }
+
+    pub fn scale(&self, d: f64) -> Vec2 {
+        Self(self.0.scale_by(d))
+    }
+
+    pub fn dot(&self, other: &Vec2) -> f64 {
+        self.0.dot_prod(other.0)
+    }
+
+    /// The `z` component of the 3D cross product.
+    pub fn cross(&self, other: &Vec2) -> f64 {
+        self.0.cross_prod(other.0)
+    }
+
+    pub fn length(&self) -> f64 {
+        self.0.l2_norm()
+    }
+
+    /// `undefined` for the zero vector.
+    pub fn normalize(&self) -> Option<Vec2> {
+        self.0.try_normalize().map(|u| Self(u.into_inner()))
+    }
+
+    pub fn lerp(&self, other: &Vec2, t: f64) -> Vec2 {
+        Self(self.0.lerp(other.0, t))
+    }
+
+    /// Clockwise rotation by `theta` radians, as in
+    /// [`XYVec::rotated_by`].
+    pub fn rotate(&self, theta: f64) -> Vec2 {
+        Self(self.0.rotated_by(theta))
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[wasm_bindgen]
+impl Vec3 {
+    #[wasm_bindgen(constructor)]
+    pub fn new(x: f64, y: f64, z: f64) -> Vec3 {
+        Self(XYZVec::new([x, y, z]))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn x(&self) -> f64 {
+        self.0.x()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn y(&self) -> f64 {
+        self.0.y()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn z(&self) -> f64 {
+        self.0.z()
+    }
+
+    pub fn add(&self, other: &Vec3) -> Vec3 {
+        Self(self.0 + other.0)
+    }
+
+    pub fn sub(&self, other: &Vec3) -> Vec3 {
+        Self(self.0 - other.0)
+    }
+
+    pub fn scale(&self, d: f64) -> Vec3 {
+        Self(self.0.scale_by(d))
+    }
+
+    pub fn dot(&self, other: &Vec3) -> f64 {
+        self.0.dot_prod(other.0)
+    }
+
+    pub fn cross(&self, other: &Vec3) -> Vec3 {
+        Self(self.0.cross(other.0))
+    }
+
+    pub fn length(&self) -> f64 {
+        self.0.l2_norm()
+    }
+
+    /// `undefined` for the zero vector.
+    pub fn normalize(&self) -> Option<Vec3> {
+        self.0.try_normalize().map(|u| Self(u.into_inner()))
+    }
+
+    pub fn lerp(&self, other: &Vec3, t: f64) -> Vec3 {
+        Self(self.0.lerp(other.0, t))
+    }
+
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_js_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Vec2, Vec3};
+    use crate::XYVec;
+    use approx::assert_relative_eq;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[test]
+    fn matches_rust_math() {
+        let v = Vec2::new(3.0, 4.0);
+        assert_eq!(v.length(), 5.0);
+        assert_eq!(v.add(&Vec2::new(1.0, -4.0)), XYVec::new([4.0, 0.0]).into());
+        let r = Vec2::new(1.0, 0.0).rotate(FRAC_PI_2);
+        assert_relative_eq!(r.x(), 0.0, epsilon = 1e-12);
+        assert_relative_eq!(r.y(), -1.0, epsilon = 1e-12);
+        assert!(Vec2::new(0.0, 0.0).normalize().is_none());
+        assert_eq!(v.to_js_string(), "(3.000, 4.000)");
+
+        let z = Vec3::new(1.0, 0.0, 0.0).cross(&Vec3::new(0.0, 1.0, 0.0));
+        assert_eq!((z.x(), z.y(), z.z()), (0.0, 0.0, 1.0));
+        assert_eq!(z.scale(2.0).normalize(), Some(z));
+    }
+}
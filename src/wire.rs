@@ -0,0 +1,174 @@
+//! A compact binary encoding for fixed-point vectors, for links between
+//! microcontrollers and hosts where every byte counts.
+//!
+//! An encoded vector is a 3-byte format tag followed by the raw bits of
+//! each component:
+//!
+//! | offset | size | contents                                          |
+//! |--------|------|---------------------------------------------------|
+//! | 0      | 1    | dimension: `2` or `3`                             |
+//! | 1      | 1    | component width in bits: `8`, `16`, `32`, or `64` |
+//! | 2      | 1    | number of fractional bits                         |
+//! | 3      | w/8  | `x`, two's complement, little-endian              |
+//! | ...    | w/8  | `y`, then `z` for 3D vectors                      |
+//!
+//! Decoding checks the tag against the requested type, so a sender and
+//! receiver that disagree about the format fail loudly instead of
+//! misreading each other.
+//! ```
+//!     use fixed::types::I16F16;
+//!     use xyzvec::{wire::WireFormat, XYVec};
+//!
+//!     let v = XYVec::new([I16F16::from_num(1.5), I16F16::from_num(-2)]);
+//!     let mut buf = [0u8; 16];
+//!     let n = v.encode_into(&mut buf).unwrap();
+//!     assert_eq!(&buf[..n], &[2, 32, 16, 0, 0x80, 1, 0, 0, 0, 0xfe, 0xff]);
+//!     assert_eq!(XYVec::<I16F16>::decode_from(&buf), Ok(v));
+//! ```
+
+use crate::{XYVec, XYZVec};
+use fixed::types::extra::{LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+use std::fmt::{self, Display, Formatter};
+
+/// Length of the format tag that starts every encoded vector.
+pub const TAG_LEN: usize = 3;
+
+/// Why a vector could not be encoded or decoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WireError {
+    /// The buffer is shorter than the encoded vector.
+    BufferTooSmall { needed: usize, found: usize },
+    /// The format tag describes a different type than the one requested.
+    FormatMismatch {
+        expected: [u8; TAG_LEN],
+        found: [u8; TAG_LEN],
+    },
+}
+
+impl Display for WireError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WireError::BufferTooSmall { needed, found } => {
+                write!(f, "buffer of {found} bytes is too small, need {needed}")
+            }
+            WireError::FormatMismatch { expected, found } => {
+                write!(f, "expected format tag {expected:?}, found {found:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for WireError {}
+
+/// Fixed-point vectors with a packed wire encoding. See the
+/// [module docs](self) for the layout.
+pub trait WireFormat: Sized {
+    /// Bytes taken by an encoded vector, tag included.
+    const ENCODED_LEN: usize;
+    /// Format tag identifying this type on the wire.
+    const TAG: [u8; TAG_LEN];
+
+    /// Writes `self` to the start of `buf`, returning the number of bytes
+    /// written.
+    fn encode_into(&self, buf: &mut [u8]) -> Result<usize, WireError>;
+
+    /// Reads a vector from the start of `buf`. Trailing bytes are ignored,
+    /// so vectors can be read one after another from a stream.
+    fn decode_from(buf: &[u8]) -> Result<Self, WireError>;
+}
+
+fn check_len(needed: usize, found: usize) -> Result<(), WireError> {
+    if found < needed {
+        return Err(WireError::BufferTooSmall { needed, found });
+    }
+    Ok(())
+}
+
+macro_rules! impl_wire {
+    ($($t:ident: $bound:ident),*) => {$(
+        impl_wire!(@vec XYVec, 2, $t, $bound);
+        impl_wire!(@vec XYZVec, 3, $t, $bound);
+    )*};
+    (@vec $vec:ident, $n:literal, $t:ident, $bound:ident) => {
+        impl<Frac: $bound> WireFormat for $vec<fixed::$t<Frac>> {
+            const ENCODED_LEN: usize = TAG_LEN + $n * std::mem::size_of::<fixed::$t<Frac>>();
+            const TAG: [u8; TAG_LEN] = [
+                $n,
+                8 * std::mem::size_of::<fixed::$t<Frac>>() as u8,
+                fixed::$t::<Frac>::FRAC_NBITS as u8,
+            ];
+
+            fn encode_into(&self, buf: &mut [u8]) -> Result<usize, WireError> {
+                check_len(Self::ENCODED_LEN, buf.len())?;
+                let (tag, body) = buf[..Self::ENCODED_LEN].split_at_mut(TAG_LEN);
+                tag.copy_from_slice(&Self::TAG);
+                let width = body.len() / $n;
+                for (chunk, c) in body.chunks_exact_mut(width).zip(self.iter()) {
+                    chunk.copy_from_slice(&c.to_le_bytes());
+                }
+                Ok(Self::ENCODED_LEN)
+            }
+
+            fn decode_from(buf: &[u8]) -> Result<Self, WireError> {
+                check_len(Self::ENCODED_LEN, buf.len())?;
+                let (tag, body) = buf[..Self::ENCODED_LEN].split_at(TAG_LEN);
+                if tag != Self::TAG {
+                    return Err(WireError::FormatMismatch {
+                        expected: Self::TAG,
+                        found: tag.try_into().unwrap(),
+                    });
+                }
+                let width = body.len() / $n;
+                Ok($vec::new(std::array::from_fn(|i| {
+                    let bytes = body[i * width..(i + 1) * width].try_into().unwrap();
+                    fixed::$t::from_le_bytes(bytes)
+                })))
+            }
+        }
+    };
+}
+
+impl_wire!(FixedI8: LeEqU8, FixedI16: LeEqU16, FixedI32: LeEqU32, FixedI64: LeEqU64);
+
+#[cfg(test)]
+mod tests {
+    use super::{WireError, WireFormat};
+    use crate::{XYVec, XYZVec};
+    use fixed::types::{I16F16, I24F8, I48F16, I4F4};
+
+    #[test]
+    fn round_trip_stream() {
+        let a = XYZVec::new([I48F16::from_num(-3.5), I48F16::MAX, I48F16::MIN]);
+        let b = XYVec::new([I4F4::from_num(0.25), I4F4::from_num(-8)]);
+        let mut buf = [0u8; 64];
+        let n = a.encode_into(&mut buf).unwrap();
+        assert_eq!(n, 3 + 3 * 8);
+        let m = b.encode_into(&mut buf[n..]).unwrap();
+        assert_eq!(m, 3 + 2);
+
+        assert_eq!(XYZVec::decode_from(&buf), Ok(a));
+        assert_eq!(XYVec::decode_from(&buf[n..]), Ok(b));
+    }
+
+    #[test]
+    fn rejects_short_buffers_and_other_formats() {
+        let v = XYVec::new([I16F16::from_num(1), I16F16::from_num(2)]);
+        let mut buf = [0u8; 11];
+        assert_eq!(
+            v.encode_into(&mut buf[..10]),
+            Err(WireError::BufferTooSmall {
+                needed: 11,
+                found: 10
+            })
+        );
+        v.encode_into(&mut buf).unwrap();
+        assert_eq!(
+            XYVec::<I24F8>::decode_from(&buf),
+            Err(WireError::FormatMismatch {
+                expected: [2, 32, 8],
+                found: [2, 32, 16]
+            })
+        );
+        assert!(XYZVec::<I16F16>::decode_from(&buf).is_err());
+    }
+}
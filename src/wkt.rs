@@ -0,0 +1,354 @@
+//! Well-known text (WKT) for points, polylines, and polygons:
+//! `POINT`, `LINESTRING`, and `POLYGON`, with a `Z` tag for 3D.
+//!
+//! Parsing is case-insensitive and tolerant of extra whitespace. Polygons
+//! with interior rings (holes) are rejected, since [`Polygon2`] has none.
+
+use crate::{Polygon2, Polyline, VecNum, Vector, XYVec, XYZVec};
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// Why a WKT string could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WktError {
+    /// The geometry tag was not the one expected, e.g. a `LINESTRING` where
+    /// a `POINT` was expected.
+    WrongType {
+        expected: &'static str,
+        found: String,
+    },
+    /// A coordinate had the wrong number of components.
+    WrongDimension { expected: usize, found: usize },
+    /// A component could not be parsed as a number.
+    InvalidNumber(String),
+    /// The geometry is valid WKT but cannot be represented, such as an
+    /// empty point or a polygon with holes.
+    Unsupported(&'static str),
+    /// Unbalanced parentheses or other malformed text.
+    Malformed,
+}
+
+impl Display for WktError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            WktError::WrongType { expected, found } => {
+                write!(f, "expected {expected} geometry, found `{found}`")
+            }
+            WktError::WrongDimension { expected, found } => {
+                write!(
+                    f,
+                    "expected {expected} coordinates per point, found {found}"
+                )
+            }
+            WktError::InvalidNumber(s) => write!(f, "invalid number `{s}`"),
+            WktError::Unsupported(what) => write!(f, "unsupported geometry: {what}"),
+            WktError::Malformed => write!(f, "malformed WKT"),
+        }
+    }
+}
+
+impl std::error::Error for WktError {}
+
+/// Point types that can be written as a WKT coordinate.
+pub trait WktCoord: Sized {
+    /// Dimension tag following the geometry type: empty for 2D, `" Z"` for 3D.
+    const DIM_TAG: &'static str;
+
+    fn write_coord(&self, out: &mut String);
+    fn parse_coord(s: &str) -> Result<Self, WktError>;
+}
+
+fn parse_components<T: FromStr, const N: usize>(s: &str) -> Result<[T; N], WktError> {
+    let parts: Vec<&str> = s.split_whitespace().collect();
+    if parts.len() != N {
+        return Err(WktError::WrongDimension {
+            expected: N,
+            found: parts.len(),
+        });
+    }
+    let mut values = Vec::with_capacity(N);
+    for part in parts {
+        let v = part
+            .parse()
+            .map_err(|_| WktError::InvalidNumber(part.to_string()))?;
+        values.push(v);
+    }
+    values.try_into().map_err(|_| WktError::Malformed)
+}
+
+impl<T: VecNum + FromStr> WktCoord for XYVec<T> {
+    const DIM_TAG: &'static str = "";
+
+    fn write_coord(&self, out: &mut String) {
+        out.push_str(&format!("{} {}", self.x(), self.y()));
+    }
+
+    fn parse_coord(s: &str) -> Result<Self, WktError> {
+        parse_components(s).map(XYVec::new)
+    }
+}
+
+impl<T: VecNum + FromStr> WktCoord for XYZVec<T> {
+    const DIM_TAG: &'static str = " Z";
+
+    fn write_coord(&self, out: &mut String) {
+        out.push_str(&format!("{} {} {}", self.x(), self.y(), self.z()));
+    }
+
+    fn parse_coord(s: &str) -> Result<Self, WktError> {
+        parse_components(s).map(XYZVec::new)
+    }
+}
+
+fn write_coords<V: WktCoord>(coords: impl Iterator<Item = V>, out: &mut String) {
+    out.push('(');
+    for (i, c) in coords.enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        c.write_coord(out);
+    }
+    out.push(')');
+}
+
+fn parse_coords<V: WktCoord>(body: &str) -> Result<Vec<V>, WktError> {
+    body.split(',').map(V::parse_coord).collect()
+}
+
+/// Splits `TAG (body)` into the tag and the text inside the outer
+/// parentheses, or `TAG EMPTY` into the tag and `None`, checking that the
+/// tag is `expected`, optionally followed by `V`'s dimension tag.
+fn split_tagged<'a, V: WktCoord>(
+    s: &'a str,
+    expected: &'static str,
+) -> Result<Option<&'a str>, WktError> {
+    let s = s.trim();
+    let (tag, body) = match s.find('(') {
+        Some(open) => {
+            let body = s[open + 1..].strip_suffix(')').ok_or(WktError::Malformed)?;
+            (&s[..open], Some(body))
+        }
+        None => {
+            let upper = s.to_ascii_uppercase();
+            let tag_len = upper
+                .trim_end()
+                .strip_suffix("EMPTY")
+                .ok_or(WktError::Malformed)?
+                .len();
+            (&s[..tag_len], None)
+        }
+    };
+    let tag = tag
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_ascii_uppercase();
+    let full = format!("{expected}{}", V::DIM_TAG);
+    if tag != full && tag != expected {
+        return Err(WktError::WrongType {
+            expected,
+            found: tag,
+        });
+    }
+    Ok(body)
+}
+
+impl<T: VecNum + FromStr> XYVec<T> {
+    /// ```
+    ///     use xyzvec::XYVec;
+    ///
+    ///     let v = XYVec::new([1.5f64, -2.0]);
+    ///     assert_eq!(v.to_wkt(), "POINT (1.5 -2)");
+    ///     assert_eq!(XYVec::from_wkt("point(1.5 -2)"), Ok(v));
+    /// ```
+    pub fn to_wkt(&self) -> String {
+        let mut out = String::from("POINT ");
+        write_coords(std::iter::once(*self), &mut out);
+        out
+    }
+
+    pub fn from_wkt(s: &str) -> Result<Self, WktError> {
+        let body = split_tagged::<Self>(s, "POINT")?.ok_or(WktError::Unsupported("empty point"))?;
+        Self::parse_coord(body)
+    }
+}
+
+impl<T: VecNum + FromStr> XYZVec<T> {
+    /// ```
+    ///     use xyzvec::XYZVec;
+    ///
+    ///     let v = XYZVec::new([1, 2, 3]);
+    ///     assert_eq!(v.to_wkt(), "POINT Z (1 2 3)");
+    ///     assert_eq!(XYZVec::from_wkt("POINT Z (1 2 3)"), Ok(v));
+    /// ```
+    pub fn to_wkt(&self) -> String {
+        let mut out = String::from("POINT Z ");
+        write_coords(std::iter::once(*self), &mut out);
+        out
+    }
+
+    pub fn from_wkt(s: &str) -> Result<Self, WktError> {
+        let body = split_tagged::<Self>(s, "POINT")?.ok_or(WktError::Unsupported("empty point"))?;
+        Self::parse_coord(body)
+    }
+}
+
+impl<V: Vector + WktCoord> Polyline<V>
+where
+    V::Scalar: VecNum,
+{
+    /// `LINESTRING`, or `LINESTRING EMPTY` for an empty polyline.
+    /// ```
+    ///     use xyzvec::{Polyline, XYVec};
+    ///
+    ///     let line = Polyline::new(vec![XYVec::new([0, 0]), XYVec::new([3, 4])]);
+    ///     assert_eq!(line.to_wkt(), "LINESTRING (0 0, 3 4)");
+    ///     assert_eq!(Polyline::from_wkt("LINESTRING (0 0, 3 4)"), Ok(line));
+    /// ```
+    pub fn to_wkt(&self) -> String {
+        let mut out = format!("LINESTRING{}", V::DIM_TAG);
+        if self.points().is_empty() {
+            out.push_str(" EMPTY");
+        } else {
+            out.push(' ');
+            write_coords(self.points().iter().copied(), &mut out);
+        }
+        out
+    }
+
+    pub fn from_wkt(s: &str) -> Result<Self, WktError> {
+        let points = match split_tagged::<V>(s, "LINESTRING")? {
+            Some(body) => parse_coords(body)?,
+            None => Vec::new(),
+        };
+        Ok(Self::new(points))
+    }
+}
+
+impl<T: VecNum + FromStr> Polygon2<T> {
+    /// `POLYGON` with a single ring, closed by repeating the first vertex.
+    /// ```
+    ///     use xyzvec::{Polygon2, XYVec};
+    ///
+    ///     let tri = Polygon2::new(vec![XYVec::new([0, 0]), XYVec::new([1, 0]), XYVec::new([0, 1])]);
+    ///     assert_eq!(tri.to_wkt(), "POLYGON ((0 0, 1 0, 0 1, 0 0))");
+    ///     assert_eq!(Polygon2::from_wkt(&tri.to_wkt()), Ok(tri));
+    /// ```
+    pub fn to_wkt(&self) -> String {
+        let vertices = self.vertices();
+        if vertices.is_empty() {
+            return String::from("POLYGON EMPTY");
+        }
+        let mut out = String::from("POLYGON (");
+        write_coords(vertices.iter().chain(vertices.first()).copied(), &mut out);
+        out.push(')');
+        out
+    }
+
+    /// Parses a `POLYGON` with a single ring. A closing vertex equal to the
+    /// first is dropped.
+    pub fn from_wkt(s: &str) -> Result<Self, WktError> {
+        let Some(body) = split_tagged::<XYVec<T>>(s, "POLYGON")? else {
+            return Ok(Self::new(Vec::new()));
+        };
+        let body = body.trim();
+        let ring = body
+            .strip_prefix('(')
+            .and_then(|b| b.strip_suffix(')'))
+            .ok_or(WktError::Malformed)?;
+        if ring.contains('(') || ring.contains(')') {
+            return Err(WktError::Unsupported("polygon with holes"));
+        }
+        let mut vertices: Vec<XYVec<T>> = parse_coords(ring)?;
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Ok(Self::new(vertices))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WktError;
+    use crate::{Polygon2, Polyline, XYVec, XYZVec};
+    use fixed::types::I16F16;
+
+    #[test]
+    fn points() {
+        assert_eq!(XYVec::new([0.25f32, 3.0]).to_wkt(), "POINT (0.25 3)");
+        assert_eq!(
+            XYVec::<f64>::from_wkt("  Point  ( 1e3   -0.5 ) "),
+            Ok(XYVec::new([1000.0, -0.5]))
+        );
+        let f = |v: f64| I16F16::from_num(v);
+        let v = XYZVec::new([f(1.5), f(-0.25), f(0.0)]);
+        assert_eq!(XYZVec::from_wkt(&v.to_wkt()), Ok(v));
+        // The Z tag is optional on input.
+        assert_eq!(
+            XYZVec::<f64>::from_wkt("POINT (1 2 3)"),
+            Ok(XYZVec::new([1.0, 2.0, 3.0]))
+        );
+        assert_eq!(
+            XYVec::<f64>::from_wkt("POINT Z (1 2 3)"),
+            Err(WktError::WrongType {
+                expected: "POINT",
+                found: "POINT Z".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            XYVec::<f64>::from_wkt("POINT (1 2 3)"),
+            Err(WktError::WrongDimension {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            XYVec::<i32>::from_wkt("POINT (1 x)"),
+            Err(WktError::InvalidNumber("x".to_string()))
+        );
+        assert_eq!(
+            XYVec::<f64>::from_wkt("LINESTRING (1 2)"),
+            Err(WktError::WrongType {
+                expected: "POINT",
+                found: "LINESTRING".to_string()
+            })
+        );
+        assert_eq!(
+            XYVec::<f64>::from_wkt("POINT EMPTY"),
+            Err(WktError::Unsupported("empty point"))
+        );
+        assert_eq!(
+            XYVec::<f64>::from_wkt("POINT (1 2"),
+            Err(WktError::Malformed)
+        );
+        assert_eq!(
+            Polygon2::<f64>::from_wkt("POLYGON ((0 0, 4 0, 0 4, 0 0), (1 1, 2 1, 1 2, 1 1))"),
+            Err(WktError::Unsupported("polygon with holes"))
+        );
+    }
+
+    #[test]
+    fn polylines_and_polygons() {
+        let line = Polyline::new(vec![
+            XYZVec::new([0.0f64, 1.0, 2.0]),
+            XYZVec::new([3.5, 4.0, 5.0]),
+        ]);
+        assert_eq!(line.to_wkt(), "LINESTRING Z (0 1 2, 3.5 4 5)");
+        assert_eq!(Polyline::from_wkt(&line.to_wkt()), Ok(line));
+
+        let empty: Polyline<XYVec<f64>> = Polyline::new(Vec::new());
+        assert_eq!(empty.to_wkt(), "LINESTRING EMPTY");
+        assert_eq!(Polyline::from_wkt("linestring empty"), Ok(empty));
+
+        let open = Polygon2::<i32>::from_wkt("POLYGON ((0 0, 2 0, 2 2))").unwrap();
+        assert_eq!(open.len(), 3);
+        assert_eq!(Polygon2::<i32>::from_wkt("POLYGON EMPTY").unwrap().len(), 0);
+        assert_eq!(Polygon2::<i32>::new(Vec::new()).to_wkt(), "POLYGON EMPTY");
+    }
+}
@@ -1,26 +1,41 @@
-use crate::VecInner;
+use crate::{
+    angle::Radians,
+    error::{exactly, VecError},
+    Mat2, VecFloat, VecInner, VecNum, Vector,
+};
 use fixed::FixedI64;
 use std::{
     fmt::{self, Formatter},
+    iter::{Product, Sum},
     ops::{Add, AddAssign, Neg, Sub, SubAssign},
 };
 
-#[derive(Clone, PartialEq, Copy)]
+/// Laid out exactly like `[T; 2]`, so slices of vectors and of scalars
+/// can be reinterpreted; see [`XYVec::cast_slice`].
+#[derive(Clone, PartialEq, Eq, Hash, Copy)]
+#[repr(transparent)]
 pub struct XYVec<T> {
     inner: [T; 2],
 }
 
-impl<T: VecInner> XYVec<T> {
+impl<T> XYVec<T> {
+    /// Works with any scalar, including ones that are not `Copy`; most
+    /// other methods need [`VecInner`].
+    #[inline]
     pub fn new(inner: [T; 2]) -> Self {
         Self { inner }
     }
+}
 
+impl<T: VecInner> XYVec<T> {
     /// `x` component of XYVec
+    #[inline]
     pub fn x(&self) -> T {
         self.inner[0]
     }
 
     /// `y` component of XYVec
+    #[inline]
     pub fn y(&self) -> T {
         self.inner[1]
     }
@@ -34,6 +49,7 @@ impl<T: VecInner> XYVec<T> {
     ///    assert_relative_eq!(scaled_v.x(), 5.0);
     ///    assert_relative_eq!(scaled_v.y(), -2.5);
     /// ```
+    #[inline]
     pub fn scale_by(&self, d: T) -> Self {
         let x = self.x() * d;
         let y = self.y() * d;
@@ -49,6 +65,7 @@ impl<T: VecInner> XYVec<T> {
     ///    assert_relative_eq!(scaled_v.x(), 5.0);
     ///    assert_relative_eq!(scaled_v.y(), -2.5);
     /// ```
+    #[inline]
     pub fn div_by(&self, d: T) -> Self {
         let x = self.x() / d;
         let y = self.y() / d;
@@ -73,6 +90,7 @@ impl<T: VecInner> XYVec<T> {
     //     let v = XYVec::new([1.0f64, -0.5f64]);
     //     assert_relative_eq!(v.l2_norm_sqd(), 1.25);
     /// ```
+    #[inline]
     pub fn l2_norm_sqd(&self) -> T {
         self.x() * self.x() + self.y() * self.y()
     }
@@ -85,6 +103,7 @@ impl<T: VecInner> XYVec<T> {
     ///    let w = XYVec::new([-2.0f64, 0.0f64]);
     ///    assert_relative_eq!(v.cross_prod(w), -1.0);
     /// ```
+    #[inline]
     pub fn cross_prod(&self, other: Self) -> T {
         self.x() * other.y() - self.y() * other.x()
     }
@@ -97,8 +116,10 @@ impl<T: VecInner> XYVec<T> {
     ///    let w = XYVec::new([-2.0f64, 0.0f64]);
     ///    assert_relative_eq!(v.cross_prod_sqd(w), 1.0);
     /// ```
+    #[inline]
     pub fn cross_prod_sqd(&self, other: Self) -> T {
-        self.cross_prod(other) * self.cross_prod(other)
+        let c = self.cross_prod(other);
+        c * c
     }
 
     /// ```   
@@ -109,10 +130,23 @@ impl<T: VecInner> XYVec<T> {
     ///    let w = XYVec::new([-2.0f64, 0.0f64]);
     ///    assert_relative_eq!(v.dot_prod(w), -2.0);
     /// ```
+    #[inline]
     pub fn dot_prod(&self, other: Self) -> T {
         self.x() * other.x() + self.y() * other.y()
     }
 
+    /// Outer product `self * other^T`, whose entry `(i, j)` is
+    /// `self[i] * other[j]`.
+    /// ```
+    ///     use xyzvec::{Mat2, XYVec};
+    ///
+    ///     let m = XYVec::new([1, 2]).outer(XYVec::new([3, 4]));
+    ///     assert_eq!(m, Mat2::new([[3, 4], [6, 8]]));
+    /// ```
+    pub fn outer(&self, other: Self) -> Mat2<T> {
+        Mat2::from_rows([other.scale_by(self.x()), other.scale_by(self.y())])
+    }
+
     /// ```   
     ///    use xyzvec::XYVec;
     ///    use approx::assert_relative_eq;
@@ -188,9 +222,105 @@ impl<T: VecInner> XYVec<T> {
     }
 }
 
+impl<T: VecFloat> XYVec<T> {
+    /// Unit vector at angle `theta` counterclockwise from the `+x` axis.
+    /// ```
+    ///     use xyzvec::XYVec;
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let v = XYVec::from_angle(std::f64::consts::FRAC_PI_2);
+    ///     assert_relative_eq!(v.x(), 0.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(v.y(), 1.0);
+    /// ```
+    pub fn from_angle(theta: impl Into<Radians<T>>) -> Self {
+        let theta = theta.into();
+        Self::new([theta.cos(), theta.sin()])
+    }
+
+    /// Angle of `self` counterclockwise from the `+x` axis, in `(-π, π]`.
+    /// Zero for the zero vector.
+    pub fn angle(&self) -> Radians<T> {
+        Radians(self.y().atan2(self.x()))
+    }
+
+    /// Turns `self` toward the direction of `target` by at most `max_angle`
+    /// radians, keeping the length of `self`. Returns `self` unchanged if
+    /// either vector is zero.
+    /// ```
+    ///     use xyzvec::XYVec;
+    ///     use approx::assert_relative_eq;
+    ///     use std::f64::consts::FRAC_PI_2;
+    ///
+    ///     let heading = XYVec::new([1.0f64, 0.0]);
+    ///     let turned = heading.rotate_towards(XYVec::new([-1.0, 1.0]), FRAC_PI_2);
+    ///     assert_relative_eq!(turned.x(), 0.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(turned.y(), 1.0, epsilon = 1e-12);
+    /// ```
+    pub fn rotate_towards(&self, target: Self, max_angle: T) -> Self {
+        let zero = T::zero();
+        let len_sqd = self.l2_norm_sqd();
+        let target_len_sqd = target.l2_norm_sqd();
+        if len_sqd == zero || target_len_sqd == zero {
+            return *self;
+        }
+        let diff = self.cross_prod(target).atan2(self.dot_prod(target));
+        if diff.abs() <= max_angle {
+            return target.scale_by((len_sqd / target_len_sqd).sqrt());
+        }
+        let step = if diff < zero { -max_angle } else { max_angle };
+        let (s, c) = (step.sin(), step.cos());
+        Self::new([self.x() * c - self.y() * s, self.x() * s + self.y() * c])
+    }
+}
+
+impl<T: VecNum> XYVec<T> {
+    /// Screen position of the grid point `self` in an isometric view, where
+    /// `tile` is the width and height of one tile's diamond on screen. The
+    /// grid `+x` axis runs down-right and `+y` down-left, with screen `y`
+    /// pointing down; grid `(0, 0)` maps to the screen origin. A `tile`
+    /// twice as wide as it is tall gives the usual 2:1 dimetric look.
+    /// ```
+    ///     use xyzvec::XYVec;
+    ///
+    ///     let tile = XYVec::new([64, 32]);
+    ///     assert_eq!(XYVec::new([1, 0]).to_iso(tile), XYVec::new([32, 16]));
+    ///     assert_eq!(XYVec::new([0, 1]).to_iso(tile), XYVec::new([-32, 16]));
+    ///     assert_eq!(XYVec::new([3, 2]).to_iso(tile).from_iso(tile), XYVec::new([3, 2]));
+    /// ```
+    pub fn to_iso(&self, tile: Self) -> Self {
+        let two = T::one() + T::one();
+        Self::new([
+            (self.x() - self.y()) * tile.x() / two,
+            (self.x() + self.y()) * tile.y() / two,
+        ])
+    }
+
+    /// Grid position under the screen point `self`; inverse of
+    /// [`to_iso`](Self::to_iso). For integer types the division truncates
+    /// toward zero, so to find the tile under a cursor convert with a float
+    /// type and take the floor.
+    /// ```
+    ///     use xyzvec::XYVec;
+    ///
+    ///     let tile = XYVec::new([64.0f64, 32.0]);
+    ///     let grid = XYVec::new([40.0, 40.0]).from_iso(tile);
+    ///     assert_eq!(grid, XYVec::new([1.875, 0.625]));
+    ///     assert_eq!(grid.floor_to_ivec(), xyzvec::IVec2::new([1, 0]));
+    /// ```
+    pub fn from_iso(&self, tile: Self) -> Self {
+        let (w, h) = (tile.x(), tile.y());
+        let area = w * h;
+        Self::new([
+            (self.x() * h + self.y() * w) / area,
+            (self.y() * w - self.x() * h) / area,
+        ])
+    }
+}
+
 impl<T: VecInner> Add for XYVec<T> {
     type Output = Self;
 
+    #[inline]
     fn add(self, other: Self) -> Self {
         let x = self.x() + other.x();
         let y = self.y() + other.y();
@@ -199,6 +329,7 @@ impl<T: VecInner> Add for XYVec<T> {
 }
 
 impl<T: VecInner> AddAssign for XYVec<T> {
+    #[inline]
     fn add_assign(&mut self, other: Self) {
         self.inner[0] += other.x();
         self.inner[1] += other.y()
@@ -208,6 +339,7 @@ impl<T: VecInner> AddAssign for XYVec<T> {
 impl<T: VecInner> Sub for XYVec<T> {
     type Output = Self;
 
+    #[inline]
     fn sub(self, other: Self) -> Self {
         let x = self.x() - other.x();
         let y = self.y() - other.y();
@@ -216,6 +348,7 @@ impl<T: VecInner> Sub for XYVec<T> {
 }
 
 impl<T: VecInner> SubAssign for XYVec<T> {
+    #[inline]
     fn sub_assign(&mut self, other: Self) {
         self.inner[0] -= other.x();
         self.inner[1] -= other.y()
@@ -225,14 +358,80 @@ impl<T: VecInner> SubAssign for XYVec<T> {
 impl<T: VecInner> Neg for XYVec<T> {
     type Output = Self;
 
+    #[inline]
     fn neg(self) -> Self::Output {
         Self::new([-self.x(), -self.y()])
     }
 }
 
-impl<T: VecInner> fmt::Debug for XYVec<T> {
+/// Component-wise sum; zero for an empty iterator.
+impl<T: VecNum> Sum for XYVec<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new([T::zero(), T::zero()]), |acc, v| acc + v)
+    }
+}
+
+impl<'a, T: VecNum> Sum<&'a XYVec<T>> for XYVec<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Component-wise product; one in every component for an empty iterator.
+impl<T: VecNum> Product for XYVec<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new([T::one(), T::one()]), |acc, v| {
+            acc.zip_map(v, |a, b| a * b)
+        })
+    }
+}
+
+impl<'a, T: VecNum> Product<&'a XYVec<T>> for XYVec<T> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+impl<T: VecInner> Vector for XYVec<T> {
+    type Scalar = T;
+    const DIM: usize = 2;
+
+    fn splat(v: T) -> Self {
+        Self::new([v; 2])
+    }
+
+    fn component(&self, i: usize) -> T {
+        self.inner[i]
+    }
+
+    fn map<F: Fn(T) -> T>(&self, f: F) -> Self {
+        Self::new([f(self.x()), f(self.y())])
+    }
+
+    fn zip_map<F: Fn(T, T) -> T>(&self, other: Self, f: F) -> Self {
+        Self::new([f(self.x(), other.x()), f(self.y(), other.y())])
+    }
+
+    #[inline]
+    fn dot_prod(&self, other: Self) -> T {
+        XYVec::dot_prod(self, other)
+    }
+
+    #[inline]
+    fn scale_by(&self, d: T) -> Self {
+        XYVec::scale_by(self, d)
+    }
+
+    #[inline]
+    fn div_by(&self, d: T) -> Self {
+        XYVec::div_by(self, d)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for XYVec<T> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "({:?}, {:?})", self.x(), self.y())
+        let [x, y] = &self.inner;
+        write!(f, "({x:?}, {y:?})")
     }
 }
 
@@ -243,6 +442,7 @@ impl<T: VecInner> fmt::Display for XYVec<T> {
 }
 
 impl XYVec<f32> {
+    #[inline]
     pub fn l2_norm(&self) -> f32 {
         self.l2_norm_sqd().sqrt()
     }
@@ -251,7 +451,12 @@ impl XYVec<f32> {
         Self { inner: [0.0; 2] }
     }
 
-    pub fn rotated_by(&self, theta: f32) -> Self {
+    /// `self` turned *clockwise* about the origin by `theta`. For the
+    /// usual counterclockwise convention, use
+    /// [`rotate_about`](Self::rotate_about) with a zero pivot.
+    #[inline]
+    pub fn rotated_by(&self, theta: impl Into<Radians<f32>>) -> Self {
+        let theta = theta.into().0;
         let c = theta.cos();
         let s = theta.sin();
 
@@ -259,9 +464,18 @@ impl XYVec<f32> {
         let y = -self.x() * s + self.y() * c;
         Self::new([x, y])
     }
+
+    /// `self` turned counterclockwise by `theta` about `pivot`.
+    pub fn rotate_about(&self, pivot: Self, theta: impl Into<Radians<f32>>) -> Self {
+        let theta = theta.into().0;
+        let (s, c) = (theta.sin(), theta.cos());
+        let d = *self - pivot;
+        pivot + Self::new([d.x() * c - d.y() * s, d.x() * s + d.y() * c])
+    }
 }
 
 impl XYVec<f64> {
+    #[inline]
     pub fn l2_norm(&self) -> f64 {
         self.l2_norm_sqd().sqrt()
     }
@@ -270,7 +484,12 @@ impl XYVec<f64> {
         Self { inner: [0.0; 2] }
     }
 
-    pub fn rotated_by(&self, theta: f64) -> Self {
+    /// `self` turned *clockwise* about the origin by `theta`. For the
+    /// usual counterclockwise convention, use
+    /// [`rotate_about`](Self::rotate_about) with a zero pivot.
+    #[inline]
+    pub fn rotated_by(&self, theta: impl Into<Radians<f64>>) -> Self {
+        let theta = theta.into().0;
         let c = theta.cos();
         let s = theta.sin();
 
@@ -278,6 +497,23 @@ impl XYVec<f64> {
         let y = -self.x() * s + self.y() * c;
         Self::new([x, y])
     }
+
+    /// `self` turned counterclockwise by `theta` about `pivot`.
+    /// ```
+    ///     use xyzvec::{angle::Degrees, XYVec};
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let corner = XYVec::new([3.0f64, 1.0]);
+    ///     let r = corner.rotate_about(XYVec::new([1.0, 1.0]), Degrees(90.0));
+    ///     assert_relative_eq!(r.x(), 1.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(r.y(), 3.0);
+    /// ```
+    pub fn rotate_about(&self, pivot: Self, theta: impl Into<Radians<f64>>) -> Self {
+        let theta = theta.into().0;
+        let (s, c) = (theta.sin(), theta.cos());
+        let d = *self - pivot;
+        pivot + Self::new([d.x() * c - d.y() * s, d.x() * s + d.y() * c])
+    }
 }
 
 impl<Frac> XYVec<FixedI64<Frac>> {
@@ -296,11 +532,17 @@ use cordic::{cos, sin, sqrt, CordicNumber};
 // use fixed::{IsLessOrEqual, True, U64, U64, U61};
 #[cfg(feature = "cordic")]
 impl<T: CordicNumber + CordicPhantomTrait + fmt::Display + fmt::Debug> XYVec<T> {
+    #[inline]
     pub fn l2_norm(&self) -> T {
         sqrt(self.l2_norm_sqd())
     }
 
-    pub fn rotated_by(&self, theta: T) -> Self {
+    /// `self` turned *clockwise* about the origin by `theta`. For the
+    /// usual counterclockwise convention, use
+    /// [`rotate_about`](Self::rotate_about) with a zero pivot.
+    #[inline]
+    pub fn rotated_by(&self, theta: impl Into<Radians<T>>) -> Self {
+        let theta = theta.into().0;
         let c = cos(theta);
         let s = sin(theta);
 
@@ -308,10 +550,69 @@ impl<T: CordicNumber + CordicPhantomTrait + fmt::Display + fmt::Debug> XYVec<T>
         let y = -self.x() * s + self.y() * c;
         Self::new([x, y])
     }
+
+    /// `self` turned counterclockwise by `theta` about `pivot`.
+    pub fn rotate_about(&self, pivot: Self, theta: impl Into<Radians<T>>) -> Self {
+        let theta = theta.into().0;
+        let (s, c) = (sin(theta), cos(theta));
+        let d = *self - pivot;
+        pivot + Self::new([d.x() * c - d.y() * s, d.x() * s + d.y() * c])
+    }
+}
+
+impl<T: VecInner> XYVec<T> {
+    /// Vector from exactly two items.
+    /// ```
+    ///     use xyzvec::{error::VecError, XYVec};
+    ///
+    ///     assert!(XYVec::try_from_iter([1.0f64, 2.0]).is_ok());
+    ///     assert_eq!(
+    ///         XYVec::try_from_iter([1.0f64]),
+    ///         Err(VecError::LengthMismatch { expected: 2, found: 1 })
+    ///     );
+    /// ```
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, VecError> {
+        exactly(iter).map(Self::new)
+    }
+}
+
+impl<T: VecInner> TryFrom<&[T]> for XYVec<T> {
+    type Error = VecError;
+
+    fn try_from(items: &[T]) -> Result<Self, VecError> {
+        Self::try_from_iter(items.iter().copied())
+    }
+}
+
+impl<T: VecInner> TryFrom<Vec<T>> for XYVec<T> {
+    type Error = VecError;
+
+    fn try_from(items: Vec<T>) -> Result<Self, VecError> {
+        Self::try_from_iter(items)
+    }
+}
+
+impl<T: VecInner> From<[T; 2]> for XYVec<T> {
+    fn from(inner: [T; 2]) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<T> AsRef<[T; 2]> for XYVec<T> {
+    fn as_ref(&self) -> &[T; 2] {
+        &self.inner
+    }
+}
+
+impl<T> AsMut<[T; 2]> for XYVec<T> {
+    fn as_mut(&mut self) -> &mut [T; 2] {
+        &mut self.inner
+    }
 }
 
-/// Build XYVec from iterator of size two.
-/// TODO: check for errors better
+/// Build XYVec from the first two items of an iterator, ignoring any
+/// more. Panics if there are fewer; see
+/// [`try_from_iter`](XYVec::try_from_iter) for a checked alternative.
 impl<T: VecInner> FromIterator<T> for XYVec<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut i = iter.into_iter();
@@ -387,6 +688,22 @@ mod tests {
         assert_relative_eq!(-v.y(), 0.5);
     }
 
+    #[test]
+    fn iso_round_trip() {
+        let tile = XYVec::new([2.0f64, 1.0]);
+        for (x, y) in [(0.0, 0.0), (1.5, -2.0), (-3.0, 4.25)] {
+            let v = XYVec::new([x, y]);
+            let back = v.to_iso(tile).from_iso(tile);
+            assert_relative_eq!(back.x(), x);
+            assert_relative_eq!(back.y(), y);
+        }
+        // Tile corners line up with the diamond's vertices.
+        assert_eq!(XYVec::new([1.0, 1.0]).to_iso(tile), XYVec::new([0.0, 1.0]));
+        let tile = XYVec::new([I28F4::from_num(64), I28F4::from_num(32)]);
+        let v = XYVec::new([I28F4::from_num(2.5), I28F4::from_num(-1)]);
+        assert_eq!(v.to_iso(tile).from_iso(tile), v);
+    }
+
     #[test]
     fn scale_fixed() {
         let v = XYVec::new([I28F4::from_num(1.0), I28F4::from_num(-0.5)]);
@@ -433,4 +750,72 @@ mod tests {
         let w = XYVec::new([I28F4::from_num(-2.0), I28F4::from_num(-0.0)]);
         assert_eq!(v.dot_prod(w), -2.0);
     }
+
+    #[test]
+    fn try_from_i32() {
+        use crate::error::VecError;
+        assert_eq!(XYVec::try_from(&[1, 2][..]), Ok(XYVec::new([1, 2])));
+        assert_eq!(XYVec::try_from(vec![3, 4]), Ok(XYVec::new([3, 4])));
+        assert_eq!(
+            XYVec::<i32>::try_from(vec![1, 2, 3]),
+            Err(VecError::LengthMismatch {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            XYVec::<i32>::try_from_iter(std::iter::empty()),
+            Err(VecError::LengthMismatch {
+                expected: 2,
+                found: 0
+            })
+        );
+        // Collecting still takes the first two and ignores the rest.
+        assert_eq!((1..10).collect::<XYVec<i32>>(), XYVec::new([1, 2]));
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let points = [XYVec::new([1, 2]), XYVec::new([3, -4]), XYVec::new([-2, 5])];
+        assert_eq!(points.iter().sum::<XYVec<i32>>(), XYVec::new([2, 3]));
+        assert_eq!(
+            points.into_iter().product::<XYVec<i32>>(),
+            XYVec::new([-6, -40])
+        );
+        let empty: [XYVec<f64>; 0] = [];
+        assert_eq!(empty.iter().sum::<XYVec<f64>>(), XYVec::new([0.0, 0.0]));
+        assert_eq!(
+            empty.into_iter().product::<XYVec<f64>>(),
+            XYVec::new([1.0, 1.0])
+        );
+        let f = |v: f64| I28F4::from_num(v);
+        let total: XYVec<I28F4> = [XYVec::new([f(0.5), f(1.0)]); 4].into_iter().sum();
+        assert_eq!(total, XYVec::new([f(2.0), f(4.0)]));
+    }
+
+    #[test]
+    fn rotate_about_f32() {
+        let pivot = XYVec::new([-1.0f32, 2.0]);
+        let v = XYVec::new([1.0f32, 2.0]);
+        let r = v.rotate_about(pivot, std::f32::consts::FRAC_PI_2);
+        assert_relative_eq!(r.x(), -1.0, epsilon = 1e-6);
+        assert_relative_eq!(r.y(), 4.0);
+        // About the origin it undoes the clockwise `rotated_by`.
+        let back = v.rotated_by(0.3).rotate_about(XYVec::<f32>::zeroes(), 0.3);
+        assert_relative_eq!(back.x(), v.x(), epsilon = 1e-6);
+        assert_relative_eq!(back.y(), v.y(), epsilon = 1e-6);
+        assert_eq!(pivot.rotate_about(pivot, 1.0), pivot);
+    }
+
+    #[cfg(feature = "cordic")]
+    #[test]
+    fn rotate_about_cordic() {
+        use fixed::types::I16F16;
+
+        let f = I16F16::from_num;
+        let v = XYVec::new([f(5.0), f(0.0)]);
+        let r = v.rotate_about(XYVec::new([f(4.0), f(0.0)]), f(std::f64::consts::PI));
+        assert!((r.x() - f(3.0)).abs() < f(0.001));
+        assert!(r.y().abs() < f(0.001));
+    }
 }
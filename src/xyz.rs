@@ -1,32 +1,48 @@
 use fixed::FixedI64;
 use std::{
     fmt::{self, Formatter},
+    iter::{Product, Sum},
     ops::{Add, AddAssign, Neg, Sub, SubAssign},
 };
 
-use crate::VecInner;
+use crate::{
+    angle::Radians,
+    error::{exactly, VecError},
+    Mat3, VecFloat, VecInner, VecNum, Vector,
+};
 
-#[derive(Clone, PartialEq, Copy)]
+/// Laid out exactly like `[T; 3]`, so slices of vectors and of scalars
+/// can be reinterpreted; see [`XYZVec::cast_slice`].
+#[derive(Clone, PartialEq, Eq, Hash, Copy)]
+#[repr(transparent)]
 pub struct XYZVec<T> {
     inner: [T; 3],
 }
 
-impl<T: VecInner> XYZVec<T> {
+impl<T> XYZVec<T> {
+    /// Works with any scalar, including ones that are not `Copy`; most
+    /// other methods need [`VecInner`].
+    #[inline]
     pub fn new(inner: [T; 3]) -> Self {
         Self { inner }
     }
+}
 
+impl<T: VecInner> XYZVec<T> {
     /// `x` component of XYZVec
+    #[inline]
     pub fn x(&self) -> T {
         self.inner[0]
     }
 
     /// `y` component of XYZVec
+    #[inline]
     pub fn y(&self) -> T {
         self.inner[1]
     }
 
     /// `z` component of XYZVec
+    #[inline]
     pub fn z(&self) -> T {
         self.inner[2]
     }
@@ -41,6 +57,7 @@ impl<T: VecInner> XYZVec<T> {
     ///    assert_relative_eq!(scaled_v.y(), 10.0);
     ///    assert_relative_eq!(scaled_v.z(), -2.5);
     /// ```
+    #[inline]
     pub fn scale_by(&self, d: T) -> Self {
         let x = self.x() * d;
         let y = self.y() * d;
@@ -58,6 +75,7 @@ impl<T: VecInner> XYZVec<T> {
     ///    assert_relative_eq!(scaled_v.y(), 10.0);
     ///    assert_relative_eq!(scaled_v.z(), -2.5);
     /// ```
+    #[inline]
     pub fn div_by(&self, d: T) -> Self {
         let x = self.x() / d;
         let y = self.y() / d;
@@ -102,6 +120,7 @@ impl<T: VecInner> XYZVec<T> {
     ///    let v = XYZVec::new([1.0f64, 2.0f64, -0.5f64]);
     ///    assert_relative_eq!(v.l2_norm_sqd(), 5.25);
     /// ```
+    #[inline]
     pub fn l2_norm_sqd(&self) -> T {
         self.x() * self.x() + self.y() * self.y() + self.z() * self.z()
     }
@@ -117,6 +136,7 @@ impl<T: VecInner> XYZVec<T> {
     ///    assert_relative_eq!(cross_prod.y(), 0.25);
     ///    assert_relative_eq!(cross_prod.z(), 1.0);
     /// ```
+    #[inline]
     pub fn cross_prod(&self, other: Self) -> Self {
         let x: T = self.x() * other.y() - self.y() * other.x();
         let y: T = self.y() * other.z() - self.z() * other.y();
@@ -132,10 +152,27 @@ impl<T: VecInner> XYZVec<T> {
     ///    let w = XYZVec::new([-2.0f64, 0.5f64, 0.0f64]);
     ///    assert_relative_eq!(v.dot_prod(w), -1.0);
     /// ```
+    #[inline]
     pub fn dot_prod(&self, other: Self) -> T {
         self.x() * other.x() + self.y() * other.y() + self.z() * other.z()
     }
 
+    /// Outer product `self * other^T`, whose entry `(i, j)` is
+    /// `self[i] * other[j]`.
+    /// ```
+    ///     use xyzvec::{Mat3, XYZVec};
+    ///
+    ///     let m = XYZVec::new([1, 2, 3]).outer(XYZVec::new([1, 0, -1]));
+    ///     assert_eq!(m, Mat3::new([[1, 0, -1], [2, 0, -2], [3, 0, -3]]));
+    /// ```
+    pub fn outer(&self, other: Self) -> Mat3<T> {
+        Mat3::from_rows([
+            other.scale_by(self.x()),
+            other.scale_by(self.y()),
+            other.scale_by(self.z()),
+        ])
+    }
+
     /// ```   
     ///    use xyzvec::XYZVec;
     ///    use approx::assert_relative_eq;
@@ -145,10 +182,43 @@ impl<T: VecInner> XYZVec<T> {
     ///    let mag_sqd = v.cross_prod_magnitude_sqd(w);
     ///    assert_relative_eq!(mag_sqd, 21.3125);
     /// ```
+    #[inline]
     pub fn cross_prod_magnitude_sqd(&self, other: Self) -> T {
         self.cross_prod(other).l2_norm_sqd()
     }
 
+    /// Right-handed cross product `self × other`.
+    /// `cross_prod` yields the same components in `(z, x, y)` order.
+    #[inline]
+    pub(crate) fn cross(&self, other: Self) -> Self {
+        let x = self.y() * other.z() - self.z() * other.y();
+        let y = self.z() * other.x() - self.x() * other.z();
+        let z = self.x() * other.y() - self.y() * other.x();
+        Self::new([x, y, z])
+    }
+
+    /// Scalar triple product `self · (b × c)`: the signed volume of the
+    /// parallelepiped spanned by the three vectors, positive when they are
+    /// right-handed. [`TripleSign`](crate::TripleSign) decides its sign
+    /// without overflow or rounding.
+    /// ```
+    ///     use xyzvec::XYZVec;
+    ///
+    ///     let [x, y, z] = [[1, 0, 0], [0, 1, 0], [0, 0, 1]].map(XYZVec::new);
+    ///     assert_eq!(x.scalar_triple(y, z), 1);
+    ///     assert_eq!(y.scalar_triple(x, z), -1);
+    ///     assert_eq!(x.scalar_triple(y, x + y), 0);
+    /// ```
+    pub fn scalar_triple(&self, b: Self, c: Self) -> T {
+        self.dot_prod(b.cross(c))
+    }
+
+    /// Vector triple product `self × (b × c)`, which lies in the plane of
+    /// `b` and `c`. Computed as `b (self · c) - c (self · b)`.
+    pub fn vector_triple(&self, b: Self, c: Self) -> Self {
+        b.scale_by(self.dot_prod(c)) - c.scale_by(self.dot_prod(b))
+    }
+
     ///```
     ///    use xyzvec::XYZVec;
     ///    use approx::assert_relative_eq;    
@@ -176,9 +246,89 @@ impl<T: VecInner> XYZVec<T> {
     }
 }
 
+impl<T: VecFloat> XYZVec<T> {
+    /// Some unit vector perpendicular to `self`. `self` must be nonzero.
+    /// ```
+    ///     use xyzvec::XYZVec;
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let n = XYZVec::new([1.0f64, 2.0, 3.0]);
+    ///     let t = n.any_orthonormal();
+    ///     assert_relative_eq!(t.dot_prod(n), 0.0, epsilon = 1e-12);
+    ///     assert_relative_eq!(t.l2_norm(), 1.0);
+    /// ```
+    pub fn any_orthonormal(&self) -> Self {
+        self.any_orthonormal_pair().0
+    }
+
+    /// Two unit vectors `(t, b)` completing the direction of `self` to a
+    /// right-handed orthonormal basis, so that `t × b` points along `self`.
+    /// `self` must be nonzero; it is normalized first.
+    ///
+    /// Uses the branchless construction of Duff et al., "Building an
+    /// Orthonormal Basis, Revisited" (2017), which is continuous everywhere
+    /// except where `self` crosses the `z = 0` plane.
+    /// ```
+    ///     use xyzvec::XYZVec;
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let (t, b) = XYZVec::new([0.0f64, 0.0, 2.0]).any_orthonormal_pair();
+    ///     assert_relative_eq!(t.x(), 1.0);
+    ///     assert_relative_eq!(b.y(), 1.0);
+    /// ```
+    pub fn any_orthonormal_pair(&self) -> (Self, Self) {
+        let one = T::one();
+        let n = self.div_by(self.l2_norm());
+        let (x, y, z) = (n.x(), n.y(), n.z());
+        let sign = if z < T::zero() { -one } else { one };
+        let a = -one / (sign + z);
+        let b = x * y * a;
+        let t = Self::new([one + sign * x * x * a, sign * b, -sign * x]);
+        let bitangent = Self::new([b, sign + y * y * a, -y]);
+        (t, bitangent)
+    }
+
+    /// Turns `self` toward the direction of `target` by at most `max_angle`
+    /// radians, about the axis perpendicular to both, keeping the length of
+    /// `self`. Returns `self` unchanged if either vector is zero.
+    /// ```
+    ///     use xyzvec::XYZVec;
+    ///     use approx::assert_relative_eq;
+    ///
+    ///     let v = XYZVec::new([1.0f64, 0.0, 0.0]);
+    ///     let turned = v.rotate_towards(XYZVec::new([0.0, 2.0, 0.0]), 0.5);
+    ///     assert_relative_eq!(turned.x(), 0.5f64.cos());
+    ///     assert_relative_eq!(turned.y(), 0.5f64.sin());
+    ///     assert_relative_eq!(turned.z(), 0.0);
+    /// ```
+    pub fn rotate_towards(&self, target: Self, max_angle: T) -> Self {
+        let zero = T::zero();
+        let len_sqd = self.l2_norm_sqd();
+        let target_len_sqd = target.l2_norm_sqd();
+        if len_sqd == zero || target_len_sqd == zero {
+            return *self;
+        }
+        let axis = self.cross(target);
+        let axis_len_sqd = axis.l2_norm_sqd();
+        let angle = axis_len_sqd.sqrt().atan2(self.dot_prod(target));
+        if angle <= max_angle {
+            return target.scale_by((len_sqd / target_len_sqd).sqrt());
+        }
+        let axis = if axis_len_sqd == zero {
+            self.any_orthonormal()
+        } else {
+            axis
+        };
+        let k = axis.div_by(axis.l2_norm_sqd().sqrt());
+        // `k` is perpendicular to `self`, so Rodrigues' formula loses its last term.
+        self.scale_by(max_angle.cos()) + k.cross(*self).scale_by(max_angle.sin())
+    }
+}
+
 impl<T: VecInner> Add for XYZVec<T> {
     type Output = Self;
 
+    #[inline]
     fn add(self, other: Self) -> Self {
         let x = self.x() + other.x();
         let y = self.y() + other.y();
@@ -188,6 +338,7 @@ impl<T: VecInner> Add for XYZVec<T> {
 }
 
 impl<T: VecInner> AddAssign for XYZVec<T> {
+    #[inline]
     fn add_assign(&mut self, other: Self) {
         self.inner[0] += other.x();
         self.inner[1] += other.y();
@@ -198,6 +349,7 @@ impl<T: VecInner> AddAssign for XYZVec<T> {
 impl<T: VecInner> Sub for XYZVec<T> {
     type Output = Self;
 
+    #[inline]
     fn sub(self, other: Self) -> Self {
         let x = self.x() - other.x();
         let y = self.y() - other.y();
@@ -207,6 +359,7 @@ impl<T: VecInner> Sub for XYZVec<T> {
 }
 
 impl<T: VecInner> SubAssign for XYZVec<T> {
+    #[inline]
     fn sub_assign(&mut self, other: Self) {
         self.inner[0] -= other.x();
         self.inner[1] -= other.y();
@@ -217,14 +370,86 @@ impl<T: VecInner> SubAssign for XYZVec<T> {
 impl<T: VecInner> Neg for XYZVec<T> {
     type Output = Self;
 
+    #[inline]
     fn neg(self) -> Self::Output {
         Self::new([-self.x(), -self.y(), -self.z()])
     }
 }
 
-impl<T: VecInner> fmt::Debug for XYZVec<T> {
+/// Component-wise sum; zero for an empty iterator.
+impl<T: VecNum> Sum for XYZVec<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new([T::zero(), T::zero(), T::zero()]), |acc, v| {
+            acc + v
+        })
+    }
+}
+
+impl<'a, T: VecNum> Sum<&'a XYZVec<T>> for XYZVec<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Component-wise product; one in every component for an empty iterator.
+impl<T: VecNum> Product for XYZVec<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new([T::one(), T::one(), T::one()]), |acc, v| {
+            acc.zip_map(v, |a, b| a * b)
+        })
+    }
+}
+
+impl<'a, T: VecNum> Product<&'a XYZVec<T>> for XYZVec<T> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+impl<T: VecInner> Vector for XYZVec<T> {
+    type Scalar = T;
+    const DIM: usize = 3;
+
+    fn splat(v: T) -> Self {
+        Self::new([v; 3])
+    }
+
+    fn component(&self, i: usize) -> T {
+        self.inner[i]
+    }
+
+    fn map<F: Fn(T) -> T>(&self, f: F) -> Self {
+        Self::new([f(self.x()), f(self.y()), f(self.z())])
+    }
+
+    fn zip_map<F: Fn(T, T) -> T>(&self, other: Self, f: F) -> Self {
+        Self::new([
+            f(self.x(), other.x()),
+            f(self.y(), other.y()),
+            f(self.z(), other.z()),
+        ])
+    }
+
+    #[inline]
+    fn dot_prod(&self, other: Self) -> T {
+        XYZVec::dot_prod(self, other)
+    }
+
+    #[inline]
+    fn scale_by(&self, d: T) -> Self {
+        XYZVec::scale_by(self, d)
+    }
+
+    #[inline]
+    fn div_by(&self, d: T) -> Self {
+        XYZVec::div_by(self, d)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for XYZVec<T> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "({:?}, {:?}, {:?})", self.x(), self.y(), self.z())
+        let [x, y, z] = &self.inner;
+        write!(f, "({x:?}, {y:?}, {z:?})")
     }
 }
 
@@ -234,7 +459,47 @@ impl<T: VecInner> fmt::Display for XYZVec<T> {
     }
 }
 
+/// `rotate_x`, `rotate_y` and `rotate_z` for a scalar type, given how to
+/// take its `(sin, cos)`.
+macro_rules! axis_rotations {
+    ($t:ty, $sin_cos:expr) => {
+        /// `self` turned by `theta` about the `+x` axis, counterclockwise
+        /// looking down the axis toward the origin.
+        pub fn rotate_x(&self, theta: impl Into<Radians<$t>>) -> Self {
+            let (s, c) = $sin_cos(theta.into().0);
+            self.rotated_in_plane(1, 2, s, c)
+        }
+
+        /// `self` turned by `theta` about the `+y` axis, counterclockwise
+        /// looking down the axis toward the origin.
+        pub fn rotate_y(&self, theta: impl Into<Radians<$t>>) -> Self {
+            let (s, c) = $sin_cos(theta.into().0);
+            self.rotated_in_plane(2, 0, s, c)
+        }
+
+        /// `self` turned by `theta` about the `+z` axis, counterclockwise
+        /// looking down the axis toward the origin.
+        pub fn rotate_z(&self, theta: impl Into<Radians<$t>>) -> Self {
+            let (s, c) = $sin_cos(theta.into().0);
+            self.rotated_in_plane(0, 1, s, c)
+        }
+    };
+}
+
+impl<T: VecInner> XYZVec<T> {
+    /// Turns the `(a, b)` components by the angle with sine `s` and cosine
+    /// `c`, from `a` toward `b`.
+    fn rotated_in_plane(&self, a: usize, b: usize, s: T, c: T) -> Self {
+        let mut inner = self.inner;
+        let (va, vb) = (inner[a], inner[b]);
+        inner[a] = va * c - vb * s;
+        inner[b] = va * s + vb * c;
+        Self { inner }
+    }
+}
+
 impl XYZVec<f32> {
+    #[inline]
     pub fn l2_norm(&self) -> f32 {
         self.l2_norm_sqd().sqrt()
     }
@@ -242,9 +507,12 @@ impl XYZVec<f32> {
     pub fn zeroes() -> Self {
         Self { inner: [0.0; 3] }
     }
+
+    axis_rotations!(f32, |t: f32| (t.sin(), t.cos()));
 }
 
 impl XYZVec<f64> {
+    #[inline]
     pub fn l2_norm(&self) -> f64 {
         self.l2_norm_sqd().sqrt()
     }
@@ -252,6 +520,8 @@ impl XYZVec<f64> {
     pub fn zeroes() -> Self {
         Self { inner: [0.0; 3] }
     }
+
+    axis_rotations!(f64, |t: f64| (t.sin(), t.cos()));
 }
 
 impl<Frac> XYZVec<FixedI64<Frac>> {
@@ -265,12 +535,15 @@ impl<Frac> XYZVec<FixedI64<Frac>> {
 #[cfg(feature = "cordic")]
 use crate::CordicPhantomTrait;
 #[cfg(feature = "cordic")]
-use cordic::{sqrt, CordicNumber};
+use cordic::{cos, sin, sqrt, CordicNumber};
 #[cfg(feature = "cordic")]
 impl<T: CordicNumber + CordicPhantomTrait + fmt::Display + fmt::Debug> XYZVec<T> {
+    #[inline]
     pub fn l2_norm(&self) -> T {
         sqrt(self.l2_norm_sqd())
     }
+
+    axis_rotations!(T, |t: T| (sin(t), cos(t)));
 }
 
 // fn rotated_by_3d(&self, _other: Self, _theta: T) -> Self {
@@ -285,8 +558,59 @@ impl<T: CordicNumber + CordicPhantomTrait + fmt::Display + fmt::Debug> XYZVec<T>
 //     // Self::new([x,y,z])
 // }
 
-/// Build XYVec from iterator of size two.
-/// TODO: check for errors better
+impl<T: VecInner> XYZVec<T> {
+    /// Vector from exactly three items.
+    /// ```
+    ///     use xyzvec::{error::VecError, XYZVec};
+    ///
+    ///     assert!(XYZVec::try_from_iter([1.0f64, 2.0, 3.0]).is_ok());
+    ///     assert_eq!(
+    ///         XYZVec::try_from_iter([1.0f64, 2.0]),
+    ///         Err(VecError::LengthMismatch { expected: 3, found: 2 })
+    ///     );
+    /// ```
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, VecError> {
+        exactly(iter).map(Self::new)
+    }
+}
+
+impl<T: VecInner> TryFrom<&[T]> for XYZVec<T> {
+    type Error = VecError;
+
+    fn try_from(items: &[T]) -> Result<Self, VecError> {
+        Self::try_from_iter(items.iter().copied())
+    }
+}
+
+impl<T: VecInner> TryFrom<Vec<T>> for XYZVec<T> {
+    type Error = VecError;
+
+    fn try_from(items: Vec<T>) -> Result<Self, VecError> {
+        Self::try_from_iter(items)
+    }
+}
+
+impl<T: VecInner> From<[T; 3]> for XYZVec<T> {
+    fn from(inner: [T; 3]) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<T> AsRef<[T; 3]> for XYZVec<T> {
+    fn as_ref(&self) -> &[T; 3] {
+        &self.inner
+    }
+}
+
+impl<T> AsMut<[T; 3]> for XYZVec<T> {
+    fn as_mut(&mut self) -> &mut [T; 3] {
+        &mut self.inner
+    }
+}
+
+/// Build XYZVec from the first three items of an iterator, ignoring any
+/// more. Panics if there are fewer; see
+/// [`try_from_iter`](XYZVec::try_from_iter) for a checked alternative.
 impl<T: VecInner> FromIterator<T> for XYZVec<T> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut i = iter.into_iter();
@@ -454,4 +778,90 @@ mod tests {
         ]);
         assert_eq!(v.dot_prod(w), -1.0);
     }
+
+    #[test]
+    fn sum_and_product() {
+        let forces = [
+            XYZVec::new([1.0f32, 0.0, -2.0]),
+            XYZVec::new([0.5, 3.0, 1.0]),
+            XYZVec::new([2.0, -1.0, 4.0]),
+        ];
+        let total: XYZVec<f32> = forces.iter().sum();
+        assert_relative_eq!(total.x(), 3.5);
+        assert_relative_eq!(total.y(), 2.0);
+        assert_relative_eq!(total.z(), 3.0);
+        let scale: XYZVec<f32> = forces.iter().product();
+        assert_relative_eq!(scale.z(), -8.0);
+        assert_eq!(
+            Vec::<XYZVec<i64>>::new().into_iter().sum::<XYZVec<i64>>(),
+            XYZVec::new([0; 3])
+        );
+    }
+
+    #[test]
+    fn axis_rotations_f64() {
+        use std::f64::consts::FRAC_PI_2;
+
+        let close = |a: XYZVec<f64>, b: [f64; 3]| {
+            assert_relative_eq!(a.x(), b[0], epsilon = 1e-12);
+            assert_relative_eq!(a.y(), b[1], epsilon = 1e-12);
+            assert_relative_eq!(a.z(), b[2], epsilon = 1e-12);
+        };
+        // Each quarter turn follows the right-hand rule: x → y → z → x.
+        close(
+            XYZVec::new([0.0f64, 1.0, 0.0]).rotate_x(FRAC_PI_2),
+            [0.0, 0.0, 1.0],
+        );
+        close(
+            XYZVec::new([0.0f64, 0.0, 1.0]).rotate_y(FRAC_PI_2),
+            [1.0, 0.0, 0.0],
+        );
+        close(
+            XYZVec::new([1.0f64, 0.0, 0.0]).rotate_z(FRAC_PI_2),
+            [0.0, 1.0, 0.0],
+        );
+        let v = XYZVec::new([1.0f64, -2.0, 3.0]);
+        assert_eq!(v.rotate_x(0.7).x(), v.x());
+        assert_relative_eq!(v.rotate_y(0.7).l2_norm(), v.l2_norm());
+        close(v.rotate_z(0.7).rotate_z(-0.7), [1.0, -2.0, 3.0]);
+    }
+
+    #[cfg(feature = "cordic")]
+    #[test]
+    fn axis_rotations_cordic() {
+        use fixed::types::I16F16;
+
+        let f = I16F16::from_num;
+        let v = XYZVec::new([f(2.0), f(0.0), f(-1.0)]);
+        let r = v.rotate_y(f(std::f64::consts::FRAC_PI_2));
+        assert!((r - XYZVec::new([f(-1.0), f(0.0), f(-2.0)]))
+            .iter()
+            .all(|c| c.abs() < f(0.001)));
+    }
+
+    #[test]
+    fn triple_products() {
+        let a = XYZVec::new([1.0f64, -2.0, 0.5]);
+        let b = XYZVec::new([3.0, 1.0, -1.0]);
+        let c = XYZVec::new([-0.5, 4.0, 2.0]);
+        // Cyclic permutations agree; swapping two vectors flips the sign.
+        assert_relative_eq!(a.scalar_triple(b, c), b.scalar_triple(c, a));
+        assert_relative_eq!(a.scalar_triple(b, c), -a.scalar_triple(c, b));
+        assert_relative_eq!(a.scalar_triple(b, c), 23.25);
+        let v = a.vector_triple(b, c);
+        let w = a.cross(b.cross(c));
+        assert_relative_eq!(v.x(), w.x(), epsilon = 1e-12);
+        assert_relative_eq!(v.y(), w.y(), epsilon = 1e-12);
+        assert_relative_eq!(v.z(), w.z(), epsilon = 1e-12);
+
+        let f = |v: f64| I28F4::from_num(v);
+        let a = XYZVec::new([f(2.0), f(0.0), f(0.0)]);
+        let b = XYZVec::new([f(0.5), f(1.5), f(0.0)]);
+        let c = XYZVec::new([f(1.0), f(-1.0), f(0.25)]);
+        assert_eq!(a.scalar_triple(b, c), f(0.75));
+        assert_eq!(
+            a.vector_triple(b, c),
+            XYZVec::new([f(0.0), f(4.0), f(-0.25)])
+        );
+    }
 }
@@ -0,0 +1,281 @@
+//! Four-component vectors, mainly as homogeneous coordinates for 3D points,
+//! and the conversions to and from homogeneous coordinates.
+//!
+//! A point maps to homogeneous coordinates with a trailing `1`. Going back
+//! divides by that last component, `w`; a `w` of zero marks a point at
+//! infinity (a direction), which has no Cartesian equivalent.
+
+use crate::{Mat4, VecInner, VecNum, Vector, XYVec, XYZVec};
+use std::{
+    fmt::{self, Formatter},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+#[derive(Clone, PartialEq, Copy)]
+pub struct XYZWVec<T> {
+    inner: [T; 4],
+}
+
+impl<T: VecInner> XYZWVec<T> {
+    pub fn new(inner: [T; 4]) -> Self {
+        Self { inner }
+    }
+
+    /// `x` component of XYZWVec
+    pub fn x(&self) -> T {
+        self.inner[0]
+    }
+
+    /// `y` component of XYZWVec
+    pub fn y(&self) -> T {
+        self.inner[1]
+    }
+
+    /// `z` component of XYZWVec
+    pub fn z(&self) -> T {
+        self.inner[2]
+    }
+
+    /// `w` component of XYZWVec
+    pub fn w(&self) -> T {
+        self.inner[3]
+    }
+
+    /// The `x`, `y`, and `z` components, without dividing by `w`.
+    pub fn xyz(&self) -> XYZVec<T> {
+        XYZVec::new([self.x(), self.y(), self.z()])
+    }
+}
+
+impl<T: VecInner> Add for XYZWVec<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.zip_map(other, |a, b| a + b)
+    }
+}
+
+impl<T: VecInner> AddAssign for XYZWVec<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: VecInner> Sub for XYZWVec<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.zip_map(other, |a, b| a - b)
+    }
+}
+
+impl<T: VecInner> SubAssign for XYZWVec<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: VecInner> Neg for XYZWVec<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(|c| -c)
+    }
+}
+
+impl<T: VecInner> Vector for XYZWVec<T> {
+    type Scalar = T;
+    const DIM: usize = 4;
+
+    fn splat(v: T) -> Self {
+        Self::new([v; 4])
+    }
+
+    fn component(&self, i: usize) -> T {
+        self.inner[i]
+    }
+
+    fn map<F: Fn(T) -> T>(&self, f: F) -> Self {
+        Self::new(self.inner.map(f))
+    }
+
+    fn zip_map<F: Fn(T, T) -> T>(&self, other: Self, f: F) -> Self {
+        Self::new(std::array::from_fn(|i| f(self.inner[i], other.inner[i])))
+    }
+
+    fn dot_prod(&self, other: Self) -> T {
+        self.x() * other.x() + self.y() * other.y() + self.z() * other.z() + self.w() * other.w()
+    }
+
+    fn scale_by(&self, d: T) -> Self {
+        self.map(|c| c * d)
+    }
+
+    fn div_by(&self, d: T) -> Self {
+        self.map(|c| c / d)
+    }
+}
+
+impl<T: VecInner> fmt::Debug for XYZWVec<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({:?}, {:?}, {:?}, {:?})",
+            self.x(),
+            self.y(),
+            self.z(),
+            self.w()
+        )
+    }
+}
+
+impl<T: VecInner> fmt::Display for XYZWVec<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "({:.3}, {:.3}, {:.3}, {:.3})",
+            self.x(),
+            self.y(),
+            self.z(),
+            self.w()
+        )
+    }
+}
+
+impl<T: VecInner> From<[T; 4]> for XYZWVec<T> {
+    fn from(inner: [T; 4]) -> Self {
+        Self::new(inner)
+    }
+}
+
+impl<T> AsRef<[T; 4]> for XYZWVec<T> {
+    fn as_ref(&self) -> &[T; 4] {
+        &self.inner
+    }
+}
+
+impl<T> AsMut<[T; 4]> for XYZWVec<T> {
+    fn as_mut(&mut self) -> &mut [T; 4] {
+        &mut self.inner
+    }
+}
+
+impl<T: VecNum> XYVec<T> {
+    /// `(x, y, 1)`, for use with 3x3 projective transforms of the plane.
+    /// ```
+    ///     use xyzvec::{XYVec, XYZVec};
+    ///
+    ///     let p = XYVec::new([3, 4]);
+    ///     assert_eq!(p.to_homogeneous(), XYZVec::new([3, 4, 1]));
+    ///     assert_eq!(XYVec::from_homogeneous(XYZVec::new([6, 8, 2])), Some(p));
+    ///     assert_eq!(XYVec::from_homogeneous(XYZVec::new([1, 0, 0])), None);
+    /// ```
+    pub fn to_homogeneous(&self) -> XYZVec<T> {
+        XYZVec::new([self.x(), self.y(), T::one()])
+    }
+
+    /// `(x / z, y / z)`, or `None` if `z` is zero.
+    pub fn from_homogeneous(h: XYZVec<T>) -> Option<Self> {
+        (h.z() != T::zero()).then(|| XYVec::new([h.x() / h.z(), h.y() / h.z()]))
+    }
+}
+
+impl<T: VecNum> XYZVec<T> {
+    /// `(x, y, z, 1)`, for use with [`Mat4`] transforms.
+    pub fn to_homogeneous(&self) -> XYZWVec<T> {
+        XYZWVec::new([self.x(), self.y(), self.z(), T::one()])
+    }
+
+    /// `(x / w, y / w, z / w)`, or `None` if `w` is zero.
+    pub fn from_homogeneous(h: XYZWVec<T>) -> Option<Self> {
+        (h.w() != T::zero()).then(|| h.xyz().div_by(h.w()))
+    }
+}
+
+impl<T: VecInner> Mat4<T> {
+    /// Matrix-vector product `self * v`.
+    pub fn mul_vec(&self, v: XYZWVec<T>) -> XYZWVec<T> {
+        XYZWVec::new(std::array::from_fn(|i| {
+            XYZWVec::new(std::array::from_fn(|j| self.get(i, j))).dot_prod(v)
+        }))
+    }
+}
+
+impl<T: VecNum> Mat4<T> {
+    /// `p` transformed as a point, including the divide by `w` that
+    /// projective transforms need. `None` if `p` maps to infinity.
+    /// ```
+    ///     use xyzvec::{Mat3, Mat4, XYZVec};
+    ///
+    ///     let m = Mat4::from_affine(Mat3::identity(), XYZVec::new([1.0f64, 2.0, 3.0]));
+    ///     assert_eq!(m.transform_point(XYZVec::new([1.0, 1.0, 1.0])), Some(XYZVec::new([2.0, 3.0, 4.0])));
+    /// ```
+    pub fn transform_point(&self, p: XYZVec<T>) -> Option<XYZVec<T>> {
+        XYZVec::from_homogeneous(self.mul_vec(p.to_homogeneous()))
+    }
+
+    /// `v` transformed as a direction, so translation does not apply.
+    pub fn transform_vector(&self, v: XYZVec<T>) -> XYZVec<T> {
+        self.mul_vec(XYZWVec::new([v.x(), v.y(), v.z(), T::zero()]))
+            .xyz()
+    }
+}
+
+impl<T: VecInner> Mul<XYZWVec<T>> for Mat4<T> {
+    type Output = XYZWVec<T>;
+
+    fn mul(self, v: XYZWVec<T>) -> XYZWVec<T> {
+        self.mul_vec(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Mat3, Mat4, Vector, XYVec, XYZVec, XYZWVec};
+    use fixed::types::I16F16;
+
+    #[test]
+    fn ops_i32() {
+        let a = XYZWVec::new([1, 2, 3, 4]);
+        let b = XYZWVec::new([4, 3, 2, 1]);
+        assert_eq!(a + b, XYZWVec::splat(5));
+        assert_eq!(a - a, XYZWVec::splat(0));
+        assert_eq!(-a, XYZWVec::new([-1, -2, -3, -4]));
+        assert_eq!(a.dot_prod(b), 20);
+        assert_eq!(a.xyz(), XYZVec::new([1, 2, 3]));
+        assert_eq!(format!("{a:?}"), "(1, 2, 3, 4)");
+        assert_eq!(Mat4::identity() * a, a);
+    }
+
+    #[test]
+    fn homogeneous_round_trip_fixed() {
+        let f = |v: f64| I16F16::from_num(v);
+        let p = XYZVec::new([f(1.5), f(-2.0), f(0.25)]);
+        assert_eq!(XYZVec::from_homogeneous(p.to_homogeneous()), Some(p));
+        let scaled = p.to_homogeneous().scale_by(f(4.0));
+        assert_eq!(XYZVec::from_homogeneous(scaled), Some(p));
+        let direction = XYZWVec::new([f(1.0), f(0.0), f(0.0), f(0.0)]);
+        assert_eq!(XYZVec::from_homogeneous(direction), None);
+        let q = XYVec::new([f(0.5), f(3.0)]);
+        assert_eq!(XYVec::from_homogeneous(q.to_homogeneous()), Some(q));
+    }
+
+    #[test]
+    fn mat4_transform_f64() {
+        let m = Mat4::from_affine(
+            Mat3::new([[2.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 2.0]]),
+            XYZVec::new([1.0f64, 0.0, 0.0]),
+        );
+        let v = XYZVec::new([1.0, 1.0, 1.0]);
+        assert_eq!(m.transform_point(v), Some(XYZVec::new([3.0, 2.0, 2.0])));
+        assert_eq!(m.transform_vector(v), XYZVec::new([2.0, 2.0, 2.0]));
+        // A last row that zeroes `w` sends everything to infinity.
+        let flat = Mat4::new([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0; 4],
+        ]);
+        assert_eq!(flat.transform_point(v), None);
+    }
+}